@@ -1,18 +1,32 @@
 use crate::analyzers::ReportConfig;
-use ariadne::sources;
-use clap::{ArgAction, Parser, ValueHint};
+use ariadne::{sources, Cache};
+use clap::{ArgAction, Parser, ValueEnum, ValueHint};
 use pyrometer::context::queries::storage_write::StorageRangeQuery;
 use pyrometer::{
+    config::{NameFilter, PyrometerConfig, Severity},
     context::{
-        analyzers::{bounds::FunctionVarsBoundAnalyzer, ReportDisplay},
+        analyzers::{
+            baseline::{Baseline, Findings, Fingerprint, Suppressions},
+            bounds::{CLIFunctionVarsBoundAnalysis, FunctionVarsBoundAnalyzer},
+            html::HtmlReport,
+            imports::UnresolvedImport,
+            json::JsonFunctionBounds,
+            panics::PanicAnalyzer,
+            sarif::{level, rule_id, SarifDisplay, SarifLog, SarifResult},
+            syntax::SyntaxError,
+            unsupported::{UnsupportedAnalysis, UnsupportedAnalyzer},
+            ReportDisplay,
+        },
         queries::storage_write::AccessStorageWriteQuery,
         *,
     },
+    foundry::FoundryConfig,
     Analyzer,
 };
+use shared::context::ContextNode;
 use shared::nodes::Concrete;
 use shared::nodes::FunctionNode;
-use shared::range::SolcRange;
+use shared::range::{interval_set::DEFAULT_INTERVAL_CAP, smt::SmtScript, SolcRange};
 use shared::Edge;
 use shared::{
     analyzer::{GraphLike, Search},
@@ -21,18 +35,36 @@ use shared::{
 use tracing_subscriber::prelude::*;
 
 use std::collections::{BTreeMap, HashMap};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use std::env::{self};
 use std::fs;
+use std::panic;
+use std::process::ExitCode;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    #[clap(value_hint = ValueHint::FilePath, value_name = "PATH")]
-    pub path: String,
+    #[clap(
+        value_hint = ValueHint::FilePath,
+        value_name = "PATH",
+        required_unless_present = "project"
+    )]
+    pub path: Option<String>,
     #[clap(long, short)]
     pub remappings: Option<String>,
+    /// Analyze every source file of the Foundry project in DIR, reading its `foundry.toml` and
+    /// remappings
+    #[clap(
+        long,
+        value_hint = ValueHint::DirPath,
+        value_name = "DIR",
+        conflicts_with_all = ["path", "remappings"]
+    )]
+    pub project: Option<String>,
+    /// Also search DIR for imports that aren't relative, like solc's `--include-path`
+    #[clap(long, value_hint = ValueHint::DirPath, value_name = "DIR")]
+    pub include_path: Vec<PathBuf>,
     #[clap(long, short)]
     pub contracts: Vec<String>,
     #[clap(long, short)]
@@ -45,12 +77,100 @@ struct Args {
     pub open_dot: bool,
     #[clap(long, short)]
     pub eval: Option<bool>,
+    /// Show bounds as simplified symbolic expressions, e.g. `x + 5`, instead of evaluating them
+    #[clap(long, default_value = "false")]
+    pub simplify: bool,
     #[clap(long, short)]
     pub show_inits: Option<bool>,
     #[clap(long, short)]
     pub access_query: Vec<String>,
     #[clap(long, short)]
     pub write_query: Vec<String>,
+    /// Report the reachable `Panic(uint256)` sites of each function, i.e. out of bounds indexing
+    /// or popping an empty array, and the paths that reach them
+    #[clap(long, short, default_value = "false")]
+    pub panics: bool,
+    /// Write one SMT-LIB2 (QF_BV) script per execution path of each function into DIR, asserting
+    /// the conditions along that path
+    #[clap(long, value_hint = ValueHint::DirPath, value_name = "DIR")]
+    pub smt: Option<String>,
+    /// Command to check paths with, reading an SMT-LIB2 script from stdin. Paths it finds
+    /// `unsat` are dropped
+    #[cfg(feature = "smt")]
+    #[clap(long, value_name = "CMD", default_value = shared::range::smt::DEFAULT_SOLVER)]
+    pub solver: String,
+    /// Track relations between variables (i.e. `x < y + 5`) to carry requirements on one variable
+    /// over to the others
+    #[clap(long, default_value = "false")]
+    pub relational: bool,
+    /// Number of disjoint intervals a range may hold before neighbouring intervals are merged
+    #[clap(long, value_name = "N", default_value_t = DEFAULT_INTERVAL_CAP)]
+    pub max_intervals: usize,
+    /// Print how effective the range evaluation cache was once analysis finishes
+    #[clap(long, default_value = "false")]
+    pub cache_stats: bool,
+    /// Format of the reports. `json` covers the bounds reports and `sarif` the panic and query
+    /// findings, with every other report printed to stderr
+    #[clap(long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+    /// Also write a static HTML page to explore the fork tree of each analyzed function
+    #[clap(long, value_hint = ValueHint::FilePath, value_name = "FILE")]
+    pub html: Option<String>,
+    /// Read settings from FILE instead of the `pyrometer.toml` found in the project, or in the
+    /// directory of the file analyzed or above it
+    #[clap(long, value_hint = ValueHint::FilePath, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+    /// Only report findings that aren't in the baseline at FILE, exiting with an error if there
+    /// are any
+    #[clap(long, value_hint = ValueHint::FilePath, value_name = "FILE")]
+    pub baseline: Option<PathBuf>,
+    /// Record every finding reported in a baseline at FILE, exiting successfully unless the
+    /// analyzer hit an internal error
+    #[clap(long, value_hint = ValueHint::FilePath, value_name = "FILE")]
+    pub write_baseline: Option<PathBuf>,
+    /// Exit with status 1 if any finding shown is at least this severe: `note`, `warning` or
+    /// `error`
+    #[clap(long, value_name = "SEVERITY", default_value = "warning")]
+    pub fail_on: Severity,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+    Sarif,
+}
+
+/// The exit status of a run
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Status {
+    /// The analysis completed without any finding at or above `--fail-on`
+    Clean = 0,
+    /// Findings at or above `--fail-on` were shown
+    Findings = 1,
+    /// A file or setting pyrometer was given couldn't be used, like the arguments clap exits
+    /// with this status on
+    Usage = 2,
+    /// Constructs the analyzer doesn't support were skipped, or imports couldn't be found, so
+    /// findings may be missing
+    Unsupported = 3,
+    /// The analyzer failed on its own error, recovered from or not, or its results couldn't be
+    /// written
+    InternalError = 4,
+}
+
+/// Why a run stopped short of a status
+enum RunError {
+    /// A file or setting pyrometer was given couldn't be used
+    Usage(String),
+    /// The results of the analysis couldn't be written
+    Output(String),
+}
+
+impl From<String> for RunError {
+    fn from(err: String) -> Self {
+        RunError::Usage(err)
+    }
 }
 
 pub fn subscriber() {
@@ -60,92 +180,409 @@ pub fn subscriber() {
         .init()
 }
 
-fn main() {
+/// Writes an SMT-LIB2 script for every path of the context into `dir`, one file per path
+fn write_smt_scripts(dir: &str, ctx: ContextNode, analyzer: &Analyzer) -> Result<(), String> {
+    fs::create_dir_all(dir)
+        .map_err(|err| format!("Could not create SMT output directory {dir:?}: {err}"))?;
+    let prefix = ctx
+        .maybe_associated_contract(analyzer)
+        .map(|contract| format!("{}.", contract.name(analyzer)))
+        .unwrap_or_default();
+    SmtScript::for_all_paths(ctx, analyzer)
+        .iter()
+        .try_for_each(|script| {
+            let file_name: String = format!("{prefix}{}", script.path)
+                .chars()
+                .map(|c| {
+                    if c.is_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                        c
+                    } else {
+                        '_'
+                    }
+                })
+                .collect();
+            let path = PathBuf::from(dir).join(format!("{file_name}.smt2"));
+            fs::write(&path, script.to_string())
+                .map_err(|err| format!("Could not write SMT script {path:?}: {err}"))
+        })
+}
+
+/// The `pyrometer.toml` passed with `--config`, else the one found from the project or the
+/// analyzed file, else the defaults
+fn load_settings(args: &Args) -> Result<PyrometerConfig, String> {
+    let path = match &args.config {
+        Some(path) => Some(path.clone()),
+        None => {
+            let dir = match (&args.project, &args.path) {
+                (Some(project), _) => PathBuf::from(project),
+                (None, Some(path)) => Path::new(path)
+                    .parent()
+                    .map(Path::to_path_buf)
+                    .unwrap_or_default(),
+                (None, None) => PathBuf::new(),
+            };
+            let dir = fs::canonicalize(&dir).unwrap_or(dir);
+            PyrometerConfig::find(&dir)
+        }
+    };
+    match path {
+        Some(path) => PyrometerConfig::load(&path),
+        None => Ok(PyrometerConfig::default()),
+    }
+}
+
+/// Where reports go in the chosen format. Structured formats are collected and printed to
+/// stdout together once analysis finishes, so any report they don't cover goes to stderr
+struct Output {
+    format: Format,
+    /// The contents of each source file, to locate spans by line and column
+    sources: BTreeMap<String, String>,
+    /// The project root, which SARIF locations are relative to
+    root: PathBuf,
+    json: Vec<JsonFunctionBounds>,
+    sarif: Vec<SarifResult>,
+    html: Option<(String, HtmlReport)>,
+    /// Which analyzers' reports are shown, and with what severity
+    settings: PyrometerConfig,
+    /// Findings disabled by comments in the sources
+    suppressions: Suppressions,
+    /// Known findings, which aren't shown
+    baseline: Option<Baseline>,
+    /// Every finding that wasn't suppressed, to write a baseline with
+    fingerprints: Vec<Fingerprint>,
+    /// Findings shown at or above this severity fail the run
+    fail_on: Severity,
+    /// How many findings were shown at or above `fail_on`
+    failing: usize,
+    /// How many unsupported constructs and unresolved imports were shown
+    incomplete: usize,
+    internal_error: bool,
+}
+
+impl Output {
+    fn bounds(
+        &mut self,
+        analysis: &CLIFunctionVarsBoundAnalysis,
+        src: &mut impl Cache<String>,
+        analyzer: &Analyzer,
+    ) {
+        if let Some((_, html)) = &mut self.html {
+            html.add_function(analysis, analyzer);
+        }
+        if self.settings.enabled(&rule_id(&analysis.report_kind())) == Some(false) {
+            return;
+        }
+        match self.format {
+            Format::Text => analysis.print_reports(src, analyzer),
+            Format::Json => self.json.push(analysis.to_json(analyzer, &self.sources)),
+            Format::Sarif => analysis.eprint_reports(src, analyzer),
+        }
+    }
+
+    fn finding(
+        &mut self,
+        report: &(impl SarifDisplay + Findings),
+        src: &mut impl Cache<String>,
+        analyzer: &Analyzer,
+    ) {
+        let shown = self.show(report, src, analyzer);
+        if self.severity(report) >= self.fail_on {
+            self.failing += shown;
+        }
+    }
+
+    /// Reports constructs that were skipped, which leave the analysis incomplete rather than
+    /// being findings about the code, so are never suppressed or put in a baseline
+    fn unsupported(
+        &mut self,
+        analysis: &UnsupportedAnalysis,
+        src: &mut impl Cache<String>,
+        analyzer: &Analyzer,
+    ) {
+        self.internal_error |= analysis.internal_error;
+        if self.display(analysis, src, analyzer) {
+            self.incomplete += analysis.constructs.len();
+        }
+    }
+
+    /// Reports imports that couldn't be found, whose definitions the analysis is missing, so
+    /// are never suppressed or put in a baseline
+    fn unresolved_import(
+        &mut self,
+        report: &UnresolvedImport,
+        src: &mut impl Cache<String>,
+        analyzer: &Analyzer,
+    ) {
+        if self.display(report, src, analyzer) {
+            self.incomplete += 1;
+        }
+    }
+
+    /// The configured severity of a report's findings, else that of its kind
+    fn severity(&self, report: &impl ReportDisplay) -> Severity {
+        let kind = report.report_kind();
+        self.settings
+            .severity(&rule_id(&kind))
+            .unwrap_or_else(|| level(&kind).parse().expect("Unknown SARIF level"))
+    }
+
+    /// Shows the findings of a report that aren't disabled, suppressed or in the baseline,
+    /// returning how many were shown
+    fn show(
+        &mut self,
+        report: &(impl SarifDisplay + Findings),
+        src: &mut impl Cache<String>,
+        analyzer: &Analyzer,
+    ) -> usize {
+        if self.settings.enabled(&rule_id(&report.report_kind())) == Some(false) {
+            return 0;
+        }
+        let retained = report.retain_findings(analyzer, &self.sources, |fingerprint, span| {
+            if self
+                .suppressions
+                .suppresses(&fingerprint.rule, span, &self.sources)
+            {
+                return false;
+            }
+            self.fingerprints.push(fingerprint.clone());
+            match &mut self.baseline {
+                Some(baseline) => !baseline.accept(fingerprint),
+                None => true,
+            }
+        });
+        let report = match retained {
+            Some(report) => report,
+            None => return 0,
+        };
+        self.display(&report, src, analyzer);
+        report.findings(analyzer, &self.sources).len()
+    }
+
+    /// Shows a report in the chosen format unless its kind is disabled, returning whether it
+    /// was shown
+    fn display(
+        &mut self,
+        report: &impl SarifDisplay,
+        src: &mut impl Cache<String>,
+        analyzer: &Analyzer,
+    ) -> bool {
+        let rule = rule_id(&report.report_kind());
+        if self.settings.enabled(&rule) == Some(false) {
+            return false;
+        }
+        match self.format {
+            Format::Text => report.print_reports(src, analyzer),
+            Format::Json => report.eprint_reports(src, analyzer),
+            Format::Sarif => {
+                let mut results = report.sarif_results(analyzer, &self.sources);
+                if let Some(severity) = self.settings.severity(&rule) {
+                    results
+                        .iter_mut()
+                        .for_each(|result| result.level = severity.as_str().to_string());
+                }
+                self.sarif.extend(results)
+            }
+        }
+        true
+    }
+
+    /// Why the run should fail, if it should, noting it on stderr
+    fn status(&self) -> Status {
+        let known = if self.baseline.is_some() {
+            " not in the baseline"
+        } else {
+            ""
+        };
+        if self.internal_error {
+            eprintln!("The analyzer hit an internal error");
+            Status::InternalError
+        } else if self.failing > 0 {
+            eprintln!(
+                "{} finding(s){known} at or above {}",
+                self.failing,
+                self.fail_on.as_str()
+            );
+            Status::Findings
+        } else if self.incomplete > 0 {
+            eprintln!(
+                "{} unsupported construct(s) or unresolved import(s) left the analysis incomplete",
+                self.incomplete
+            );
+            Status::Unsupported
+        } else {
+            Status::Clean
+        }
+    }
+
+    fn finish(self) -> Result<(), String> {
+        if let Some((path, html)) = &self.html {
+            fs::write(path, html.to_html())
+                .map_err(|err| format!("Could not write HTML report {path:?}: {err}"))?;
+        }
+        let out = match self.format {
+            Format::Text => return Ok(()),
+            Format::Json => serde_json::to_string_pretty(&self.json),
+            Format::Sarif => serde_json::to_string_pretty(&SarifLog::new(self.sarif, &self.root)),
+        };
+        println!("{}", out.expect("Could not serialize reports"));
+        Ok(())
+    }
+}
+
+fn main() -> ExitCode {
     subscriber();
     let args = Args::parse();
-    let path_str = args.path.to_string();
+    // a panic escaping the analysis is still an internal error to whatever runs pyrometer
+    let status = match panic::catch_unwind(|| run(args)) {
+        Ok(Ok(status)) => status,
+        Ok(Err(RunError::Usage(err))) => {
+            eprintln!("error: {err}");
+            Status::Usage
+        }
+        Ok(Err(RunError::Output(err))) => {
+            eprintln!("error: {err}");
+            Status::InternalError
+        }
+        Err(_) => Status::InternalError,
+    };
+    ExitCode::from(status as u8)
+}
+
+/// Runs the analysis, failing before it if a file or setting it was given can't be used, or
+/// after it if its results can't be written
+fn run(args: Args) -> Result<Status, RunError> {
     let verbosity = args.verbosity;
+    let mut settings = load_settings(&args)?;
     let config = match verbosity {
         0 => ReportConfig {
-            eval_bounds: args.eval.unwrap_or(true),
+            eval_bounds: true,
             simplify_bounds: false,
             show_tmps: false,
             show_consts: false,
             show_subctxs: true,
-            show_initial_bounds: args.show_inits.unwrap_or(false),
+            show_initial_bounds: false,
             show_all_lines: false,
         },
         1 => ReportConfig {
-            eval_bounds: args.eval.unwrap_or(true),
+            eval_bounds: true,
             simplify_bounds: false,
             show_tmps: false,
             show_consts: true,
             show_subctxs: true,
-            show_initial_bounds: args.show_inits.unwrap_or(false),
+            show_initial_bounds: false,
             show_all_lines: false,
         },
         2 => ReportConfig {
-            eval_bounds: args.eval.unwrap_or(true),
+            eval_bounds: true,
             simplify_bounds: false,
             show_tmps: true,
             show_consts: true,
             show_subctxs: true,
-            show_initial_bounds: args.show_inits.unwrap_or(false),
+            show_initial_bounds: false,
             show_all_lines: false,
         },
         3 => ReportConfig {
-            eval_bounds: args.eval.unwrap_or(true),
+            eval_bounds: true,
             simplify_bounds: false,
             show_tmps: true,
             show_consts: true,
             show_subctxs: true,
-            show_initial_bounds: args.show_inits.unwrap_or(true),
+            show_initial_bounds: true,
             show_all_lines: false,
         },
         4 => ReportConfig {
-            eval_bounds: args.eval.unwrap_or(true),
+            eval_bounds: true,
             simplify_bounds: false,
             show_tmps: true,
             show_consts: true,
             show_subctxs: true,
-            show_initial_bounds: args.show_inits.unwrap_or(true),
+            show_initial_bounds: true,
             show_all_lines: true,
         },
         _ => ReportConfig {
-            eval_bounds: args.eval.unwrap_or(true),
+            eval_bounds: true,
             simplify_bounds: false,
             show_tmps: true,
             show_consts: true,
             show_subctxs: true,
-            show_initial_bounds: args.show_inits.unwrap_or(true),
+            show_initial_bounds: true,
             show_all_lines: true,
         },
     };
-
-    let sol = fs::read_to_string(args.path.clone()).expect("Could not find file");
+    let mut config = settings.report.apply(config);
+    // flags take precedence over the configuration file
+    if args.simplify {
+        config.simplify_bounds = true;
+        config.eval_bounds = false;
+    }
+    if let Some(eval) = args.eval {
+        config.eval_bounds = eval;
+    }
+    if let Some(show_inits) = args.show_inits {
+        config.show_initial_bounds = show_inits;
+    }
+    if args.panics {
+        settings
+            .analyzers
+            .entry("panic".to_string())
+            .or_default()
+            .enabled = Some(true);
+    }
+    let panics = settings.enabled("panic").unwrap_or(false);
 
     let mut analyzer = Analyzer {
         root: env::current_dir().unwrap(),
         ..Default::default()
     };
-    if args.remappings.is_some() {
-        let remappings = args.remappings.unwrap();
-        analyzer.set_remappings_and_root(remappings);
+    analyzer.settings.relational = args.relational;
+    analyzer.settings.max_intervals = args.max_intervals;
+    #[cfg(feature = "smt")]
+    {
+        analyzer.settings.solver = args.solver.clone();
     }
+    analyzer.include_paths = args.include_path.clone();
     let t0 = std::time::Instant::now();
-    let (maybe_entry, mut all_sources) =
-        analyzer.parse(&sol, &PathBuf::from(args.path.clone()), true);
+    let (entries, all_sources) = if let Some(project) = &args.project {
+        let project = FoundryConfig::load(Path::new(project))?;
+        analyzer.set_foundry_project(&project);
+        analyzer.set_config(&settings);
+        let all_sources = analyzer.parse_project(&project.sources())?;
+        // files under `src` may have been parsed as imports of one another, so are picked out
+        // of every parsed source rather than just the entries
+        let src_dir = project.root.join(&project.src);
+        let src_dir = fs::canonicalize(&src_dir)
+            .map_err(|err| format!("Could not find the sources directory {src_dir:?}: {err}"))?;
+        let mut entries = all_sources
+            .iter()
+            .filter(|(_entry, name, _src, _num)| {
+                fs::canonicalize(name).is_ok_and(|path| path.starts_with(&src_dir))
+            })
+            .filter_map(|(entry, name, _src, _num)| Some((name.clone(), (*entry)?)))
+            .collect::<Vec<_>>();
+        entries.sort();
+        (
+            entries.into_iter().map(|(_name, entry)| entry).collect(),
+            all_sources,
+        )
+    } else {
+        let path = args.path.clone().unwrap();
+        let sol =
+            fs::read_to_string(&path).map_err(|err| format!("Could not read {path:?}: {err}"))?;
+        if let Some(remappings) = args.remappings.clone() {
+            analyzer.set_remappings_and_root(remappings);
+        }
+        analyzer.set_config(&settings);
+        let (maybe_entry, mut all_sources) = analyzer.parse(&sol, &PathBuf::from(&path), true);
+        all_sources.push((maybe_entry, path, sol, 0));
+        // a file that failed to parse has nothing to analyze, its syntax errors being reported
+        (maybe_entry.into_iter().collect::<Vec<_>>(), all_sources)
+    };
     let _parse_time = t0.elapsed().as_millis();
-    all_sources.push((maybe_entry, args.path, sol, 0));
-    let entry = maybe_entry.unwrap();
 
-    let mut file_mapping: BTreeMap<_, _> = vec![(0usize, path_str)].into_iter().collect();
-    file_mapping.extend(
-        all_sources
-            .iter()
-            .map(|(_entry, name, _src, num)| (*num, name.clone()))
-            .collect::<BTreeMap<_, _>>(),
-    );
+    let file_mapping: BTreeMap<_, _> = all_sources
+        .iter()
+        .map(|(_entry, name, _src, num)| (*num, name.clone()))
+        .collect();
 
     let mut source_map = sources(
         all_sources
@@ -154,85 +591,143 @@ fn main() {
             .collect::<HashMap<_, _>>(),
     );
 
+    let mut output = Output {
+        format: args.format,
+        sources: all_sources
+            .iter()
+            .map(|(_entry, name, src, _num)| (name.clone(), src.clone()))
+            .collect(),
+        root: env::current_dir().unwrap().join(&analyzer.root),
+        json: vec![],
+        sarif: vec![],
+        html: None,
+        settings: settings.clone(),
+        suppressions: Suppressions::default(),
+        baseline: args
+            .baseline
+            .as_ref()
+            .map(|path| Baseline::load(path))
+            .transpose()?,
+        fingerprints: vec![],
+        fail_on: args.fail_on,
+        failing: 0,
+        incomplete: 0,
+        internal_error: false,
+    };
+    output.suppressions = Suppressions::new(&output.sources);
+    if let Some(path) = &args.html {
+        output.html = Some((path.clone(), HtmlReport::new(output.sources.clone())));
+    }
+
+    // reported ahead of the queries, whose results may be off for what the imports define
+    analyzer.missing_imports.iter().for_each(|missing| {
+        output.unresolved_import(
+            &UnresolvedImport::new(&file_mapping, missing),
+            &mut source_map,
+            &analyzer,
+        )
+    });
+    analyzer.parse_errors.iter().for_each(|diagnostic| {
+        output.finding(
+            &SyntaxError::new(&file_mapping, diagnostic),
+            &mut source_map,
+            &analyzer,
+        )
+    });
+
     if args.dot {
         println!("{}", analyzer.dot_str_no_tmps());
     }
 
-    let all_contracts = analyzer
-        .search_children(entry, &Edge::Contract)
-        .into_iter()
-        .map(ContractNode::from)
-        .collect::<Vec<_>>();
     let _t1 = std::time::Instant::now();
-    if args.contracts.is_empty() {
-        let funcs = analyzer.search_children(entry, &Edge::Func);
-        for func in funcs.into_iter() {
-            if !args.funcs.is_empty() {
-                if args.funcs.iter().any(|analyze_for| {
-                    FunctionNode::from(func)
-                        .name(&analyzer)
-                        .starts_with(analyze_for)
-                }) {
-                    if let Some(ctx) = FunctionNode::from(func).maybe_body_ctx(&analyzer) {
-                        let analysis = analyzer
-                            .bounds_for_all(&file_mapping, ctx, config)
-                            .as_cli_compat(&file_mapping);
-                        analysis.print_reports(&mut source_map, &analyzer);
-                    }
-                }
-            } else if let Some(ctx) = FunctionNode::from(func).maybe_body_ctx(&analyzer) {
-                let analysis = analyzer
-                    .bounds_for_all(&file_mapping, ctx, config)
-                    .as_cli_compat(&file_mapping);
-                analysis.print_reports(&mut source_map, &analyzer);
+    // constructs the analysis couldn't handle, summarized once every function is analyzed
+    let mut unsupported = vec![];
+    // `--contracts` and `--funcs` take the place of the configuration file's globs
+    let mut selection = settings.clone();
+    if !args.contracts.is_empty() {
+        selection.contracts = NameFilter::default();
+    }
+    if !args.funcs.is_empty() {
+        selection.functions = NameFilter::default();
+    }
+    for entry in entries.iter().copied() {
+        let funcs = if args.contracts.is_empty() {
+            analyzer
+                .search_children(entry, &Edge::Func)
+                .into_iter()
+                .map(FunctionNode::from)
+                .filter(|func| {
+                    args.funcs.is_empty()
+                        || args
+                            .funcs
+                            .iter()
+                            .any(|analyze_for| func.name(&analyzer).starts_with(analyze_for))
+                })
+                .collect::<Vec<_>>()
+        } else {
+            analyzer
+                .search_children(entry, &Edge::Contract)
+                .into_iter()
+                .map(ContractNode::from)
+                .filter(|contract| args.contracts.contains(&contract.name(&analyzer)))
+                .flat_map(|contract| contract.funcs(&analyzer))
+                .filter(|func| args.funcs.is_empty() || args.funcs.contains(&func.name(&analyzer)))
+                .collect::<Vec<_>>()
+        };
+        let ctxs = funcs
+            .into_iter()
+            .filter(|func| {
+                let contract = func
+                    .maybe_associated_contract(&analyzer)
+                    .map(|contract| contract.name(&analyzer));
+                selection.analyzes(contract.as_deref(), &func.name(&analyzer))
+            })
+            .filter_map(|func| func.maybe_body_ctx(&analyzer))
+            .collect::<Vec<_>>();
+        for ctx in ctxs.into_iter() {
+            let analysis = analyzer
+                .bounds_for_all(&file_mapping, ctx, config)
+                .as_cli_compat(&file_mapping);
+            output.bounds(&analysis, &mut source_map, &analyzer);
+            unsupported.push(analyzer.unsupported_constructs(&file_mapping, ctx));
+            if panics {
+                output.finding(
+                    &analyzer.reachable_panics(&file_mapping, ctx),
+                    &mut source_map,
+                    &analyzer,
+                );
+            }
+            if let Some(dir) = &args.smt {
+                write_smt_scripts(dir, ctx, &analyzer).map_err(RunError::Output)?;
             }
         }
-    } else {
-        // println!("specified contracts: {:?}", all_contracts);
-        all_contracts
-            .iter()
-            .filter(|contract| args.contracts.contains(&contract.name(&analyzer)))
-            .for_each(|contract| {
-                let funcs = contract.funcs(&analyzer);
-                for func in funcs.into_iter() {
-                    if !args.funcs.is_empty() {
-                        if args.funcs.contains(&func.name(&analyzer)) {
-                            let ctx = func.body_ctx(&analyzer);
-                            let analysis = analyzer
-                                .bounds_for_all(&file_mapping, ctx, config)
-                                .as_cli_compat(&file_mapping);
-                            analysis.print_reports(&mut source_map, &analyzer);
-                        }
-                    } else {
-                        let ctx = func.body_ctx(&analyzer);
-                        let analysis = analyzer
-                            .bounds_for_all(&file_mapping, ctx, config)
-                            .as_cli_compat(&file_mapping);
-                        analysis.print_reports(&mut source_map, &analyzer);
-                    }
-                }
-            });
     }
 
+    // queries name a contract, which in a project may be in any of its files
+    let query_root = match entries.first() {
+        Some(entry) if args.project.is_none() => *entry,
+        _ => analyzer.entry,
+    };
     args.access_query.iter().for_each(|query| {
         let split: Vec<&str> = query.split('.').collect();
-        analyzer
-            .access_query(
-                entry,
-                &file_mapping,
-                config,
-                split[0].to_string(),
-                split[1].to_string(),
-            )
-            .print_reports(&mut source_map, &analyzer);
-        println!();
+        let report = analyzer.access_query(
+            query_root,
+            &file_mapping,
+            config,
+            split[0].to_string(),
+            split[1].to_string(),
+        );
+        output.finding(&report, &mut source_map, &analyzer);
+        if args.format == Format::Text {
+            println!();
+        }
     });
 
     args.write_query.iter().for_each(|query| {
         let split: Vec<&str> = query.split('.').collect();
         // println!("{:?}", split);
         if let Some(report) = analyzer.func_query(
-            entry,
+            query_root,
             &file_mapping,
             config,
             split[0].to_string(),
@@ -244,12 +739,36 @@ fn main() {
                 vec![],
             ),
         ) {
-            report.print_reports(&mut source_map, &analyzer);
+            output.finding(&report, &mut source_map, &analyzer);
+        }
+        if args.format == Format::Text {
+            println!();
         }
-        println!();
     });
 
+    unsupported
+        .iter()
+        .filter(|analysis| !analysis.constructs.is_empty())
+        .for_each(|analysis| output.unsupported(analysis, &mut source_map, &analyzer));
+
+    if let Some(path) = &args.write_baseline {
+        Baseline::new(output.fingerprints.clone())
+            .write(path)
+            .map_err(RunError::Output)?;
+    }
+    let mut status = output.status();
+    // recording a baseline accepts the findings in it, so they don't fail that run either
+    if args.write_baseline.is_some() && status == Status::Findings {
+        status = Status::Clean;
+    }
+    output.finish().map_err(RunError::Output)?;
+
+    if args.cache_stats {
+        eprintln!("{}", analyzer.eval_cache.stats());
+    }
+
     if args.open_dot {
         analyzer.open_dot()
     }
+    Ok(status)
 }
@@ -0,0 +1,87 @@
+use std::env;
+use std::process::Command;
+
+fn fixture(name: &str) -> String {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    format!("{manifest_dir}/../tests/test_data/{name}")
+}
+
+fn exit_status(args: &[&str]) -> i32 {
+    Command::new(env!("CARGO_BIN_EXE_pyrometer"))
+        .args(args)
+        .output()
+        .expect("Could not run pyrometer")
+        .status
+        .code()
+        .unwrap()
+}
+
+#[test]
+fn test_exit_status() {
+    let baseline = fixture("baseline.sol");
+    // bounds reports alone aren't findings
+    assert_eq!(exit_status(&[&baseline]), 0);
+    // reachable panics are errors, which fail the run by default
+    assert_eq!(exit_status(&[&baseline, "--panics"]), 1);
+    assert_eq!(
+        exit_status(&[&baseline, "--panics", "--fail-on", "error"]),
+        1
+    );
+    // as are files that can't be parsed
+    assert_eq!(exit_status(&[&fixture("syntax_error.sol")]), 1);
+
+    // skipped constructs and imports that can't be found leave the analysis incomplete,
+    // whatever the threshold
+    assert_eq!(
+        exit_status(&[&fixture("unsupported.sol"), "--fail-on", "note"]),
+        3
+    );
+    assert_eq!(
+        exit_status(&[&fixture("remapping_import.sol"), "--fail-on", "note"]),
+        3
+    );
+    // files and settings that can't be used are usage errors, like invalid arguments
+    assert_eq!(exit_status(&[&fixture("missing.sol")]), 2);
+    assert_eq!(
+        exit_status(&[&baseline, "--baseline", &fixture("missing.json")]),
+        2
+    );
+    assert_eq!(
+        exit_status(&[&baseline, "--config", &fixture("missing.toml")]),
+        2
+    );
+    assert_eq!(exit_status(&[&baseline, "--fail-on", "fatal"]), 2);
+    // results that can't be written once the analysis is done aren't usage errors
+    assert_eq!(
+        exit_status(&[
+            &baseline,
+            "--write-baseline",
+            &fixture("missing/baseline.json")
+        ]),
+        4
+    );
+}
+
+#[test]
+fn test_exit_status_with_baseline() {
+    let baseline = fixture("baseline.sol");
+    let path = env::temp_dir().join(format!("pyrometer-exit-status-{}.json", std::process::id()));
+    let path = path.to_string_lossy().to_string();
+    // recording the findings accepts them
+    assert_eq!(
+        exit_status(&[&baseline, "--panics", "--write-baseline", &path]),
+        0
+    );
+    // every finding is known, so nothing fails the run
+    assert_eq!(
+        exit_status(&[&baseline, "--panics", "--baseline", &path]),
+        0
+    );
+    std::fs::remove_file(&path).unwrap();
+
+    // skipped constructs aren't findings, so aren't recorded or hidden by a baseline
+    let unsupported = fixture("unsupported.sol");
+    assert_eq!(exit_status(&[&unsupported, "--write-baseline", &path]), 3);
+    assert_eq!(exit_status(&[&unsupported, "--baseline", &path]), 3);
+    std::fs::remove_file(&path).unwrap();
+}
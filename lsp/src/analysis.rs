@@ -0,0 +1,375 @@
+use pyrometer::context::analyzers::{
+    bounds::FunctionVarsBoundAnalyzer, panics::PanicAnalyzer, unsupported::UnsupportedAnalyzer,
+    LocSpan, LocStrSpan, ReportConfig,
+};
+use pyrometer::Analyzer;
+use shared::{
+    analyzer::Search,
+    nodes::FunctionNode,
+    range::{range_string::*, Range, SolcRange},
+    Edge, NodeIdx,
+};
+
+use ariadne::Span;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// Converts between byte offsets and LSP positions, whose characters are UTF-16 code units
+#[derive(Debug, Clone)]
+pub struct LineIndex {
+    text: String,
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    pub fn new(text: &str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            text: text.to_string(),
+            line_starts,
+        }
+    }
+
+    pub fn position(&self, offset: usize) -> Value {
+        let offset = offset.min(self.text.len());
+        let line = self
+            .line_starts
+            .partition_point(|start| *start <= offset)
+            .saturating_sub(1);
+        let character: usize = self.text[self.line_starts[line]..offset]
+            .chars()
+            .map(char::len_utf16)
+            .sum();
+        json!({ "line": line, "character": character })
+    }
+
+    pub fn range(&self, start: usize, end: usize) -> Value {
+        json!({ "start": self.position(start), "end": self.position(end) })
+    }
+
+    /// The byte offset of an LSP position, clamped to its line and to the end of the text
+    pub fn offset(&self, position: &Value) -> Option<usize> {
+        let line = position["line"].as_u64()? as usize;
+        let character = position["character"].as_u64()? as usize;
+        let start = match self.line_starts.get(line) {
+            Some(start) => *start,
+            None => return Some(self.text.len()),
+        };
+        let end = self
+            .line_starts
+            .get(line + 1)
+            .copied()
+            .unwrap_or(self.text.len());
+        let mut units = 0;
+        for (i, c) in self.text[start..end].char_indices() {
+            if units >= character {
+                return Some(start + i);
+            }
+            units += c.len_utf16();
+        }
+        Some(end)
+    }
+
+    /// The identifier around a byte offset
+    pub fn word_at(&self, offset: usize) -> Option<&str> {
+        let is_ident = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+        let start = self.text[..offset]
+            .rfind(|c| !is_ident(c))
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let end = self.text[offset..]
+            .find(|c| !is_ident(c))
+            .map(|i| offset + i)
+            .unwrap_or(self.text.len());
+        (start < end).then(|| &self.text[start..end])
+    }
+}
+
+/// The evaluated bounds of a variable at some point along a context path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoundEntry {
+    pub name: String,
+    pub path: String,
+    /// The byte span of the function the path belongs to
+    pub func: (usize, usize),
+    pub span: (usize, usize),
+    /// Whether these are the bounds the variable was declared with
+    pub init: bool,
+    /// The bounds, e.g. `∈ [ 6, 2**256 - 1 ]`
+    pub bounds: String,
+}
+
+/// What is known about a saved document: the bounds of its variables and its diagnostics
+#[derive(Debug, Clone, Default)]
+pub struct DocumentAnalysis {
+    pub entries: Vec<BoundEntry>,
+    pub diagnostics: Vec<Value>,
+}
+
+fn describe(range: &SolcRange, analyzer: &Analyzer) -> String {
+    let min = range
+        .evaled_range_min(analyzer)
+        .to_range_string(false, analyzer)
+        .s;
+    let max = range
+        .evaled_range_max(analyzer)
+        .to_range_string(true, analyzer)
+        .s;
+    let mut s = if min == max {
+        format!("== {min}")
+    } else {
+        format!("∈ [ {min}, {max} ]")
+    };
+    let mut excls = range
+        .range_exclusions()
+        .iter()
+        .map(|excl| excl.to_range_string(false, analyzer).s)
+        .collect::<Vec<_>>();
+    excls.dedup();
+    if !excls.is_empty() {
+        s.push_str(&format!(" && ∉ {{ {} }}", excls.join(", ")));
+    }
+    s
+}
+
+fn diagnostic(index: &LineIndex, span: &LocStrSpan, severity: u8, msg: String) -> Value {
+    json!({
+        "range": index.range(span.start(), span.end()),
+        "severity": severity,
+        "source": "pyrometer",
+        "message": msg,
+    })
+}
+
+/// An error for each import of the document that matched no file. The document is the first
+/// file parsed, so is file 0
+pub fn missing_import_diagnostics(analyzer: &Analyzer, index: &LineIndex) -> Vec<Value> {
+    analyzer
+        .missing_imports
+        .iter()
+        .map(|missing| (LocSpan(missing.loc), missing))
+        .filter(|(span, _)| *span.source() == 0)
+        .map(|(span, missing)| {
+            let searched = missing
+                .searched
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect::<Vec<_>>();
+            json!({
+                "range": index.range(span.start(), span.end()),
+                "severity": 1,
+                "source": "pyrometer",
+                "message": format!(
+                    "Could not find a file for import \"{}\". Searched: {}",
+                    missing.path,
+                    searched.join(", ")
+                ),
+            })
+        })
+        .collect()
+}
+
+/// An error for each syntax error of the document, which leaves it unanalyzed
+pub fn syntax_error_diagnostics(analyzer: &Analyzer, index: &LineIndex) -> Vec<Value> {
+    analyzer
+        .parse_errors
+        .iter()
+        .map(|diagnostic| (LocSpan(diagnostic.loc), diagnostic))
+        .filter(|(span, _)| *span.source() == 0)
+        .map(|(span, diagnostic)| {
+            json!({
+                "range": index.range(span.start(), span.end()),
+                "severity": 1,
+                "source": "pyrometer",
+                "message": format!("Could not parse: {}", diagnostic.message),
+            })
+        })
+        .collect()
+}
+
+impl DocumentAnalysis {
+    /// Analyzes every function of a parsed document. `file` is the name the document was
+    /// parsed under, and `file_mapping` maps file numbers to names
+    pub fn new(
+        analyzer: &Analyzer,
+        entry: NodeIdx,
+        file: &str,
+        file_mapping: &BTreeMap<usize, String>,
+        index: &LineIndex,
+    ) -> Self {
+        let config = ReportConfig {
+            show_consts: true,
+            ..Default::default()
+        };
+        // the search is recursive, so this includes the functions of contracts
+        let funcs = analyzer
+            .search_children(entry, &Edge::Func)
+            .into_iter()
+            .map(FunctionNode::from)
+            .collect::<Vec<_>>();
+
+        let mut analysis = Self::default();
+        let mut killed: BTreeMap<LocStrSpan, Vec<String>> = BTreeMap::default();
+        let mut panics: BTreeMap<LocStrSpan, String> = BTreeMap::default();
+        let mut unsupported: BTreeMap<LocStrSpan, String> = BTreeMap::default();
+        funcs
+            .iter()
+            .filter_map(|func| func.maybe_body_ctx(analyzer))
+            .for_each(|ctx| {
+                let bounds = analyzer.bounds_for_all(file_mapping, ctx, config);
+                let func = (bounds.ctx_loc.start(), bounds.ctx_loc.end());
+                bounds.vars_by_ctx.iter().for_each(|(ctx, analyses)| {
+                    let path = ctx.path(analyzer);
+                    analyses.iter().for_each(|bound| {
+                        let init = bound
+                            .var_def
+                            .1
+                            .as_ref()
+                            .map(|range| (true, &bound.var_def.0, range));
+                        init.into_iter()
+                            .chain(
+                                bound
+                                    .bound_changes
+                                    .iter()
+                                    .map(|(span, range)| (false, span, range)),
+                            )
+                            .filter(|(_, span, _)| span.source() == file)
+                            .for_each(|(init, span, range)| {
+                                analysis.entries.push(BoundEntry {
+                                    name: bound.var_display_name.clone(),
+                                    path: path.clone(),
+                                    func,
+                                    span: (span.start(), span.end()),
+                                    init,
+                                    bounds: describe(range, analyzer),
+                                })
+                            });
+                    });
+                });
+
+                analyzer
+                    .reachable_panics(file_mapping, ctx)
+                    .panics
+                    .into_iter()
+                    .for_each(|(span, (code, _array, paths))| {
+                        panics.insert(
+                            span,
+                            format!("{code} is reachable, via: {}", paths.join(", ")),
+                        );
+                    });
+
+                analyzer
+                    .unsupported_constructs(file_mapping, ctx)
+                    .constructs
+                    .into_iter()
+                    .for_each(|(span, (reason, paths))| {
+                        unsupported.insert(span, format!("{reason}, via: {}", paths.join(", ")));
+                    });
+
+                let mut stack = vec![ctx];
+                while let Some(curr) = stack.pop() {
+                    if let Some(loc) = curr.killed_loc(analyzer) {
+                        killed
+                            .entry(LocStrSpan::new(file_mapping, loc))
+                            .or_default()
+                            .push(curr.path(analyzer));
+                    }
+                    let underlying = curr.underlying(analyzer);
+                    stack.extend(underlying.forks.iter());
+                    stack.extend(underlying.children.iter());
+                }
+            });
+
+        analysis.diagnostics.extend(
+            panics
+                .iter()
+                .filter(|(span, _)| span.source() == file)
+                .map(|(span, msg)| diagnostic(index, span, 1, msg.clone())),
+        );
+        analysis.diagnostics.extend(
+            killed
+                .iter()
+                // a reachable panic kills its fork, and is the more useful message
+                .filter(|(span, _)| span.source() == file && !panics.contains_key(*span))
+                .map(|(span, paths)| {
+                    let mut paths = paths.clone();
+                    paths.sort();
+                    paths.dedup();
+                    diagnostic(
+                        index,
+                        span,
+                        2,
+                        format!(
+                            "Execution guaranteed to revert here, via: {}",
+                            paths.join(", ")
+                        ),
+                    )
+                }),
+        );
+        analysis.diagnostics.extend(
+            unsupported
+                .iter()
+                .filter(|(span, _)| span.source() == file)
+                .map(|(span, msg)| diagnostic(index, span, 2, msg.clone())),
+        );
+        analysis
+    }
+
+    /// Markdown listing the bounds of the variable under `offset`, as of the latest change
+    /// before it along each context path through the enclosing function
+    pub fn hover(&self, index: &LineIndex, offset: usize) -> Option<String> {
+        let name = index.word_at(offset)?;
+        let mut by_path: BTreeMap<&str, &BoundEntry> = BTreeMap::default();
+        self.entries
+            .iter()
+            .filter(|entry| {
+                entry.name == name
+                    && entry.func.0 <= offset
+                    && offset <= entry.func.1
+                    && entry.span.0 <= offset
+            })
+            .for_each(|entry| {
+                let latest = by_path.entry(&entry.path).or_insert(entry);
+                if entry.span.0 > latest.span.0 {
+                    *latest = entry;
+                }
+            });
+        if by_path.is_empty() {
+            return None;
+        }
+        let lines = by_path
+            .iter()
+            .map(|(path, entry)| format!("- `{path}`: `{name} {}`", entry.bounds))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Some(format!("**{name}**\n\n{lines}"))
+    }
+
+    /// An inlay hint after each change to a variable's bounds within the byte range, merging
+    /// the bounds of every path through it
+    pub fn inlay_hints(&self, index: &LineIndex, start: usize, end: usize) -> Vec<Value> {
+        let mut hints: BTreeMap<(usize, &str), Vec<&str>> = BTreeMap::default();
+        self.entries
+            .iter()
+            .filter(|entry| !entry.init && start <= entry.span.1 && entry.span.1 <= end)
+            .for_each(|entry| {
+                let bounds = hints.entry((entry.span.1, &entry.name)).or_default();
+                if !bounds.contains(&entry.bounds.as_str()) {
+                    bounds.push(&entry.bounds);
+                }
+            });
+        hints
+            .into_iter()
+            .map(|((offset, name), bounds)| {
+                json!({
+                    "position": index.position(offset),
+                    "label": format!("{name} {}", bounds.join(" | ")),
+                    "kind": 1,
+                    "paddingLeft": true,
+                })
+            })
+            .collect()
+    }
+}
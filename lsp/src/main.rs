@@ -0,0 +1,28 @@
+use lsp::server::Server;
+use lsp::transport::{read_message, write_message};
+use tracing_subscriber::prelude::*;
+
+use std::io::{self, BufReader};
+
+pub fn subscriber() {
+    // stdout carries the protocol, so logs go to stderr
+    tracing_subscriber::Registry::default()
+        .with(tracing_subscriber::filter::EnvFilter::from_default_env())
+        .with(tracing_subscriber::fmt::layer().with_writer(io::stderr))
+        .init()
+}
+
+fn main() {
+    subscriber();
+    let mut stdin = BufReader::new(io::stdin());
+    let mut stdout = io::stdout();
+    let mut server = Server::default();
+    while let Some(msg) = read_message(&mut stdin).expect("Could not read message") {
+        if msg["method"] == "exit" {
+            std::process::exit(if server.shutdown { 0 } else { 1 });
+        }
+        server.handle(&msg).iter().for_each(|reply| {
+            write_message(&mut stdout, reply).expect("Could not write message");
+        });
+    }
+}
@@ -0,0 +1,312 @@
+use crate::analysis::{
+    missing_import_diagnostics, syntax_error_diagnostics, DocumentAnalysis, LineIndex,
+};
+use pyrometer::{
+    config::{PyrometerConfig, CONFIG_FILE},
+    foundry::FoundryConfig,
+    Analyzer,
+};
+
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+/// A workspace folder, which sets how the imports of its documents are resolved. Its analyzer
+/// holds the imports parsed so far, which each analysis of one of its documents starts from
+#[derive(Debug, Default)]
+pub struct Workspace {
+    pub root: PathBuf,
+    pub analyzer: Analyzer,
+    /// The names of the files parsed into `analyzer`, by file number
+    pub files: BTreeMap<usize, String>,
+}
+
+impl Workspace {
+    /// A workspace resolving imports like forge when it is a Foundry project, or else with its
+    /// `remappings.txt` if there is one
+    pub fn new(root: PathBuf) -> Self {
+        let mut analyzer = Analyzer {
+            root: root.clone(),
+            ..Default::default()
+        };
+        let remappings = root.join("remappings.txt");
+        if root.join("foundry.toml").is_file() {
+            match FoundryConfig::load(&root) {
+                Ok(config) => analyzer.set_foundry_project(&config),
+                Err(err) => tracing::warn!("{err}"),
+            }
+        } else if remappings.is_file() {
+            analyzer.set_remappings_and_root(remappings.to_string_lossy().to_string());
+        }
+        let config = root.join(CONFIG_FILE);
+        if config.is_file() {
+            match PyrometerConfig::load(&config) {
+                Ok(config) => analyzer.set_config(&config),
+                Err(err) => tracing::warn!("{err}"),
+            }
+        }
+        Self {
+            root,
+            analyzer,
+            files: Default::default(),
+        }
+    }
+
+    /// An analyzer to parse a document with as file 0. Imports of the document the workspace
+    /// hasn't parsed yet are parsed once and kept, while saving one of them starts the
+    /// workspace over
+    fn analyzer_for(&mut self, text: &str, path: &Path) -> Analyzer {
+        let is_import = fs::canonicalize(path)
+            .map(|path| self.analyzer.imported_srcs.contains(path.as_os_str()))
+            .unwrap_or(false);
+        if is_import {
+            *self = Self::new(self.root.clone());
+        }
+        let imported = self.analyzer.parse_imports(text, path);
+        self.files.extend(
+            imported
+                .into_iter()
+                .map(|(_entry, name, _src, num)| (num, name)),
+        );
+        // the document records these itself when it is parsed, and they are tried again on
+        // the next save
+        self.analyzer.missing_imports.clear();
+        let mut analyzer = self.analyzer.clone();
+        analyzer.file_no = 0;
+        analyzer
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Document {
+    pub index: LineIndex,
+    pub analysis: DocumentAnalysis,
+}
+
+/// A language server answering with the bounds pyrometer finds. Documents are analyzed
+/// when opened and each time they are saved
+#[derive(Debug, Default)]
+pub struct Server {
+    pub workspaces: BTreeMap<PathBuf, Workspace>,
+    pub documents: BTreeMap<String, Document>,
+    pub shutdown: bool,
+}
+
+/// The path of a `file://` URI
+pub fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?;
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| path.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).ok().map(PathBuf::from)
+}
+
+fn response(id: &Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+impl Server {
+    /// Handles a message from the client, returning the messages to send back
+    pub fn handle(&mut self, msg: &Value) -> Vec<Value> {
+        let method = msg["method"].as_str().unwrap_or_default();
+        let params = &msg["params"];
+        let id = msg.get("id");
+        match (method, id) {
+            ("initialize", Some(id)) => {
+                self.initialize(params);
+                vec![response(
+                    id,
+                    json!({
+                        "capabilities": {
+                            "textDocumentSync": {
+                                "openClose": true,
+                                "change": 0,
+                                "save": { "includeText": true },
+                            },
+                            "hoverProvider": true,
+                            "inlayHintProvider": true,
+                        },
+                        "serverInfo": { "name": "pyrometer-lsp", "version": env!("CARGO_PKG_VERSION") },
+                    }),
+                )]
+            }
+            ("shutdown", Some(id)) => {
+                self.shutdown = true;
+                vec![response(id, Value::Null)]
+            }
+            ("textDocument/didOpen", _) => {
+                let doc = &params["textDocument"];
+                self.analyze(doc["uri"].as_str(), doc["text"].as_str())
+            }
+            ("textDocument/didSave", _) => self.analyze(
+                params["textDocument"]["uri"].as_str(),
+                params["text"].as_str(),
+            ),
+            ("textDocument/didClose", _) => {
+                let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+                self.documents.remove(uri);
+                vec![notification(
+                    "textDocument/publishDiagnostics",
+                    json!({ "uri": uri, "diagnostics": [] }),
+                )]
+            }
+            ("textDocument/hover", Some(id)) => {
+                let hover = self.document(params).and_then(|doc| {
+                    let offset = doc.index.offset(&params["position"])?;
+                    let value = doc.analysis.hover(&doc.index, offset)?;
+                    Some(json!({ "contents": { "kind": "markdown", "value": value } }))
+                });
+                vec![response(id, hover.unwrap_or(Value::Null))]
+            }
+            ("textDocument/inlayHint", Some(id)) => {
+                let hints = self
+                    .document(params)
+                    .and_then(|doc| {
+                        let start = doc.index.offset(&params["range"]["start"])?;
+                        let end = doc.index.offset(&params["range"]["end"])?;
+                        Some(doc.analysis.inlay_hints(&doc.index, start, end))
+                    })
+                    .unwrap_or_default();
+                vec![response(id, Value::Array(hints))]
+            }
+            (_, Some(id)) if !method.is_empty() => vec![json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": -32601, "message": format!("unhandled method: {method}") },
+            })],
+            // notifications we don't use, and responses to requests we never send
+            _ => vec![],
+        }
+    }
+
+    fn initialize(&mut self, params: &Value) {
+        let folders = params["workspaceFolders"]
+            .as_array()
+            .map(|folders| {
+                folders
+                    .iter()
+                    .filter_map(|folder| uri_to_path(folder["uri"].as_str()?))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let roots = if folders.is_empty() {
+            params["rootUri"]
+                .as_str()
+                .and_then(uri_to_path)
+                .into_iter()
+                .collect()
+        } else {
+            folders
+        };
+        roots.into_iter().for_each(|root| {
+            self.workspaces.insert(root.clone(), Workspace::new(root));
+        });
+    }
+
+    fn document(&self, params: &Value) -> Option<&Document> {
+        self.documents.get(params["textDocument"]["uri"].as_str()?)
+    }
+
+    /// The workspace a file belongs to: the innermost folder containing it, or else its own
+    /// directory
+    fn workspace_for(&mut self, path: &Path) -> &mut Workspace {
+        let root = self
+            .workspaces
+            .keys()
+            .filter(|root| path.starts_with(root))
+            .max_by_key(|root| root.components().count())
+            .cloned()
+            .unwrap_or_else(|| path.parent().unwrap_or(Path::new("/")).to_path_buf());
+        self.workspaces
+            .entry(root.clone())
+            .or_insert_with(|| Workspace::new(root))
+    }
+
+    /// Re-analyzes a document, reading it from disk if the client didn't send its text, and
+    /// publishes its diagnostics
+    fn analyze(&mut self, uri: Option<&str>, text: Option<&str>) -> Vec<Value> {
+        let (uri, path) = match uri.and_then(|uri| Some((uri, uri_to_path(uri)?))) {
+            Some(found) => found,
+            None => return vec![],
+        };
+        let text = match text
+            .map(str::to_string)
+            .or_else(|| fs::read_to_string(&path).ok())
+        {
+            Some(text) => text,
+            None => return vec![],
+        };
+        let index = LineIndex::new(&text);
+        let file = path.to_string_lossy().to_string();
+        let workspace = self.workspace_for(&path);
+
+        // the analyzer panics on code it can't handle, which shouldn't take the server down
+        let analyzed = panic::catch_unwind(AssertUnwindSafe(|| {
+            let mut analyzer = workspace.analyzer_for(&text, &path);
+            let (maybe_entry, _sources) = analyzer.parse(&text, &path, true);
+            let mut file_mapping = workspace.files.clone();
+            file_mapping.insert(0, file.clone());
+            let mut analysis = maybe_entry
+                .map(|entry| DocumentAnalysis::new(&analyzer, entry, &file, &file_mapping, &index))
+                .unwrap_or_default();
+            analysis
+                .diagnostics
+                .extend(missing_import_diagnostics(&analyzer, &index));
+            analysis
+                .diagnostics
+                .extend(syntax_error_diagnostics(&analyzer, &index));
+            analysis
+        }));
+        let analysis = match analyzed {
+            Ok(analysis) => analysis,
+            Err(err) => {
+                // the imports may have been left half parsed
+                *workspace = Workspace::new(workspace.root.clone());
+                let reason = err
+                    .downcast_ref::<String>()
+                    .cloned()
+                    .or_else(|| err.downcast_ref::<&str>().map(|s| s.to_string()))
+                    .unwrap_or_default();
+                DocumentAnalysis {
+                    entries: vec![],
+                    diagnostics: vec![json!({
+                        "range": index.range(0, 0),
+                        "severity": 1,
+                        "source": "pyrometer",
+                        "message": format!("pyrometer could not analyze this file: {reason}"),
+                    })],
+                }
+            }
+        };
+
+        let diagnostics = analysis.diagnostics.clone();
+        self.documents
+            .insert(uri.to_string(), Document { index, analysis });
+        vec![notification(
+            "textDocument/publishDiagnostics",
+            json!({ "uri": uri, "diagnostics": diagnostics }),
+        )]
+    }
+}
@@ -0,0 +1,236 @@
+use lsp::server::Server;
+use lsp::transport::{read_message, write_message};
+use serde_json::{json, Value};
+use std::env;
+use std::io::Cursor;
+
+fn fixture(name: &str) -> String {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    format!("file://{manifest_dir}/../tests/test_data/{name}")
+}
+
+fn open(server: &mut Server, uri: &str, text: &str) -> Vec<Value> {
+    server.handle(&json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didOpen",
+        "params": { "textDocument": { "uri": uri, "languageId": "solidity", "version": 1, "text": text } },
+    }))
+}
+
+fn request(server: &mut Server, method: &str, params: Value) -> Value {
+    let replies =
+        server.handle(&json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }));
+    replies[0]["result"].clone()
+}
+
+#[test]
+fn test_hover_and_inlay_hints() {
+    let uri = fixture("html_report.sol");
+    let mut server = Server::default();
+    let init = request(&mut server, "initialize", json!({ "rootUri": null }));
+    assert_eq!(init["capabilities"]["hoverProvider"], true);
+
+    let published = open(
+        &mut server,
+        &uri,
+        include_str!("../../tests/test_data/html_report.sol"),
+    );
+    assert_eq!(published[0]["method"], "textDocument/publishDiagnostics");
+    let diagnostics = published[0]["params"]["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0]["range"]["start"],
+        json!({ "line": 4, "character": 20 })
+    );
+    assert!(diagnostics[0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("branch(uint256).fork-0"));
+
+    // the `x` of `return x;` in the else branch
+    let hover = request(
+        &mut server,
+        "textDocument/hover",
+        json!({ "textDocument": { "uri": uri }, "position": { "line": 8, "character": 19 } }),
+    );
+    let value = hover["contents"]["value"].as_str().unwrap();
+    assert!(
+        value.contains("`branch(uint256).fork-1`: `x ∈ [ 1, 11 ]`"),
+        "{value}"
+    );
+
+    let nothing = request(
+        &mut server,
+        "textDocument/hover",
+        json!({ "textDocument": { "uri": uri }, "position": { "line": 1, "character": 0 } }),
+    );
+    assert!(nothing.is_null());
+
+    let hints = request(
+        &mut server,
+        "textDocument/inlayHint",
+        json!({
+            "textDocument": { "uri": uri },
+            "range": { "start": { "line": 0, "character": 0 }, "end": { "line": 100, "character": 0 } },
+        }),
+    );
+    let labels = hints
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|hint| hint["label"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert!(labels.contains(&"x ∈ [ 1, 11 ]"), "{labels:?}");
+}
+
+#[test]
+fn test_diagnostics() {
+    let mut server = Server::default();
+    let published = open(
+        &mut server,
+        &fixture("index_bounds.sol"),
+        include_str!("../../tests/test_data/index_bounds.sol"),
+    );
+    let diagnostics = published[0]["params"]["diagnostics"].as_array().unwrap();
+    let panic = diagnostics
+        .iter()
+        .find(|diag| diag["severity"] == 1)
+        .unwrap();
+    assert!(panic["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("Panic(0x32)"));
+    assert_eq!(
+        panic["range"]["start"],
+        json!({ "line": 4, "character": 15 })
+    );
+
+    // a file that can't be parsed has its syntax errors reported
+    let published = open(&mut server, &fixture("missing.sol"), "contract {");
+    let diagnostics = published[0]["params"]["diagnostics"].as_array().unwrap();
+    assert!(!diagnostics.is_empty());
+    assert!(diagnostics.iter().all(|diag| diag["severity"] == 1
+        && diag["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("Could not parse: ")));
+
+    let closed = server.handle(&json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didClose",
+        "params": { "textDocument": { "uri": fixture("index_bounds.sol") } },
+    }));
+    assert_eq!(closed[0]["params"]["diagnostics"], json!([]));
+}
+
+#[test]
+fn test_transport_roundtrip() {
+    let msg = json!({ "jsonrpc": "2.0", "id": 7, "method": "shutdown" });
+    let mut buf = vec![];
+    write_message(&mut buf, &msg).unwrap();
+    write_message(&mut buf, &msg).unwrap();
+    let mut reader = Cursor::new(buf);
+    assert_eq!(read_message(&mut reader).unwrap(), Some(msg.clone()));
+    assert_eq!(read_message(&mut reader).unwrap(), Some(msg));
+    assert_eq!(read_message(&mut reader).unwrap(), None);
+}
+
+#[test]
+fn test_missing_import_diagnostic() {
+    let uri = fixture("npm_imports/contracts/Missing.sol");
+    let mut server = Server::default();
+    request(&mut server, "initialize", json!({ "rootUri": null }));
+    let published = open(
+        &mut server,
+        &uri,
+        include_str!("../../tests/test_data/npm_imports/contracts/Missing.sol"),
+    );
+    let diagnostics = published[0]["params"]["diagnostics"].as_array().unwrap();
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0]["severity"], 1);
+    assert_eq!(
+        diagnostics[0]["range"],
+        json!({ "start": { "line": 2, "character": 7 }, "end": { "line": 2, "character": 36 } })
+    );
+    assert!(diagnostics[0]["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("Could not find a file for import \"@acme/contracts/Missing.sol\""));
+}
+
+#[test]
+fn test_unsupported_diagnostic() {
+    let uri = fixture("unsupported.sol");
+    let mut server = Server::default();
+    request(&mut server, "initialize", json!({ "rootUri": null }));
+    let published = open(
+        &mut server,
+        &uri,
+        include_str!("../../tests/test_data/unsupported.sol"),
+    );
+    let diagnostics = published[0]["params"]["diagnostics"].as_array().unwrap();
+    let do_while = diagnostics
+        .iter()
+        .find(|diagnostic| {
+            diagnostic["message"]
+                .as_str()
+                .unwrap()
+                .starts_with("Unsupported: do while loop")
+        })
+        .unwrap();
+    assert_eq!(do_while["severity"], 2);
+    assert_eq!(
+        do_while["range"]["start"],
+        json!({ "line": 11, "character": 8 })
+    );
+}
+
+#[test]
+fn test_workspace_reuses_imports() {
+    let root = fixture("foundry_project");
+    let mut server = Server::default();
+    request(&mut server, "initialize", json!({ "rootUri": root }));
+    let a = fixture("foundry_project/src/A.sol");
+    let a_text = include_str!("../../tests/test_data/foundry_project/src/A.sol");
+    let published = open(&mut server, &a, a_text);
+    assert_eq!(published[0]["params"]["diagnostics"], json!([]));
+    let workspace = server.workspaces.values().next().unwrap();
+    // `Dep.sol`, the file it imports and `Half.sol`
+    assert_eq!(workspace.files.len(), 3);
+    let nodes = workspace.analyzer.graph.node_count();
+
+    // saving again analyzes the document on top of the imports already parsed
+    let saved = server.handle(&json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didSave",
+        "params": { "textDocument": { "uri": a }, "text": a_text },
+    }));
+    assert_eq!(saved[0]["params"]["diagnostics"], json!([]));
+    let hover = request(
+        &mut server,
+        "textDocument/hover",
+        json!({ "textDocument": { "uri": a }, "position": { "line": 9, "character": 15 } }),
+    );
+    assert!(!hover.is_null());
+    let workspace = server.workspaces.values().next().unwrap();
+    assert_eq!(workspace.analyzer.graph.node_count(), nodes);
+
+    // as does another document sharing them
+    open(
+        &mut server,
+        &fixture("foundry_project/src/B.sol"),
+        include_str!("../../tests/test_data/foundry_project/src/B.sol"),
+    );
+    let workspace = server.workspaces.values().next().unwrap();
+    assert_eq!(workspace.files.len(), 3);
+    assert_eq!(workspace.analyzer.graph.node_count(), nodes);
+
+    // while saving one of the imports parses them again
+    server.handle(&json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/didSave",
+        "params": { "textDocument": { "uri": fixture("foundry_project/src/math/Half.sol") } },
+    }));
+    let workspace = server.workspaces.values().next().unwrap();
+    assert!(workspace.files.is_empty());
+}
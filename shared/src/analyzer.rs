@@ -1,4 +1,7 @@
 use crate::as_dot_str;
+use crate::range::eval_cache::EvalCache;
+use crate::range::interval_set::DEFAULT_INTERVAL_CAP;
+use crate::range::smt::{SmtCache, DEFAULT_SOLVER};
 use crate::range::Range;
 use crate::BlockNode;
 
@@ -15,10 +18,70 @@ use std::collections::BTreeSet;
 
 use petgraph::dot::Dot;
 use petgraph::{graph::*, Directed, Direction};
+use solang_parser::pt::Loc;
 use std::collections::HashMap;
 
+/// Why the analyzer gave up on part of a function
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AnalyzerError {
+    /// Solidity the analyzer doesn't handle yet
+    Unsupported(Loc, String),
+    /// The analyzer failed partway through a function, on something it should have handled
+    Internal(Loc, String),
+}
+
+impl AnalyzerError {
+    pub fn loc(&self) -> Loc {
+        match self {
+            AnalyzerError::Unsupported(loc, _) | AnalyzerError::Internal(loc, _) => *loc,
+        }
+    }
+
+    pub fn reason(&self) -> &str {
+        match self {
+            AnalyzerError::Unsupported(_, reason) | AnalyzerError::Internal(_, reason) => reason,
+        }
+    }
+}
+
+impl std::fmt::Display for AnalyzerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnalyzerError::Unsupported(_, reason) => write!(f, "Unsupported: {reason}"),
+            AnalyzerError::Internal(_, reason) => write!(f, "Analyzer error: {reason}"),
+        }
+    }
+}
+
+/// Settings that change how much the analyzer tracks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnalysisSettings {
+    /// Whether difference-bound relations between variables (i.e. `x < y + 5`) are tracked
+    pub relational: bool,
+    /// The number of disjoint intervals a range may hold before the closest neighbours are merged
+    pub max_intervals: usize,
+    /// The command paths are checked with when built with the `smt` feature, reading an
+    /// SMT-LIB2 script from stdin
+    pub solver: String,
+}
+
+impl Default for AnalysisSettings {
+    fn default() -> Self {
+        Self {
+            relational: false,
+            max_intervals: DEFAULT_INTERVAL_CAP,
+            solver: DEFAULT_SOLVER.to_string(),
+        }
+    }
+}
+
 pub trait AnalyzerLike: GraphLike {
     type Expr;
+    fn settings(&self) -> &AnalysisSettings;
+    /// The results of paths already checked with the solver, if the analyzer keeps them
+    fn smt_cache(&self) -> Option<&SmtCache> {
+        None
+    }
     fn builtin_fns(&self) -> &HashMap<String, Function>;
     fn builtin_fn_inputs(&self) -> &HashMap<String, (Vec<FunctionParam>, Vec<FunctionReturn>)>;
     fn builtins(&self) -> &HashMap<Builtin, NodeIdx>;
@@ -27,7 +90,7 @@ pub trait AnalyzerLike: GraphLike {
         if let Some(idx) = self.builtins().get(&builtin) {
             *idx
         } else {
-            let idx = self.add_node(Node::Builtin(builtin.clone()));
+            let idx = self.add_node(Node::Builtin(Box::new(builtin.clone())));
             self.builtins_mut().insert(builtin, idx);
             idx
         }
@@ -73,6 +136,11 @@ pub trait GraphLike {
             .expect("Index not in graph")
     }
 
+    /// The cache of evaluated variable bounds, if the graph keeps one
+    fn eval_cache(&self) -> Option<&EvalCache> {
+        None
+    }
+
     fn open_dot(&self)
     where
         Self: std::marker::Sized,
@@ -1,5 +1,6 @@
-use crate::analyzer::{AnalyzerLike, Search};
+use crate::analyzer::{AnalyzerError, AnalyzerLike, Search};
 use crate::nodes::FunctionNode;
+use crate::range::elem::RangeOp;
 use crate::ContractNode;
 use crate::StructNode;
 use std::collections::BTreeSet;
@@ -12,7 +13,9 @@ use std::collections::BTreeMap;
 use std::collections::HashMap;
 
 mod var;
+mod zone;
 pub use var::*;
+pub use zone::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
 pub enum ContextEdge {
@@ -45,6 +48,34 @@ pub enum ContextEdge {
     Range,
 }
 
+/// A Solidity `Panic(uint256)` error code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum PanicCode {
+    /// `Panic(0x31)`: `.pop()` was called on an empty array
+    EmptyArrayPop,
+    /// `Panic(0x32)`: an array, `bytes` or slice was accessed at an out-of-bounds index
+    IndexOutOfBounds,
+}
+
+impl PanicCode {
+    /// The code passed to `Panic(uint256)`
+    pub fn code(&self) -> u8 {
+        match self {
+            PanicCode::EmptyArrayPop => 0x31,
+            PanicCode::IndexOutOfBounds => 0x32,
+        }
+    }
+}
+
+impl std::fmt::Display for PanicCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PanicCode::EmptyArrayPop => write!(f, "Panic(0x31): pop on empty array"),
+            PanicCode::IndexOutOfBounds => write!(f, "Panic(0x32): index out of bounds"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct ModifierState {
     pub num: usize,
@@ -86,10 +117,18 @@ pub struct Context {
     /// Variables whose bounds are required to be met for this context fork to exist. i.e. a conditional operator
     /// like an if statement
     pub ctx_deps: HashMap<String, ContextVarNode>,
+    /// Relational facts (`x - y <= c`) between variables that hold along this context's path
+    pub zone: Zone,
     /// A string that represents the path taken from the root context (i.e. `fn_entry.fork.1`)
     pub path: String,
     /// Denotes whether this context was killed by an unsatisfiable require, assert, etc. statement
     pub killed: Option<Loc>,
+    /// Denotes whether this context reverted via a reachable `Panic(uint256)`, i.e. an out-of-bounds index access,
+    /// and the array it reverted on
+    pub panic: Option<(Loc, PanicCode, ContextVarNode)>,
+    /// Constructs the analyzer couldn't handle in this context, and why. The context's variables
+    /// are havocked at each, so it carries on knowing nothing about them
+    pub unsupported: Vec<AnalyzerError>,
     /// Denotes whether this context is a fork of another context
     pub is_fork: bool,
     /// Denotes whether this context is the result of a internal function call, and points to the FunctionNode
@@ -119,7 +158,10 @@ impl Context {
             path: fn_name,
             tmp_var_ctr: 0,
             killed: None,
+            panic: None,
+            unsupported: vec![],
             ctx_deps: Default::default(),
+            zone: Default::default(),
             is_fork: false,
             fn_call: None,
             ext_fn_call: None,
@@ -172,7 +214,10 @@ impl Context {
             fn_call,
             ext_fn_call,
             ctx_deps: parent_ctx.underlying(analyzer).ctx_deps.clone(),
+            zone: parent_ctx.underlying(analyzer).zone.clone(),
             killed: None,
+            panic: None,
+            unsupported: vec![],
             forks: vec![],
             children: vec![],
             tmp_var_ctr: parent_ctx.underlying(analyzer).tmp_var_ctr,
@@ -303,15 +348,11 @@ impl ContextNode {
             mapping
                 .into_values()
                 .map(|modifier_set| {
-                    let as_vec = modifier_set.iter().collect::<Vec<_>>();
-
-                    if as_vec.len() > 2 {
-                        panic!("3+ visible functions with the same name. This is invalid solidity")
-                    } else if as_vec.len() == 2 {
-                        as_vec[0].get_overriding(as_vec[1], analyzer)
-                    } else {
-                        *as_vec[0]
-                    }
+                    // a chain of overrides through several inherited contracts gives more
+                    // than two
+                    let mut iter = modifier_set.iter();
+                    let first = *iter.next().expect("names come from the functions");
+                    iter.fold(first, |acc, func| acc.get_overriding(func, analyzer))
                 })
                 .collect()
         } else {
@@ -362,15 +403,11 @@ impl ContextNode {
             mapping
                 .into_values()
                 .map(|funcs_set| {
-                    let as_vec = funcs_set.iter().collect::<Vec<_>>();
-
-                    if as_vec.len() > 2 {
-                        panic!("3+ visible functions with the same name. This is invalid solidity")
-                    } else if as_vec.len() == 2 {
-                        as_vec[0].get_overriding(as_vec[1], analyzer)
-                    } else {
-                        *as_vec[0]
-                    }
+                    // a chain of overrides through several inherited contracts gives more
+                    // than two
+                    let mut iter = funcs_set.iter();
+                    let first = *iter.next().expect("names come from the functions");
+                    iter.fold(first, |acc, func| acc.get_overriding(func, analyzer))
                 })
                 .collect()
         } else {
@@ -534,6 +571,31 @@ impl ContextNode {
             .map(|var| var.latest_version(analyzer))
     }
 
+    /// Gets the latest version of every index access into `arr` that is visible from this
+    /// context (i.e. `arr[a]`, `arr[b]`, etc.), one per key version used to access it
+    pub fn index_access_vars(
+        &self,
+        analyzer: &impl AnalyzerLike,
+        arr: ContextVarNode,
+    ) -> Vec<ContextVarNode> {
+        let arr = arr.origin(analyzer);
+        let mut seen = vec![];
+        let mut accesses = vec![];
+        let mut curr = Some(*self);
+        while let Some(ctx) = curr {
+            ctx.local_vars(analyzer).into_iter().for_each(|var| {
+                if let Some((accessed, key)) = var.index_access_parts(analyzer) {
+                    if !seen.contains(&key) && accessed.origin(analyzer) == arr {
+                        seen.push(key);
+                        accesses.push(var.latest_version(analyzer));
+                    }
+                }
+            });
+            curr = ctx.underlying(analyzer).parent_ctx;
+        }
+        accesses
+    }
+
     /// Reads the current temporary counter and increments the counter
     pub fn new_tmp(&self, analyzer: &mut impl AnalyzerLike) -> usize {
         let context = self.underlying_mut(analyzer);
@@ -581,6 +643,37 @@ impl ContextNode {
         }
     }
 
+    /// Marks the context as reverting with a `Panic(uint256)` on `array` and kills it
+    pub fn panic(
+        &self,
+        analyzer: &mut impl AnalyzerLike,
+        panic_loc: Loc,
+        code: PanicCode,
+        array: ContextVarNode,
+    ) {
+        self.underlying_mut(analyzer).panic = Some((panic_loc, code, array));
+        self.kill(analyzer, panic_loc);
+    }
+
+    /// Returns where, why and on which array the context reverted with a `Panic(uint256)`, if
+    /// it did
+    pub fn panicked(
+        &self,
+        analyzer: &impl AnalyzerLike,
+    ) -> Option<(Loc, PanicCode, ContextVarNode)> {
+        self.underlying(analyzer).panic
+    }
+
+    /// Records a construct the analyzer couldn't handle in this context
+    pub fn mark_unsupported(&self, analyzer: &mut impl AnalyzerLike, err: AnalyzerError) {
+        self.underlying_mut(analyzer).unsupported.push(err);
+    }
+
+    /// Returns the constructs the analyzer couldn't handle in this context, and why
+    pub fn unsupported(&self, analyzer: &impl AnalyzerLike) -> Vec<AnalyzerError> {
+        self.underlying(analyzer).unsupported.clone()
+    }
+
     /// Kills if and only if all subcontexts are killed
     pub fn end_if_all_forks_ended(&self, analyzer: &mut impl AnalyzerLike, kill_loc: Loc) {
         let context = self.underlying(analyzer);
@@ -666,6 +759,41 @@ impl ContextNode {
         }
     }
 
+    /// Returns the relational facts known along this context's path
+    pub fn zone<'a>(&self, analyzer: &'a impl AnalyzerLike) -> &'a Zone {
+        &self.underlying(analyzer).zone
+    }
+
+    /// Records that `new` is a version of (or a copy of) `old` holding the same value. Does
+    /// nothing unless relations are tracked
+    pub fn alias_var(
+        &self,
+        new: ContextVarNode,
+        old: ContextVarNode,
+        analyzer: &mut impl AnalyzerLike,
+    ) {
+        if new != old && analyzer.settings().relational {
+            self.underlying_mut(analyzer).zone.alias(new, old);
+        }
+    }
+
+    /// Records `lhs op rhs` in the relational facts of this context, if relations are tracked.
+    /// Returns false if the facts can no longer all hold
+    pub fn add_relation(
+        &self,
+        op: RangeOp,
+        lhs: (ZoneVar, i128),
+        rhs: (ZoneVar, i128),
+        analyzer: &mut impl AnalyzerLike,
+    ) -> bool {
+        if !analyzer.settings().relational {
+            return true;
+        }
+        let zone = &mut self.underlying_mut(analyzer).zone;
+        zone.add_cmp(op, lhs, rhs);
+        !zone.is_infeasible()
+    }
+
     pub fn add_return_node(
         &self,
         ret_stmt_loc: Loc,
@@ -1,7 +1,10 @@
+use crate::range::congruence::Congruence;
 use crate::range::elem::RangeElem;
 use crate::range::elem_ty::Dynamic;
 use crate::range::elem_ty::Elem;
 use crate::range::elem_ty::RangeConcrete;
+use crate::range::interval_set::IntervalSet;
+use crate::range::known_bits::KnownBits;
 use crate::range::range_string::ToRangeString;
 use crate::range::Range;
 use crate::range::SolcRange;
@@ -75,6 +78,10 @@ impl ContextVarNode {
     }
 
     pub fn underlying_mut<'a>(&self, analyzer: &'a mut impl GraphLike) -> &'a mut ContextVar {
+        // whatever is about to change, bounds evaluated from the variable may no longer hold
+        if let Some(cache) = analyzer.eval_cache() {
+            cache.invalidate(self.0.into());
+        }
         match analyzer.node_mut(*self) {
             Node::ContextVar(c) => c,
             e => panic!("Node type confusion: expected node to be ContextVar but it was: {e:?}"),
@@ -133,9 +140,15 @@ impl ContextVarNode {
         analyzer: &mut (impl GraphLike + AnalyzerLike),
     ) {
         if let Some(mut range) = self.range(analyzer) {
+            let intervals = range.intervals.clone();
+            let known_bits = range.known_bits;
+            let congruence = range.congruence;
             range.update_deps(ctx, analyzer);
             self.set_range_min(analyzer, range.min);
             self.set_range_max(analyzer, range.max);
+            self.set_range_intervals(analyzer, intervals);
+            self.set_range_known_bits(analyzer, known_bits);
+            self.set_range_congruence(analyzer, congruence);
         }
     }
 
@@ -167,6 +180,24 @@ impl ContextVarNode {
         Some(self.range(analyzer)?.evaled_range_max(analyzer))
     }
 
+    /// The concrete value of the variable, if it is known exactly
+    pub fn maybe_concrete(&self, analyzer: &impl GraphLike) -> Option<Concrete> {
+        match &self.underlying(analyzer).ty {
+            VarType::Concrete(c) => Some(c.underlying(analyzer).clone()),
+            VarType::BuiltIn(bn, _) => {
+                let min = self.evaled_range_min(analyzer)?.maybe_concrete()?;
+                let max = self.evaled_range_max(analyzer)?.maybe_concrete()?;
+                if min.val == max.val {
+                    let builtin = bn.underlying(analyzer).clone();
+                    Some(min.val.clone().cast(builtin).unwrap_or(min.val))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
     pub fn is_const(&self, analyzer: &impl GraphLike) -> bool {
         let underlying = self.underlying(analyzer);
         underlying.storage.is_none() && underlying.ty.is_const(analyzer)
@@ -255,6 +286,75 @@ impl ContextVarNode {
         Some(ContextVarNode::from(*index))
     }
 
+    /// The array and the version of the key an index access was made with, i.e. `arr` and `a`
+    /// for `arr[a]`. Unlike `index_to_array`, this is `None` for variables derived from an index
+    /// access, like `arr[a].length`.
+    pub fn index_access_parts(
+        &self,
+        analyzer: &impl GraphLike,
+    ) -> Option<(ContextVarNode, ContextVarNode)> {
+        let first = self.first_version(analyzer);
+        let arr = analyzer
+            .graph()
+            .edges_directed(first.0.into(), Direction::Outgoing)
+            .find(|edge| *edge.weight() == Edge::Context(ContextEdge::IndexAccess))?
+            .target();
+        let key = analyzer
+            .graph()
+            .edges_directed(first.0.into(), Direction::Incoming)
+            .find(|edge| *edge.weight() == Edge::Context(ContextEdge::Index))?
+            .source();
+        Some((ContextVarNode::from(arr), ContextVarNode::from(key)))
+    }
+
+    /// Checks whether two index variables *may* refer to the same key, i.e. their
+    /// ranges are not provably disjoint
+    pub fn may_alias(&self, other: &Self, analyzer: &impl GraphLike) -> bool {
+        if self == other {
+            return true;
+        }
+
+        match (
+            self.evaled_range_min(analyzer),
+            self.evaled_range_max(analyzer),
+            other.evaled_range_min(analyzer),
+            other.evaled_range_max(analyzer),
+        ) {
+            (Some(self_min), Some(self_max), Some(other_min), Some(other_max)) => {
+                !matches!(
+                    self_max.range_ord(&other_min),
+                    Some(std::cmp::Ordering::Less)
+                ) && !matches!(
+                    other_max.range_ord(&self_min),
+                    Some(std::cmp::Ordering::Less)
+                )
+            }
+            _ => true,
+        }
+    }
+
+    /// Checks whether two index variables *must* refer to the same key, i.e. they
+    /// are the same version of a variable or both ranges collapse to the same single value
+    pub fn must_alias(&self, other: &Self, analyzer: &impl GraphLike) -> bool {
+        if self == other {
+            return true;
+        }
+
+        match (
+            self.evaled_range_min(analyzer),
+            self.evaled_range_max(analyzer),
+            other.evaled_range_min(analyzer),
+            other.evaled_range_max(analyzer),
+        ) {
+            (Some(self_min), Some(self_max), Some(other_min), Some(other_max)) => {
+                self_min.range_eq(&self_max)
+                    && other_min.range_eq(&other_max)
+                    && self_min.range_eq(&other_min)
+            }
+            _ => false,
+        }
+    }
+
     pub fn as_range_elem(&self, analyzer: &impl GraphLike, loc: Loc) -> Elem<Concrete> {
         match self.underlying(analyzer).ty {
             VarType::Concrete(c) => Elem::Concrete(RangeConcrete {
@@ -393,6 +493,121 @@ impl ContextVarNode {
             .try_set_range_exclusions(new_exclusions, fallback)
     }
 
+    pub fn range_intervals(&self, analyzer: &'_ impl GraphLike) -> Option<IntervalSet> {
+        self.range(analyzer)?.intervals
+    }
+
+    pub fn set_range_intervals(
+        &self,
+        analyzer: &mut impl AnalyzerLike,
+        intervals: Option<IntervalSet>,
+    ) {
+        if let Some(mut range) = self.range(analyzer) {
+            let cap = analyzer.settings().max_intervals;
+            range.intervals = intervals
+                .map(|mut intervals| {
+                    intervals.cap(cap);
+                    intervals
+                })
+                .filter(|intervals| intervals.is_disjoint());
+            self.set_range(analyzer, range);
+        }
+    }
+
+    pub fn range_known_bits(&self, analyzer: &'_ impl GraphLike) -> Option<KnownBits> {
+        self.range(analyzer)?.known_bits
+    }
+
+    pub fn set_range_known_bits(
+        &self,
+        analyzer: &mut impl AnalyzerLike,
+        known_bits: Option<KnownBits>,
+    ) {
+        if let Some(mut range) = self.range(analyzer) {
+            range.known_bits = known_bits;
+            range.min_cached = None;
+            range.max_cached = None;
+            self.set_range(analyzer, range);
+        }
+    }
+
+    /// The bits known about an unsigned integer variable, from both its tracked bits and its
+    /// evaluated bounds
+    pub fn known_bits(&self, analyzer: &'_ impl GraphLike) -> Option<KnownBits> {
+        let range = self.range(analyzer)?;
+        let from_bounds = KnownBits::from_elems(
+            &range.evaled_range_min(analyzer),
+            &range.evaled_range_max(analyzer),
+        )?;
+        Some(match range.known_bits {
+            Some(known_bits) => known_bits.intersect(&from_bounds),
+            None => from_bounds,
+        })
+    }
+
+    pub fn range_congruence(&self, analyzer: &'_ impl GraphLike) -> Option<Congruence> {
+        self.range(analyzer)?.congruence
+    }
+
+    pub fn set_range_congruence(
+        &self,
+        analyzer: &mut impl AnalyzerLike,
+        congruence: Option<Congruence>,
+    ) {
+        if let Some(mut range) = self.range(analyzer) {
+            range.congruence = congruence.filter(|congruence| !congruence.is_unknown());
+            range.min_cached = None;
+            range.max_cached = None;
+            self.set_range(analyzer, range);
+        }
+    }
+
+    /// The congruence an unsigned integer variable is known to satisfy, from both its tracked
+    /// congruence and its evaluated bounds
+    pub fn congruence(&self, analyzer: &'_ impl GraphLike) -> Option<Congruence> {
+        let range = self.range(analyzer)?;
+        let from_bounds = Congruence::from_elems(
+            &range.evaled_range_min(analyzer),
+            &range.evaled_range_max(analyzer),
+        )?;
+        Some(match range.congruence {
+            Some(congruence) => congruence.meet(&from_bounds).unwrap_or(congruence),
+            None => from_bounds,
+        })
+    }
+
+    /// Narrows the range to the given intervals, tightening the min and max to their hull where
+    /// that is stricter. Returns false if no value of the current range is in the intervals
+    pub fn narrow_to_intervals(
+        &self,
+        analyzer: &mut impl AnalyzerLike,
+        intervals: IntervalSet,
+    ) -> bool {
+        let (min, max) = match (
+            self.evaled_range_min(analyzer),
+            self.evaled_range_max(analyzer),
+        ) {
+            (Some(min), Some(max)) => (min, max),
+            _ => return true,
+        };
+        let mut intervals = intervals;
+        if min.maybe_concrete().is_some() && max.maybe_concrete().is_some() {
+            intervals = intervals.intersect(&IntervalSet::new(min.clone(), max.clone()));
+        }
+        let (hull_min, hull_max) = match intervals.hull() {
+            Some(hull) => hull,
+            None => return false,
+        };
+        if matches!(hull_min.range_ord(&min), Some(std::cmp::Ordering::Greater)) {
+            self.set_range_min(analyzer, hull_min);
+        }
+        if matches!(hull_max.range_ord(&max), Some(std::cmp::Ordering::Less)) {
+            self.set_range_max(analyzer, hull_max);
+        }
+        self.set_range_intervals(analyzer, Some(intervals));
+        true
+    }
+
     pub fn latest_version(&self, analyzer: &'_ impl GraphLike) -> Self {
         let mut latest = *self;
         while let Some(next) = latest.next_version(analyzer) {
@@ -442,6 +657,28 @@ impl ContextVarNode {
         earlier
     }
 
+    /// The first version of the variable in the context that declared it. A variable that is
+    /// updated in a subcontext starts a new set of versions there, so this follows it back
+    /// to the same named variable in the parent contexts.
+    pub fn origin(&self, analyzer: &'_ impl AnalyzerLike) -> Self {
+        let mut origin = self.first_version(analyzer);
+        let name = origin.name(analyzer);
+        let mut curr = origin
+            .maybe_ctx(analyzer)
+            .and_then(|ctx| ctx.underlying(analyzer).parent_ctx);
+        while let Some(ctx) = curr {
+            if let Some(inherited) = ctx
+                .local_vars(analyzer)
+                .into_iter()
+                .find(|var| var.name(analyzer) == name)
+            {
+                origin = inherited.first_version(analyzer);
+            }
+            curr = ctx.underlying(analyzer).parent_ctx;
+        }
+        origin
+    }
+
     pub fn num_versions(&self, analyzer: &'_ impl GraphLike) -> usize {
         let mut count = 1;
         let mut earlier = self.latest_version(analyzer);
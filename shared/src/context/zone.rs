@@ -0,0 +1,350 @@
+use crate::context::ContextVarNode;
+use crate::range::elem::RangeOp;
+use crate::{Builtin, Concrete, GraphLike, VarType};
+
+use ethers_core::types::{I256, U256};
+
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A vertex of the difference-bound graph: either a variable or the constant zero, which
+/// lets `x <= c` be written as `x - 0 <= c`
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum ZoneVar {
+    Zero,
+    Var(ContextVarNode),
+}
+
+/// A relational (difference-bound) domain kept alongside the interval ranges of a context.
+///
+/// Tracks facts of the form `x - y <= c` between versions of variables, which intervals
+/// lose as soon as a `Dynamic` reference is evaluated (i.e. `x < y; y < 10` implies `x < 9`).
+/// Versions of a variable that are known to hold the same value (a read, a copy, a
+/// narrowing by `require`) are aliased onto a single representative, so only variables
+/// that actually take part in a relation end up in the bound matrix.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Zone {
+    /// Maps a version of a variable to the representative it is known to be equal to
+    aliases: BTreeMap<ContextVarNode, ContextVarNode>,
+    /// `bounds[(x, y)] = c` means `x - y <= c`, kept transitively closed. Missing entries are unbounded
+    bounds: BTreeMap<(ZoneVar, ZoneVar), i128>,
+    /// Constants a representative is known to not be equal to
+    neqs: BTreeSet<(ContextVarNode, i128)>,
+    /// Whether the facts contradict each other
+    infeasible: bool,
+}
+
+impl Zone {
+    /// Whether the facts in the zone can't all hold at once
+    pub fn is_infeasible(&self) -> bool {
+        self.infeasible
+    }
+
+    /// Whether the zone knows nothing beyond aliases
+    pub fn is_empty(&self) -> bool {
+        self.bounds.is_empty() && self.neqs.is_empty()
+    }
+
+    /// The representative of a variable's equality class
+    pub fn rep(&self, cvar: ContextVarNode) -> ContextVarNode {
+        *self.aliases.get(&cvar).unwrap_or(&cvar)
+    }
+
+    /// The representatives that take part in any relation
+    pub fn vars(&self) -> BTreeSet<ContextVarNode> {
+        self.bounds
+            .keys()
+            .flat_map(|(x, y)| [*x, *y])
+            .filter_map(|v| match v {
+                ZoneVar::Var(cvar) => Some(cvar),
+                ZoneVar::Zero => None,
+            })
+            .chain(self.neqs.iter().map(|(cvar, _)| *cvar))
+            .collect()
+    }
+
+    /// All variables known to be equal to `rep`, including itself
+    pub fn aliases_of(&self, rep: ContextVarNode) -> Vec<ContextVarNode> {
+        std::iter::once(rep)
+            .chain(
+                self.aliases
+                    .iter()
+                    .filter(|(_, r)| **r == rep)
+                    .map(|(alias, _)| *alias),
+            )
+            .collect()
+    }
+
+    /// Records that `new` holds the same value as `old`
+    pub fn alias(&mut self, new: ContextVarNode, old: ContextVarNode) {
+        let rep = self.rep(old);
+        if new == rep || self.aliases.contains_key(&new) {
+            return;
+        }
+
+        if self.vars().contains(&new) {
+            // `new` is already related to other variables, so merge through the bounds instead
+            self.add_diff(ZoneVar::Var(new), ZoneVar::Var(rep), 0);
+            self.add_diff(ZoneVar::Var(rep), ZoneVar::Var(new), 0);
+        } else {
+            self.aliases.insert(new, rep);
+        }
+    }
+
+    /// The tightest known `c` such that `x - y <= c`
+    pub fn upper(&self, x: ZoneVar, y: ZoneVar) -> Option<i128> {
+        let (x, y) = (self.rep_of(x), self.rep_of(y));
+        if x == y {
+            Some(0)
+        } else {
+            self.bounds.get(&(x, y)).copied()
+        }
+    }
+
+    /// The known `[min, max]` of a variable, if any
+    pub fn bounds_of(&self, cvar: ContextVarNode) -> (Option<i128>, Option<i128>) {
+        let v = ZoneVar::Var(cvar);
+        (
+            self.upper(ZoneVar::Zero, v).and_then(i128::checked_neg),
+            self.upper(v, ZoneVar::Zero),
+        )
+    }
+
+    /// The constants a variable is known to not be equal to
+    pub fn neqs_of(&self, cvar: ContextVarNode) -> Vec<i128> {
+        let rep = self.rep(cvar);
+        self.neqs
+            .iter()
+            .filter(|(r, _)| *r == rep)
+            .map(|(_, c)| *c)
+            .collect()
+    }
+
+    /// Adds `(lhs.0 + lhs.1) op (rhs.0 + rhs.1)` to the zone
+    pub fn add_cmp(&mut self, op: RangeOp, lhs: (ZoneVar, i128), rhs: (ZoneVar, i128)) {
+        let (l, a) = lhs;
+        let (r, b) = rhs;
+        // l + a - (r + b) <= c  <=>  l - r <= c - a + b
+        let shift = |c: i128| c.checked_sub(a).and_then(|c| c.checked_add(b));
+        match op {
+            RangeOp::Lt => {
+                if let Some(c) = shift(-1) {
+                    self.add_diff(l, r, c)
+                }
+            }
+            RangeOp::Lte => {
+                if let Some(c) = shift(0) {
+                    self.add_diff(l, r, c)
+                }
+            }
+            RangeOp::Gt => self.add_cmp(RangeOp::Lt, rhs, lhs),
+            RangeOp::Gte => self.add_cmp(RangeOp::Lte, rhs, lhs),
+            RangeOp::Eq => {
+                self.add_cmp(RangeOp::Lte, lhs, rhs);
+                self.add_cmp(RangeOp::Lte, rhs, lhs);
+            }
+            RangeOp::Neq => match (self.rep_of(l), self.rep_of(r)) {
+                (ZoneVar::Var(v), ZoneVar::Zero) => {
+                    if let Some(c) = b.checked_sub(a) {
+                        self.add_neq(v, c)
+                    }
+                }
+                (ZoneVar::Zero, ZoneVar::Var(v)) => {
+                    if let Some(c) = a.checked_sub(b) {
+                        self.add_neq(v, c)
+                    }
+                }
+                (x, y) if x == y && a == b => self.infeasible = true,
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    /// Whether `(lhs.0 + lhs.1) op (rhs.0 + rhs.1)` is known to be true or false
+    pub fn implies(&self, op: RangeOp, lhs: (ZoneVar, i128), rhs: (ZoneVar, i128)) -> Option<bool> {
+        let (l, a) = lhs;
+        let (r, b) = rhs;
+        // is `l - r <= c - a + b` known?
+        let holds = |x: ZoneVar, y: ZoneVar, c: i128, a: i128, b: i128| {
+            let c = c.checked_sub(a)?.checked_add(b)?;
+            Some(self.upper(x, y)? <= c)
+        };
+        match op {
+            RangeOp::Lt => {
+                if holds(l, r, -1, a, b) == Some(true) {
+                    Some(true)
+                } else if holds(r, l, 0, b, a) == Some(true) {
+                    Some(false)
+                } else {
+                    None
+                }
+            }
+            RangeOp::Lte => {
+                if holds(l, r, 0, a, b) == Some(true) {
+                    Some(true)
+                } else if holds(r, l, -1, b, a) == Some(true) {
+                    Some(false)
+                } else {
+                    None
+                }
+            }
+            RangeOp::Gt => self.implies(RangeOp::Lt, rhs, lhs),
+            RangeOp::Gte => self.implies(RangeOp::Lte, rhs, lhs),
+            RangeOp::Eq => {
+                let lt = self.implies(RangeOp::Lt, lhs, rhs);
+                let gt = self.implies(RangeOp::Gt, lhs, rhs);
+                match (lt, gt) {
+                    (Some(true), _) | (_, Some(true)) => Some(false),
+                    (Some(false), Some(false)) => Some(true),
+                    _ => match (self.rep_of(l), self.rep_of(r)) {
+                        (ZoneVar::Var(v), ZoneVar::Zero)
+                            if b.checked_sub(a)
+                                .is_some_and(|c| self.neqs.contains(&(v, c))) =>
+                        {
+                            Some(false)
+                        }
+                        (ZoneVar::Zero, ZoneVar::Var(v))
+                            if a.checked_sub(b)
+                                .is_some_and(|c| self.neqs.contains(&(v, c))) =>
+                        {
+                            Some(false)
+                        }
+                        _ => None,
+                    },
+                }
+            }
+            RangeOp::Neq => self.implies(RangeOp::Eq, lhs, rhs).map(|eq| !eq),
+            _ => None,
+        }
+    }
+
+    fn rep_of(&self, v: ZoneVar) -> ZoneVar {
+        match v {
+            ZoneVar::Var(cvar) => ZoneVar::Var(self.rep(cvar)),
+            ZoneVar::Zero => ZoneVar::Zero,
+        }
+    }
+
+    fn add_neq(&mut self, rep: ContextVarNode, c: i128) {
+        let (min, max) = self.bounds_of(rep);
+        if min == Some(c) && max == Some(c) {
+            self.infeasible = true;
+        }
+        self.neqs.insert((rep, c));
+    }
+
+    /// Adds `x - y <= c` and restores the closure. The closure was already closed before,
+    /// so only paths through the new edge need to be considered
+    fn add_diff(&mut self, x: ZoneVar, y: ZoneVar, c: i128) {
+        let (x, y) = (self.rep_of(x), self.rep_of(y));
+        if x == y {
+            if c < 0 {
+                self.infeasible = true;
+            }
+            return;
+        }
+        if matches!(self.upper(x, y), Some(old) if old <= c) {
+            return;
+        }
+
+        let mut nodes: BTreeSet<ZoneVar> = self.bounds.keys().flat_map(|(i, j)| [*i, *j]).collect();
+        nodes.insert(x);
+        nodes.insert(y);
+
+        let dist = |zone: &Self, i: ZoneVar, j: ZoneVar| {
+            if i == j {
+                Some(0)
+            } else {
+                zone.bounds.get(&(i, j)).copied()
+            }
+        };
+
+        let mut updates = vec![];
+        for i in nodes.iter() {
+            let to_x = match dist(self, *i, x) {
+                Some(to_x) => to_x,
+                None => continue,
+            };
+            for j in nodes.iter() {
+                let through =
+                    dist(self, y, *j).and_then(|from_y| to_x.checked_add(c)?.checked_add(from_y));
+                let through = match through {
+                    Some(through) => through,
+                    None => continue,
+                };
+                if i == j {
+                    if through < 0 {
+                        self.infeasible = true;
+                    }
+                } else if !matches!(dist(self, *i, *j), Some(old) if old <= through) {
+                    updates.push(((*i, *j), through));
+                }
+            }
+        }
+        self.bounds.extend(updates);
+
+        let neqs = self.neqs.clone();
+        if neqs.iter().any(|(rep, c)| {
+            let (min, max) = self.bounds_of(*rep);
+            min == Some(*c) && max == Some(*c)
+        }) {
+            self.infeasible = true;
+        }
+    }
+}
+
+/// The zone term for a numeric variable: a constant becomes an offset from zero
+pub fn zone_term(cvar: ContextVarNode, analyzer: &impl GraphLike) -> Option<(ZoneVar, i128)> {
+    let numeric = match &cvar.underlying(analyzer).ty {
+        VarType::BuiltIn(bn, _) => {
+            matches!(bn.underlying(analyzer), Builtin::Uint(_) | Builtin::Int(_))
+        }
+        VarType::Concrete(c) => matches!(
+            c.underlying(analyzer),
+            Concrete::Uint(..) | Concrete::Int(..)
+        ),
+        _ => false,
+    };
+    if !numeric {
+        return None;
+    }
+
+    if cvar.is_const(analyzer) {
+        Some((
+            ZoneVar::Zero,
+            concrete_to_i128(&cvar.maybe_concrete(analyzer)?)?,
+        ))
+    } else {
+        Some((ZoneVar::Var(cvar), 0))
+    }
+}
+
+/// Converts a numeric concrete into a zone offset, if it fits
+pub fn concrete_to_i128(c: &Concrete) -> Option<i128> {
+    match c {
+        Concrete::Uint(_, val) => {
+            if *val <= U256::from(i128::MAX as u128) {
+                Some(val.as_u128() as i128)
+            } else {
+                None
+            }
+        }
+        Concrete::Int(_, val) => {
+            if *val >= I256::from(i128::MIN) && *val <= I256::from(i128::MAX) {
+                Some(val.as_i128())
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Converts a zone offset back into a concrete of the given builtin type, if it fits
+pub fn i128_to_concrete(val: i128, builtin: &Builtin) -> Option<Concrete> {
+    match builtin {
+        Builtin::Uint(size) if val >= 0 => Some(Concrete::Uint(*size, U256::from(val as u128))),
+        Builtin::Int(size) => Some(Concrete::Int(*size, I256::from(val))),
+        _ => None,
+    }
+}
@@ -28,7 +28,7 @@ pub enum Node {
     ContextVar(ContextVar),
     ContextFork,
     FunctionCall,
-    Builtin(Builtin),
+    Builtin(Box<Builtin>),
     VarType(VarType),
     Entry,
     SourceUnit(usize),
@@ -39,8 +39,8 @@ impl From<NodeIdx> for BlockNode {
 }
 
 /// Represents block-based environment variables available in solidity. These can
-/// be set in the configuration (TODO) - if they are not set they are assumed to be
-/// in their types default full range (e.g.: `uint256 -> [0, 2**256 - 1]`).
+/// be set in the configuration (`[env.block]` of `pyrometer.toml`) - if they are not set they
+/// are assumed to be in their types default full range (e.g.: `uint256 -> [0, 2**256 - 1]`).
 #[derive(Debug, Default, Clone, Eq, PartialEq)]
 pub struct Block {
     /// The block's hash
@@ -1,6 +1,9 @@
 use crate::Builtin;
 use crate::{analyzer::GraphLike, Node, NodeIdx};
-use ethers_core::types::{Address, H256, I256, U256};
+use ethers_core::{
+    abi::{self, Token},
+    types::{Address, H256, I256, U256},
+};
 
 /// An index in the graph that references a [`Concrete`] node
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
@@ -122,29 +125,23 @@ impl Concrete {
     /// Convert a U256 back into it's original type. This is used mostly
     /// for range calculations to improve ergonomics. Basically
     /// the EVM only operates on U256 words, so most of this should
-    /// be fine.
-    pub fn u256_as_original(&self, uint: U256) -> Self {
+    /// be fine. Types that aren't a single word have no such value
+    pub fn u256_as_original(&self, uint: U256) -> Option<Self> {
         match self {
-            Concrete::Uint(size, _) => Concrete::Uint(*size, uint),
-            Concrete::Int(size, _) => Concrete::Int(*size, I256::from_raw(uint)),
+            Concrete::Uint(size, _) => Some(Concrete::Uint(*size, uint)),
+            Concrete::Int(size, _) => Some(Concrete::Int(*size, I256::from_raw(uint))),
             Concrete::Bytes(size, _) => {
                 let mut h = H256::default();
                 uint.to_big_endian(h.as_mut());
-                Concrete::Bytes(*size, h)
+                Some(Concrete::Bytes(*size, h))
             }
             Concrete::Address(_) => {
                 let mut bytes = [0u8; 32];
                 uint.to_big_endian(&mut bytes);
-                Concrete::Address(Address::from_slice(&bytes[12..]))
-            }
-            Concrete::Bool(_) => {
-                if uint > U256::zero() {
-                    Concrete::Bool(true)
-                } else {
-                    Concrete::Bool(false)
-                }
+                Some(Concrete::Address(Address::from_slice(&bytes[12..])))
             }
-            e => todo!("Unsupported: {e:?}"),
+            Concrete::Bool(_) => Some(Concrete::Bool(uint > U256::zero())),
+            Concrete::DynBytes(_) | Concrete::String(_) | Concrete::Array(_) => None,
         }
     }
 
@@ -186,28 +183,7 @@ impl Concrete {
     }
 
     pub fn literal_cast(self, builtin: Builtin) -> Option<Self> {
-        match self {
-            Concrete::Uint(_, val) => match builtin {
-                Builtin::Bytes(size) => {
-                    let mask = if size == 32 {
-                        U256::MAX
-                    } else {
-                        U256::from(2).pow((size as u16 * 8).into()) - 1
-                    };
-
-                    let h = H256::from_slice(
-                        &(val & mask)
-                            .0
-                            .iter()
-                            .flat_map(|v| v.to_le_bytes())
-                            .collect::<Vec<_>>()[..],
-                    );
-                    Some(Concrete::Bytes(size, h))
-                }
-                _ => self.cast(builtin),
-            },
-            _ => self.cast(builtin),
-        }
+        self.cast(builtin)
     }
 
     /// Cast the concrete to another type as denoted by a [`Builtin`].
@@ -233,12 +209,8 @@ impl Concrete {
                         }
                     }
                     Builtin::Int(size) => {
-                        let mask = if size == 256 {
-                            // we ignore the top bit
-                            U256::from(2).pow(255.into()) - 1
-                        } else {
-                            U256::from(2).pow(size.into()) - 1
-                        };
+                        // we ignore the top bit
+                        let mask = U256::from(2).pow((size - 1).into()) - 1;
 
                         if val < mask {
                             Some(Concrete::Int(size, I256::from_raw(val)))
@@ -250,13 +222,14 @@ impl Concrete {
                         let mask = if size == 32 {
                             U256::MAX
                         } else {
-                            let v = U256::from(2).pow((size as u16 * 8).into()) - 1;
-                            v << v.leading_zeros()
+                            U256::from(2).pow((size as u16 * 8).into()) - 1
                         };
 
-                        // let h = H256::from_slice(&(val & mask).0.iter().flat_map(|v| v.to_le_bytes()).collect::<Vec<_>>()[..]);
+                        // the value's bytes are left aligned, i.e. `bytes4(0xa9059cbb)`
+                        let mut bytes = [0u8; 32];
+                        (val & mask).to_big_endian(&mut bytes);
                         let mut h = H256::default();
-                        (val & mask).to_big_endian(&mut h.0);
+                        h.0[..size as usize].copy_from_slice(&bytes[32 - size as usize..]);
                         Some(Concrete::Bytes(size, h))
                     }
                     _ => None,
@@ -293,12 +266,14 @@ impl Concrete {
                     };
 
                     let (sign, abs) = val.into_sign_and_abs();
-                    if abs < mask {
+                    // two's complement, so the min is one further from zero than the max
+                    let limit = if sign.is_negative() { mask + 1 } else { mask };
+                    if abs <= limit {
                         Some(Concrete::Int(size, val))
                     } else {
                         Some(Concrete::Int(
                             size,
-                            I256::checked_from_sign_and_abs(sign, mask).unwrap(),
+                            I256::checked_from_sign_and_abs(sign, limit).unwrap(),
                         ))
                     }
                 }
@@ -322,7 +297,8 @@ impl Concrete {
                     } else {
                         U256::from(2).pow(size.into()) - 1
                     };
-                    let val = U256::from_big_endian(b.as_bytes());
+                    // the bytes are left aligned, so only the first `cap` make up the value
+                    let val = U256::from_big_endian(&b.0[..cap as usize]);
                     Some(Concrete::Uint(size, val & mask))
                 }
                 Builtin::Int(size) => {
@@ -331,7 +307,7 @@ impl Concrete {
                     } else {
                         U256::from(2).pow(size.into()) - 1
                     };
-                    let val = U256::from_big_endian(b.as_bytes());
+                    let val = U256::from_big_endian(&b.0[..cap as usize]);
                     Some(Concrete::Int(size, I256::from_raw(val & mask)))
                 }
                 Builtin::Bytes(size) => {
@@ -383,9 +359,9 @@ impl Concrete {
                 }
                 _ => None,
             },
-            Concrete::DynBytes(ref _b) => match builtin {
+            Concrete::DynBytes(ref b) => match builtin {
                 Builtin::DynamicBytes => Some(self),
-                Builtin::String => todo!(),
+                Builtin::String => Some(Concrete::String(String::from_utf8_lossy(b).to_string())),
                 _ => None,
             },
             _ => None,
@@ -553,13 +529,8 @@ impl Concrete {
         match self {
             Concrete::Uint(size, _) => Some(Concrete::Uint(*size, 0.into())),
             Concrete::Int(size, _) => {
-                let max = if size == &256u16 {
-                    I256::MAX
-                } else {
-                    I256::from_raw(U256::from(1u8) << U256::from(*size - 1)) - I256::from(1)
-                };
-
-                let min = max * I256::from(-1i32);
+                // two's complement, so the min is one further from zero than the max
+                let min = I256::from_raw(U256::MAX << U256::from(*size - 1));
                 Some(Concrete::Int(*size, min))
             }
             Concrete::Bytes(size, _) => {
@@ -626,7 +597,14 @@ impl Concrete {
                     hex::encode(a)
                 }
             }
-            e => todo!("Concrete as string {e:?}"),
+            Concrete::Array(elems) => format!(
+                "[{}]",
+                elems
+                    .iter()
+                    .map(|elem| elem.as_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
         }
     }
 
@@ -703,7 +681,93 @@ impl Concrete {
                     hex::encode(a)
                 }
             }
-            e => todo!("Concrete as string: {e:?}"),
+            Concrete::Array(elems) => format!(
+                "[{}]",
+                elems
+                    .iter()
+                    .map(|elem| elem.as_human_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    /// Converts to an ABI token, as used by `abi.encode`
+    pub fn as_abi_token(&self) -> Token {
+        match self {
+            Concrete::Uint(_, val) => Token::Uint(*val),
+            Concrete::Int(_, val) => Token::Int(val.into_raw()),
+            Concrete::Bytes(size, b) => Token::FixedBytes(b.0[..*size as usize].to_vec()),
+            Concrete::Address(a) => Token::Address(*a),
+            Concrete::Bool(b) => Token::Bool(*b),
+            Concrete::DynBytes(b) => Token::Bytes(b.clone()),
+            Concrete::String(s) => Token::String(s.clone()),
+            Concrete::Array(arr) => Token::Array(arr.iter().map(|c| c.as_abi_token()).collect()),
+        }
+    }
+
+    /// The result of `abi.encode(..)` on a list of concrete values
+    pub fn abi_encode(vals: &[Concrete]) -> Vec<u8> {
+        abi::encode(&vals.iter().map(|c| c.as_abi_token()).collect::<Vec<_>>())
+    }
+
+    /// The result of `abi.encodePacked(..)` on a list of concrete values. Returns `None`
+    /// if one of the values cannot be packed (i.e. nested arrays)
+    pub fn abi_encode_packed(vals: &[Concrete]) -> Option<Vec<u8>> {
+        let mut out = vec![];
+        for val in vals.iter() {
+            out.extend(val.packed_bytes(false)?);
+        }
+        Some(out)
+    }
+
+    /// The packed encoding of a single value. Inside of an array, elements are padded to 32 bytes
+    fn packed_bytes(&self, in_array: bool) -> Option<Vec<u8>> {
+        let width = |bits: u16| {
+            if in_array {
+                32
+            } else {
+                (bits as usize).div_ceil(8)
+            }
+        };
+        match self {
+            Concrete::Uint(size, val) => {
+                let mut buf = [0u8; 32];
+                val.to_big_endian(&mut buf);
+                Some(buf[32 - width(*size)..].to_vec())
+            }
+            Concrete::Int(size, val) => {
+                let mut buf = [0u8; 32];
+                val.into_raw().to_big_endian(&mut buf);
+                Some(buf[32 - width(*size)..].to_vec())
+            }
+            Concrete::Bytes(size, b) => {
+                let mut bytes = b.0[..*size as usize].to_vec();
+                if in_array {
+                    bytes.resize(32, 0);
+                }
+                Some(bytes)
+            }
+            Concrete::Address(a) => {
+                let mut bytes = vec![0u8; width(160) - 20];
+                bytes.extend(a.as_bytes());
+                Some(bytes)
+            }
+            Concrete::Bool(b) => {
+                let mut bytes = vec![0u8; width(8) - 1];
+                bytes.push(*b as u8);
+                Some(bytes)
+            }
+            Concrete::DynBytes(b) if !in_array => Some(b.clone()),
+            Concrete::String(s) if !in_array => Some(s.as_bytes().to_vec()),
+            Concrete::Array(arr) if !in_array => {
+                let mut out = vec![];
+                for val in arr.iter() {
+                    out.extend(val.packed_bytes(true)?);
+                }
+                Some(out)
+            }
+            _ => None,
         }
     }
 }
@@ -380,7 +380,8 @@ pub fn var_def_to_ret(expr: Expression) -> (Loc, Option<Parameter>) {
             | Type::Uint(_)
             | Type::Bytes(_)
             | Type::Rational
-            | Type::DynamicBytes => (
+            | Type::DynamicBytes
+            | Type::Function { .. } => (
                 ty_loc,
                 Some(Parameter {
                     loc: ty_loc,
@@ -389,7 +390,6 @@ pub fn var_def_to_ret(expr: Expression) -> (Loc, Option<Parameter>) {
                     name: None,
                 }),
             ),
-            e => panic!("Unsupported type: {e:?}"),
         },
         Expression::ArraySubscript(_loc, sub_expr, _) => {
             // its an array, add the index as a parameter
@@ -437,7 +437,8 @@ pub fn var_def_to_params(expr: Expression) -> Vec<(Loc, Option<Parameter>)> {
                 | Type::Uint(_)
                 | Type::Bytes(_)
                 | Type::Rational
-                | Type::DynamicBytes => {
+                | Type::DynamicBytes
+                | Type::Function { .. } => {
                     // if !is_recursion {
                     //     params.push((ty_loc, Some(Parameter {
                     //         loc: ty_loc,
@@ -447,7 +448,6 @@ pub fn var_def_to_params(expr: Expression) -> Vec<(Loc, Option<Parameter>)> {
                     //     })));
                     // }
                 }
-                e => panic!("Unsupported type: {e:?}"),
             }
         }
         Expression::ArraySubscript(loc, sub_expr, _) => {
@@ -543,9 +543,13 @@ impl FunctionParamNode {
     }
 
     pub fn ty_str(&self, analyzer: &'_ impl GraphLike) -> String {
-        let var_ty = VarType::try_from_idx(analyzer, self.underlying(analyzer).ty)
-            .expect("Non-typeable as type");
-        var_ty.as_dot_str(analyzer)
+        let ty = self.underlying(analyzer).ty;
+        match (VarType::try_from_idx(analyzer, ty), analyzer.node(ty)) {
+            (Some(var_ty), _) => var_ty.as_dot_str(analyzer),
+            // the type as written, i.e. an array sized by a constant
+            (None, Node::Unresolved(ident)) => ident.name.clone(),
+            _ => panic!("Non-typeable as type"),
+        }
     }
 
     pub fn ty(&self, analyzer: &'_ impl GraphLike) -> NodeIdx {
@@ -241,8 +241,14 @@ impl VarType {
             (Self::BuiltIn(from_bn, _), Self::BuiltIn(to_bn, _)) => {
                 from_bn.implicitly_castable_to(to_bn, analyzer)
             }
-            (Self::Concrete(from_c), Self::BuiltIn(_to_bn, _)) => {
-                todo!("here, {from_c:?}")
+            (Self::Concrete(from_c), Self::BuiltIn(to_bn, _)) => {
+                match from_c.underlying(analyzer) {
+                    // arrays have no builtin of their own, and so never cast implicitly
+                    Concrete::Array(_) => false,
+                    c => c
+                        .as_builtin()
+                        .implicitly_castable_to(to_bn.underlying(analyzer)),
+                }
             }
             _ => false,
         }
@@ -279,7 +285,7 @@ impl VarType {
                 let zero = Concrete::from(enum_node.variants(analyzer).first()?.clone());
                 Some(SolcRange::new(zero.clone().into(), zero.into(), vec![]))
             }
-            Self::BuiltIn(bn, None) => bn.zero_range(analyzer),
+            Self::BuiltIn(bn, _) => bn.zero_range(analyzer),
             Self::Concrete(cnode) => cnode.underlying(analyzer).as_builtin().zero_range(),
             _ => None,
         }
@@ -339,6 +345,13 @@ impl VarType {
         }
     }
 
+    pub fn fixed_len(&self, analyzer: &impl GraphLike) -> Option<U256> {
+        match self {
+            Self::BuiltIn(node, _) => node.underlying(analyzer).fixed_len(),
+            _ => None,
+        }
+    }
+
     pub fn is_mapping(&self, analyzer: &impl AnalyzerLike) -> bool {
         match self {
             Self::BuiltIn(node, _) => node.is_mapping(analyzer),
@@ -448,6 +461,7 @@ impl BuiltInNode {
     pub fn dynamic_underlying_ty(&self, analyzer: &mut impl AnalyzerLike) -> VarType {
         match self.underlying(analyzer) {
             Builtin::Array(v_ty) => v_ty.clone(),
+            Builtin::SizedArray(_, v_ty) => *v_ty.clone(),
             Builtin::Mapping(_, v_ty) => v_ty.clone(),
             Builtin::DynamicBytes => VarType::BuiltIn(
                 analyzer.builtin_or_add(Builtin::Bytes(1)).into(),
@@ -502,6 +516,7 @@ pub enum Builtin {
     Rational,
     DynamicBytes,
     Array(VarType),
+    SizedArray(U256, Box<VarType>),
     Mapping(VarType, VarType),
     Func(Vec<VarType>, Vec<VarType>),
 }
@@ -526,6 +541,14 @@ impl Builtin {
                 }));
                 Some(SolcRange::new(zero.clone(), zero, vec![]))
             }
+            Builtin::SizedArray(size, _) => {
+                let zero = Elem::ConcreteDyn(Box::new(RangeDyn {
+                    len: Elem::from(Concrete::from(*size)),
+                    val: Default::default(),
+                    loc: Loc::Implicit,
+                }));
+                Some(SolcRange::new(zero.clone(), zero, vec![]))
+            }
             Builtin::Rational | Builtin::Func(_, _) => None,
         }
     }
@@ -562,10 +585,11 @@ impl Builtin {
                     .filter_map(|(_, param)| param.as_ref())
                     .map(|param| analyzer.parse_expr(&param.ty))
                     .collect::<Vec<_>>();
+                // like a mapping, a function type with an unresolved part is unresolved
                 let inputs = inputs
                     .iter()
-                    .map(|idx| VarType::try_from_idx(analyzer, *idx).expect("Couldn't parse param"))
-                    .collect::<Vec<_>>();
+                    .map(|idx| VarType::try_from_idx(analyzer, *idx))
+                    .collect::<Option<Vec<_>>>()?;
                 let mut outputs = vec![];
                 if let Some((params, _attrs)) = returns {
                     let tmp_outputs = params
@@ -575,11 +599,8 @@ impl Builtin {
                         .collect::<Vec<_>>();
                     outputs = tmp_outputs
                         .iter()
-                        .map(|idx| {
-                            VarType::try_from_idx(analyzer, *idx)
-                                .expect("Couldn't parse output param")
-                        })
-                        .collect::<Vec<_>>();
+                        .map(|idx| VarType::try_from_idx(analyzer, *idx))
+                        .collect::<Option<Vec<_>>>()?;
                 }
                 Some(Builtin::Func(inputs, outputs))
             }
@@ -589,10 +610,22 @@ impl Builtin {
     pub fn is_dyn(&self) -> bool {
         matches!(
             self,
-            Builtin::DynamicBytes | Builtin::Array(..) | Builtin::Mapping(..) | Builtin::String
+            Builtin::DynamicBytes
+                | Builtin::Array(..)
+                | Builtin::SizedArray(..)
+                | Builtin::Mapping(..)
+                | Builtin::String
         )
     }
 
+    /// The length of a fixed size array, i.e. `3` for `uint256[3]`
+    pub fn fixed_len(&self) -> Option<U256> {
+        match self {
+            Builtin::SizedArray(size, _) => Some(*size),
+            _ => None,
+        }
+    }
+
     pub fn requires_input(&self) -> bool {
         matches!(self, Builtin::Array(..) | Builtin::Mapping(..))
     }
@@ -650,6 +683,7 @@ impl Builtin {
             Rational => "rational".to_string(),
             DynamicBytes => "bytes".to_string(),
             Array(v_ty) => format!("{}[]", v_ty.as_string(analyzer)),
+            SizedArray(size, v_ty) => format!("{}[{size}]", v_ty.as_string(analyzer)),
             Mapping(key_ty, v_ty) => format!(
                 "mapping ({} => {})",
                 key_ty.as_string(analyzer),
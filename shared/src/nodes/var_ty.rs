@@ -79,6 +79,7 @@ pub struct Var {
     pub attrs: Vec<VariableAttribute>,
     pub name: Option<Identifier>,
     pub initializer: Option<NodeIdx>,
+    pub initializer_expr: Option<Expression>,
     pub in_contract: bool,
 }
 
@@ -99,11 +100,21 @@ impl Var {
             ty: analyzer.parse_expr(&var.ty),
             attrs: var.attrs,
             name: var.name,
-            initializer: var.initializer.map(|init| analyzer.parse_expr(&init)),
+            initializer: var
+                .initializer
+                .as_ref()
+                .map(|init| analyzer.parse_expr(init)),
+            initializer_expr: var.initializer,
             in_contract,
         }
     }
 
+    pub fn is_const(&self) -> bool {
+        self.attrs
+            .iter()
+            .any(|var_attr| matches!(var_attr, VariableAttribute::Constant(_)))
+    }
+
     pub fn is_public(&self) -> bool {
         self.attrs.iter().any(|var_attr| {
             matches!(
@@ -0,0 +1,197 @@
+use crate::range::elem_ty::Elem;
+use crate::Concrete;
+
+use ethers_core::types::U256;
+
+/// A congruence `x ≡ residue (mod modulus)` an unsigned integer is known to satisfy. A modulus
+/// of zero means the value is exactly `residue`, a modulus of one says nothing
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Ord, PartialOrd, Hash)]
+pub struct Congruence {
+    pub modulus: U256,
+    pub residue: U256,
+}
+
+fn gcd(mut a: U256, mut b: U256) -> U256 {
+    while !b.is_zero() {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+impl Congruence {
+    /// Creates a normalized congruence, with the residue less than the modulus
+    pub fn new(modulus: U256, residue: U256) -> Self {
+        if modulus.is_zero() {
+            Self { modulus, residue }
+        } else {
+            Self {
+                modulus,
+                residue: residue % modulus,
+            }
+        }
+    }
+
+    /// Nothing is known
+    pub fn unknown() -> Self {
+        Self::new(U256::one(), U256::zero())
+    }
+
+    /// The value is exactly `val`
+    pub fn constant(val: U256) -> Self {
+        Self::new(U256::zero(), val)
+    }
+
+    /// The congruence of a `uint` with the given evaluated bounds, which is only known if they
+    /// are equal. Anything but unsigned integers is unsupported
+    pub fn from_elems(min: &Elem<Concrete>, max: &Elem<Concrete>) -> Option<Self> {
+        match (min.maybe_concrete()?.val, max.maybe_concrete()?.val) {
+            (Concrete::Uint(_, min), Concrete::Uint(_, max)) if min == max => {
+                Some(Self::constant(min))
+            }
+            (Concrete::Uint(..), Concrete::Uint(..)) => Some(Self::unknown()),
+            _ => None,
+        }
+    }
+
+    pub fn is_unknown(&self) -> bool {
+        self.modulus == U256::one()
+    }
+
+    pub fn is_constant(&self) -> bool {
+        self.modulus.is_zero()
+    }
+
+    /// The smallest value at or above `min` satisfying the congruence
+    pub fn round_up(&self, min: U256) -> U256 {
+        if self.is_constant() {
+            return min.max(self.residue);
+        }
+        let rem = min % self.modulus;
+        let offset = if self.residue >= rem {
+            self.residue - rem
+        } else {
+            self.modulus - (rem - self.residue)
+        };
+        min.checked_add(offset).unwrap_or(min)
+    }
+
+    /// The largest value at or below `max` satisfying the congruence
+    pub fn round_down(&self, max: U256) -> U256 {
+        if self.is_constant() {
+            return max.min(self.residue);
+        }
+        let rem = max % self.modulus;
+        let offset = if rem >= self.residue {
+            rem - self.residue
+        } else {
+            self.modulus - (self.residue - rem)
+        };
+        max.checked_sub(offset).unwrap_or(max)
+    }
+
+    /// Combines two congruences known about the same value. Returns `None` if no value can
+    /// satisfy both
+    pub fn meet(&self, other: &Self) -> Option<Self> {
+        match (self.is_constant(), other.is_constant()) {
+            (true, true) => (self.residue == other.residue).then_some(*self),
+            (true, false) => (self.residue % other.modulus == other.residue).then_some(*self),
+            (false, true) => (other.residue % self.modulus == self.residue).then_some(*other),
+            (false, false) => {
+                let (coarse, fine) = if self.modulus <= other.modulus {
+                    (self, other)
+                } else {
+                    (other, self)
+                };
+                if (fine.modulus % coarse.modulus).is_zero() {
+                    (fine.residue % coarse.modulus == coarse.residue).then_some(*fine)
+                } else {
+                    let g = gcd(self.modulus, other.modulus);
+                    (self.residue % g == other.residue % g).then_some(*fine)
+                }
+            }
+        }
+    }
+
+    pub fn add(&self, other: &Self) -> Option<Self> {
+        let modulus = gcd(self.modulus, other.modulus);
+        if modulus.is_zero() {
+            return Some(Self::constant(self.residue.checked_add(other.residue)?));
+        }
+        let residue = (self.residue % modulus).checked_add(other.residue % modulus)?;
+        Some(Self::new(modulus, residue))
+    }
+
+    pub fn sub(&self, other: &Self) -> Option<Self> {
+        let modulus = gcd(self.modulus, other.modulus);
+        if modulus.is_zero() {
+            return Some(Self::constant(self.residue.checked_sub(other.residue)?));
+        }
+        let residue = (self.residue % modulus).checked_add(modulus - other.residue % modulus)?;
+        Some(Self::new(modulus, residue))
+    }
+
+    pub fn mul(&self, other: &Self) -> Option<Self> {
+        let modulus = gcd(
+            self.modulus.checked_mul(other.modulus)?,
+            gcd(
+                self.modulus.checked_mul(other.residue)?,
+                other.modulus.checked_mul(self.residue)?,
+            ),
+        );
+        Some(Self::new(modulus, self.residue.checked_mul(other.residue)?))
+    }
+
+    /// Division by a constant, which only keeps a congruence if it divides the modulus
+    pub fn div(&self, other: &Self) -> Option<Self> {
+        if !other.is_constant() || other.residue.is_zero() {
+            return None;
+        }
+        let divisor = other.residue;
+        if self.is_constant() {
+            Some(Self::constant(self.residue / divisor))
+        } else if (self.modulus % divisor).is_zero() {
+            Some(Self::new(self.modulus / divisor, self.residue / divisor))
+        } else {
+            Some(Self::unknown())
+        }
+    }
+
+    /// Remainder by a constant, which keeps the part of the congruence the divisor shares
+    pub fn rem(&self, other: &Self) -> Option<Self> {
+        if !other.is_constant() || other.residue.is_zero() {
+            return None;
+        }
+        let divisor = other.residue;
+        if self.is_constant() {
+            return Some(Self::constant(self.residue % divisor));
+        }
+        Some(Self::new(gcd(self.modulus, divisor), self.residue))
+    }
+
+    /// Left shift of a `uint{size}` by a constant. Shifted out bits are dropped, so only a
+    /// power of two modulus survives
+    pub fn shl(&self, other: &Self, size: u16) -> Option<Self> {
+        if !other.is_constant() {
+            return None;
+        }
+        if other.residue >= U256::from(size) {
+            return Some(Self::constant(U256::zero()));
+        }
+        let shift = other.residue.as_usize();
+        let truncate = |val: U256| {
+            if size >= 256 {
+                val
+            } else {
+                val % (U256::one() << size as usize)
+            }
+        };
+        let pow = shift + self.modulus.trailing_zeros() as usize;
+        if self.is_constant() || pow >= size as usize {
+            // the modulus is shifted out entirely, leaving only the residue
+            return Some(Self::constant(truncate(self.residue << shift)));
+        }
+        Some(Self::new(U256::one() << pow, self.residue << shift))
+    }
+}
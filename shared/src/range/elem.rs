@@ -74,6 +74,15 @@ impl RangeOp {
             _ => None, // e => panic!("tried to inverse unreversable op: {:?}", e),
         }
     }
+
+    /// Whether the operands of the operation can be swapped without changing its result
+    pub fn commutative(self) -> bool {
+        use RangeOp::*;
+        matches!(
+            self,
+            Add | Mul | Min | Max | Eq | Neq | And | Or | BitAnd | BitOr | BitXor
+        )
+    }
 }
 
 impl ToString for RangeOp {
@@ -1,9 +1,12 @@
 use crate::context::ContextVarNode;
 use crate::nodes::VarType;
+use crate::range::known_bits::KnownBits;
 use crate::range::range_ops::*;
+use crate::range::simplify;
 use crate::range::Range;
 use crate::range::{elem::RangeOp, *};
 use crate::{Concrete, NodeIdx};
+use ethers_core::types::U256;
 use solang_parser::pt::Loc;
 use std::collections::BTreeMap;
 use std::ops::*;
@@ -21,6 +24,63 @@ impl Dynamic {
     pub fn new(idx: NodeIdx, loc: Loc) -> Self {
         Self { idx, loc }
     }
+
+    /// Evaluates the referenced variable's bound, through the graph's evaluation cache if it has one
+    fn cached_eval(&self, maximize: bool, analyzer: &impl GraphLike) -> Elem<Concrete> {
+        match analyzer.eval_cache() {
+            Some(cache) => cache.get_or_eval(
+                self.idx,
+                maximize,
+                || {
+                    ContextVarNode::from(self.idx)
+                        .range(analyzer)
+                        .map(|range| range.dependent_on())
+                        .unwrap_or_default()
+                },
+                || self.eval(maximize, analyzer),
+            ),
+            None => self.eval(maximize, analyzer),
+        }
+    }
+
+    fn eval(&self, maximize: bool, analyzer: &impl GraphLike) -> Elem<Concrete> {
+        let cvar = ContextVarNode::from(self.idx).underlying(analyzer);
+        match &cvar.ty {
+            VarType::BuiltIn(_, maybe_range) => {
+                if let Some(range) = maybe_range {
+                    if maximize {
+                        range.evaled_range_max(analyzer)
+                    } else {
+                        range.evaled_range_min(analyzer)
+                    }
+                } else {
+                    Elem::Dynamic(*self)
+                }
+            }
+            VarType::Concrete(concrete_node) => Elem::Concrete(RangeConcrete {
+                val: concrete_node.underlying(analyzer).clone(),
+                loc: cvar.loc.unwrap_or(Loc::Implicit),
+            }),
+            _e => Elem::Dynamic(*self),
+        }
+    }
+
+    /// The value of a constant, or the expression a temporary variable was built from. Inlining
+    /// these lets simplification see through chains of temporaries
+    fn inlined_value(&self, analyzer: &impl GraphLike) -> Option<Elem<Concrete>> {
+        let cvar = ContextVarNode::from(self.idx);
+        if cvar.is_const(analyzer) {
+            let value = cvar.range(analyzer)?.evaled_range_min(analyzer);
+            return value.maybe_concrete().map(Elem::Concrete);
+        }
+        let tmp_of = cvar.tmp_of(analyzer)?;
+        let rhs = tmp_of.rhs?;
+        Some(Elem::Expr(RangeExpr::new(
+            Elem::from(Dynamic::new(tmp_of.lhs.into(), self.loc)),
+            tmp_of.op,
+            Elem::from(Dynamic::new(rhs.into(), self.loc)),
+        )))
+    }
 }
 
 impl RangeElem<Concrete> for Dynamic {
@@ -28,8 +88,13 @@ impl RangeElem<Concrete> for Dynamic {
         false
     }
 
-    fn range_ord(&self, _other: &Self) -> Option<std::cmp::Ordering> {
-        todo!()
+    fn range_ord(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        // only the same variable is known to be ordered against itself without evaluating
+        if self.idx == other.idx {
+            Some(std::cmp::Ordering::Equal)
+        } else {
+            None
+        }
     }
 
     fn dependent_on(&self) -> Vec<ContextVarNode> {
@@ -45,46 +110,24 @@ impl RangeElem<Concrete> for Dynamic {
     fn filter_recursion(&mut self, _: NodeIdx, _: Elem<Concrete>) {}
 
     fn maximize(&self, analyzer: &impl GraphLike) -> Elem<Concrete> {
-        let cvar = ContextVarNode::from(self.idx).underlying(analyzer);
-        match &cvar.ty {
-            VarType::BuiltIn(_, maybe_range) => {
-                if let Some(range) = maybe_range {
-                    range.evaled_range_max(analyzer)
-                } else {
-                    Elem::Dynamic(*self)
-                }
-            }
-            VarType::Concrete(concrete_node) => Elem::Concrete(RangeConcrete {
-                val: concrete_node.underlying(analyzer).clone(),
-                loc: cvar.loc.unwrap_or(Loc::Implicit),
-            }),
-            _e => Elem::Dynamic(*self),
-        }
+        self.cached_eval(true, analyzer)
     }
 
     fn minimize(&self, analyzer: &impl GraphLike) -> Elem<Concrete> {
-        let cvar = ContextVarNode::from(self.idx).underlying(analyzer);
-        match &cvar.ty {
-            VarType::BuiltIn(_, maybe_range) => {
-                if let Some(range) = maybe_range {
-                    range.evaled_range_min(analyzer)
-                } else {
-                    Elem::Dynamic(*self)
-                }
-            }
-            VarType::Concrete(concrete_node) => Elem::Concrete(RangeConcrete {
-                val: concrete_node.underlying(analyzer).clone(),
-                loc: cvar.loc.unwrap_or(Loc::Implicit),
-            }),
-            _e => Elem::Dynamic(*self),
-        }
+        self.cached_eval(false, analyzer)
     }
 
-    fn simplify_maximize(&self, _: &impl GraphLike) -> Elem<Concrete> {
-        Elem::Dynamic(*self)
+    fn simplify_maximize(&self, analyzer: &impl GraphLike) -> Elem<Concrete> {
+        match self.inlined_value(analyzer) {
+            Some(value) => value.simplify_maximize(analyzer),
+            None => Elem::Dynamic(*self),
+        }
     }
-    fn simplify_minimize(&self, _: &impl GraphLike) -> Elem<Concrete> {
-        Elem::Dynamic(*self)
+    fn simplify_minimize(&self, analyzer: &impl GraphLike) -> Elem<Concrete> {
+        match self.inlined_value(analyzer) {
+            Some(value) => value.simplify_minimize(analyzer),
+            None => Elem::Dynamic(*self),
+        }
     }
 }
 
@@ -102,7 +145,7 @@ impl RangeElem<Concrete> for RangeDyn<Concrete> {
     }
 
     fn range_ord(&self, _other: &Self) -> Option<std::cmp::Ordering> {
-        todo!()
+        None
     }
 
     fn dependent_on(&self) -> Vec<ContextVarNode> {
@@ -202,6 +245,8 @@ impl RangeElem<Concrete> for RangeConcrete<Concrete> {
         match (self.val.into_u256(), other.val.into_u256()) {
             (Some(self_val), Some(other_val)) => self_val == other_val,
             _ => match (&self.val, &other.val) {
+                // two negatives
+                (Concrete::Int(_, s), Concrete::Int(_, o)) => s == o,
                 (Concrete::DynBytes(s), Concrete::DynBytes(o)) => s == o,
                 (Concrete::String(s), Concrete::String(o)) => s == o,
                 (Concrete::DynBytes(s), Concrete::String(o)) => s == o.as_bytes(),
@@ -311,7 +356,7 @@ impl RangeElem<Concrete> for RangeExpr<Concrete> {
     }
 
     fn range_ord(&self, _other: &Self) -> Option<std::cmp::Ordering> {
-        todo!()
+        None
     }
 
     fn dependent_on(&self) -> Vec<ContextVarNode> {
@@ -784,11 +829,6 @@ impl ExecOp<Concrete> for RangeExpr<Concrete> {
             rhs_max
         );
 
-        let lhs_min_neg = lhs_min.pre_evaled_is_negative();
-        let lhs_max_neg = lhs_max.pre_evaled_is_negative();
-        let rhs_min_neg = rhs_min.pre_evaled_is_negative();
-        let rhs_max_neg = rhs_max.pre_evaled_is_negative();
-
         // println!("op: {}, lhs_min: {} lhs_max: {}, rhs_min: {}, rhs_max: {}", self.op.to_string(), lhs_min.to_range_string(false, analyzer).s, lhs_max.to_range_string(true, analyzer).s, rhs_min.to_range_string(false, analyzer).s, rhs_max.to_range_string(true, analyzer).s);
         match self.op {
             RangeOp::Add => {
@@ -817,115 +857,47 @@ impl ExecOp<Concrete> for RangeExpr<Concrete> {
                 }
             }
             RangeOp::Mul => {
+                // the product is monotone in each operand for a fixed sign of the other, so
+                // its extremes lie on the corners
+                let candidates = vec![
+                    lhs_min.range_mul(&rhs_min),
+                    lhs_min.range_mul(&rhs_max),
+                    lhs_max.range_mul(&rhs_min),
+                    lhs_max.range_mul(&rhs_max),
+                ];
+                let mut candidates = candidates.into_iter().flatten().collect::<Vec<_>>();
+                candidates.sort_by(|a, b| match a.range_ord(b) {
+                    Some(r) => r,
+                    _ => std::cmp::Ordering::Less,
+                });
+
+                if candidates.is_empty() {
+                    return Elem::Expr(self.clone());
+                }
+
                 if maximize {
-                    // if we are maximizing, and both mins are negative and both maxes are positive,
-                    // we dont know which will be larger of the two (i.e. -1*2**255 * -1*2**255 > 100*100)
-                    match (lhs_min_neg, lhs_max_neg, rhs_min_neg, rhs_max_neg) {
-                        (true, true, true, true) => {
-                            // all negative, will be min * min because those are furthest from 0 resulting in the
-                            // largest positive value
-                            lhs_min
-                                .range_mul(&rhs_min)
-                                .unwrap_or(Elem::Expr(self.clone()))
-                        }
-                        (true, false, true, false) => {
-                            // we dont know if lhs_max * rhs_min is larger or lhs_min * rhs_max is smaller
-                            match (lhs_min.range_mul(&rhs_min), lhs_max.range_mul(&rhs_max)) {
-                                (Some(min_expr), Some(max_expr)) => {
-                                    match min_expr.range_ord(&max_expr) {
-                                        Some(std::cmp::Ordering::Less) => max_expr,
-                                        Some(std::cmp::Ordering::Greater) => min_expr,
-                                        _ => max_expr,
-                                    }
-                                }
-                                (None, Some(max_expr)) => max_expr,
-                                (Some(min_expr), None) => min_expr,
-                                (None, None) => Elem::Expr(self.clone()),
-                            }
-                        }
-                        (_, false, _, false) => {
-                            // rhs_max is positive, lhs_max is positive, guaranteed to be largest max value
-                            lhs_max
-                                .range_mul(&rhs_max)
-                                .unwrap_or(Elem::Expr(self.clone()))
-                        }
-                        (false, false, true, true) => {
-                            // since we are forced to go negative here, values closest to 0 will ensure we get the maximum
-                            lhs_min
-                                .range_mul(&rhs_max)
-                                .unwrap_or(Elem::Expr(self.clone()))
-                        }
-                        (true, true, false, false) => {
-                            // since we are forced to go negative here, values closest to 0 will ensure we get the maximum
-                            lhs_max
-                                .range_mul(&rhs_min)
-                                .unwrap_or(Elem::Expr(self.clone()))
-                        }
-                        (true, _, true, _) => lhs_min
-                            .range_mul(&rhs_min)
-                            .unwrap_or(Elem::Expr(self.clone())),
-                        (false, true, _, _) | (_, _, false, true) => {
-                            panic!("unsatisfiable range")
-                        }
-                    }
+                    candidates[candidates.len() - 1].clone()
                 } else {
-                    match (lhs_min_neg, lhs_max_neg, rhs_min_neg, rhs_max_neg) {
-                        (false, false, false, false) => {
-                            // rhs_min is positive, lhs_min is positive, guaranteed to be smallest max value
-                            lhs_min
-                                .range_mul(&rhs_min)
-                                .unwrap_or(Elem::Expr(self.clone()))
-                        }
-                        (true, true, true, true) => {
-                            // all negative, will be max * max because those are closest to 0 resulting in the
-                            // smallest positive value
-                            lhs_max
-                                .range_mul(&rhs_max)
-                                .unwrap_or(Elem::Expr(self.clone()))
-                        }
-                        (true, false, true, false) => {
-                            // we dont know if lhs_max * rhs_min is smaller or lhs_min * rhs_max is smaller
-                            match (lhs_max.range_mul(&rhs_min), lhs_min.range_mul(&rhs_max)) {
-                                (Some(min_expr), Some(max_expr)) => {
-                                    match min_expr.range_ord(&max_expr) {
-                                        Some(std::cmp::Ordering::Less) => min_expr,
-                                        Some(std::cmp::Ordering::Greater) => max_expr,
-                                        _ => min_expr,
-                                    }
-                                }
-                                (None, Some(max_expr)) => max_expr,
-                                (Some(min_expr), None) => min_expr,
-                                (None, None) => Elem::Expr(self.clone()),
-                            }
-                        }
-                        (true, _, _, false) => {
-                            // rhs_max is positive, lhs_min is negative, guaranteed to be largest min value
-                            lhs_min
-                                .range_mul(&rhs_max)
-                                .unwrap_or(Elem::Expr(self.clone()))
-                        }
-                        (_, false, _, true) => {
-                            // just lhs has a positive value, most negative will be lhs_max, rhs_max
-                            lhs_max
-                                .range_mul(&rhs_max)
-                                .unwrap_or(Elem::Expr(self.clone()))
-                        }
-                        (false, false, true, false) => lhs_max
-                            .range_mul(&rhs_min)
-                            .unwrap_or(Elem::Expr(self.clone())),
-                        (false, true, _, _) | (_, _, false, true) => {
-                            panic!("unsatisfiable range")
-                        }
-                    }
+                    candidates[0].clone()
                 }
             }
             RangeOp::Div => {
-                let candidates = vec![
+                let mut candidates = vec![
                     lhs_min.range_div(&rhs_min),
                     lhs_min.range_div(&rhs_max),
                     lhs_max.range_div(&rhs_min),
                     lhs_max.range_div(&rhs_max),
                 ];
+                // the divisors closest to zero give the largest quotients, and aren't
+                // corners when the divisor can be zero
+                for unit in Self::inner_units(&rhs_min, &rhs_max) {
+                    // `type(int).min / -1` overflows, but the value just above it gives the max
+                    let type_max = lhs_min
+                        .maybe_concrete()
+                        .and_then(|c| Some(Elem::from(Concrete::max(&c.val)?)));
+                    candidates.push(lhs_min.range_div(&unit).or(type_max));
+                    candidates.push(lhs_max.range_div(&unit));
+                }
                 let mut candidates = candidates.into_iter().flatten().collect::<Vec<_>>();
                 candidates.sort_by(|a, b| match a.range_ord(b) {
                     Some(r) => r,
@@ -1467,7 +1439,14 @@ impl ExecOp<Concrete> for RangeExpr<Concrete> {
                     Elem::Expr(self.clone())
                 }
             }
+            RangeOp::Mod => Self::mod_bound(maximize, &lhs_min, &lhs_max, &rhs_min, &rhs_max)
+                .unwrap_or(Elem::Expr(self.clone())),
             RangeOp::Shl => {
+                if let Some(bound) =
+                    Self::shl_bound(maximize, &lhs_min, &lhs_max, &rhs_min, &rhs_max)
+                {
+                    return bound;
+                }
                 let candidates = vec![
                     lhs_min.range_shl(&rhs_min),
                     lhs_min.range_shl(&rhs_max),
@@ -1628,6 +1607,11 @@ impl ExecOp<Concrete> for RangeExpr<Concrete> {
                 }
             }
             RangeOp::BitAnd => {
+                if let Some(bound) =
+                    Self::bitwise_bound(self.op, maximize, &lhs_min, &lhs_max, &rhs_min, &rhs_max)
+                {
+                    return bound;
+                }
                 if maximize {
                     lhs_max
                         .range_bit_and(&rhs_max)
@@ -1639,6 +1623,11 @@ impl ExecOp<Concrete> for RangeExpr<Concrete> {
                 }
             }
             RangeOp::BitOr => {
+                if let Some(bound) =
+                    Self::bitwise_bound(self.op, maximize, &lhs_min, &lhs_max, &rhs_min, &rhs_max)
+                {
+                    return bound;
+                }
                 if maximize {
                     lhs_max
                         .range_bit_or(&rhs_max)
@@ -1650,6 +1639,11 @@ impl ExecOp<Concrete> for RangeExpr<Concrete> {
                 }
             }
             RangeOp::BitXor => {
+                if let Some(bound) =
+                    Self::bitwise_bound(self.op, maximize, &lhs_min, &lhs_max, &rhs_min, &rhs_max)
+                {
+                    return bound;
+                }
                 if maximize {
                     lhs_max
                         .range_bit_xor(&rhs_max)
@@ -1664,7 +1658,222 @@ impl ExecOp<Concrete> for RangeExpr<Concrete> {
         }
     }
 
-    fn simplify_exec_op(&self, _maximize: bool, _analyzer: &impl GraphLike) -> Elem<Concrete> {
-        todo!();
+    fn simplify_exec_op(&self, maximize: bool, analyzer: &impl GraphLike) -> Elem<Concrete> {
+        let simplify = |elem: &Elem<Concrete>, maximize: bool| {
+            if maximize {
+                elem.simplify_maximize(analyzer)
+            } else {
+                elem.simplify_minimize(analyzer)
+            }
+        };
+        // the rhs of a subtraction or division pulls the result the opposite way
+        let rhs_maximize = match self.op {
+            RangeOp::Sub | RangeOp::Div => !maximize,
+            _ => maximize,
+        };
+        let expr = RangeExpr::new(
+            simplify(&self.lhs, maximize),
+            self.op,
+            simplify(&self.rhs, rhs_maximize),
+        );
+        simplify::simplify_expr(expr, maximize, analyzer)
+    }
+}
+
+impl RangeExpr<Concrete> {
+    /// The integers `1` and `-1` of the bounds' type, where they lie within `[min, max]`
+    fn inner_units(min: &Elem<Concrete>, max: &Elem<Concrete>) -> Vec<Elem<Concrete>> {
+        let min_c = match min.maybe_concrete() {
+            Some(min_c) => min_c,
+            None => return vec![],
+        };
+        let units = match min_c.val {
+            Concrete::Uint(size, _) => vec![Concrete::Uint(size, U256::one())],
+            Concrete::Int(size, _) => vec![
+                Concrete::Int(size, I256::one()),
+                Concrete::Int(size, I256::minus_one()),
+            ],
+            _ => vec![],
+        };
+        units
+            .into_iter()
+            .map(|val| {
+                Elem::Concrete(RangeConcrete {
+                    val,
+                    loc: min_c.loc,
+                })
+            })
+            .filter(|unit| {
+                matches!(
+                    min.range_ord(unit),
+                    Some(std::cmp::Ordering::Less | std::cmp::Ordering::Equal)
+                ) && matches!(
+                    max.range_ord(unit),
+                    Some(std::cmp::Ordering::Greater | std::cmp::Ordering::Equal)
+                )
+            })
+            .collect()
+    }
+
+    /// Bounds a bitwise operation on integers. The operations don't preserve order, so the
+    /// endpoints say little on their own, but the bits shared by every value on each side do.
+    /// Intervals that cross zero don't share any bits, so they fall back to the type's bounds
+    fn bitwise_bound(
+        op: RangeOp,
+        maximize: bool,
+        lhs_min: &Elem<Concrete>,
+        lhs_max: &Elem<Concrete>,
+        rhs_min: &Elem<Concrete>,
+        rhs_max: &Elem<Concrete>,
+    ) -> Option<Elem<Concrete>> {
+        let lhs_min = lhs_min.maybe_concrete()?;
+        let lhs_max = lhs_max.maybe_concrete()?.val;
+        let rhs_min = rhs_min.maybe_concrete()?.val;
+        let rhs_max = rhs_max.maybe_concrete()?.val;
+        let ty = lhs_min.val.clone();
+        if !matches!(ty, Concrete::Uint(..) | Concrete::Int(..)) {
+            return None;
+        }
+
+        let words = (
+            lhs_min.val.into_u256(),
+            lhs_max.into_u256(),
+            rhs_min.into_u256(),
+            rhs_max.into_u256(),
+        );
+        let val = match words {
+            (Some(lhs_min), Some(lhs_max), Some(rhs_min), Some(rhs_max)) => {
+                let lhs = KnownBits::from_interval(lhs_min, lhs_max);
+                let rhs = KnownBits::from_interval(rhs_min, rhs_max);
+                let bits = lhs.apply(op, &rhs)?.truncate(ty.int_size()?);
+                if maximize {
+                    ty.u256_as_original(bits.max_value())?
+                } else {
+                    ty.u256_as_original(bits.min_value())?
+                }
+            }
+            _ if maximize => Concrete::max(&ty)?,
+            _ => Concrete::min(&ty)?,
+        };
+        Some(Elem::Concrete(RangeConcrete {
+            val,
+            loc: lhs_min.loc,
+        }))
+    }
+
+    /// Bounds the remainder of integers. It takes the sign of the dividend and is smaller in
+    /// magnitude than both the dividend and the largest divisor
+    fn mod_bound(
+        maximize: bool,
+        lhs_min: &Elem<Concrete>,
+        lhs_max: &Elem<Concrete>,
+        rhs_min: &Elem<Concrete>,
+        rhs_max: &Elem<Concrete>,
+    ) -> Option<Elem<Concrete>> {
+        let lhs = lhs_min.maybe_concrete()?;
+        let vals = (
+            lhs.val.clone(),
+            lhs_max.maybe_concrete()?.val,
+            rhs_min.maybe_concrete()?.val,
+            rhs_max.maybe_concrete()?.val,
+        );
+        let val = match vals {
+            (
+                Concrete::Uint(size, lhs_min),
+                Concrete::Uint(_, lhs_max),
+                Concrete::Uint(_, rhs_min),
+                Concrete::Uint(_, rhs_max),
+            ) => {
+                if lhs_max < rhs_min {
+                    // the dividend is always smaller than the divisor, so is left as is
+                    Concrete::Uint(size, if maximize { lhs_max } else { lhs_min })
+                } else if maximize {
+                    Concrete::Uint(size, lhs_max.min(rhs_max.saturating_sub(U256::one())))
+                } else {
+                    Concrete::Uint(size, U256::zero())
+                }
+            }
+            (
+                Concrete::Int(size, lhs_min),
+                Concrete::Int(_, lhs_max),
+                Concrete::Int(_, rhs_min),
+                Concrete::Int(_, rhs_max),
+            ) => {
+                let max_abs = rhs_min
+                    .unsigned_abs()
+                    .max(rhs_max.unsigned_abs())
+                    .saturating_sub(U256::one());
+                if maximize {
+                    if lhs_max.is_negative() {
+                        Concrete::Int(size, I256::zero())
+                    } else {
+                        Concrete::Int(size, I256::from_raw(lhs_max.into_raw().min(max_abs)))
+                    }
+                } else if lhs_min.is_negative() {
+                    let abs = lhs_min.unsigned_abs().min(max_abs);
+                    Concrete::Int(size, I256::zero() - I256::from_raw(abs))
+                } else {
+                    Concrete::Int(size, I256::zero())
+                }
+            }
+            _ => return None,
+        };
+        Some(Elem::Concrete(RangeConcrete { val, loc: lhs.loc }))
+    }
+
+    /// Bounds a left shift of an integer. Shifting never reverts, it wraps, so the corners
+    /// only bound the result when none of them shifts bits out of the type. Otherwise the
+    /// result can be anything the type holds
+    fn shl_bound(
+        maximize: bool,
+        lhs_min: &Elem<Concrete>,
+        lhs_max: &Elem<Concrete>,
+        rhs_min: &Elem<Concrete>,
+        rhs_max: &Elem<Concrete>,
+    ) -> Option<Elem<Concrete>> {
+        let lhs = lhs_min.maybe_concrete()?;
+        let size = match lhs.val {
+            Concrete::Uint(size, _) | Concrete::Int(size, _) => size,
+            _ => return None,
+        };
+        let widest = Elem::from(Concrete::from(U256::from(size - 1)));
+        let type_bound = |maximize: bool| {
+            let val = if maximize {
+                Concrete::max(&lhs.val)
+            } else {
+                Concrete::min(&lhs.val)
+            };
+            val.map(|val| Elem::Concrete(RangeConcrete { val, loc: lhs.loc }))
+        };
+
+        let mut candidates = vec![];
+        for shift in [rhs_min, rhs_max] {
+            // shifting by the whole type or more leaves zero, which is added below
+            let shift = shift.range_min(&widest)?;
+            for val in [lhs_min, lhs_max] {
+                let shifted = val.range_shl(&shift)?;
+                let roundtrip = shifted.range_shr(&shift)?;
+                if !roundtrip.range_eq(val) {
+                    return type_bound(maximize);
+                }
+                candidates.push(shifted);
+            }
+        }
+        if rhs_max.range_ord(&widest) == Some(std::cmp::Ordering::Greater) {
+            candidates.push(Elem::Concrete(RangeConcrete {
+                val: lhs.val.u256_as_original(U256::zero())?,
+                loc: lhs.loc,
+            }));
+        }
+
+        candidates.sort_by(|a, b| match a.range_ord(b) {
+            Some(r) => r,
+            _ => std::cmp::Ordering::Less,
+        });
+        if maximize {
+            candidates.pop()
+        } else {
+            candidates.into_iter().next()
+        }
     }
 }
@@ -0,0 +1,124 @@
+use crate::context::ContextVarNode;
+use crate::range::elem_ty::Elem;
+use crate::{Concrete, NodeIdx};
+
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeSet, HashMap};
+
+/// Evaluated bounds of variables, shared across the whole graph. Evaluating a range follows
+/// every variable it references, so without it deep chains get re-walked for every use.
+///
+/// An entry is keyed by the variable and whether it is the max, and is dropped whenever the
+/// variable, or any variable its range depends on, is modified
+#[derive(Debug, Clone, Default)]
+pub struct EvalCache {
+    entries: RefCell<HashMap<(NodeIdx, bool), Elem<Concrete>>>,
+    /// The variables whose cached bounds were evaluated from each variable
+    dependents: RefCell<HashMap<NodeIdx, BTreeSet<NodeIdx>>>,
+    hits: Cell<usize>,
+    misses: Cell<usize>,
+    invalidations: Cell<usize>,
+}
+
+/// Counters of how effective an [`EvalCache`] has been
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EvalCacheStats {
+    pub hits: usize,
+    pub misses: usize,
+    /// Cached bounds dropped because their variable or one of its dependencies changed
+    pub invalidations: usize,
+    /// Bounds currently cached
+    pub entries: usize,
+}
+
+impl EvalCacheStats {
+    /// The fraction of lookups answered by the cache
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+impl std::fmt::Display for EvalCacheStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "range evaluation cache: {} hits, {} misses ({:.1}% hit rate), {} invalidations, {} entries",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0,
+            self.invalidations,
+            self.entries
+        )
+    }
+}
+
+impl EvalCache {
+    /// Returns the cached bound of `var`, evaluating and caching it with `eval` on a miss.
+    /// `deps` are the variables the bound is evaluated from
+    pub fn get_or_eval(
+        &self,
+        var: NodeIdx,
+        maximize: bool,
+        deps: impl FnOnce() -> Vec<ContextVarNode>,
+        eval: impl FnOnce() -> Elem<Concrete>,
+    ) -> Elem<Concrete> {
+        if let Some(cached) = self.entries.borrow().get(&(var, maximize)) {
+            self.hits.set(self.hits.get() + 1);
+            return cached.clone();
+        }
+        self.misses.set(self.misses.get() + 1);
+        // evaluation may recurse into the cache, so nothing is borrowed across it
+        let evaled = eval();
+        {
+            let mut dependents = self.dependents.borrow_mut();
+            deps().into_iter().for_each(|dep| {
+                dependents.entry(dep.into()).or_default().insert(var);
+            });
+        }
+        self.entries
+            .borrow_mut()
+            .insert((var, maximize), evaled.clone());
+        evaled
+    }
+
+    /// Drops the cached bounds of `var` and of every variable evaluated from it
+    pub fn invalidate(&self, var: NodeIdx) {
+        let mut entries = self.entries.borrow_mut();
+        let mut dependents = self.dependents.borrow_mut();
+        let mut stack = vec![var];
+        let mut seen = BTreeSet::new();
+        while let Some(var) = stack.pop() {
+            if !seen.insert(var) {
+                continue;
+            }
+            let dropped = [true, false]
+                .iter()
+                .filter(|maximize| entries.remove(&(var, **maximize)).is_some())
+                .count();
+            self.invalidations.set(self.invalidations.get() + dropped);
+            if let Some(deps) = dependents.remove(&var) {
+                stack.extend(deps);
+            }
+        }
+    }
+
+    /// Drops every cached bound, leaving the counters as they are
+    pub fn clear(&self) {
+        self.entries.borrow_mut().clear();
+        self.dependents.borrow_mut().clear();
+    }
+
+    pub fn stats(&self) -> EvalCacheStats {
+        EvalCacheStats {
+            hits: self.hits.get(),
+            misses: self.misses.get(),
+            invalidations: self.invalidations.get(),
+            entries: self.entries.borrow().len(),
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use crate::range::elem::RangeElem;
+use crate::range::elem_ty::Elem;
+use crate::range::range_ops::RangeSub;
+use crate::Concrete;
+
+use std::cmp::Ordering;
+
+/// The default number of disjoint intervals a range holds before neighbours get merged
+pub const DEFAULT_INTERVAL_CAP: usize = 4;
+
+/// A sorted set of disjoint, concrete intervals. Lets a range express disjunctions like
+/// `x < 10 || x > 100` that a single `[min, max]` would lose
+#[derive(Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Hash)]
+pub struct IntervalSet {
+    intervals: Vec<(Elem<Concrete>, Elem<Concrete>)>,
+}
+
+impl IntervalSet {
+    /// Creates a set holding a single interval
+    pub fn new(min: Elem<Concrete>, max: Elem<Concrete>) -> Self {
+        Self::from_intervals(vec![(min, max)])
+    }
+
+    /// Creates a set from possibly overlapping intervals. Intervals that are not concrete or
+    /// are empty are dropped and overlapping ones are merged
+    pub fn from_intervals(intervals: Vec<(Elem<Concrete>, Elem<Concrete>)>) -> Self {
+        let mut intervals: Vec<_> = intervals
+            .into_iter()
+            .filter(|(min, max)| {
+                min.maybe_concrete().is_some()
+                    && max.maybe_concrete().is_some()
+                    && matches!(
+                        min.range_ord(max),
+                        Some(Ordering::Less) | Some(Ordering::Equal)
+                    )
+            })
+            .collect();
+        intervals.sort_by(|a, b| a.0.range_ord(&b.0).unwrap_or(Ordering::Equal));
+
+        let mut merged: Vec<(Elem<Concrete>, Elem<Concrete>)> = vec![];
+        intervals
+            .into_iter()
+            .for_each(|(min, max)| match merged.last_mut() {
+                Some((_, last_max))
+                    if matches!(
+                        min.range_ord(last_max),
+                        Some(Ordering::Less) | Some(Ordering::Equal)
+                    ) =>
+                {
+                    if matches!(max.range_ord(last_max), Some(Ordering::Greater)) {
+                        *last_max = max;
+                    }
+                }
+                _ => merged.push((min, max)),
+            });
+
+        Self { intervals: merged }
+    }
+
+    /// The intervals, sorted from lowest to highest
+    pub fn intervals(&self) -> &[(Elem<Concrete>, Elem<Concrete>)] {
+        &self.intervals
+    }
+
+    /// Whether no value is in the set
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Whether the set is more precise than its hull
+    pub fn is_disjoint(&self) -> bool {
+        self.intervals.len() > 1
+    }
+
+    /// The smallest single interval containing the set
+    pub fn hull(&self) -> Option<(Elem<Concrete>, Elem<Concrete>)> {
+        Some((
+            self.intervals.first()?.0.clone(),
+            self.intervals.last()?.1.clone(),
+        ))
+    }
+
+    /// The values in either set
+    pub fn union(&self, other: &Self) -> Self {
+        Self::from_intervals(
+            self.intervals
+                .iter()
+                .chain(other.intervals.iter())
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// The values in both sets
+    pub fn intersect(&self, other: &Self) -> Self {
+        Self::from_intervals(
+            self.intervals
+                .iter()
+                .flat_map(|(a_min, a_max)| {
+                    other.intervals.iter().map(move |(b_min, b_max)| {
+                        let min = if matches!(a_min.range_ord(b_min), Some(Ordering::Less)) {
+                            b_min.clone()
+                        } else {
+                            a_min.clone()
+                        };
+                        let max = if matches!(a_max.range_ord(b_max), Some(Ordering::Greater)) {
+                            b_max.clone()
+                        } else {
+                            a_max.clone()
+                        };
+                        (min, max)
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    /// Whether the value is in the set
+    pub fn contains(&self, elem: &Elem<Concrete>) -> bool {
+        self.intervals.iter().any(|(min, max)| {
+            matches!(
+                min.range_ord(elem),
+                Some(Ordering::Less) | Some(Ordering::Equal)
+            ) && matches!(
+                max.range_ord(elem),
+                Some(Ordering::Greater) | Some(Ordering::Equal)
+            )
+        })
+    }
+
+    /// Merges the two neighbours with the smallest gap between them until at most `cap` intervals remain
+    pub fn cap(&mut self, cap: usize) {
+        while self.intervals.len() > cap.max(1) {
+            let closest = (0..self.intervals.len() - 1)
+                .filter_map(|i| {
+                    let gap = self.intervals[i + 1].0.range_sub(&self.intervals[i].1)?;
+                    Some((i, gap))
+                })
+                .min_by(|(_, a), (_, b)| a.range_ord(b).unwrap_or(Ordering::Equal))
+                .map(|(i, _)| i)
+                .unwrap_or(0);
+            let (_, max) = self.intervals.remove(closest + 1);
+            self.intervals[closest].1 = max;
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use crate::range::elem::RangeOp;
+use crate::range::elem_ty::Elem;
+use crate::Concrete;
+
+use ethers_core::types::U256;
+
+/// Bits of an unsigned integer that are known to be zero or one regardless of the path taken.
+/// Bitwise operations propagate these exactly, where intervals lose almost everything
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Ord, PartialOrd, Hash)]
+pub struct KnownBits {
+    /// Bits known to be zero
+    pub zeros: U256,
+    /// Bits known to be one
+    pub ones: U256,
+}
+
+impl KnownBits {
+    /// No bit is known
+    pub fn unknown() -> Self {
+        Self {
+            zeros: U256::zero(),
+            ones: U256::zero(),
+        }
+    }
+
+    /// Every bit is known
+    pub fn constant(val: U256) -> Self {
+        Self {
+            zeros: !val,
+            ones: val,
+        }
+    }
+
+    /// The bits shared by every value in `[min, max]`, i.e. the common prefix of the bounds
+    pub fn from_interval(min: U256, max: U256) -> Self {
+        if min > max {
+            return Self::unknown();
+        }
+        let differing = min ^ max;
+        let unknown = if differing.is_zero() {
+            U256::zero()
+        } else {
+            U256::MAX >> (differing.leading_zeros() as usize)
+        };
+        Self {
+            zeros: !min & !unknown,
+            ones: min & !unknown,
+        }
+    }
+
+    /// The bits of a `uint{size}` with the given evaluated bounds. Anything but unsigned
+    /// integers is unsupported
+    pub fn from_elems(min: &Elem<Concrete>, max: &Elem<Concrete>) -> Option<Self> {
+        match (min.maybe_concrete()?.val, max.maybe_concrete()?.val) {
+            (Concrete::Uint(size, min), Concrete::Uint(_, max)) => {
+                Some(Self::from_interval(min, max).truncate(size))
+            }
+            _ => None,
+        }
+    }
+
+    /// Marks every bit above `size` as zero
+    pub fn truncate(self, size: u16) -> Self {
+        let mask = if size >= 256 {
+            U256::MAX
+        } else {
+            (U256::one() << size as usize) - 1
+        };
+        Self {
+            zeros: self.zeros | !mask,
+            ones: self.ones & mask,
+        }
+    }
+
+    /// Whether every bit is known
+    pub fn is_constant(&self) -> bool {
+        (self.zeros | self.ones) == U256::MAX
+    }
+
+    /// Whether a bit is known to be both zero and one, i.e. no value has these bits
+    pub fn is_conflicting(&self) -> bool {
+        !(self.zeros & self.ones).is_zero()
+    }
+
+    /// The smallest value with these bits
+    pub fn min_value(&self) -> U256 {
+        self.ones
+    }
+
+    /// The largest value with these bits
+    pub fn max_value(&self) -> U256 {
+        !self.zeros
+    }
+
+    /// Combines what is known from two sources about the same value
+    pub fn intersect(&self, other: &Self) -> Self {
+        Self {
+            zeros: self.zeros | other.zeros,
+            ones: self.ones | other.ones,
+        }
+    }
+
+    pub fn and(&self, other: &Self) -> Self {
+        Self {
+            zeros: self.zeros | other.zeros,
+            ones: self.ones & other.ones,
+        }
+    }
+
+    pub fn or(&self, other: &Self) -> Self {
+        Self {
+            zeros: self.zeros & other.zeros,
+            ones: self.ones | other.ones,
+        }
+    }
+
+    pub fn xor(&self, other: &Self) -> Self {
+        Self {
+            zeros: (self.zeros & other.zeros) | (self.ones & other.ones),
+            ones: (self.zeros & other.ones) | (self.ones & other.zeros),
+        }
+    }
+
+    /// Shifts left by a constant, shifting in known zeros
+    pub fn shl(&self, shift: U256) -> Self {
+        if shift >= U256::from(256) {
+            return Self::constant(U256::zero());
+        }
+        let shift = shift.as_usize();
+        Self {
+            zeros: (self.zeros << shift) | !(U256::MAX << shift),
+            ones: self.ones << shift,
+        }
+    }
+
+    /// Shifts right by a constant, shifting in known zeros
+    pub fn shr(&self, shift: U256) -> Self {
+        if shift >= U256::from(256) {
+            return Self::constant(U256::zero());
+        }
+        let shift = shift.as_usize();
+        Self {
+            zeros: (self.zeros >> shift) | !(U256::MAX >> shift),
+            ones: self.ones >> shift,
+        }
+    }
+
+    /// Applies a bitwise operation. Shifts are only supported by a known amount
+    pub fn apply(&self, op: RangeOp, other: &Self) -> Option<Self> {
+        match op {
+            RangeOp::BitAnd => Some(self.and(other)),
+            RangeOp::BitOr => Some(self.or(other)),
+            RangeOp::BitXor => Some(self.xor(other)),
+            RangeOp::Shl if other.is_constant() => Some(self.shl(other.ones)),
+            RangeOp::Shr if other.is_constant() => Some(self.shr(other.ones)),
+            _ => None,
+        }
+    }
+}
@@ -2,12 +2,15 @@ use crate::analyzer::AnalyzerLike;
 use crate::analyzer::AsDotStr;
 use crate::context::ContextNode;
 use crate::context::ContextVarNode;
+use crate::range::congruence::Congruence;
 use crate::range::elem::RangeElem;
 use crate::range::elem::RangeOp;
 use crate::range::elem_ty::Dynamic;
 use crate::range::elem_ty::Elem;
 use crate::range::elem_ty::RangeConcrete;
 use crate::range::elem_ty::RangeDyn;
+use crate::range::interval_set::IntervalSet;
+use crate::range::known_bits::KnownBits;
 use crate::range::range_string::ToRangeString;
 use crate::Builtin;
 use crate::Concrete;
@@ -21,10 +24,16 @@ use std::collections::BTreeMap;
 
 use solang_parser::pt::Loc;
 
+pub mod congruence;
 pub mod elem;
 pub mod elem_ty;
+pub mod eval_cache;
+pub mod interval_set;
+pub mod known_bits;
 pub mod range_ops;
 pub mod range_string;
+pub mod simplify;
+pub mod smt;
 
 #[derive(Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Hash)]
 pub struct SolcRange {
@@ -33,6 +42,15 @@ pub struct SolcRange {
     pub max: Elem<Concrete>,
     pub max_cached: Option<Elem<Concrete>>,
     pub exclusions: Vec<Elem<Concrete>>,
+    /// Disjoint intervals the value is known to lie in, tighter than `[min, max]`. Cleared
+    /// whenever the min or max is replaced
+    pub intervals: Option<IntervalSet>,
+    /// Bits of the value known to be zero or one, which bound the evaluated min and max.
+    /// Cleared whenever the min or max is replaced
+    pub known_bits: Option<KnownBits>,
+    /// A congruence the value is known to satisfy, which bounds the evaluated min and max.
+    /// Cleared whenever the min or max is replaced
+    pub congruence: Option<Congruence>,
 }
 
 impl AsDotStr for SolcRange {
@@ -73,6 +91,53 @@ impl SolcRange {
             max,
             max_cached: None,
             exclusions,
+            intervals: None,
+            known_bits: None,
+            congruence: None,
+        }
+    }
+
+    /// Raises an evaluated minimum to the smallest value the known bits and congruence allow
+    fn tightened_min(&self, min: Elem<Concrete>) -> Elem<Concrete> {
+        match min {
+            Elem::Concrete(RangeConcrete {
+                val: Concrete::Uint(size, mut val),
+                loc,
+            }) => {
+                if let Some(bits) = &self.known_bits {
+                    val = val.max(bits.min_value());
+                }
+                if let Some(congruence) = &self.congruence {
+                    val = congruence.round_up(val);
+                }
+                Elem::Concrete(RangeConcrete {
+                    val: Concrete::Uint(size, val),
+                    loc,
+                })
+            }
+            min => min,
+        }
+    }
+
+    /// Lowers an evaluated maximum to the largest value the known bits and congruence allow
+    fn tightened_max(&self, max: Elem<Concrete>) -> Elem<Concrete> {
+        match max {
+            Elem::Concrete(RangeConcrete {
+                val: Concrete::Uint(size, mut val),
+                loc,
+            }) => {
+                if let Some(bits) = &self.known_bits {
+                    val = val.min(bits.max_value());
+                }
+                if let Some(congruence) = &self.congruence {
+                    val = congruence.round_down(val);
+                }
+                Elem::Concrete(RangeConcrete {
+                    val: Concrete::Uint(size, val),
+                    loc,
+                })
+            }
+            max => max,
         }
     }
 
@@ -179,7 +244,7 @@ impl SolcRange {
                 } else {
                     let max: I256 =
                         I256::from_raw(U256::from(1u8) << U256::from(size - 1)) - I256::from(1);
-                    let min = max * I256::from(-1i32);
+                    let min = I256::from_raw(U256::MAX << U256::from(size - 1));
                     Some(SolcRange::new(
                         Elem::Concrete(RangeConcrete {
                             val: Concrete::Int(*size, min),
@@ -247,6 +312,14 @@ impl SolcRange {
                 })),
                 vec![],
             )),
+            Builtin::SizedArray(size, _) => {
+                let len = Elem::ConcreteDyn(Box::new(RangeDyn {
+                    len: Elem::from(Concrete::from(*size)),
+                    val: Default::default(),
+                    loc: Loc::Implicit,
+                }));
+                Some(SolcRange::new(len.clone(), len, vec![]))
+            }
             _ => None,
         }
     }
@@ -347,9 +420,13 @@ impl SolcRange {
     }
 
     pub fn mul_dyn(self, other: ContextVarNode, loc: Loc) -> Self {
+        let elem = Elem::Dynamic(Dynamic::new(other.into(), loc));
+        // a negative factor swaps which bound's product is the smaller
+        let lhs = self.min * elem.clone();
+        let rhs = self.max * elem;
         Self::new(
-            self.min * Elem::Dynamic(Dynamic::new(other.into(), loc)),
-            self.max * Elem::Dynamic(Dynamic::new(other.into(), loc)),
+            Elem::min(lhs.clone(), rhs.clone()),
+            Elem::max(lhs, rhs),
             self.exclusions,
         )
     }
@@ -362,39 +439,58 @@ impl SolcRange {
         )
     }
 
-    pub fn bit_and_dyn(self, other: ContextVarNode, loc: Loc) -> Self {
-        Self::new(
-            self.min & Elem::Dynamic(Dynamic::new(other.into(), loc)),
-            self.max & Elem::Dynamic(Dynamic::new(other.into(), loc)),
-            self.exclusions,
+    /// The smallest and largest values of the type of `elem`
+    fn type_bounds(elem: &Elem<Concrete>) -> (Elem<Concrete>, Elem<Concrete>) {
+        (
+            Elem::from(Concrete::from(I256::MIN)).cast(elem.clone()),
+            Elem::from(Concrete::from(U256::MAX)).cast(elem.clone()),
         )
     }
 
+    // bitwise operations aren't monotonic, so applying them to the bounds doesn't bound the
+    // result. These only keep what holds for any operands in two's complement, the bits
+    // themselves are tracked by `BinOp::op`
+
+    pub fn bit_and_dyn(self, other: ContextVarNode, loc: Loc) -> Self {
+        let elem = Elem::Dynamic(Dynamic::new(other.into(), loc));
+        let (type_min, _) = Self::type_bounds(&elem);
+        // clearing bits never makes a value larger than both operands
+        Self::new(type_min, self.max.max(elem), self.exclusions)
+    }
+
     pub fn bit_or_dyn(self, other: ContextVarNode, loc: Loc) -> Self {
-        Self::new(
-            self.min | Elem::Dynamic(Dynamic::new(other.into(), loc)),
-            self.max | Elem::Dynamic(Dynamic::new(other.into(), loc)),
-            self.exclusions,
-        )
+        let elem = Elem::Dynamic(Dynamic::new(other.into(), loc));
+        let (_, type_max) = Self::type_bounds(&elem);
+        // setting bits never makes a value smaller than both operands
+        Self::new(self.min.min(elem), type_max, self.exclusions)
     }
 
     pub fn bit_xor_dyn(self, other: ContextVarNode, loc: Loc) -> Self {
-        Self::new(
-            self.min ^ Elem::Dynamic(Dynamic::new(other.into(), loc)),
-            self.max ^ Elem::Dynamic(Dynamic::new(other.into(), loc)),
-            self.exclusions,
-        )
+        let elem = Elem::Dynamic(Dynamic::new(other.into(), loc));
+        let (type_min, type_max) = Self::type_bounds(&elem);
+        Self::new(type_min, type_max, self.exclusions)
     }
 
     pub fn div_dyn(self, other: ContextVarNode, loc: Loc) -> Self {
         let elem = Elem::Dynamic(Dynamic::new(other.into(), loc));
-        Self::new(self.min / elem.clone(), self.max / elem, self.exclusions)
+        // as with `mul_dyn`, a negative divisor swaps the bounds
+        let lhs = self.min / elem.clone();
+        let rhs = self.max / elem;
+        Self::new(
+            Elem::min(lhs.clone(), rhs.clone()),
+            Elem::max(lhs, rhs),
+            self.exclusions,
+        )
     }
 
     pub fn shl_dyn(self, other: ContextVarNode, loc: Loc) -> Self {
+        let elem = Elem::Dynamic(Dynamic::new(other.into(), loc));
+        // shifting the bound of larger magnitude may overflow and wrap below the other's
+        let lhs = self.min << elem.clone();
+        let rhs = self.max << elem;
         Self::new(
-            self.min << Elem::Dynamic(Dynamic::new(other.into(), loc)),
-            self.max << Elem::Dynamic(Dynamic::new(other.into(), loc)),
+            Elem::min(lhs.clone(), rhs.clone()),
+            Elem::max(lhs, rhs),
             self.exclusions,
         )
     }
@@ -409,9 +505,16 @@ impl SolcRange {
 
     pub fn mod_dyn(self, other: ContextVarNode, loc: Loc) -> Self {
         let elem = Elem::Dynamic(Dynamic::new(other.into(), loc));
+        let zero = Elem::from(Concrete::from(U256::zero())).cast(elem.clone());
+        // the result is smaller in magnitude than the divisor and takes the sign of the lhs
+        let max_abs = Elem::max(elem.clone(), zero.clone() - elem.clone())
+            - Elem::from(Concrete::from(U256::from(1))).cast(elem);
         Self::new(
-            Elem::from(Concrete::from(U256::zero())),
-            elem.clone() - Elem::from(Concrete::from(U256::from(1))).cast(elem),
+            Elem::min(
+                zero.clone(),
+                Elem::max(self.min, zero.clone() - max_abs.clone()),
+            ),
+            Elem::max(zero, Elem::min(self.max, max_abs)),
             self.exclusions,
         )
     }
@@ -456,10 +559,10 @@ impl Range<Concrete> for SolcRange {
 
     fn cache_eval(&mut self, analyzer: &impl GraphLike) {
         if self.min_cached.is_none() {
-            self.min_cached = Some(self.range_min().minimize(analyzer));
+            self.min_cached = Some(self.tightened_min(self.range_min().minimize(analyzer)));
         }
         if self.max_cached.is_none() {
-            self.max_cached = Some(self.range_max().maximize(analyzer));
+            self.max_cached = Some(self.tightened_max(self.range_max().maximize(analyzer)));
         }
     }
 
@@ -467,7 +570,7 @@ impl Range<Concrete> for SolcRange {
         if let Some(cached) = &self.min_cached {
             cached.clone()
         } else {
-            self.range_min().minimize(analyzer)
+            self.tightened_min(self.range_min().minimize(analyzer))
         }
     }
 
@@ -475,7 +578,7 @@ impl Range<Concrete> for SolcRange {
         if let Some(cached) = &self.max_cached {
             cached.clone()
         } else {
-            self.range_max().maximize(analyzer)
+            self.tightened_max(self.range_max().maximize(analyzer))
         }
     }
 
@@ -490,10 +593,16 @@ impl Range<Concrete> for SolcRange {
     }
     fn set_range_min(&mut self, new: Self::ElemTy) {
         self.min_cached = None;
+        self.intervals = None;
+        self.known_bits = None;
+        self.congruence = None;
         self.min = new;
     }
     fn set_range_max(&mut self, new: Self::ElemTy) {
         self.max_cached = None;
+        self.intervals = None;
+        self.known_bits = None;
+        self.congruence = None;
         self.max = new;
     }
     fn set_range_exclusions(&mut self, new: Vec<Self::ElemTy>) {
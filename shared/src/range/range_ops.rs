@@ -1,3 +1,7 @@
+use crate::range::congruence::Congruence;
+use crate::range::elem::RangeElem;
+use crate::range::elem::RangeOp;
+use crate::range::interval_set::IntervalSet;
 use crate::range::Elem;
 use crate::range::RangeConcrete;
 use crate::range::RangeDyn;
@@ -5,8 +9,188 @@ use crate::Concrete;
 use ethers_core::types::H256;
 use ethers_core::types::I256;
 use ethers_core::types::U256;
+use ethers_core::types::U512;
+use std::cmp::Ordering;
 use std::collections::BTreeMap;
 
+/// An exact integer wide enough to hold the result of any arithmetic on two 256 bit integers,
+/// signed or not. Results are computed exactly and only then clamped back into the lhs's type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct WideInt {
+    neg: bool,
+    mag: U512,
+}
+
+impl WideInt {
+    fn new(neg: bool, mag: U512) -> Self {
+        // there is no negative zero
+        Self {
+            neg: neg && !mag.is_zero(),
+            mag,
+        }
+    }
+
+    /// Only unsigned and signed integers are supported
+    fn from_concrete(val: &Concrete) -> Option<Self> {
+        match val {
+            Concrete::Uint(_, val) => Some(Self::new(false, U512::from(*val))),
+            Concrete::Int(_, val) => {
+                let (sign, abs) = val.into_sign_and_abs();
+                Some(Self::new(sign.is_negative(), U512::from(abs)))
+            }
+            _ => None,
+        }
+    }
+
+    /// The bounds of the type of `val`, or `None` if it isn't an integer
+    fn bounds(val: &Concrete) -> Option<(Self, Self)> {
+        Some((
+            Self::from_concrete(&Concrete::min(val)?)?,
+            Self::from_concrete(&Concrete::max(val)?)?,
+        ))
+    }
+
+    fn add(self, other: Self) -> Self {
+        if self.neg == other.neg {
+            // neither magnitude exceeds 2**256, so this can't overflow
+            Self::new(self.neg, self.mag + other.mag)
+        } else if self.mag >= other.mag {
+            Self::new(self.neg, self.mag - other.mag)
+        } else {
+            Self::new(other.neg, other.mag - self.mag)
+        }
+    }
+
+    fn sub(self, other: Self) -> Self {
+        self.add(Self::new(!other.neg, other.mag))
+    }
+
+    fn mul(self, other: Self) -> Option<Self> {
+        Some(Self::new(
+            self.neg != other.neg,
+            self.mag.checked_mul(other.mag)?,
+        ))
+    }
+
+    /// Division truncating towards zero, like `SDIV`. `None` when dividing by zero
+    fn div(self, other: Self) -> Option<Self> {
+        if other.mag.is_zero() {
+            return None;
+        }
+        Some(Self::new(self.neg != other.neg, self.mag / other.mag))
+    }
+
+    /// Remainder taking the sign of the dividend, like `SMOD`. `None` when dividing by zero
+    fn rem(self, other: Self) -> Option<Self> {
+        if other.mag.is_zero() {
+            return None;
+        }
+        Some(Self::new(self.neg, self.mag % other.mag))
+    }
+
+    /// Multiplication by `2**shift`, saturating the magnitude if it leaves the wide range
+    fn shl(self, shift: U256) -> Self {
+        if self.mag.is_zero() {
+            return self;
+        }
+        if shift > U256::from(self.mag.leading_zeros()) {
+            return Self::new(self.neg, U512::MAX);
+        }
+        Self::new(self.neg, self.mag << shift.as_usize())
+    }
+
+    /// Division by `2**shift` rounding towards negative infinity, like `SAR`
+    fn shr(self, shift: U256) -> Self {
+        if shift >= U256::from(512) {
+            return if self.neg {
+                Self::new(true, U512::one())
+            } else {
+                Self::new(false, U512::zero())
+            };
+        }
+        let shift = shift.as_usize();
+        let truncated = self.mag >> shift;
+        if self.neg && truncated << shift != self.mag {
+            Self::new(true, truncated + 1)
+        } else {
+            Self::new(self.neg, truncated)
+        }
+    }
+
+    /// Converts the result of an operation back into the type of `lhs`, saturating at its
+    /// bounds, so an unsigned result below zero is zero
+    fn into_concrete(self, lhs: &Concrete) -> Option<Concrete> {
+        let (min, max) = Self::bounds(lhs)?;
+        let val = if self < min {
+            min
+        } else if self > max {
+            max
+        } else {
+            self
+        };
+        // in range of the type, so it fits in 256 bits
+        let mag = U256::try_from(val.mag).ok()?;
+        match *lhs {
+            Concrete::Uint(size, _) => Some(Concrete::Uint(size, mag)),
+            Concrete::Int(size, _) if val.neg => Some(Concrete::Int(
+                size,
+                I256::from_raw((!mag).overflowing_add(U256::one()).0),
+            )),
+            Concrete::Int(size, _) => Some(Concrete::Int(size, I256::from_raw(mag))),
+            _ => None,
+        }
+    }
+
+    /// Like [`WideInt::into_concrete`], but results outside the lhs's type are `None`, i.e. the
+    /// operation reverts
+    fn into_concrete_checked(self, lhs: &Concrete) -> Option<Concrete> {
+        let (min, max) = Self::bounds(lhs)?;
+        if self < min || self > max {
+            None
+        } else {
+            self.into_concrete(lhs)
+        }
+    }
+}
+
+impl PartialOrd for WideInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for WideInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.neg, other.neg) {
+            (false, false) => self.mag.cmp(&other.mag),
+            (true, true) => other.mag.cmp(&self.mag),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+        }
+    }
+}
+
+impl RangeConcrete<Concrete> {
+    /// Applies an integer operation exactly and converts the result back into the lhs's type.
+    /// `None` if either side isn't an integer or the operation reverts
+    fn wide_op(
+        &self,
+        other: &Self,
+        op: impl FnOnce(WideInt, WideInt) -> Option<Concrete>,
+    ) -> Option<Elem<Concrete>> {
+        let lhs = WideInt::from_concrete(&self.val)?;
+        let rhs = WideInt::from_concrete(&other.val)?;
+        Some(Elem::Concrete(RangeConcrete {
+            val: op(lhs, rhs)?,
+            loc: self.loc,
+        }))
+    }
+
+    fn is_integer(&self) -> bool {
+        matches!(self.val, Concrete::Uint(..) | Concrete::Int(..))
+    }
+}
+
 pub trait RangeAdd<T, Rhs = Self> {
     /// Perform addition between two range elements
     fn range_add(&self, other: &Rhs) -> Option<Elem<T>>;
@@ -14,54 +198,20 @@ pub trait RangeAdd<T, Rhs = Self> {
 
 impl RangeAdd<Concrete> for RangeConcrete<Concrete> {
     fn range_add(&self, other: &Self) -> Option<Elem<Concrete>> {
+        if self.is_integer() && other.is_integer() {
+            return self.wide_op(other, |lhs, rhs| lhs.add(rhs).into_concrete(&self.val));
+        }
         match (self.val.into_u256(), other.val.into_u256()) {
             (Some(lhs_val), Some(rhs_val)) => {
                 let max = Concrete::max(&self.val).unwrap().into_u256().unwrap();
                 Some(Elem::Concrete(RangeConcrete {
                     val: self
                         .val
-                        .u256_as_original(lhs_val.saturating_add(rhs_val).min(max)),
+                        .u256_as_original(lhs_val.saturating_add(rhs_val).min(max))?,
                     loc: self.loc,
                 }))
             }
-            _ => {
-                match (&self.val, &other.val) {
-                    (Concrete::Uint(lhs_size, val), Concrete::Int(_, neg_v))
-                    | (Concrete::Int(lhs_size, neg_v), Concrete::Uint(_, val)) => {
-                        // neg_v guaranteed to be negative here
-                        if neg_v.into_raw() > *val {
-                            Some(Elem::Concrete(RangeConcrete {
-                                val: Concrete::Int(
-                                    *lhs_size,
-                                    neg_v.saturating_add(I256::from_raw(*val)),
-                                ),
-                                loc: self.loc,
-                            }))
-                        } else {
-                            Some(Elem::Concrete(RangeConcrete {
-                                val: self
-                                    .val
-                                    .u256_as_original(val.saturating_sub(neg_v.into_raw())),
-                                loc: self.loc,
-                            }))
-                        }
-                    }
-                    (Concrete::Int(lhs_size, l), Concrete::Int(_rhs_size, r)) => {
-                        let max = if *lhs_size == 256 {
-                            I256::MAX
-                        } else {
-                            I256::from_raw(U256::from(1u8) << U256::from(*lhs_size - 1))
-                                - I256::from(1)
-                        };
-                        let min = max * I256::from(-1i32);
-                        Some(Elem::Concrete(RangeConcrete {
-                            val: Concrete::Int(*lhs_size, l.saturating_add(*r).max(min)),
-                            loc: self.loc,
-                        }))
-                    }
-                    _ => None,
-                }
-            }
+            _ => None,
         }
     }
 }
@@ -86,70 +236,15 @@ pub trait RangeSub<T, Rhs = Self> {
 
 impl RangeSub<Concrete> for RangeConcrete<Concrete> {
     fn range_sub(&self, other: &Self) -> Option<Elem<Concrete>> {
+        if self.is_integer() && other.is_integer() {
+            return self.wide_op(other, |lhs, rhs| lhs.sub(rhs).into_concrete(&self.val));
+        }
         match (self.val.into_u256(), other.val.into_u256()) {
-            (Some(lhs_val), Some(rhs_val)) => {
-                if lhs_val > rhs_val {
-                    let val = lhs_val.saturating_sub(rhs_val);
-                    Some(Elem::Concrete(RangeConcrete {
-                        val: self.val.u256_as_original(val),
-                        loc: self.loc,
-                    }))
-                } else {
-                    match self.val {
-                        Concrete::Int(size, val) => Some(Elem::Concrete(RangeConcrete {
-                            val: Concrete::Int(size, val.saturating_sub(I256::from_raw(rhs_val))),
-                            loc: self.loc,
-                        })),
-                        _ => {
-                            // TODO: this should cause a revert
-                            let val = lhs_val.saturating_sub(rhs_val);
-                            Some(Elem::Concrete(RangeConcrete {
-                                val: self.val.u256_as_original(val),
-                                loc: self.loc,
-                            }))
-                        }
-                    }
-                }
-            }
-            _ => match (&self.val, &other.val) {
-                (Concrete::Uint(lhs_size, val), Concrete::Int(_, neg_v)) => {
-                    let max = if *lhs_size == 256 {
-                        U256::MAX
-                    } else {
-                        U256::from(2).pow(U256::from(*lhs_size)) - 1
-                    };
-                    Some(Elem::Concrete(RangeConcrete {
-                        val: self
-                            .val
-                            .u256_as_original(val.saturating_add(neg_v.into_raw()).min(max)),
-                        loc: self.loc,
-                    }))
-                }
-                (Concrete::Int(lhs_size, neg_v), Concrete::Uint(_, val)) => {
-                    let max = if *lhs_size == 256 {
-                        I256::MAX
-                    } else {
-                        I256::from_raw(U256::from(1u8) << U256::from(*lhs_size - 1)) - I256::from(1)
-                    };
-
-                    let min = max * I256::from(-1i32);
-
-                    Some(Elem::Concrete(RangeConcrete {
-                        val: Concrete::Int(
-                            *lhs_size,
-                            neg_v.saturating_sub(I256::from_raw(*val).max(min)),
-                        ),
-                        loc: self.loc,
-                    }))
-                }
-                (Concrete::Int(lhs_size, l), Concrete::Int(_rhs_size, r)) => {
-                    Some(Elem::Concrete(RangeConcrete {
-                        val: Concrete::Int(*lhs_size, l.saturating_sub(*r)),
-                        loc: self.loc,
-                    }))
-                }
-                _ => None,
-            },
+            (Some(lhs_val), Some(rhs_val)) => Some(Elem::Concrete(RangeConcrete {
+                val: self.val.u256_as_original(lhs_val.saturating_sub(rhs_val))?,
+                loc: self.loc,
+            })),
+            _ => None,
         }
     }
 }
@@ -171,6 +266,12 @@ pub trait RangeMul<T, Rhs = Self> {
 
 impl RangeMul<Concrete> for RangeConcrete<Concrete> {
     fn range_mul(&self, other: &Self) -> Option<Elem<Concrete>> {
+        if self.is_integer() && other.is_integer() {
+            return self.wide_op(other, |lhs, rhs| {
+                // the product of two 256 bit integers always fits, so this always succeeds
+                lhs.mul(rhs)?.into_concrete(&self.val)
+            });
+        }
         match (self.val.into_u256(), other.val.into_u256()) {
             (Some(lhs_val), Some(rhs_val)) => {
                 let max = Concrete::max(&self.val).unwrap();
@@ -178,40 +279,11 @@ impl RangeMul<Concrete> for RangeConcrete<Concrete> {
                     .saturating_mul(rhs_val)
                     .min(max.into_u256().unwrap());
                 Some(Elem::Concrete(RangeConcrete {
-                    val: self.val.u256_as_original(res),
+                    val: self.val.u256_as_original(res)?,
                     loc: self.loc,
                 }))
             }
-            _ => match (&self.val, &other.val) {
-                (Concrete::Uint(lhs_size, val), Concrete::Int(_, neg_v))
-                | (Concrete::Int(lhs_size, neg_v), Concrete::Uint(_, val)) => {
-                    let max = if *lhs_size == 256 {
-                        I256::MAX
-                    } else {
-                        I256::from_raw(U256::from(1u8) << U256::from(*lhs_size - 1)) - I256::from(1)
-                    };
-                    let min = max * I256::from(-1i32);
-                    Some(Elem::Concrete(RangeConcrete {
-                        val: Concrete::Int(
-                            *lhs_size,
-                            neg_v.saturating_mul(I256::from_raw(*val)).max(min),
-                        ),
-                        loc: self.loc,
-                    }))
-                }
-                (Concrete::Int(lhs_size, l), Concrete::Int(_rhs_size, r)) => {
-                    let max = if *lhs_size == 256 {
-                        I256::MAX
-                    } else {
-                        I256::from_raw(U256::from(1u8) << U256::from(*lhs_size - 1)) - I256::from(1)
-                    };
-                    Some(Elem::Concrete(RangeConcrete {
-                        val: Concrete::Int(*lhs_size, l.saturating_mul(*r).min(max)),
-                        loc: self.loc,
-                    }))
-                }
-                _ => None,
-            },
+            _ => None,
         }
     }
 }
@@ -241,12 +313,14 @@ impl RangeExp<Concrete> for RangeConcrete<Concrete> {
                 let max = Concrete::max(&self.val).unwrap();
                 if let Some(num) = lhs_val.checked_pow(rhs_val) {
                     Some(Elem::Concrete(RangeConcrete {
-                        val: self.val.u256_as_original(num.min(max.into_u256().unwrap())),
+                        val: self
+                            .val
+                            .u256_as_original(num.min(max.into_u256().unwrap()))?,
                         loc: self.loc,
                     }))
                 } else {
                     Some(Elem::Concrete(RangeConcrete {
-                        val: self.val.u256_as_original(max.into_u256().unwrap()),
+                        val: self.val.u256_as_original(max.into_u256().unwrap())?,
                         loc: self.loc,
                     }))
                 }
@@ -322,53 +396,24 @@ pub trait RangeDiv<T, Rhs = Self> {
 
 impl RangeDiv<Concrete> for RangeConcrete<Concrete> {
     fn range_div(&self, other: &Self) -> Option<Elem<Concrete>> {
+        if self.is_integer() && other.is_integer() {
+            // dividing by zero reverts, as does `type(intN).min / -1`
+            return self.wide_op(other, |lhs, rhs| {
+                lhs.div(rhs)?.into_concrete_checked(&self.val)
+            });
+        }
         match (self.val.into_u256(), other.val.into_u256()) {
             (Some(lhs_val), Some(rhs_val)) => {
                 if rhs_val == 0.into() {
                     None
                 } else {
                     Some(Elem::Concrete(RangeConcrete {
-                        val: self.val.u256_as_original(lhs_val / rhs_val),
+                        val: self.val.u256_as_original(lhs_val / rhs_val)?,
                         loc: self.loc,
                     }))
                 }
             }
-            _ => match (&self.val, &other.val) {
-                (Concrete::Uint(lhs_size, val), Concrete::Int(_, neg_v)) => {
-                    if neg_v == &I256::from(0) {
-                        None
-                    } else {
-                        Some(Elem::Concrete(RangeConcrete {
-                            val: Concrete::Int(
-                                *lhs_size,
-                                I256::from_raw(val / neg_v.into_raw()) * I256::from(-1i32),
-                            ),
-                            loc: self.loc,
-                        }))
-                    }
-                }
-                (Concrete::Int(lhs_size, neg_v), Concrete::Uint(_, val)) => {
-                    if val == &U256::from(0) {
-                        None
-                    } else {
-                        Some(Elem::Concrete(RangeConcrete {
-                            val: Concrete::Int(*lhs_size, *neg_v / I256::from_raw(*val)),
-                            loc: self.loc,
-                        }))
-                    }
-                }
-                (Concrete::Int(lhs_size, l), Concrete::Int(_rhs_size, r)) => {
-                    if r == &I256::from(0) {
-                        None
-                    } else {
-                        Some(Elem::Concrete(RangeConcrete {
-                            val: Concrete::Int(*lhs_size, *l / *r),
-                            loc: self.loc,
-                        }))
-                    }
-                }
-                _ => None,
-            },
+            _ => None,
         }
     }
 }
@@ -389,32 +434,18 @@ pub trait RangeMod<T, Rhs = Self> {
 
 impl RangeMod<Concrete> for RangeConcrete<Concrete> {
     fn range_mod(&self, other: &Self) -> Option<Elem<Concrete>> {
+        if self.is_integer() && other.is_integer() {
+            // the result takes the sign of the dividend, and `type(intN).min % -1` is zero
+            return self.wide_op(other, |lhs, rhs| lhs.rem(rhs)?.into_concrete(&self.val));
+        }
         match (self.val.into_u256(), other.val.into_u256()) {
-            (Some(lhs_val), Some(rhs_val)) => Some(Elem::Concrete(RangeConcrete {
-                val: self.val.u256_as_original(lhs_val % rhs_val),
-                loc: self.loc,
-            })),
-            _ => match (&self.val, &other.val) {
-                (Concrete::Uint(lhs_size, val), Concrete::Int(_, neg_v)) => {
-                    Some(Elem::Concrete(RangeConcrete {
-                        val: Concrete::Int(*lhs_size, I256::from_raw(*val) % *neg_v),
-                        loc: self.loc,
-                    }))
-                }
-                (Concrete::Int(lhs_size, neg_v), Concrete::Uint(_, val)) => {
-                    Some(Elem::Concrete(RangeConcrete {
-                        val: Concrete::Int(*lhs_size, *neg_v % I256::from_raw(*val)),
-                        loc: self.loc,
-                    }))
-                }
-                (Concrete::Int(lhs_size, l), Concrete::Int(_rhs_size, r)) => {
-                    Some(Elem::Concrete(RangeConcrete {
-                        val: Concrete::Int(*lhs_size, *l % *r),
-                        loc: self.loc,
-                    }))
-                }
-                _ => None,
-            },
+            (Some(lhs_val), Some(rhs_val)) if !rhs_val.is_zero() => {
+                Some(Elem::Concrete(RangeConcrete {
+                    val: self.val.u256_as_original(lhs_val % rhs_val)?,
+                    loc: self.loc,
+                }))
+            }
+            _ => None,
         }
     }
 }
@@ -437,7 +468,7 @@ impl RangeMin<Concrete> for RangeConcrete<Concrete> {
     fn range_min(&self, other: &Self) -> Option<Elem<Concrete>> {
         match (self.val.into_u256(), other.val.into_u256()) {
             (Some(lhs_val), Some(rhs_val)) => Some(Elem::Concrete(RangeConcrete {
-                val: self.val.u256_as_original(lhs_val.min(rhs_val)),
+                val: self.val.u256_as_original(lhs_val.min(rhs_val))?,
                 loc: self.loc,
             })),
             _ => match (&self.val, &other.val) {
@@ -483,7 +514,7 @@ impl RangeMax<Concrete> for RangeConcrete<Concrete> {
     fn range_max(&self, other: &Self) -> Option<Elem<Concrete>> {
         match (self.val.into_u256(), other.val.into_u256()) {
             (Some(lhs_val), Some(rhs_val)) => Some(Elem::Concrete(RangeConcrete {
-                val: self.val.u256_as_original(lhs_val.max(rhs_val)),
+                val: self.val.u256_as_original(lhs_val.max(rhs_val))?,
                 loc: self.loc,
             })),
             _ => match (&self.val, &other.val) {
@@ -758,129 +789,58 @@ pub trait RangeShift<T, Rhs = Self> {
 
 impl RangeShift<Concrete> for RangeConcrete<Concrete> {
     fn range_shl(&self, other: &Self) -> Option<Elem<Concrete>> {
+        if self.is_integer() && other.is_integer() {
+            // shift amounts are unsigned
+            let shift = other.val.into_u256()?;
+            if shift >= U256::from(self.val.int_size()?) {
+                // every bit is shifted out
+                return Some(Elem::Concrete(RangeConcrete {
+                    val: self.val.u256_as_original(U256::zero())?,
+                    loc: self.loc,
+                }));
+            }
+            return self.wide_op(other, |lhs, _| lhs.shl(shift).into_concrete(&self.val));
+        }
         match (self.val.into_u256(), other.val.into_u256()) {
             (Some(lhs_val), Some(rhs_val)) => {
                 let max = Concrete::max(&self.val).unwrap().into_u256().unwrap();
                 if rhs_val > lhs_val.leading_zeros().into() {
                     Some(Elem::Concrete(RangeConcrete {
-                        val: max.into(),
+                        val: self.val.u256_as_original(max)?,
                         loc: self.loc,
                     }))
                 } else {
                     Some(Elem::Concrete(RangeConcrete {
-                        val: self.val.u256_as_original((lhs_val << rhs_val).min(max)),
+                        val: self.val.u256_as_original((lhs_val << rhs_val).min(max))?,
                         loc: self.loc,
                     }))
                 }
             }
-            _ => match (&self.val, &other.val) {
-                (Concrete::Int(lhs_size, neg_v), Concrete::Uint(_, val)) => {
-                    if val == &U256::zero() {
-                        return Some(Elem::Concrete(self.clone()));
-                    }
-
-                    let max = if *lhs_size == 256 {
-                        I256::MAX
-                    } else {
-                        I256::from_raw(U256::from(1u8) << U256::from(*lhs_size - 1)) - I256::from(1)
-                    };
-
-                    let min = max * I256::from(-1i32);
-                    let (abs, is_min) = neg_v.overflowing_abs();
-                    if is_min {
-                        if val > &U256::zero() {
-                            Some(Elem::from(self.clone()))
-                        } else {
-                            Some(Elem::Concrete(RangeConcrete {
-                                val: Concrete::Int(*lhs_size, I256::zero()),
-                                loc: self.loc,
-                            }))
-                        }
-                    } else if val > &U256::from(abs.leading_zeros()) {
-                        Some(Elem::Concrete(RangeConcrete {
-                            val: Concrete::Int(*lhs_size, I256::zero()),
-                            loc: self.loc,
-                        }))
-                    } else {
-                        let raw = I256::from_raw(abs.into_raw() << val);
-                        let as_int = if raw == I256::MIN {
-                            raw
-                        } else {
-                            I256::from(-1i32) * raw
-                        };
-                        Some(Elem::Concrete(RangeConcrete {
-                            val: Concrete::Int(*lhs_size, as_int.max(min)),
-                            loc: self.loc,
-                        }))
-                    }
-                }
-                _ => None,
-            },
+            _ => None,
         }
     }
 
     fn range_shr(&self, other: &Self) -> Option<Elem<Concrete>> {
+        if self.is_integer() && other.is_integer() {
+            // signed values shift arithmetically, rounding towards negative infinity
+            let shift = other.val.into_u256()?;
+            return self.wide_op(other, |lhs, _| lhs.shr(shift).into_concrete(&self.val));
+        }
         match (self.val.into_u256(), other.val.into_u256()) {
             (Some(lhs_val), Some(rhs_val)) => {
-                if rhs_val == U256::zero() {
-                    Some(Elem::Concrete(self.clone()))
-                } else if rhs_val > U256::from(256) {
+                if rhs_val >= U256::from(256) {
                     Some(Elem::Concrete(RangeConcrete {
-                        val: self.val.u256_as_original(U256::zero()),
+                        val: self.val.u256_as_original(U256::zero())?,
                         loc: self.loc,
                     }))
                 } else {
                     Some(Elem::Concrete(RangeConcrete {
-                        val: self.val.u256_as_original(lhs_val >> rhs_val),
+                        val: self.val.u256_as_original(lhs_val >> rhs_val)?,
                         loc: self.loc,
                     }))
                 }
             }
-            _ => match (&self.val, &other.val) {
-                (Concrete::Int(lhs_size, neg_v), Concrete::Uint(_, val)) => {
-                    if val == &U256::zero() {
-                        Some(Elem::Concrete(self.clone()))
-                    } else if val > &U256::from(*lhs_size) {
-                        Some(Elem::Concrete(RangeConcrete {
-                            val: Concrete::Int(*lhs_size, I256::from(-1i32)),
-                            loc: self.loc,
-                        }))
-                    } else {
-                        let max = if *lhs_size == 256 {
-                            I256::MAX
-                        } else {
-                            I256::from_raw(U256::from(1u8) << U256::from(*lhs_size - 1))
-                                - I256::from(1)
-                        };
-                        let min = max * I256::from(-1i32);
-
-                        let (abs, is_min) = neg_v.overflowing_abs();
-                        let bits = if is_min {
-                            255
-                        } else {
-                            255 - abs.leading_zeros()
-                        };
-
-                        if val >= &U256::from(bits) {
-                            Some(Elem::Concrete(RangeConcrete {
-                                val: Concrete::Int(*lhs_size, I256::from(-1i32)),
-                                loc: self.loc,
-                            }))
-                        } else {
-                            let shr_val = abs.into_raw() >> val;
-                            let as_int = I256::from_raw(shr_val);
-                            Some(Elem::Concrete(RangeConcrete {
-                                val: Concrete::Int(
-                                    *lhs_size,
-                                    (I256::from(-1i32) * as_int).max(min),
-                                ),
-                                loc: self.loc,
-                            }))
-                        }
-                    }
-                }
-                _ => None,
-            },
+            _ => None,
         }
     }
 }
@@ -1040,7 +1000,7 @@ impl RangeCast<Concrete, Box<RangeDyn<Concrete>>> for RangeConcrete<Concrete> {
                     loc: other.loc,
                 })))
             }
-            e => panic!("here00: {e:?}"),
+            _ => None,
         }
     }
 }
@@ -1125,7 +1085,7 @@ impl RangeCast<Concrete, RangeDyn<Concrete>> for RangeDyn<Concrete> {
             (Some((_, l @ Elem::Dynamic(_))), None) => Some(l.clone()),
             (None, Some((_, r @ Elem::Dynamic(_)))) => Some(r.clone()),
             (None, None) => Some(Elem::ConcreteDyn(Box::new(self.clone()))),
-            e => panic!("here0: {e:?}"),
+            _ => None,
         }
     }
 }
@@ -1232,3 +1192,161 @@ impl RangeBitwise<Concrete> for Elem<Concrete> {
         }
     }
 }
+
+pub trait RangeLift<Rhs = Self> {
+    /// Perform an operation interval-wise between two interval sets. Each pair of intervals maps
+    /// to the hull of the operation applied to their endpoints, so only operations that are
+    /// monotonic in each argument over an interval are supported
+    fn range_lift(&self, op: RangeOp, other: &Rhs) -> Option<IntervalSet>;
+    /// Cast each interval to the type bounded by `ty_min` and `ty_max`. Only casts that keep
+    /// every value are supported, as a truncating one could wrap an interval around
+    fn range_lift_cast(
+        &self,
+        ty_min: &Elem<Concrete>,
+        ty_max: &Elem<Concrete>,
+    ) -> Option<IntervalSet>;
+    /// Whether a comparison holds between every pair of intervals, if it is known to, or
+    /// is known not to for every pair
+    fn range_lift_cmp(&self, op: RangeOp, other: &Rhs) -> Option<bool>;
+}
+
+impl RangeLift for IntervalSet {
+    fn range_lift(&self, op: RangeOp, other: &Self) -> Option<IntervalSet> {
+        let apply = |a: &Elem<Concrete>, b: &Elem<Concrete>| match op {
+            RangeOp::Add => a.range_add(b),
+            RangeOp::Sub => a.range_sub(b),
+            RangeOp::Mul => a.range_mul(b),
+            RangeOp::Div => a.range_div(b),
+            RangeOp::Min => a.range_min(b),
+            RangeOp::Max => a.range_max(b),
+            _ => None,
+        };
+
+        let mut intervals = vec![];
+        for (a_min, a_max) in self.intervals() {
+            for (b_min, b_max) in other.intervals() {
+                if matches!(op, RangeOp::Div) {
+                    // a zero of the divisor's own type, so signed divisors compare against an int
+                    let zero =
+                        Elem::from(b_min.maybe_concrete()?.val.u256_as_original(U256::zero())?);
+                    if IntervalSet::new(b_min.clone(), b_max.clone()).contains(&zero) {
+                        return None;
+                    }
+                }
+                let corners = vec![
+                    apply(a_min, b_min)?,
+                    apply(a_min, b_max)?,
+                    apply(a_max, b_min)?,
+                    apply(a_max, b_max)?,
+                ];
+                let mut min = corners[0].clone();
+                let mut max = corners[0].clone();
+                for corner in corners.into_iter().skip(1) {
+                    match corner.range_ord(&min) {
+                        Some(std::cmp::Ordering::Less) => min = corner.clone(),
+                        None => return None,
+                        _ => {}
+                    }
+                    match corner.range_ord(&max) {
+                        Some(std::cmp::Ordering::Greater) => max = corner,
+                        None => return None,
+                        _ => {}
+                    }
+                }
+                intervals.push((min, max));
+            }
+        }
+        Some(IntervalSet::from_intervals(intervals))
+    }
+
+    fn range_lift_cast(
+        &self,
+        ty_min: &Elem<Concrete>,
+        ty_max: &Elem<Concrete>,
+    ) -> Option<IntervalSet> {
+        let intervals = self
+            .intervals()
+            .iter()
+            .map(|(min, max)| {
+                let fits = matches!(
+                    min.range_ord(ty_min),
+                    Some(std::cmp::Ordering::Greater | std::cmp::Ordering::Equal)
+                ) && matches!(
+                    max.range_ord(ty_max),
+                    Some(std::cmp::Ordering::Less | std::cmp::Ordering::Equal)
+                );
+                let cast = |elem: &Elem<Concrete>| {
+                    let ty = ty_min.maybe_concrete()?.val;
+                    Some(Elem::from(elem.maybe_concrete()?.val.cast_from(&ty)?))
+                };
+                if fits {
+                    Some((cast(min)?, cast(max)?))
+                } else {
+                    None
+                }
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(IntervalSet::from_intervals(intervals))
+    }
+
+    fn range_lift_cmp(&self, op: RangeOp, other: &Self) -> Option<bool> {
+        use std::cmp::Ordering::*;
+        let decide = |(a_min, a_max): &(Elem<Concrete>, Elem<Concrete>),
+                      (b_min, b_max): &(Elem<Concrete>, Elem<Concrete>)| {
+            let below = a_max.range_ord(b_min)?;
+            let above = a_min.range_ord(b_max)?;
+            let single = matches!(a_min.range_ord(a_max), Some(Equal))
+                && matches!(b_min.range_ord(b_max), Some(Equal));
+            let eq = if single && below == Equal {
+                Some(true)
+            } else if below == Less || above == Greater {
+                Some(false)
+            } else {
+                None
+            };
+            match op {
+                RangeOp::Lt if below == Less => Some(true),
+                RangeOp::Lt if above != Less => Some(false),
+                RangeOp::Lte if below != Greater => Some(true),
+                RangeOp::Lte if above == Greater => Some(false),
+                RangeOp::Gt if above == Greater => Some(true),
+                RangeOp::Gt if below != Greater => Some(false),
+                RangeOp::Gte if above != Less => Some(true),
+                RangeOp::Gte if below == Less => Some(false),
+                RangeOp::Eq => eq,
+                RangeOp::Neq => eq.map(|eq| !eq),
+                _ => None,
+            }
+        };
+        let mut known = None;
+        for a in self.intervals() {
+            for b in other.intervals() {
+                let curr = decide(a, b)?;
+                if known.is_some_and(|known| known != curr) {
+                    return None;
+                }
+                known = Some(curr);
+            }
+        }
+        known
+    }
+}
+
+pub trait RangeCongruence<Rhs = Self> {
+    /// Compute the congruence the result of an operation on a `uint{size}` satisfies
+    fn range_congruence(&self, op: RangeOp, other: &Rhs, size: u16) -> Option<Congruence>;
+}
+
+impl RangeCongruence for Congruence {
+    fn range_congruence(&self, op: RangeOp, other: &Self, size: u16) -> Option<Congruence> {
+        match op {
+            RangeOp::Add => self.add(other),
+            RangeOp::Sub => self.sub(other),
+            RangeOp::Mul => self.mul(other),
+            RangeOp::Div => self.div(other),
+            RangeOp::Mod => self.rem(other),
+            RangeOp::Shl => self.shl(other, size),
+            _ => None,
+        }
+    }
+}
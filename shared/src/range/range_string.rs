@@ -3,6 +3,7 @@ use crate::range::elem::RangeElem;
 use crate::range::elem::RangeOp;
 use crate::range::elem_ty::Dynamic;
 use crate::range::elem_ty::RangeExpr;
+use crate::range::interval_set::IntervalSet;
 use crate::range::Elem;
 use crate::range::RangeDyn;
 use crate::Concrete;
@@ -255,3 +256,32 @@ impl ToRangeString for RangeExpr<Concrete> {
         }
     }
 }
+
+impl ToRangeString for IntervalSet {
+    fn def_string(&self, analyzer: &impl GraphLike) -> RangeElemString {
+        self.to_range_string(false, analyzer)
+    }
+
+    fn to_range_string(&self, _maximize: bool, analyzer: &impl GraphLike) -> RangeElemString {
+        let loc = self
+            .intervals()
+            .first()
+            .and_then(|(min, _)| min.maybe_concrete())
+            .map(|c| c.loc)
+            .unwrap_or(Loc::Implicit);
+        RangeElemString::new(
+            self.intervals()
+                .iter()
+                .map(|(min, max)| {
+                    format!(
+                        "[{}, {}]",
+                        min.to_range_string(false, analyzer).s,
+                        max.to_range_string(true, analyzer).s
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" ∪ "),
+            loc,
+        )
+    }
+}
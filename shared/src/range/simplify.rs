@@ -0,0 +1,138 @@
+use crate::range::elem::{RangeElem, RangeOp};
+use crate::range::elem_ty::{Elem, ExecOp, RangeConcrete, RangeExpr};
+use crate::range::range_ops::*;
+use crate::Concrete;
+use crate::GraphLike;
+
+use ethers_core::types::{I256, U256};
+use std::cmp::Ordering;
+
+/// Rewrites an expression whose operands are already simplified into a simpler equivalent one,
+/// leaving symbolic values in place. Constants are folded, identity and annihilator laws applied,
+/// and constants of chained additions and multiplications gathered into one, so that `(x + 2) + 3`
+/// reads `x + 5` and `((x + 1) - 1) * 1` reads `x`
+pub fn simplify_expr(
+    expr: RangeExpr<Concrete>,
+    maximize: bool,
+    analyzer: &impl GraphLike,
+) -> Elem<Concrete> {
+    // a cast only takes the type of its rhs, so a constant can be cast whatever the rhs is
+    if is_concrete(&expr.lhs) && (is_concrete(&expr.rhs) || expr.op == RangeOp::Cast) {
+        let folded = expr.exec_op(maximize, analyzer);
+        if let Elem::Concrete(_) = folded {
+            return folded;
+        }
+    }
+    let RangeExpr { lhs, op, rhs } = expr;
+
+    // keep constants on the right so the rules below only look one way
+    let (lhs, rhs) = if op.commutative() && is_concrete(&lhs) && !is_concrete(&rhs) {
+        (*rhs, *lhs)
+    } else {
+        (*lhs, *rhs)
+    };
+
+    if let Some(simplified) = identity(&lhs, op, &rhs) {
+        return simplified;
+    }
+    if let Some(simplified) = reassociate(&lhs, op, &rhs) {
+        return simplify_expr(simplified, maximize, analyzer);
+    }
+    Elem::Expr(RangeExpr::new(lhs, op, rhs))
+}
+
+fn is_concrete(elem: &Elem<Concrete>) -> bool {
+    matches!(elem, Elem::Concrete(_))
+}
+
+/// Whether the element is the integer `val`
+fn is_int(elem: &Elem<Concrete>, val: u64) -> bool {
+    match elem {
+        Elem::Concrete(RangeConcrete {
+            val: Concrete::Uint(_, v),
+            ..
+        }) => *v == U256::from(val),
+        Elem::Concrete(RangeConcrete {
+            val: Concrete::Int(_, v),
+            ..
+        }) => *v == I256::from(val),
+        _ => false,
+    }
+}
+
+/// The integer `val` in the type of a concrete element
+fn int_like(elem: &Elem<Concrete>, val: u64) -> Option<Elem<Concrete>> {
+    let c = elem.maybe_concrete()?;
+    match c.val {
+        Concrete::Uint(..) | Concrete::Int(..) => Some(Elem::Concrete(RangeConcrete {
+            val: c.val.u256_as_original(U256::from(val))?,
+            loc: c.loc,
+        })),
+        _ => None,
+    }
+}
+
+/// Identity and annihilator laws, with any constant on the right
+fn identity(lhs: &Elem<Concrete>, op: RangeOp, rhs: &Elem<Concrete>) -> Option<Elem<Concrete>> {
+    match op {
+        RangeOp::Add | RangeOp::Sub | RangeOp::BitOr | RangeOp::BitXor if is_int(rhs, 0) => {
+            Some(lhs.clone())
+        }
+        RangeOp::Shl | RangeOp::Shr if is_int(rhs, 0) || is_int(lhs, 0) => Some(lhs.clone()),
+        RangeOp::Mul | RangeOp::Div | RangeOp::Exp if is_int(rhs, 1) => Some(lhs.clone()),
+        RangeOp::Mul | RangeOp::BitAnd if is_int(rhs, 0) => Some(rhs.clone()),
+        RangeOp::Div if is_int(lhs, 0) => Some(lhs.clone()),
+        RangeOp::Mod if is_int(rhs, 1) => int_like(rhs, 0),
+        RangeOp::Exp if is_int(rhs, 0) => int_like(rhs, 1),
+        RangeOp::Min | RangeOp::Max if lhs == rhs => Some(lhs.clone()),
+        _ => None,
+    }
+}
+
+/// Applies an operation to two constants, only if it neither overflows nor truncates
+fn fold_exact(lhs: &Elem<Concrete>, op: RangeOp, rhs: &Elem<Concrete>) -> Option<Elem<Concrete>> {
+    let res = match op {
+        RangeOp::Add => lhs.range_add(rhs)?,
+        RangeOp::Sub => lhs.range_sub(rhs)?,
+        RangeOp::Mul => lhs.range_mul(rhs)?,
+        _ => return None,
+    };
+    let undone = match op {
+        RangeOp::Add => res.range_sub(rhs)?,
+        RangeOp::Sub => res.range_add(rhs)?,
+        _ if is_int(rhs, 0) => return Some(res),
+        _ => res.range_div(rhs)?,
+    };
+    undone.range_eq(lhs).then_some(res)
+}
+
+/// Gathers the constants of `(x op c1) op c2` into one, e.g. `(x + c1) - c2` into `x + (c1 - c2)`
+fn reassociate(
+    lhs: &Elem<Concrete>,
+    op: RangeOp,
+    rhs: &Elem<Concrete>,
+) -> Option<RangeExpr<Concrete>> {
+    let inner = match lhs {
+        Elem::Expr(inner) if is_concrete(&inner.rhs) && is_concrete(rhs) => inner,
+        _ => return None,
+    };
+    let (x, inner_op, c1, c2) = (&*inner.lhs, inner.op, &*inner.rhs, rhs);
+    let (op, c) = match (inner_op, op) {
+        (RangeOp::Add, RangeOp::Add) => (RangeOp::Add, fold_exact(c1, RangeOp::Add, c2)?),
+        (RangeOp::Sub, RangeOp::Sub) => (RangeOp::Sub, fold_exact(c1, RangeOp::Add, c2)?),
+        (RangeOp::Mul, RangeOp::Mul) => (RangeOp::Mul, fold_exact(c1, RangeOp::Mul, c2)?),
+        // `(x + c1) - c2` and `(x - c2) + c1` both add `c1 - c2`
+        (RangeOp::Add, RangeOp::Sub) => offset(c1, c2)?,
+        (RangeOp::Sub, RangeOp::Add) => offset(c2, c1)?,
+        _ => return None,
+    };
+    Some(RangeExpr::new(x.clone(), op, c))
+}
+
+/// Adding `add` and subtracting `sub` as a single operation, without going negative
+fn offset(add: &Elem<Concrete>, sub: &Elem<Concrete>) -> Option<(RangeOp, Elem<Concrete>)> {
+    match add.range_ord(sub)? {
+        Ordering::Less => Some((RangeOp::Sub, fold_exact(sub, RangeOp::Sub, add)?)),
+        _ => Some((RangeOp::Add, fold_exact(add, RangeOp::Sub, sub)?)),
+    }
+}
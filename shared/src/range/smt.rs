@@ -0,0 +1,648 @@
+use crate::analyzer::AnalyzerLike;
+use crate::context::ContextNode;
+use crate::context::ContextVarNode;
+use crate::nodes::VarType;
+use crate::range::elem::RangeElem;
+use crate::range::elem::RangeOp;
+use crate::range::elem_ty::Dynamic;
+use crate::range::elem_ty::RangeExpr;
+use crate::range::Elem;
+use crate::range::Range;
+use crate::Builtin;
+use crate::Concrete;
+use crate::GraphLike;
+use ethers_core::types::U256;
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::fmt;
+
+use solang_parser::pt::Loc;
+
+/// The solver command used unless another is set in the
+/// [`AnalysisSettings`](crate::analyzer::AnalysisSettings)
+pub const DEFAULT_SOLVER: &str = "z3 -in -smt2 -T:5";
+
+/// The SMT-LIB2 sort of a term in the `QF_BV` logic
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum SmtSort {
+    Bool,
+    /// A bitvector of a given width, and whether it is interpreted as two's complement
+    BitVec(u16, bool),
+    /// A `bytesN`, as a bitvector of `8 * N` bits with the first byte in the high bits
+    Bytes(u8),
+}
+
+impl SmtSort {
+    /// The sort for a [`Builtin`], if it can be represented as a fixed width bitvector
+    pub fn from_builtin(builtin: &Builtin) -> Option<Self> {
+        match builtin {
+            Builtin::Bool => Some(SmtSort::Bool),
+            Builtin::Uint(size) => Some(SmtSort::BitVec(*size, false)),
+            Builtin::Int(size) => Some(SmtSort::BitVec(*size, true)),
+            Builtin::Bytes(size) => Some(SmtSort::Bytes(*size)),
+            Builtin::Address | Builtin::AddressPayable | Builtin::Payable => {
+                Some(SmtSort::BitVec(160, false))
+            }
+            _ => None,
+        }
+    }
+
+    /// The sort of a variable, if it can be represented as a fixed width bitvector
+    pub fn from_var(cvar: ContextVarNode, analyzer: &impl GraphLike) -> Option<Self> {
+        match cvar.ty(analyzer) {
+            VarType::BuiltIn(bn, _) => Self::from_builtin(bn.underlying(analyzer)),
+            VarType::Concrete(c) => match c.underlying(analyzer) {
+                Concrete::Array(_) | Concrete::DynBytes(_) | Concrete::String(_) => None,
+                c => Self::from_builtin(&c.as_builtin()),
+            },
+            _ => None,
+        }
+    }
+
+    pub fn is_signed(&self) -> bool {
+        matches!(self, SmtSort::BitVec(_, true))
+    }
+
+    /// The number of bits in the sort, with booleans taking one
+    pub fn width(&self) -> u16 {
+        match self {
+            SmtSort::Bool => 1,
+            SmtSort::BitVec(width, _) => *width,
+            SmtSort::Bytes(size) => *size as u16 * 8,
+        }
+    }
+}
+
+impl fmt::Display for SmtSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SmtSort::Bool => write!(f, "Bool"),
+            SmtSort::BitVec(width, _) => write!(f, "(_ BitVec {width})"),
+            SmtSort::Bytes(_) => write!(f, "(_ BitVec {})", self.width()),
+        }
+    }
+}
+
+/// The SMT-LIB2 symbol for a variable. Each version of a variable gets its own symbol
+pub fn smt_symbol(cvar: ContextVarNode, analyzer: &impl GraphLike) -> String {
+    let name = cvar.display_name(analyzer).replace(['|', '\\'], "_");
+    format!("|{name}@{}|", cvar.0)
+}
+
+/// Converts a term of one sort into another, extending or truncating bitvectors as needed.
+/// Integers keep their low bits and `bytesN` their high bits, as solidity conversions do
+fn coerce(term: String, from: SmtSort, to: SmtSort) -> String {
+    match (from, to) {
+        (SmtSort::Bool, SmtSort::Bool) => term,
+        (SmtSort::Bool, _) => {
+            let width = to.width();
+            format!("(ite {term} (_ bv1 {width}) (_ bv0 {width}))")
+        }
+        (_, SmtSort::Bool) => format!("(distinct {term} (_ bv0 {}))", from.width()),
+        (SmtSort::Bytes(from_size), SmtSort::Bytes(to_size)) => {
+            let (from_width, to_width) = (from.width(), to.width());
+            if from_size == to_size {
+                term
+            } else if from_size < to_size {
+                format!("(concat {term} (_ bv0 {}))", to_width - from_width)
+            } else {
+                format!(
+                    "((_ extract {} {}) {term})",
+                    from_width - 1,
+                    from_width - to_width
+                )
+            }
+        }
+        // between integers and `bytesN` the bits are reinterpreted as an unsigned integer
+        (SmtSort::Bytes(_), SmtSort::BitVec(..)) => {
+            coerce(term, SmtSort::BitVec(from.width(), false), to)
+        }
+        (SmtSort::BitVec(..), SmtSort::Bytes(_)) => {
+            coerce(term, from, SmtSort::BitVec(to.width(), false))
+        }
+        (SmtSort::BitVec(from_width, signed), SmtSort::BitVec(to_width, _)) => {
+            if from_width == to_width {
+                term
+            } else if from_width < to_width {
+                let ext = if signed { "sign_extend" } else { "zero_extend" };
+                format!("((_ {ext} {}) {term})", to_width - from_width)
+            } else {
+                format!("((_ extract {} 0) {term})", to_width - 1)
+            }
+        }
+    }
+}
+
+/// Encoding of range elements as SMT-LIB2 terms
+pub trait ToSmt {
+    /// The sort the element naturally evaluates to
+    fn smt_sort(&self, analyzer: &impl GraphLike) -> Option<SmtSort>;
+    /// Encodes the element as a term of the given sort, if it can be represented
+    fn to_smt(&self, sort: SmtSort, analyzer: &impl GraphLike) -> Option<String>;
+}
+
+impl ToSmt for Concrete {
+    fn smt_sort(&self, _analyzer: &impl GraphLike) -> Option<SmtSort> {
+        match self {
+            Concrete::Uint(..)
+            | Concrete::Int(..)
+            | Concrete::Bytes(..)
+            | Concrete::Address(..)
+            | Concrete::Bool(..) => SmtSort::from_builtin(&self.as_builtin()),
+            _ => None,
+        }
+    }
+
+    fn to_smt(&self, sort: SmtSort, _analyzer: &impl GraphLike) -> Option<String> {
+        let val = match self {
+            Concrete::Bool(b) => {
+                return Some(coerce(b.to_string(), SmtSort::Bool, sort));
+            }
+            // negative values are in 256 bit two's complement, so truncating them to the
+            // width below also sign extends them
+            Concrete::Int(_, val) => val.into_raw(),
+            Concrete::Bytes(size, b) => {
+                // encoded in its own width, so that converting it to a wider `bytesN` keeps
+                // it in the high bits
+                let val = U256::from_big_endian(&b.0[..*size as usize]);
+                let from = SmtSort::Bytes(*size);
+                return Some(coerce(format!("(_ bv{val} {})", from.width()), from, sort));
+            }
+            _ => self.into_u256()?,
+        };
+        match sort {
+            SmtSort::Bool => Some((!val.is_zero()).to_string()),
+            SmtSort::BitVec(..) | SmtSort::Bytes(_) => {
+                let width = sort.width();
+                let masked = if width >= 256 {
+                    val
+                } else {
+                    val & ((U256::from(1) << width) - 1)
+                };
+                Some(format!("(_ bv{masked} {width})"))
+            }
+        }
+    }
+}
+
+impl ToSmt for Elem<Concrete> {
+    fn smt_sort(&self, analyzer: &impl GraphLike) -> Option<SmtSort> {
+        match self {
+            Elem::Concrete(c) => c.val.smt_sort(analyzer),
+            Elem::Dynamic(Dynamic { idx, .. }) => {
+                SmtSort::from_var(ContextVarNode::from(*idx), analyzer)
+            }
+            Elem::Expr(expr) => expr.smt_sort(analyzer),
+            Elem::ConcreteDyn(_) | Elem::Null => None,
+        }
+    }
+
+    fn to_smt(&self, sort: SmtSort, analyzer: &impl GraphLike) -> Option<String> {
+        match self {
+            Elem::Concrete(c) => c.val.to_smt(sort, analyzer),
+            Elem::Dynamic(Dynamic { idx, .. }) => {
+                let cvar = ContextVarNode::from(*idx);
+                let var_sort = SmtSort::from_var(cvar, analyzer)?;
+                Some(coerce(smt_symbol(cvar, analyzer), var_sort, sort))
+            }
+            Elem::Expr(expr) => expr.to_smt(sort, analyzer),
+            Elem::ConcreteDyn(_) | Elem::Null => None,
+        }
+    }
+}
+
+impl ToSmt for RangeExpr<Concrete> {
+    fn smt_sort(&self, analyzer: &impl GraphLike) -> Option<SmtSort> {
+        use RangeOp::*;
+        match self.op {
+            Lt | Lte | Gt | Gte | Eq | Neq | And | Or => Some(SmtSort::Bool),
+            Cast => self.rhs.smt_sort(analyzer),
+            _ => self
+                .lhs
+                .smt_sort(analyzer)
+                .or_else(|| self.rhs.smt_sort(analyzer)),
+        }
+    }
+
+    fn to_smt(&self, sort: SmtSort, analyzer: &impl GraphLike) -> Option<String> {
+        // range arithmetic saturates at the bounds of the type instead of wrapping
+        expr_to_smt(self, sort, true, analyzer)
+    }
+}
+
+/// Encodes a range expression, with `+`, `-`, `*` and `**` either saturating at the bounds
+/// of the type (as range arithmetic does) or wrapping (as the evm does)
+fn expr_to_smt(
+    expr: &RangeExpr<Concrete>,
+    sort: SmtSort,
+    saturating: bool,
+    analyzer: &impl GraphLike,
+) -> Option<String> {
+    use RangeOp::*;
+    let self_sort = expr.smt_sort(analyzer)?;
+    let arith = |op: &str, lhs: String, rhs: String| {
+        if saturating {
+            saturate(op, lhs, rhs, self_sort)
+        } else {
+            format!("({op} {lhs} {rhs})")
+        }
+    };
+    let term = match expr.op {
+        Cast => {
+            let lhs_sort = expr.lhs.smt_sort(analyzer)?;
+            coerce(expr.lhs.to_smt(lhs_sort, analyzer)?, lhs_sort, self_sort)
+        }
+        Not => {
+            let lhs = expr.lhs.to_smt(self_sort, analyzer)?;
+            match self_sort {
+                SmtSort::Bool => format!("(not {lhs})"),
+                SmtSort::BitVec(..) | SmtSort::Bytes(_) => format!("(bvnot {lhs})"),
+            }
+        }
+        And | Or => {
+            let lhs = expr.lhs.to_smt(SmtSort::Bool, analyzer)?;
+            let rhs = expr.rhs.to_smt(SmtSort::Bool, analyzer)?;
+            let op = if expr.op == And { "and" } else { "or" };
+            format!("({op} {lhs} {rhs})")
+        }
+        Lt | Lte | Gt | Gte | Eq | Neq => {
+            let operand_sort = expr
+                .lhs
+                .smt_sort(analyzer)
+                .or_else(|| expr.rhs.smt_sort(analyzer))?;
+            let lhs = expr.lhs.to_smt(operand_sort, analyzer)?;
+            let rhs = expr.rhs.to_smt(operand_sort, analyzer)?;
+            let op = match (expr.op, operand_sort.is_signed()) {
+                (Eq, _) => "=",
+                (Neq, _) => "distinct",
+                (Lt, false) => "bvult",
+                (Lt, true) => "bvslt",
+                (Lte, false) => "bvule",
+                (Lte, true) => "bvsle",
+                (Gt, false) => "bvugt",
+                (Gt, true) => "bvsgt",
+                (_, false) => "bvuge",
+                (_, true) => "bvsge",
+            };
+            format!("({op} {lhs} {rhs})")
+        }
+        Min | Max => {
+            let lhs = expr.lhs.to_smt(self_sort, analyzer)?;
+            let rhs = expr.rhs.to_smt(self_sort, analyzer)?;
+            let cmp = match (expr.op, self_sort.is_signed()) {
+                (Min, false) => "bvule",
+                (Min, true) => "bvsle",
+                (_, false) => "bvuge",
+                (_, true) => "bvsge",
+            };
+            format!("(ite ({cmp} {lhs} {rhs}) {lhs} {rhs})")
+        }
+        Exp => {
+            // only small concrete exponents are expanded into repeated multiplication
+            let exp = match &*expr.rhs {
+                Elem::Concrete(c) => c.val.into_u256()?,
+                _ => return None,
+            };
+            if exp > U256::from(16) {
+                return None;
+            }
+            let width = match self_sort {
+                SmtSort::BitVec(width, _) => width,
+                SmtSort::Bool | SmtSort::Bytes(_) => return None,
+            };
+            let lhs = expr.lhs.to_smt(self_sort, analyzer)?;
+            (0..exp.as_usize()).fold(format!("(_ bv1 {width})"), |acc, _| {
+                arith("bvmul", acc, lhs.clone())
+            })
+        }
+        Where => return None,
+        _ => {
+            if self_sort == SmtSort::Bool {
+                return None;
+            }
+            let signed = self_sort.is_signed();
+            let lhs = expr.lhs.to_smt(self_sort, analyzer)?;
+            let rhs = expr.rhs.to_smt(self_sort, analyzer)?;
+            match expr.op {
+                Add => return Some(coerce(arith("bvadd", lhs, rhs), self_sort, sort)),
+                Sub => return Some(coerce(arith("bvsub", lhs, rhs), self_sort, sort)),
+                Mul => return Some(coerce(arith("bvmul", lhs, rhs), self_sort, sort)),
+                _ => {}
+            }
+            let op = match expr.op {
+                Div if signed => "bvsdiv",
+                Div => "bvudiv",
+                Mod if signed => "bvsrem",
+                Mod => "bvurem",
+                Shl => "bvshl",
+                Shr if signed => "bvashr",
+                Shr => "bvlshr",
+                BitAnd => "bvand",
+                BitOr => "bvor",
+                BitXor => "bvxor",
+                _ => return None,
+            };
+            format!("({op} {lhs} {rhs})")
+        }
+    };
+    Some(coerce(term, self_sort, sort))
+}
+
+/// Performs `op` on two bitvectors of `sort` in a width wide enough to not overflow,
+/// clamping the result to the bounds of `sort`
+fn saturate(op: &str, lhs: String, rhs: String, sort: SmtSort) -> String {
+    let (width, signed) = match sort {
+        SmtSort::BitVec(width, signed) => (width, signed),
+        SmtSort::Bool | SmtSort::Bytes(_) => return format!("({op} {lhs} {rhs})"),
+    };
+    let wide = SmtSort::BitVec(width * 2 + 1, signed);
+    let (min, max) = if signed {
+        let half = U256::from(1) << (width - 1);
+        (half, half - 1)
+    } else if width >= 256 {
+        (U256::zero(), U256::MAX)
+    } else {
+        (U256::zero(), (U256::from(1) << width) - 1)
+    };
+    let min = coerce(format!("(_ bv{min} {width})"), sort, wide);
+    let max = coerce(format!("(_ bv{max} {width})"), sort, wide);
+    let lhs = coerce(lhs, sort, wide);
+    let rhs = coerce(rhs, sort, wide);
+    coerce(
+        format!("(let ((r ({op} {lhs} {rhs}))) (ite (bvslt r {min}) {min} (ite (bvsgt r {max}) {max} r)))"),
+        wide,
+        sort,
+    )
+}
+
+/// The result of checking an [`SmtScript`] with a solver
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SmtResult {
+    /// The path is feasible
+    Sat,
+    /// The path can never be taken
+    Unsat,
+    /// The solver timed out, gave up, or could not be run
+    Unknown,
+}
+
+/// An SMT-LIB2 script in the `QF_BV` logic asserting the constraints of a single path
+#[derive(Debug, Clone, Default)]
+pub struct SmtScript {
+    /// The path the script was generated for
+    pub path: String,
+    /// Symbol declarations, keyed by symbol
+    pub decls: BTreeMap<String, SmtSort>,
+    /// Assertions, each with the source of the constraint as a comment
+    pub asserts: Vec<(String, String)>,
+    /// Constraints that could not be encoded, and so were left out
+    pub skipped: Vec<String>,
+    /// Whether the solver is asked for a model after checking satisfiability
+    pub get_model: bool,
+}
+
+impl SmtScript {
+    /// Builds a script for every path under a context, i.e. every context that has no
+    /// forks or children of its own
+    pub fn for_all_paths(ctx: ContextNode, analyzer: &impl AnalyzerLike) -> Vec<Self> {
+        let mut scripts = vec![];
+        let mut stack = vec![ctx];
+        while let Some(curr) = stack.pop() {
+            let underlying = curr.underlying(analyzer);
+            if underlying.forks.is_empty() && underlying.children.is_empty() {
+                scripts.push(Self::for_path(curr, analyzer));
+            } else {
+                stack.extend(underlying.forks.iter().rev());
+                stack.extend(underlying.children.iter().rev());
+            }
+        }
+        scripts
+    }
+
+    /// Builds the script for a path by walking the context's dependencies and the
+    /// range expressions of every variable they transitively depend on
+    pub fn for_path(ctx: ContextNode, analyzer: &impl AnalyzerLike) -> Self {
+        let mut script = SmtScript {
+            path: ctx.path(analyzer),
+            ..Default::default()
+        };
+
+        let mut deps = ctx
+            .ctx_deps(analyzer)
+            .into_values()
+            .collect::<Vec<ContextVarNode>>();
+        deps.sort();
+        let mut seen: BTreeSet<ContextVarNode> = BTreeSet::default();
+        let mut stack = deps.clone();
+        while let Some(cvar) = stack.pop() {
+            if !seen.insert(cvar) {
+                continue;
+            }
+            stack.extend(script.add_var(cvar, analyzer));
+        }
+
+        deps.into_iter().for_each(|dep| {
+            if let Some(sort) = SmtSort::from_var(dep, analyzer) {
+                // a non-boolean dependency holds when it is nonzero
+                script.asserts.push((
+                    coerce(smt_symbol(dep, analyzer), sort, SmtSort::Bool),
+                    format!("path condition: {}", dep.display_name(analyzer)),
+                ));
+            }
+        });
+
+        script
+    }
+
+    /// Declares a variable and asserts its range and construction. Returns the variables
+    /// the constraints depend on
+    fn add_var(
+        &mut self,
+        cvar: ContextVarNode,
+        analyzer: &impl AnalyzerLike,
+    ) -> Vec<ContextVarNode> {
+        let sort = match SmtSort::from_var(cvar, analyzer) {
+            Some(sort) => sort,
+            None => return vec![],
+        };
+        let symbol = smt_symbol(cvar, analyzer);
+        let name = cvar.display_name(analyzer);
+        self.decls.insert(symbol.clone(), sort);
+        let mut deps = vec![];
+
+        if let Some(tmp) = cvar.tmp_of(analyzer) {
+            let rhs = tmp
+                .rhs
+                .map(|rhs| Elem::Dynamic(Dynamic::new(rhs.into(), Loc::Implicit)))
+                .unwrap_or(Elem::Null);
+            let expr = RangeExpr::new(
+                Elem::Dynamic(Dynamic::new(tmp.lhs.into(), Loc::Implicit)),
+                tmp.op,
+                rhs,
+            );
+            deps.extend(expr.dependent_on());
+            // the operation itself wraps, overflow is a separate constraint on the path
+            let term = expr_to_smt(&expr, sort, false, analyzer);
+            self.assert_eq(&symbol, &name, term, "construction");
+        }
+
+        if let Some(range) = cvar.range(analyzer) {
+            let (min, max) = (range.range_min(), range.range_max());
+            deps.extend(min.dependent_on());
+            deps.extend(max.dependent_on());
+            if sort == SmtSort::Bool {
+                // booleans are only constrained when known exactly
+                if matches!((&min, &max), (Elem::Concrete(a), Elem::Concrete(b)) if a.val == b.val)
+                {
+                    let term = min.to_smt(sort, analyzer);
+                    self.assert_eq(&symbol, &name, term, "value");
+                }
+            } else {
+                let le = if sort.is_signed() { "bvsle" } else { "bvule" };
+                match min.to_smt(sort, analyzer) {
+                    Some(min) => self
+                        .asserts
+                        .push((format!("({le} {min} {symbol})"), format!("{name} min"))),
+                    None => self.skipped.push(format!("{name} min")),
+                }
+                match max.to_smt(sort, analyzer) {
+                    Some(max) => self
+                        .asserts
+                        .push((format!("({le} {symbol} {max})"), format!("{name} max"))),
+                    None => self.skipped.push(format!("{name} max")),
+                }
+            }
+            range.range_exclusions().iter().for_each(|excl| {
+                deps.extend(excl.dependent_on());
+                match excl.to_smt(sort, analyzer) {
+                    Some(excl) => self.asserts.push((
+                        format!("(distinct {symbol} {excl})"),
+                        format!("{name} exclusion"),
+                    )),
+                    None => self.skipped.push(format!("{name} exclusion")),
+                }
+            });
+        }
+
+        deps
+    }
+
+    fn assert_eq(&mut self, symbol: &str, name: &str, term: Option<String>, kind: &str) {
+        match term {
+            Some(term) => self
+                .asserts
+                .push((format!("(= {symbol} {term})"), format!("{name} {kind}"))),
+            None => self.skipped.push(format!("{name} {kind}")),
+        }
+    }
+}
+
+impl fmt::Display for SmtScript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "; path: {}", self.path)?;
+        writeln!(f, "(set-logic QF_BV)")?;
+        for (symbol, sort) in self.decls.iter() {
+            writeln!(f, "(declare-const {symbol} {sort})")?;
+        }
+        for (assertion, source) in self.asserts.iter() {
+            writeln!(f, "; {source}")?;
+            writeln!(f, "(assert {assertion})")?;
+        }
+        for source in self.skipped.iter() {
+            writeln!(f, "; skipped, not representable: {source}")?;
+        }
+        writeln!(f, "(check-sat)")?;
+        if self.get_model {
+            writeln!(f, "(get-model)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Results of scripts already sent to the solver. A script names its path and holds every
+/// constraint on it, so a path is only solved again once its constraints change
+#[derive(Debug, Clone, Default)]
+pub struct SmtCache {
+    results: RefCell<HashMap<String, SmtResult>>,
+    hits: Cell<usize>,
+}
+
+impl SmtCache {
+    /// The number of checks answered without running the solver
+    pub fn hits(&self) -> usize {
+        self.hits.get()
+    }
+
+    /// The number of scripts whose results are cached
+    pub fn entries(&self) -> usize {
+        self.results.borrow().len()
+    }
+}
+
+#[cfg(feature = "smt")]
+impl SmtCache {
+    /// Checks the script with the solver, unless the same script was checked before
+    pub fn check(&self, script: &SmtScript, solver: &str) -> SmtResult {
+        let script = script.to_string();
+        if let Some(res) = self.results.borrow().get(&script) {
+            self.hits.set(self.hits.get() + 1);
+            return *res;
+        }
+        let res = SmtScript::check_str(&script, solver);
+        self.results.borrow_mut().insert(script, res);
+        res
+    }
+}
+
+#[cfg(feature = "smt")]
+impl SmtScript {
+    /// Checks the script with a local solver, run as the `solver` command with the script
+    /// on its stdin
+    pub fn check(&self, solver: &str) -> SmtResult {
+        Self::check_str(&self.to_string(), solver)
+    }
+
+    fn check_str(script: &str, solver: &str) -> SmtResult {
+        use std::io::Write;
+        use std::process::{Command, Stdio};
+
+        let mut parts = solver.split_whitespace();
+        let solver = match parts.next() {
+            Some(solver) => solver,
+            None => return SmtResult::Unknown,
+        };
+        let child = Command::new(solver)
+            .args(parts)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn();
+        let mut child = match child {
+            Ok(child) => child,
+            Err(e) => {
+                tracing::trace!("Could not run solver {solver}: {e}");
+                return SmtResult::Unknown;
+            }
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            if stdin.write_all(script.as_bytes()).is_err() {
+                return SmtResult::Unknown;
+            }
+        }
+        let output = match child.wait_with_output() {
+            Ok(output) => output,
+            Err(_) => return SmtResult::Unknown,
+        };
+        match String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .next()
+            .map(str::trim)
+        {
+            Some("sat") => SmtResult::Sat,
+            Some("unsat") => SmtResult::Unsat,
+            _ => SmtResult::Unknown,
+        }
+    }
+}
@@ -26,6 +26,15 @@ pub fn builtin_fns() -> HashMap<String, Function> {
                 Loc::Builtin,
             )))],
         ),
+        builtin_fn!(
+            name: Some(Identifier {
+                loc: Loc::Builtin,
+                name: "pop".to_string(),
+            }),
+            attributes: vec![FunctionAttribute::Visibility(Visibility::External(Some(
+                Loc::Builtin,
+            )))],
+        ),
         builtin_fn!(
             name: Some(Identifier {
                 loc: Loc::Builtin,
@@ -237,6 +246,7 @@ pub fn builtin_fns_inputs(
 ) -> HashMap<String, (Vec<FunctionParam>, Vec<FunctionReturn>)> {
     let funcs = [
         ("push", vec![], vec![]),
+        ("pop", vec![], vec![]),
         (
             "ecrecover",
             vec![
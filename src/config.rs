@@ -0,0 +1,377 @@
+use crate::context::analyzers::ReportConfig;
+use crate::foundry::parse_remapping;
+use ethers_core::types::{Address, U256};
+use glob::Pattern;
+use serde::de::{self, value::MapAccessDeserializer, Deserializer, MapAccess, Visitor};
+use serde::Deserialize;
+use shared::nodes::{Block, Msg};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// The name of the configuration file looked for in a project
+pub const CONFIG_FILE: &str = "pyrometer.toml";
+
+/// How serious a finding is, from least to most
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(try_from = "String")]
+pub enum Severity {
+    Note,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// The SARIF level of the severity
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Note => "note",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+impl FromStr for Severity {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "note" => Ok(Severity::Note),
+            "warning" => Ok(Severity::Warning),
+            "error" => Ok(Severity::Error),
+            e => Err(format!(
+                "Unknown severity `{e}`, expected one of `note`, `warning` or `error`"
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for Severity {
+    type Error = String;
+    fn try_from(s: String) -> Result<Self, String> {
+        s.parse()
+    }
+}
+
+/// The fields of a [`ReportConfig`] set in `[report]`, overriding those picked by the verbosity
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReportSettings {
+    pub eval_bounds: Option<bool>,
+    pub simplify_bounds: Option<bool>,
+    pub show_tmps: Option<bool>,
+    pub show_consts: Option<bool>,
+    pub show_subctxs: Option<bool>,
+    pub show_initial_bounds: Option<bool>,
+    pub show_all_lines: Option<bool>,
+}
+
+impl ReportSettings {
+    /// `config` with each field that is set replaced
+    pub fn apply(&self, config: ReportConfig) -> ReportConfig {
+        let simplify_bounds = self.simplify_bounds.unwrap_or(config.simplify_bounds);
+        ReportConfig {
+            // like `--simplify`, simplified bounds aren't evaluated unless asked to be
+            eval_bounds: self
+                .eval_bounds
+                .unwrap_or(config.eval_bounds && !simplify_bounds),
+            simplify_bounds,
+            show_tmps: self.show_tmps.unwrap_or(config.show_tmps),
+            show_consts: self.show_consts.unwrap_or(config.show_consts),
+            show_subctxs: self.show_subctxs.unwrap_or(config.show_subctxs),
+            show_initial_bounds: self
+                .show_initial_bounds
+                .unwrap_or(config.show_initial_bounds),
+            show_all_lines: self.show_all_lines.unwrap_or(config.show_all_lines),
+        }
+    }
+}
+
+/// Globs picking names to analyze, e.g. `include = ["Vault*"]`. A name is picked if it matches
+/// an include glob, or there are none, and matches no exclude glob
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NameFilter {
+    #[serde(deserialize_with = "globs")]
+    pub include: Vec<String>,
+    #[serde(deserialize_with = "globs")]
+    pub exclude: Vec<String>,
+}
+
+impl NameFilter {
+    pub fn matches(&self, name: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|glob| glob_match(glob, name)))
+            && !self.exclude.iter().any(|glob| glob_match(glob, name))
+    }
+}
+
+/// Whether `name` matches `glob`, a [`Pattern`] in which `*` matches any run of characters, `?`
+/// any one, and `[...]` any one of a class like `[A-Z]` or, negated, `[!0-9]`. `**` is a path
+/// wildcard, only valid on its own, where it matches any name. A glob that isn't a valid
+/// pattern matches nothing
+pub fn glob_match(glob: &str, name: &str) -> bool {
+    Pattern::new(glob).is_ok_and(|pattern| pattern.matches(name))
+}
+
+/// Globs, rejecting any that aren't valid patterns rather than letting them match nothing
+fn globs<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    let globs = Vec::<String>::deserialize(deserializer)?;
+    match globs
+        .iter()
+        .find_map(|glob| Pattern::new(glob).err().map(|err| (glob, err)))
+    {
+        Some((glob, err)) => Err(de::Error::custom(format!("Invalid glob `{glob}`: {err}"))),
+        None => Ok(globs),
+    }
+}
+
+/// Settings of an analyzer, keyed in `[analyzers]` by the rule id of its reports, e.g. `panic`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AnalyzerSettings {
+    pub enabled: Option<bool>,
+    /// Replaces the severity the analyzer's reports are given
+    pub severity: Option<Severity>,
+}
+
+/// The settings of an analyzer, or a boolean: `panic = true` is short for
+/// `panic = { enabled = true }`
+struct AnalyzerEntry(AnalyzerSettings);
+
+impl<'de> Deserialize<'de> for AnalyzerEntry {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EntryVisitor;
+        impl<'de> Visitor<'de> for EntryVisitor {
+            type Value = AnalyzerEntry;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a boolean or a table of analyzer settings")
+            }
+
+            fn visit_bool<E: de::Error>(self, enabled: bool) -> Result<Self::Value, E> {
+                Ok(AnalyzerEntry(AnalyzerSettings {
+                    enabled: Some(enabled),
+                    severity: None,
+                }))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+                AnalyzerSettings::deserialize(MapAccessDeserializer::new(map)).map(AnalyzerEntry)
+            }
+        }
+        deserializer.deserialize_any(EntryVisitor)
+    }
+}
+
+/// The contents of a `pyrometer.toml`, before its paths are resolved
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    report: ReportSettings,
+    contracts: NameFilter,
+    functions: NameFilter,
+    env: EnvFile,
+    analyzers: BTreeMap<String, AnalyzerEntry>,
+    imports: ImportsFile,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct EnvFile {
+    msg: MsgFile,
+    block: BlockFile,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct MsgFile {
+    #[serde(deserialize_with = "address")]
+    sender: Option<Address>,
+    #[serde(deserialize_with = "address")]
+    origin: Option<Address>,
+    #[serde(deserialize_with = "uint")]
+    value: Option<U256>,
+    #[serde(deserialize_with = "uint")]
+    gasprice: Option<U256>,
+    #[serde(deserialize_with = "uint")]
+    gaslimit: Option<U256>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct BlockFile {
+    #[serde(deserialize_with = "address")]
+    coinbase: Option<Address>,
+    #[serde(deserialize_with = "uint")]
+    basefee: Option<U256>,
+    #[serde(deserialize_with = "uint")]
+    chainid: Option<U256>,
+    #[serde(deserialize_with = "uint")]
+    difficulty: Option<U256>,
+    #[serde(deserialize_with = "uint")]
+    gaslimit: Option<U256>,
+    #[serde(deserialize_with = "uint")]
+    number: Option<U256>,
+    #[serde(deserialize_with = "uint")]
+    prevrandao: Option<U256>,
+    #[serde(deserialize_with = "uint")]
+    timestamp: Option<U256>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ImportsFile {
+    remappings: Vec<String>,
+    include_paths: Vec<String>,
+}
+
+/// A project's `pyrometer.toml`. Every setting is optional, and a flag passed on the command
+/// line takes precedence over the file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PyrometerConfig {
+    /// The directory holding the file, which relative paths in it are relative to
+    pub root: PathBuf,
+    pub report: ReportSettings,
+    /// Contracts to analyze the functions of, by name
+    pub contracts: NameFilter,
+    /// Functions to analyze, by name with or without their parameter types
+    pub functions: NameFilter,
+    /// Known values of `msg` and `block`, e.g. a `msg.value` of 0 for non-payable entry points
+    pub msg: Msg,
+    pub block: Block,
+    pub analyzers: BTreeMap<String, AnalyzerSettings>,
+    /// Remappings to resolve imports with, as `(prefix, target)`, their targets joined onto
+    /// the directory holding the file
+    pub remappings: Vec<(String, String)>,
+    pub include_paths: Vec<PathBuf>,
+}
+
+impl PyrometerConfig {
+    /// The `pyrometer.toml` in `dir` or the closest of its ancestors, if any
+    pub fn find(dir: &Path) -> Option<PathBuf> {
+        dir.ancestors()
+            .map(|dir| dir.join(CONFIG_FILE))
+            .find(|path| path.is_file())
+    }
+
+    /// Reads the configuration file at `path`
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let file: ConfigFile = toml_edit::de::from_str(
+            &fs::read_to_string(path).map_err(|err| format!("Could not read {path:?}: {err}"))?,
+        )
+        .map_err(|err| format!("Invalid {path:?}: {err}"))?;
+        let root = path.parent().unwrap_or(Path::new("")).to_path_buf();
+        let remappings = file
+            .imports
+            .remappings
+            .iter()
+            .map(|remapping| {
+                let (name, target) = parse_remapping(remapping).ok_or_else(|| {
+                    format!("Invalid remapping `{remapping}`, expected `prefix=target`")
+                })?;
+                Ok((name, root.join(target).to_string_lossy().to_string()))
+            })
+            .collect::<Result<_, String>>()?;
+        let include_paths = file
+            .imports
+            .include_paths
+            .iter()
+            .map(|path| root.join(path))
+            .collect();
+        let EnvFile { msg, block } = file.env;
+        Ok(Self {
+            report: file.report,
+            contracts: file.contracts,
+            functions: file.functions,
+            msg: Msg {
+                sender: msg.sender,
+                origin: msg.origin,
+                value: msg.value,
+                gasprice: msg.gasprice,
+                gaslimit: msg.gaslimit,
+                ..Default::default()
+            },
+            block: Block {
+                coinbase: block.coinbase,
+                basefee: block.basefee,
+                chainid: block.chainid,
+                difficulty: block.difficulty,
+                gaslimit: block.gaslimit,
+                number: block.number,
+                prevrandao: block.prevrandao,
+                timestamp: block.timestamp,
+                ..Default::default()
+            },
+            analyzers: file
+                .analyzers
+                .into_iter()
+                .map(|(name, AnalyzerEntry(settings))| (name, settings))
+                .collect(),
+            remappings,
+            include_paths,
+            root,
+        })
+    }
+
+    /// Whether the analyzer whose reports have the rule id `rule` is turned on or off, if set
+    pub fn enabled(&self, rule: &str) -> Option<bool> {
+        self.analyzers.get(rule)?.enabled
+    }
+
+    /// The severity the reports with the rule id `rule` are given instead of their own, if set
+    pub fn severity(&self, rule: &str) -> Option<Severity> {
+        self.analyzers.get(rule)?.severity
+    }
+
+    /// Whether a function should be analyzed, given its name and that of its contract, if any.
+    /// Free functions are only picked by the function globs
+    pub fn analyzes(&self, contract: Option<&str>, func: &str) -> bool {
+        let bare = func.split('(').next().unwrap_or_default();
+        let contract_matches = match contract {
+            Some(contract) => self.contracts.matches(contract),
+            None => true,
+        };
+        contract_matches && (self.functions.matches(func) || self.functions.matches(bare))
+    }
+}
+
+/// A number given as an integer, or as a decimal or `0x` prefixed hex string for values that
+/// don't fit TOML's 64 bit integers
+fn uint<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<U256>, D::Error> {
+    struct UintVisitor;
+    impl<'de> Visitor<'de> for UintVisitor {
+        type Value = U256;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("an unsigned integer")
+        }
+
+        fn visit_u64<E: de::Error>(self, val: u64) -> Result<Self::Value, E> {
+            Ok(U256::from(val))
+        }
+
+        fn visit_i64<E: de::Error>(self, val: i64) -> Result<Self::Value, E> {
+            u64::try_from(val)
+                .map(U256::from)
+                .map_err(|_| E::invalid_value(de::Unexpected::Signed(val), &self))
+        }
+
+        fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+            match s.strip_prefix("0x") {
+                Some(hex) => U256::from_str_radix(hex, 16).ok(),
+                None => U256::from_dec_str(s).ok(),
+            }
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Str(s), &self))
+        }
+    }
+    deserializer.deserialize_any(UintVisitor).map(Some)
+}
+
+fn address<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Address>, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    Address::from_str(&s)
+        .map(Some)
+        .map_err(|_| de::Error::invalid_value(de::Unexpected::Str(&s), &"an address"))
+}
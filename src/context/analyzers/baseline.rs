@@ -0,0 +1,372 @@
+use crate::analyzers::json::line_col;
+use crate::analyzers::panics::PanicAnalysis;
+use crate::analyzers::sarif::rule_id;
+use crate::analyzers::syntax::SyntaxError;
+use crate::analyzers::{LocStrSpan, ReportDisplay};
+use crate::context::queries::storage_write::{AccessStorageWriteReport, StorageRangeReport};
+use shared::{
+    analyzer::{AnalyzerLike, Search},
+    context::*,
+};
+
+use ariadne::Span;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// The comment that suppresses findings on the line after it, followed by the rule ids to
+/// suppress, or none to suppress every rule, e.g. `// pyrometer-disable-next-line panic`
+pub const DISABLE_NEXT_LINE: &str = "pyrometer-disable-next-line";
+
+/// What identifies a finding from one run to the next. Nothing in it depends on where the
+/// finding is in its file, so it holds as code around the finding is edited
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Fingerprint {
+    /// The rule id of the report holding the finding, e.g. `panic`
+    pub rule: String,
+    pub contract: Option<String>,
+    pub function: Option<String>,
+    /// What the finding is about that isn't a contract or function, e.g. an import's path
+    pub variable: Option<String>,
+    /// The source of the finding's span, without its formatting
+    pub span: String,
+}
+
+impl Fingerprint {
+    /// The fingerprint of a finding of `report` at `span`, in the function of `ctx` if any, and
+    /// about `variable` if anything other than its contract or function
+    pub fn new(
+        report: &impl ReportDisplay,
+        analyzer: &(impl AnalyzerLike + Search),
+        ctx: Option<ContextNode>,
+        variable: Option<String>,
+        span: &LocStrSpan,
+        sources: &BTreeMap<String, String>,
+    ) -> Self {
+        Self {
+            rule: rule_id(&report.report_kind()),
+            contract: ctx
+                .and_then(|ctx| ctx.maybe_associated_contract(analyzer))
+                .map(|contract| contract.name(analyzer)),
+            function: ctx.map(|ctx| ctx.associated_fn_name(analyzer)),
+            variable,
+            span: normalized_source(span, sources),
+        }
+    }
+
+    /// A hash of the fingerprint as hex, which is what a baseline matches findings by
+    pub fn id(&self) -> String {
+        let mut hasher = Sha256::new();
+        [
+            Some(&self.rule),
+            self.contract.as_ref(),
+            self.function.as_ref(),
+            self.variable.as_ref(),
+            Some(&self.span),
+        ]
+        .iter()
+        .for_each(|part| {
+            hasher.update(part.map(String::as_str).unwrap_or_default());
+            hasher.update([0]);
+        });
+        hasher.finalize()[..8]
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+}
+
+/// The source of `span` with its formatting taken out, keeping whitespace only as a single space
+/// between two words
+fn normalized_source(span: &LocStrSpan, sources: &BTreeMap<String, String>) -> String {
+    let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '$');
+    let src = sources
+        .get(span.source())
+        .and_then(|src| src.get(span.start()..span.end()))
+        .unwrap_or_default();
+    let mut normalized = String::with_capacity(src.len());
+    src.split_whitespace().for_each(|word| {
+        if is_word(normalized.chars().last()) && is_word(word.chars().next()) {
+            normalized.push(' ');
+        }
+        normalized.push_str(word);
+    });
+    normalized
+}
+
+/// A finding recorded in a baseline
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub id: String,
+    #[serde(flatten)]
+    pub fingerprint: Fingerprint,
+}
+
+/// The findings already known of when the baseline was written, which aren't reported again
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub findings: Vec<BaselineEntry>,
+}
+
+impl Baseline {
+    pub fn new(fingerprints: impl IntoIterator<Item = Fingerprint>) -> Self {
+        let mut findings = fingerprints
+            .into_iter()
+            .map(|fingerprint| BaselineEntry {
+                id: fingerprint.id(),
+                fingerprint,
+            })
+            .collect::<Vec<_>>();
+        findings.sort_by(|a, b| a.fingerprint.cmp(&b.fingerprint));
+        Self { findings }
+    }
+
+    /// Reads the baseline at `path`
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let json = fs::read_to_string(path)
+            .map_err(|err| format!("Could not read baseline {path:?}: {err}"))?;
+        serde_json::from_str(&json).map_err(|err| format!("Invalid baseline {path:?}: {err}"))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).expect("Could not serialize baseline");
+        fs::write(path, json + "\n")
+            .map_err(|err| format!("Could not write baseline {path:?}: {err}"))
+    }
+
+    /// Takes the finding off the baseline if it is on it, returning whether it was. Each entry
+    /// accepts a single finding, so another copy of a known finding is still new
+    pub fn accept(&mut self, fingerprint: &Fingerprint) -> bool {
+        let id = fingerprint.id();
+        match self.findings.iter().position(|entry| entry.id == id) {
+            Some(pos) => {
+                self.findings.remove(pos);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// The `// pyrometer-disable-next-line` comments of the sources
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Suppressions {
+    /// The rule ids suppressed on each line of each file, none meaning every rule
+    pub lines: BTreeMap<(String, usize), Vec<String>>,
+}
+
+impl Suppressions {
+    pub fn new(sources: &BTreeMap<String, String>) -> Self {
+        let mut lines = BTreeMap::default();
+        sources.iter().for_each(|(name, src)| {
+            line_comments(src).into_iter().for_each(|(i, comment)| {
+                if let Some(rules) = comment.trim().strip_prefix(DISABLE_NEXT_LINE) {
+                    let rules = rules
+                        .split(|c: char| c == ',' || c.is_whitespace())
+                        .filter(|rule| !rule.is_empty())
+                        .map(str::to_string)
+                        .collect();
+                    // the line after the comment, counting from 1
+                    lines.insert((name.clone(), i + 2), rules);
+                }
+            })
+        });
+        Self { lines }
+    }
+
+    /// Whether a finding of `rule` starting at `span` is suppressed
+    pub fn suppresses(
+        &self,
+        rule: &str,
+        span: &LocStrSpan,
+        sources: &BTreeMap<String, String>,
+    ) -> bool {
+        let line = sources
+            .get(span.source())
+            .and_then(|src| line_col(src, span.start()))
+            .map(|(line, _col)| line);
+        match line.and_then(|line| self.lines.get(&(span.source().clone(), line))) {
+            Some(rules) => rules.is_empty() || rules.iter().any(|r| r == rule),
+            None => false,
+        }
+    }
+}
+
+/// The text after the `//` of each line comment in `src`, and the line it is on counting from 0.
+/// A `//` in a string literal or block comment doesn't start one
+fn line_comments(src: &str) -> Vec<(usize, &str)> {
+    let mut comments = vec![];
+    let mut line = 0;
+    // the quote of the string literal being scanned, if in one
+    let mut quote: Option<u8> = None;
+    let mut in_block = false;
+    let bytes = src.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        match (bytes[i], bytes.get(i + 1)) {
+            (b'\n', _) => {
+                line += 1;
+                // a string literal can't span lines, so an unterminated one ends here
+                quote = None;
+            }
+            (b'*', Some(b'/')) if in_block => {
+                in_block = false;
+                i += 1;
+            }
+            _ if in_block => {}
+            (b'\\', Some(next)) if quote.is_some() && *next != b'\n' => i += 1,
+            (c, _) if quote == Some(c) => quote = None,
+            _ if quote.is_some() => {}
+            (c @ (b'"' | b'\''), _) => quote = Some(c),
+            (b'/', Some(b'*')) => {
+                in_block = true;
+                i += 1;
+            }
+            (b'/', Some(b'/')) => {
+                let end = src[i..].find('\n').map_or(src.len(), |end| i + end);
+                comments.push((line, &src[i + 2..end]));
+                i = end;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    comments
+}
+
+/// A report made up of findings that can be suppressed or accepted by a baseline one by one
+pub trait Findings: ReportDisplay + Sized {
+    /// The fingerprint and span of each finding in the report
+    fn findings(
+        &self,
+        analyzer: &(impl AnalyzerLike + Search),
+        sources: &BTreeMap<String, String>,
+    ) -> Vec<(Fingerprint, LocStrSpan)>;
+
+    /// The report with only the findings `keep` is true of, or `None` if no finding is left
+    fn retain_findings(
+        &self,
+        analyzer: &(impl AnalyzerLike + Search),
+        sources: &BTreeMap<String, String>,
+        keep: impl FnMut(&Fingerprint, &LocStrSpan) -> bool,
+    ) -> Option<Self>;
+}
+
+impl Findings for PanicAnalysis {
+    fn findings(
+        &self,
+        analyzer: &(impl AnalyzerLike + Search),
+        sources: &BTreeMap<String, String>,
+    ) -> Vec<(Fingerprint, LocStrSpan)> {
+        self.panics
+            .iter()
+            .map(|(span, (_code, array, _paths))| {
+                let fingerprint = Fingerprint::new(
+                    self,
+                    analyzer,
+                    Some(self.ctx),
+                    Some(array.clone()),
+                    span,
+                    sources,
+                );
+                (fingerprint, span.clone())
+            })
+            .collect()
+    }
+
+    fn retain_findings(
+        &self,
+        analyzer: &(impl AnalyzerLike + Search),
+        sources: &BTreeMap<String, String>,
+        mut keep: impl FnMut(&Fingerprint, &LocStrSpan) -> bool,
+    ) -> Option<Self> {
+        let mut retained = self.clone();
+        retained.panics.retain(|span, (_code, array, _paths)| {
+            keep(
+                &Fingerprint::new(
+                    self,
+                    analyzer,
+                    Some(self.ctx),
+                    Some(array.clone()),
+                    span,
+                    sources,
+                ),
+                span,
+            )
+        });
+        (!retained.panics.is_empty()).then_some(retained)
+    }
+}
+
+impl Findings for SyntaxError {
+    fn findings(
+        &self,
+        analyzer: &(impl AnalyzerLike + Search),
+        sources: &BTreeMap<String, String>,
+    ) -> Vec<(Fingerprint, LocStrSpan)> {
+        let fingerprint = Fingerprint::new(
+            self,
+            analyzer,
+            None,
+            Some(self.diagnostic.message.clone()),
+            &self.span,
+            sources,
+        );
+        vec![(fingerprint, self.span.clone())]
+    }
+
+    fn retain_findings(
+        &self,
+        analyzer: &(impl AnalyzerLike + Search),
+        sources: &BTreeMap<String, String>,
+        mut keep: impl FnMut(&Fingerprint, &LocStrSpan) -> bool,
+    ) -> Option<Self> {
+        let kept = self
+            .findings(analyzer, sources)
+            .iter()
+            .all(|(fingerprint, span)| keep(fingerprint, span));
+        kept.then(|| self.clone())
+    }
+}
+
+/// Query reports answer what was asked of them rather than flag anything, so are always kept
+impl Findings for StorageRangeReport {
+    fn findings(
+        &self,
+        _analyzer: &(impl AnalyzerLike + Search),
+        _sources: &BTreeMap<String, String>,
+    ) -> Vec<(Fingerprint, LocStrSpan)> {
+        vec![]
+    }
+
+    fn retain_findings(
+        &self,
+        _analyzer: &(impl AnalyzerLike + Search),
+        _sources: &BTreeMap<String, String>,
+        _keep: impl FnMut(&Fingerprint, &LocStrSpan) -> bool,
+    ) -> Option<Self> {
+        Some(self.clone())
+    }
+}
+
+impl Findings for AccessStorageWriteReport {
+    fn findings(
+        &self,
+        _analyzer: &(impl AnalyzerLike + Search),
+        _sources: &BTreeMap<String, String>,
+    ) -> Vec<(Fingerprint, LocStrSpan)> {
+        vec![]
+    }
+
+    fn retain_findings(
+        &self,
+        _analyzer: &(impl AnalyzerLike + Search),
+        _sources: &BTreeMap<String, String>,
+        _keep: impl FnMut(&Fingerprint, &LocStrSpan) -> bool,
+    ) -> Option<Self> {
+        Some(self.clone())
+    }
+}
@@ -162,6 +162,7 @@ impl OrderedAnalysis {
 pub enum RangePart {
     Equal(String),
     Inclusion(String, String),
+    Union(Vec<RangePart>),
     Exclusion(Vec<RangePart>),
 }
 
@@ -172,6 +173,19 @@ impl RangePart {
             RangePart::Inclusion(min, max) => {
                 format!(" ∈ [ {}, {} ]", min.fg(MIN_COLOR), max.fg(MAX_COLOR))
             }
+            RangePart::Union(parts) => format!(
+                " ∈ {}",
+                parts
+                    .into_iter()
+                    .map(|p| match p {
+                        RangePart::Inclusion(min, max) => {
+                            format!("[ {}, {} ]", min.fg(MIN_COLOR), max.fg(MAX_COLOR))
+                        }
+                        p => p.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ∪ ")
+            ),
             RangePart::Exclusion(parts) => format!(
                 "&& ∉ {{{}}}",
                 parts
@@ -188,7 +202,9 @@ impl RangePart {
     pub fn to_normal_string(&self) -> String {
         match self {
             e @ RangePart::Equal(_) => format!(" == {}", e.to_string()),
-            e @ RangePart::Inclusion(..) => format!(" ∈ {}", e.to_string()),
+            e @ RangePart::Inclusion(..) | e @ RangePart::Union(_) => {
+                format!(" ∈ {}", e.to_string())
+            }
             e @ RangePart::Exclusion(_) => format!("&& ∉ {{{}}}", e.to_string()),
         }
     }
@@ -268,6 +284,11 @@ impl ToString for RangePart {
         match self {
             RangePart::Equal(inner) => inner.to_string(),
             RangePart::Inclusion(min, max) => format!("[ {}, {} ]", min, max),
+            RangePart::Union(inner) => inner
+                .iter()
+                .map(|part| part.to_string())
+                .collect::<Vec<_>>()
+                .join(" ∪ "),
             RangePart::Exclusion(inner) => format!(
                 "{{{}}}",
                 inner
@@ -333,10 +354,23 @@ impl BoundAnalysis {
             range.range_max().to_range_string(true, analyzer).s
         };
 
-        if min == max {
-            parts.push(RangePart::Equal(min));
-        } else {
-            parts.push(RangePart::Inclusion(min, max));
+        match &range.intervals {
+            Some(intervals) if self.report_config.eval_bounds && intervals.is_disjoint() => {
+                parts.push(RangePart::Union(
+                    intervals
+                        .intervals()
+                        .iter()
+                        .map(|(min, max)| {
+                            RangePart::Inclusion(
+                                min.to_range_string(false, analyzer).s,
+                                max.to_range_string(true, analyzer).s,
+                            )
+                        })
+                        .collect(),
+                ));
+            }
+            _ if min == max => parts.push(RangePart::Equal(min)),
+            _ => parts.push(RangePart::Inclusion(min, max)),
         }
 
         let range_excl = range.range_exclusions();
@@ -972,7 +1006,11 @@ impl<'a> ReportDisplay for CLIFunctionVarsBoundAnalysis<'a> {
                                     .evaled_range_max(analyzer)
                                     .to_range_string(true, analyzer)
                                     .s;
-                                let r_str = if min == max {
+                                let r_str = if let Some(intervals) =
+                                    range.intervals.as_ref().filter(|i| i.is_disjoint())
+                                {
+                                    format!(" ∈ {}", intervals.to_range_string(true, analyzer).s)
+                                } else if min == max {
                                     format!(" == {}", min.fg(MAX_COLOR))
                                 } else {
                                     format!(" ∈ [ {}, {} ]", min.fg(MIN_COLOR), max.fg(MAX_COLOR),)
@@ -1075,7 +1113,14 @@ impl<'a> ReportDisplay for CLIFunctionVarsBoundAnalysis<'a> {
                                         .evaled_range_max(analyzer)
                                         .to_range_string(true, analyzer)
                                         .s;
-                                    let r_str = if min == max {
+                                    let r_str = if let Some(intervals) =
+                                        range.intervals.as_ref().filter(|i| i.is_disjoint())
+                                    {
+                                        format!(
+                                            " ∈ {}",
+                                            intervals.to_range_string(true, analyzer).s
+                                        )
+                                    } else if min == max {
                                         format!(" == {}", min.fg(MAX_COLOR))
                                     } else {
                                         format!(
@@ -1133,6 +1178,50 @@ impl<'a> ReportDisplay for CLIFunctionVarsBoundAnalysis<'a> {
 
 impl<T> FunctionVarsBoundAnalyzer for T where T: BoundAnalyzer + Search + AnalyzerLike + Sized {}
 pub trait FunctionVarsBoundAnalyzer: BoundAnalyzer + Search + AnalyzerLike + Sized {
+    /// The bounds of the variables of the function context `ctx` along the path to `child`,
+    /// one of its descendant contexts
+    fn bounds_for_ctx(
+        &self,
+        file_mapping: &'_ BTreeMap<usize, String>,
+        ctx: ContextNode,
+        child: ContextNode,
+        report_config: ReportConfig,
+    ) -> Vec<BoundAnalysis> {
+        let mut parents = child.parent_list(self);
+        parents.reverse();
+        parents.push(child);
+        let _children: Vec<_> = parents
+            .iter()
+            .flat_map(|p| p.returning_child_list(self))
+            .collect();
+        let mut vars = ctx.vars(self);
+        vars.sort_by_key(|a| a.name(self));
+        vars.dedup_by(|a, b| a.name(self) == b.name(self));
+        vars.iter()
+            .filter_map(|var| {
+                let name = var.name(self);
+
+                let is_ret = var.is_return_node_in_any(&parents, self);
+                if is_ret | report_config.show_tmps | report_config.show_initial_bounds
+                    && report_config.show_consts | report_config.show_tmps
+                    && !var.is_const(self) | report_config.show_consts
+                    && !var.is_tmp(self) | !var.is_tmp(self)
+                    && !var.is_const(self)
+                {
+                    // println!("var: {}", name);
+                    Some(self.bounds_for_var_in_family_tree(
+                        file_mapping,
+                        parents.clone(),
+                        name,
+                        report_config,
+                    ))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
     fn bounds_for_all<'a>(
         &'a self,
         file_mapping: &'a BTreeMap<usize, String>,
@@ -1143,41 +1232,9 @@ pub trait FunctionVarsBoundAnalyzer: BoundAnalyzer + Search + AnalyzerLike + Siz
             .terminal_child_list(self)
             .iter()
             .map(|child| {
-                let mut parents = child.parent_list(self);
-                parents.reverse();
-                parents.push(*child);
-                let _children: Vec<_> = parents
-                    .iter()
-                    .flat_map(|p| p.returning_child_list(self))
-                    .collect();
-                let mut vars = ctx.vars(self);
-                vars.sort_by_key(|a| a.name(self));
-                vars.dedup_by(|a, b| a.name(self) == b.name(self));
                 (
                     *child,
-                    vars.iter()
-                        .filter_map(|var| {
-                            let name = var.name(self);
-
-                            let is_ret = var.is_return_node_in_any(&parents, self);
-                            if is_ret | report_config.show_tmps | report_config.show_initial_bounds
-                                && report_config.show_consts | report_config.show_tmps
-                                && !var.is_const(self) | report_config.show_consts
-                                && !var.is_tmp(self) | !var.is_tmp(self)
-                                && !var.is_const(self)
-                            {
-                                // println!("var: {}", name);
-                                Some(self.bounds_for_var_in_family_tree(
-                                    file_mapping,
-                                    parents.clone(),
-                                    name,
-                                    report_config,
-                                ))
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<Vec<BoundAnalysis>>(),
+                    self.bounds_for_ctx(file_mapping, ctx, *child, report_config),
                 )
             })
             .collect::<BTreeMap<ContextNode, Vec<BoundAnalysis>>>();
@@ -0,0 +1,138 @@
+use crate::analyzers::bounds::{CLIFunctionVarsBoundAnalysis, FunctionVarsBoundAnalyzer};
+use crate::analyzers::json::{line_col, JsonBound, JsonSpan};
+use crate::analyzers::LocStrSpan;
+use shared::{
+    analyzer::{AnalyzerLike, Search},
+    context::{ContextNode, CtxTree},
+    range::{range_string::*, Range},
+};
+
+use ariadne::Span;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+const TEMPLATE: &str = include_str!("./report.html");
+
+/// A context of a function's fork tree, with the bounds along the path to it
+#[derive(Debug, Clone, Serialize)]
+pub struct HtmlCtxNode {
+    pub path: String,
+    pub killed_at: Option<JsonSpan>,
+    /// The conditions the path depends on, e.g. `"x" ∈ [ 101, 2**256 - 1 ]`
+    pub conditions: Vec<String>,
+    pub bounds: Vec<JsonBound>,
+    pub returns: Vec<JsonBound>,
+    pub forks: Vec<HtmlCtxNode>,
+}
+
+/// A function's fork tree
+#[derive(Debug, Clone, Serialize)]
+pub struct HtmlFunction {
+    pub function: String,
+    pub span: JsonSpan,
+    /// The last line of the function
+    pub end_line: usize,
+    pub tree: HtmlCtxNode,
+}
+
+/// A single static HTML page to explore the forks of analyzed functions
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HtmlReport {
+    /// The contents of each source file
+    pub sources: BTreeMap<String, String>,
+    pub functions: Vec<HtmlFunction>,
+}
+
+impl HtmlReport {
+    pub fn new(sources: BTreeMap<String, String>) -> Self {
+        Self {
+            sources,
+            functions: vec![],
+        }
+    }
+
+    /// Adds the fork tree of an analyzed function, with bounds shown as in the analysis
+    pub fn add_function(
+        &mut self,
+        analysis: &CLIFunctionVarsBoundAnalysis,
+        analyzer: &(impl AnalyzerLike + Search),
+    ) {
+        let ctx = analysis.func_var_bound_analysis.ctx;
+        let ctx_loc = &analysis.func_var_bound_analysis.ctx_loc;
+        let end_line = self
+            .sources
+            .get(ctx_loc.source())
+            .and_then(|src| line_col(src, ctx_loc.end()))
+            .map(|(line, _)| line)
+            .unwrap_or_default();
+        let tree = self.node(ctx.into_ctx_tree(analyzer), ctx, analysis, analyzer);
+        self.functions.push(HtmlFunction {
+            function: ctx.associated_fn_name(analyzer),
+            span: JsonSpan::new(ctx_loc, &self.sources),
+            end_line,
+            tree,
+        });
+    }
+
+    fn node(
+        &self,
+        tree: CtxTree,
+        fn_ctx: ContextNode,
+        analysis: &CLIFunctionVarsBoundAnalysis,
+        analyzer: &(impl AnalyzerLike + Search),
+    ) -> HtmlCtxNode {
+        let ctx = tree.node;
+        let file_mapping = analysis.file_mapping;
+        let report_config = analysis.func_var_bound_analysis.report_config;
+        let deps = ctx
+            .ctx_deps(analyzer)
+            .values()
+            .map(|var| (var.display_name(analyzer), *var))
+            .collect::<BTreeMap<_, _>>();
+        HtmlCtxNode {
+            path: ctx.path(analyzer),
+            killed_at: ctx
+                .killed_loc(analyzer)
+                .map(|loc| JsonSpan::new(&LocStrSpan::new(file_mapping, loc), &self.sources)),
+            conditions: deps
+                .iter()
+                .filter_map(|(name, var)| {
+                    let range = var.range(analyzer)?;
+                    let min = range
+                        .evaled_range_min(analyzer)
+                        .to_range_string(false, analyzer)
+                        .s;
+                    let max = range
+                        .evaled_range_max(analyzer)
+                        .to_range_string(true, analyzer)
+                        .s;
+                    if min == max {
+                        Some(format!("{name} == {min}"))
+                    } else {
+                        Some(format!("\"{name}\" ∈ [ {min}, {max} ]"))
+                    }
+                })
+                .collect(),
+            bounds: analyzer
+                .bounds_for_ctx(file_mapping, fn_ctx, ctx, report_config)
+                .iter()
+                .flat_map(|analysis| JsonBound::from_analysis(analysis, analyzer, &self.sources))
+                .collect(),
+            returns: JsonBound::returns(ctx, file_mapping, analyzer, &self.sources),
+            forks: [tree.lhs, tree.rhs]
+                .into_iter()
+                .flatten()
+                .map(|fork| self.node(*fork, fn_ctx, analysis, analyzer))
+                .collect(),
+        }
+    }
+
+    /// The page, with the report embedded as JSON for its script to render
+    pub fn to_html(&self) -> String {
+        let data = serde_json::to_string(self)
+            .expect("Could not serialize HTML report")
+            // keep source text from closing the script tag early
+            .replace("</", "<\\/");
+        TEMPLATE.replace("/*REPORT_DATA*/null", &data)
+    }
+}
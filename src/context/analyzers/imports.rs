@@ -0,0 +1,88 @@
+use crate::analyzers::{LocStrSpan, ReportDisplay};
+use crate::import_resolution::MissingImport;
+use shared::analyzer::{AnalyzerLike, Search};
+
+use ariadne::{Cache, Color, Config, Fmt, Label, Report, ReportKind, Span};
+use std::collections::BTreeMap;
+
+/// An import that matched no file, which the analysis went on without
+#[derive(Debug, Clone)]
+pub struct UnresolvedImport {
+    pub span: LocStrSpan,
+    pub missing: MissingImport,
+}
+
+impl UnresolvedImport {
+    pub fn new(file_mapping: &BTreeMap<usize, String>, missing: &MissingImport) -> Self {
+        Self {
+            span: LocStrSpan::new(file_mapping, missing.loc),
+            missing: missing.clone(),
+        }
+    }
+
+    /// Where the import was looked for
+    pub fn searched(&self) -> String {
+        let searched = self
+            .missing
+            .searched
+            .iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        format!("Searched: {}", searched.join(", "))
+    }
+}
+
+impl ReportDisplay for UnresolvedImport {
+    fn report_kind(&self) -> ReportKind<'_> {
+        ReportKind::Custom("Unresolved import", Color::Red)
+    }
+
+    fn msg(&self, _analyzer: &(impl AnalyzerLike + Search)) -> String {
+        format!(
+            "Could not find a file for import: {}",
+            format!("\"{}\"", self.missing.path).fg(Color::Cyan)
+        )
+    }
+
+    fn labels(&self, _analyzer: &(impl AnalyzerLike + Search)) -> Vec<Label<LocStrSpan>> {
+        vec![Label::new(self.span.clone())
+            .with_message(self.searched().fg(Color::Red))
+            .with_color(Color::Red)]
+    }
+
+    fn reports(&self, analyzer: &(impl AnalyzerLike + Search)) -> Vec<Report<'_, LocStrSpan>> {
+        let mut report = Report::build(self.report_kind(), self.span.source(), self.span.start())
+            .with_message(self.msg(analyzer))
+            .with_config(
+                Config::default()
+                    .with_cross_gap(false)
+                    .with_underlines(true)
+                    .with_tab_width(4),
+            );
+
+        report.add_labels(self.labels(analyzer));
+        vec![report.finish()]
+    }
+
+    fn print_reports(
+        &self,
+        mut src: &mut impl Cache<String>,
+        analyzer: &(impl AnalyzerLike + Search),
+    ) {
+        let reports = self.reports(analyzer);
+        reports.into_iter().for_each(|report| {
+            report.print(&mut src).unwrap();
+        });
+    }
+
+    fn eprint_reports(
+        &self,
+        mut src: &mut impl Cache<String>,
+        analyzer: &(impl AnalyzerLike + Search),
+    ) {
+        let reports = self.reports(analyzer);
+        reports.into_iter().for_each(|report| {
+            report.eprint(&mut src).unwrap();
+        });
+    }
+}
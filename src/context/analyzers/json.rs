@@ -0,0 +1,218 @@
+use crate::analyzers::bounds::{BoundAnalysis, CLIFunctionVarsBoundAnalysis};
+use crate::analyzers::LocStrSpan;
+use shared::{
+    analyzer::{AnalyzerLike, Search},
+    context::ContextNode,
+    nodes::Concrete,
+    range::{elem::RangeElem, elem_ty::Elem, range_string::*, Range, RangeEval, SolcRange},
+};
+
+use ariadne::Span;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// A span of source code, as both byte offsets and a 1-based line and column
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JsonSpan {
+    pub file: String,
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl JsonSpan {
+    /// Locates the span in `sources`, a map of file names to their contents. Files that
+    /// aren't in the map have no line or column, and are reported at `0:0`
+    pub fn new(span: &LocStrSpan, sources: &BTreeMap<String, String>) -> Self {
+        let start = span.start();
+        let (line, column) = sources
+            .get(span.source())
+            .and_then(|src| line_col(src, start))
+            .unwrap_or_default();
+        Self {
+            file: span.source().clone(),
+            start,
+            end: span.end(),
+            line,
+            column,
+        }
+    }
+}
+
+/// The 1-based line and column of a byte offset, counting the column in characters
+pub(crate) fn line_col(src: &str, offset: usize) -> Option<(usize, usize)> {
+    let before = src.get(..offset)?;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    Some((
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    ))
+}
+
+/// A bound as an integer string, in decimal for numbers and booleans and in hex for `bytesN`
+/// and addresses. Bounds that don't evaluate to a value have none
+fn int_string(elem: &Elem<Concrete>) -> Option<String> {
+    match elem {
+        Elem::Concrete(c) => match &c.val {
+            Concrete::Uint(_, val) => Some(val.to_string()),
+            Concrete::Int(_, val) => Some(val.to_string()),
+            Concrete::Bool(b) => Some((*b as u8).to_string()),
+            Concrete::Bytes(size, b) => Some(format!("0x{}", hex::encode(&b.0[..*size as usize]))),
+            Concrete::Address(addr) => Some(format!("{addr:?}")),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The bounds of a variable at some point in the code
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JsonBound {
+    pub name: String,
+    pub span: JsonSpan,
+    /// Whether these are the bounds the variable was declared with
+    pub init: bool,
+    /// The bounds as integer strings, in decimal or in hex for `bytesN` and addresses, if they
+    /// evaluate to a value
+    pub min: Option<String>,
+    pub max: Option<String>,
+    pub exclusions: Vec<Option<String>>,
+    /// The bounds as shown in the text reports, i.e. `2**256 - 1`
+    pub min_pretty: String,
+    pub max_pretty: String,
+    pub exclusions_pretty: Vec<String>,
+    /// Whether no value can satisfy the bounds
+    pub unsat: bool,
+}
+
+impl JsonBound {
+    fn new(
+        name: &str,
+        span: &LocStrSpan,
+        init: bool,
+        range: &SolcRange,
+        analyzer: &(impl AnalyzerLike + Search),
+        sources: &BTreeMap<String, String>,
+    ) -> Self {
+        let min = range.evaled_range_min(analyzer);
+        let max = range.evaled_range_max(analyzer);
+        let exclusions = range.range_exclusions();
+        Self {
+            name: name.to_string(),
+            span: JsonSpan::new(span, sources),
+            init,
+            min: int_string(&min),
+            max: int_string(&max),
+            exclusions: exclusions
+                .iter()
+                .map(|excl| int_string(&excl.minimize(analyzer)))
+                .collect(),
+            min_pretty: min.to_range_string(false, analyzer).s,
+            max_pretty: max.to_range_string(true, analyzer).s,
+            exclusions_pretty: exclusions
+                .iter()
+                .map(|excl| excl.to_range_string(false, analyzer).s)
+                .collect(),
+            unsat: range.unsat(analyzer),
+        }
+    }
+
+    /// The declared bounds of the analyzed variable followed by each change to them
+    pub(crate) fn from_analysis(
+        analysis: &BoundAnalysis,
+        analyzer: &(impl AnalyzerLike + Search),
+        sources: &BTreeMap<String, String>,
+    ) -> Vec<Self> {
+        let name = &analysis.var_display_name;
+        let init = analysis
+            .var_def
+            .1
+            .as_ref()
+            .map(|range| Self::new(name, &analysis.var_def.0, true, range, analyzer, sources));
+        init.into_iter()
+            .chain(
+                analysis
+                    .bound_changes
+                    .iter()
+                    .map(|(span, range)| Self::new(name, span, false, range, analyzer, sources)),
+            )
+            .collect()
+    }
+
+    /// The bounds of the values the context returns
+    pub(crate) fn returns(
+        ctx: ContextNode,
+        file_mapping: &BTreeMap<usize, String>,
+        analyzer: &(impl AnalyzerLike + Search),
+        sources: &BTreeMap<String, String>,
+    ) -> Vec<Self> {
+        ctx.return_nodes(analyzer)
+            .into_iter()
+            .filter_map(|(loc, var)| {
+                let range = var.range(analyzer)?;
+                let span = LocStrSpan::new(file_mapping, loc);
+                let name = var.display_name(analyzer);
+                Some(Self::new(&name, &span, false, &range, analyzer, sources))
+            })
+            .collect()
+    }
+}
+
+/// The bounds along one path through a function
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JsonContext {
+    /// The path taken, i.e. [`Context::path`](shared::context::Context)
+    pub path: String,
+    /// Whether the path is guaranteed to revert
+    pub killed: bool,
+    pub killed_at: Option<JsonSpan>,
+    pub bounds: Vec<JsonBound>,
+    /// The bounds of the returned values, at their `return` statements
+    pub returns: Vec<JsonBound>,
+}
+
+/// The bounds analysis of a function
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JsonFunctionBounds {
+    pub function: String,
+    pub span: JsonSpan,
+    pub contexts: Vec<JsonContext>,
+}
+
+impl CLIFunctionVarsBoundAnalysis<'_> {
+    /// The analysis as serializable structs. `sources` maps file names to their contents, to
+    /// locate spans by line and column
+    pub fn to_json(
+        &self,
+        analyzer: &(impl AnalyzerLike + Search),
+        sources: &BTreeMap<String, String>,
+    ) -> JsonFunctionBounds {
+        let analysis = &self.func_var_bound_analysis;
+        JsonFunctionBounds {
+            function: analysis.ctx.associated_fn_name(analyzer),
+            span: JsonSpan::new(&analysis.ctx_loc, sources),
+            contexts: analysis
+                .vars_by_ctx
+                .iter()
+                .map(|(ctx, analyses)| {
+                    let killed_at = ctx.killed_loc(analyzer).map(|loc| {
+                        JsonSpan::new(&LocStrSpan::new(self.file_mapping, loc), sources)
+                    });
+                    JsonContext {
+                        path: ctx.path(analyzer),
+                        killed: killed_at.is_some(),
+                        killed_at,
+                        bounds: analyses
+                            .iter()
+                            .flat_map(|analysis| {
+                                JsonBound::from_analysis(analysis, analyzer, sources)
+                            })
+                            .collect(),
+                        returns: JsonBound::returns(*ctx, self.file_mapping, analyzer, sources),
+                    }
+                })
+                .collect(),
+        }
+    }
+}
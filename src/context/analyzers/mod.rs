@@ -1,6 +1,19 @@
+pub mod baseline;
 pub mod bounds;
+pub mod html;
+pub mod imports;
+pub mod json;
 use bounds::*;
 
+pub mod panics;
+use panics::*;
+
+pub mod sarif;
+pub mod syntax;
+
+pub mod unsupported;
+use unsupported::*;
+
 use crate::AnalyzerLike;
 use ariadne::{Cache, Label, Report, ReportKind, Span};
 use shared::analyzer::Search;
@@ -8,11 +21,21 @@ use solang_parser::pt::Loc;
 use std::collections::BTreeMap;
 
 pub trait ContextAnalyzer:
-    AnalyzerLike + Search + BoundAnalyzer + FunctionVarsBoundAnalyzer
+    AnalyzerLike
+    + Search
+    + BoundAnalyzer
+    + FunctionVarsBoundAnalyzer
+    + PanicAnalyzer
+    + UnsupportedAnalyzer
 {
 }
 impl<T> ContextAnalyzer for T where
-    T: AnalyzerLike + Search + BoundAnalyzer + FunctionVarsBoundAnalyzer
+    T: AnalyzerLike
+        + Search
+        + BoundAnalyzer
+        + FunctionVarsBoundAnalyzer
+        + PanicAnalyzer
+        + UnsupportedAnalyzer
 {
 }
 
@@ -30,24 +53,22 @@ impl Span for LocSpan {
     fn source(&self) -> &Self::SourceId {
         match self.0 {
             Loc::File(ref f, _, _) => f,
-            Loc::Implicit => &0,
-            _ => todo!("handle non file loc"),
+            // builtins and the like have no source, so they are shown at the start of the entry
+            _ => &0,
         }
     }
 
     fn start(&self) -> usize {
         match self.0 {
             Loc::File(_, start, _) => start,
-            Loc::Implicit => 0,
-            _ => todo!("handle non file loc"),
+            _ => 0,
         }
     }
 
     fn end(&self) -> usize {
         match self.0 {
             Loc::File(_, _, end) => end,
-            Loc::Implicit => 0,
-            _ => todo!("handle non file loc"),
+            _ => 0,
         }
     }
 }
@@ -62,11 +83,10 @@ impl Default for LocStrSpan {
 }
 
 impl LocStrSpan {
-    fn new(file_mapping: &BTreeMap<usize, String>, loc: Loc) -> Self {
+    pub fn new(file_mapping: &BTreeMap<usize, String>, loc: Loc) -> Self {
         let source = match loc {
             Loc::File(ref f, _, _) => f,
-            Loc::Implicit => &0,
-            _ => todo!("handle non file loc"),
+            _ => &0,
         };
         LocStrSpan(
             file_mapping
@@ -87,16 +107,14 @@ impl Span for LocStrSpan {
     fn start(&self) -> usize {
         match self.1 {
             Loc::File(_, start, _) => start,
-            Loc::Implicit => 0,
-            _ => todo!("handle non file loc"),
+            _ => 0,
         }
     }
 
     fn end(&self) -> usize {
         match self.1 {
             Loc::File(_, _, end) => end,
-            Loc::Implicit => 0,
-            _ => todo!("handle non file loc"),
+            _ => 0,
         }
     }
 }
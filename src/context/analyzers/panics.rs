@@ -0,0 +1,120 @@
+use crate::analyzers::{LocStrSpan, ReportDisplay};
+use shared::{
+    analyzer::{AnalyzerLike, Search},
+    context::*,
+};
+
+use ariadne::{Cache, Color, Config, Fmt, Label, Report, ReportKind, Span};
+use std::collections::BTreeMap;
+
+/// The reachable `Panic(uint256)` sites of a function
+#[derive(Debug, Clone)]
+pub struct PanicAnalysis {
+    pub ctx_loc: LocStrSpan,
+    pub ctx: ContextNode,
+    /// Each reachable panic site, the panic code, the name of the array it is on, and the paths
+    /// that reach it
+    pub panics: BTreeMap<LocStrSpan, (PanicCode, String, Vec<String>)>,
+}
+
+impl ReportDisplay for PanicAnalysis {
+    fn report_kind(&self) -> ReportKind<'_> {
+        ReportKind::Custom("Panic", Color::Red)
+    }
+
+    fn msg(&self, analyzer: &(impl AnalyzerLike + Search)) -> String {
+        let func = format!("function {}", self.ctx.associated_fn_name(analyzer));
+        format!("Reachable panics in function: {}", func.fg(Color::Cyan))
+    }
+
+    fn labels(&self, _analyzer: &(impl AnalyzerLike + Search)) -> Vec<Label<LocStrSpan>> {
+        self.panics
+            .iter()
+            .map(|(span, (code, _array, paths))| {
+                Label::new(span.clone())
+                    .with_message(
+                        format!("{code} is reachable, via: {}", paths.join(", ")).fg(Color::Red),
+                    )
+                    .with_color(Color::Red)
+            })
+            .collect()
+    }
+
+    fn reports(&self, analyzer: &(impl AnalyzerLike + Search)) -> Vec<Report<'_, LocStrSpan>> {
+        if self.panics.is_empty() {
+            return vec![];
+        }
+
+        let mut report = Report::build(
+            self.report_kind(),
+            self.ctx_loc.source(),
+            self.ctx_loc.start(),
+        )
+        .with_message(self.msg(analyzer))
+        .with_config(
+            Config::default()
+                .with_cross_gap(false)
+                .with_underlines(true)
+                .with_tab_width(4),
+        );
+
+        report.add_labels(self.labels(analyzer));
+        vec![report.finish()]
+    }
+
+    fn print_reports(
+        &self,
+        mut src: &mut impl Cache<String>,
+        analyzer: &(impl AnalyzerLike + Search),
+    ) {
+        let reports = self.reports(analyzer);
+        reports.into_iter().for_each(|report| {
+            report.print(&mut src).unwrap();
+        });
+    }
+
+    fn eprint_reports(
+        &self,
+        mut src: &mut impl Cache<String>,
+        analyzer: &(impl AnalyzerLike + Search),
+    ) {
+        let reports = self.reports(analyzer);
+        reports.into_iter().for_each(|report| {
+            report.eprint(&mut src).unwrap();
+        });
+    }
+}
+
+impl<T> PanicAnalyzer for T where T: Search + AnalyzerLike + Sized {}
+pub trait PanicAnalyzer: Search + AnalyzerLike + Sized {
+    /// Collects every context under `ctx` that reverted via a reachable `Panic(uint256)`,
+    /// i.e. an out-of-bounds index access whose out-of-bounds fork was satisfiable
+    fn reachable_panics(
+        &self,
+        file_mapping: &BTreeMap<usize, String>,
+        ctx: ContextNode,
+    ) -> PanicAnalysis {
+        let mut panics: BTreeMap<LocStrSpan, (PanicCode, String, Vec<String>)> =
+            BTreeMap::default();
+        let mut stack = vec![ctx];
+        while let Some(curr) = stack.pop() {
+            if let Some((loc, code, array)) = curr.panicked(self) {
+                let entry = panics.entry(LocStrSpan::new(file_mapping, loc)).or_insert((
+                    code,
+                    array.display_name(self),
+                    vec![],
+                ));
+                entry.2.push(curr.path(self));
+            }
+            let underlying = curr.underlying(self);
+            stack.extend(underlying.forks.iter());
+            stack.extend(underlying.children.iter());
+        }
+
+        PanicAnalysis {
+            ctx_loc: LocStrSpan::new(file_mapping, ctx.underlying(self).loc),
+            ctx,
+            panics,
+        }
+    }
+}
@@ -0,0 +1,533 @@
+use crate::analyzers::bounds::{BoundAnalysis, StrippedAnalysisItem};
+use crate::analyzers::imports::UnresolvedImport;
+use crate::analyzers::json::line_col;
+use crate::analyzers::panics::PanicAnalysis;
+use crate::analyzers::syntax::SyntaxError;
+use crate::analyzers::unsupported::UnsupportedAnalysis;
+use crate::analyzers::{LocSpan, LocStrSpan, ReportDisplay};
+use crate::context::queries::storage_write::{AccessStorageWriteReport, StorageRangeReport};
+use shared::analyzer::{AnalyzerLike, Search};
+
+use ariadne::{Color, ReportKind, Span};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Component, Path};
+
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+pub const SARIF_VERSION: &str = "2.1.0";
+/// The base id that artifact locations relative to the project root are resolved against
+pub const SRCROOT: &str = "%SRCROOT%";
+
+/// A SARIF 2.1.0 log, holding a single run of pyrometer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub version: String,
+    pub runs: Vec<SarifRun>,
+}
+
+impl SarifLog {
+    /// A log of `results`, declaring a rule for each distinct rule id among them. Files under
+    /// `root` are located relative to it, and any others by their `file://` URI
+    pub fn new(mut results: Vec<SarifResult>, root: &Path) -> Self {
+        results.iter_mut().for_each(|result| {
+            result
+                .locations
+                .iter_mut()
+                .chain(result.related_locations.iter_mut())
+                .for_each(|location| {
+                    let artifact = &mut location.physical_location.artifact_location;
+                    *artifact = SarifArtifactLocation::from_path(&artifact.uri, root);
+                })
+        });
+        let rules = results
+            .iter()
+            .map(|result| (result.rule_id.clone(), result.rule_name.clone()))
+            .collect::<BTreeMap<_, _>>()
+            .into_iter()
+            .map(|(id, name)| SarifRule {
+                id,
+                short_description: SarifMessage { text: name.clone() },
+                name,
+            })
+            .collect();
+        Self {
+            schema: SARIF_SCHEMA.to_string(),
+            version: SARIF_VERSION.to_string(),
+            runs: vec![SarifRun {
+                tool: SarifTool {
+                    driver: SarifDriver {
+                        name: "pyrometer".to_string(),
+                        version: env!("CARGO_PKG_VERSION").to_string(),
+                        rules,
+                    },
+                },
+                original_uri_base_ids: vec![(
+                    SRCROOT.to_string(),
+                    SarifArtifactLocation {
+                        // a base uri has to end in a slash
+                        uri: file_uri(root).trim_end_matches('/').to_string() + "/",
+                        uri_base_id: None,
+                    },
+                )]
+                .into_iter()
+                .collect(),
+                results,
+            }],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRun {
+    pub tool: SarifTool,
+    pub original_uri_base_ids: BTreeMap<String, SarifArtifactLocation>,
+    pub results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SarifTool {
+    pub driver: SarifDriver,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SarifDriver {
+    pub name: String,
+    pub version: String,
+    pub rules: Vec<SarifRule>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRule {
+    pub id: String,
+    pub name: String,
+    pub short_description: SarifMessage,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: String,
+    /// The display name of the rule, only used to declare it in the log
+    #[serde(skip)]
+    pub rule_name: String,
+    pub level: String,
+    pub message: SarifMessage,
+    pub locations: Vec<SarifLocation>,
+    pub related_locations: Vec<SarifLocation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SarifMessage {
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLocation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<usize>,
+    pub physical_location: SarifPhysicalLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<SarifMessage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifPhysicalLocation {
+    pub artifact_location: SarifArtifactLocation,
+    pub region: SarifRegion,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifArtifactLocation {
+    pub uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uri_base_id: Option<String>,
+}
+
+impl SarifArtifactLocation {
+    /// Locates a file relative to `root` if it is under it, by its `file://` URI if it is
+    /// elsewhere, and as is if it is already relative
+    pub fn from_path(path: &str, root: &Path) -> Self {
+        let path = Path::new(path);
+        match path.strip_prefix(root) {
+            Ok(relative) => Self {
+                uri: uri_path(relative),
+                uri_base_id: Some(SRCROOT.to_string()),
+            },
+            Err(_) if path.is_absolute() => Self {
+                uri: file_uri(path),
+                uri_base_id: None,
+            },
+            Err(_) => Self {
+                uri: uri_path(path),
+                uri_base_id: None,
+            },
+        }
+    }
+}
+
+/// The `file://` URI of an absolute path
+pub fn file_uri(path: &Path) -> String {
+    format!("file://{}", uri_path(path))
+}
+
+/// A path as the path of a URI, joined by `/` and percent-encoded
+fn uri_path(path: &Path) -> String {
+    let segments = path
+        .components()
+        .filter_map(|component| match component {
+            Component::Prefix(prefix) => Some(prefix.as_os_str().to_string_lossy().to_string()),
+            Component::Normal(segment) => Some(segment.to_string_lossy().to_string()),
+            Component::ParentDir => Some("..".to_string()),
+            Component::RootDir | Component::CurDir => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    let path = if path.has_root() {
+        format!("/{segments}")
+    } else {
+        segments
+    };
+    let mut encoded = String::with_capacity(path.len());
+    path.bytes().for_each(|b| match b {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => {
+            encoded.push(b as char)
+        }
+        b => encoded.push_str(&format!("%{b:02X}")),
+    });
+    encoded
+}
+
+/// A region of a file. Lines and columns are 1-based, and left out when the file's contents
+/// aren't known
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRegion {
+    pub byte_offset: usize,
+    pub byte_length: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_column: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<usize>,
+}
+
+impl SarifPhysicalLocation {
+    /// Locates the span in `sources`, a map of file names to their contents
+    pub fn new(span: &LocStrSpan, sources: &BTreeMap<String, String>) -> Self {
+        let src = sources.get(span.source());
+        let start = src.and_then(|src| line_col(src, span.start()));
+        let end = src.and_then(|src| line_col(src, span.end()));
+        Self {
+            artifact_location: SarifArtifactLocation {
+                uri: span.source().clone(),
+                uri_base_id: None,
+            },
+            region: SarifRegion {
+                byte_offset: span.start(),
+                byte_length: span.end().saturating_sub(span.start()),
+                start_line: start.map(|(line, _)| line),
+                start_column: start.map(|(_, column)| column),
+                end_line: end.map(|(line, _)| line),
+                end_column: end.map(|(_, column)| column),
+            },
+        }
+    }
+}
+
+/// The rule id of a kind of report, e.g. `storage-write-query`
+pub fn rule_id(kind: &ReportKind) -> String {
+    kind.to_string().to_lowercase().replace(' ', "-")
+}
+
+/// The SARIF level of a kind of report. Custom kinds are ranked by their colour, red being an
+/// error and yellow a warning
+pub fn level(kind: &ReportKind) -> &'static str {
+    match kind {
+        ReportKind::Error | ReportKind::Custom(_, Color::Red) => "error",
+        ReportKind::Warning | ReportKind::Custom(_, Color::Yellow) => "warning",
+        _ => "note",
+    }
+}
+
+/// Removes the ANSI colour codes that reports embed in their messages
+pub fn strip_ansi(s: &str) -> String {
+    let mut stripped = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            // skip the control sequence up to and including its final byte
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) && c != '[' {
+                    break;
+                }
+            }
+        } else {
+            stripped.push(c);
+        }
+    }
+    stripped
+}
+
+/// Reports that can be written as SARIF results, e.g. for code-scanning dashboards
+pub trait SarifDisplay: ReportDisplay {
+    /// Where the report is anchored, if anywhere
+    fn sarif_location(&self) -> Option<LocStrSpan>;
+    /// The spans and uncoloured messages of the report's labels
+    fn sarif_related(&self, analyzer: &(impl AnalyzerLike + Search)) -> Vec<(LocStrSpan, String)>;
+
+    /// The report as a result, with a related location for each label
+    fn sarif_result(
+        &self,
+        analyzer: &(impl AnalyzerLike + Search),
+        sources: &BTreeMap<String, String>,
+    ) -> SarifResult {
+        let kind = self.report_kind();
+        SarifResult {
+            rule_id: rule_id(&kind),
+            rule_name: kind.to_string(),
+            level: level(&kind).to_string(),
+            message: SarifMessage {
+                text: strip_ansi(&self.msg(analyzer)),
+            },
+            locations: self
+                .sarif_location()
+                .map(|span| SarifLocation {
+                    id: None,
+                    physical_location: SarifPhysicalLocation::new(&span, sources),
+                    message: None,
+                })
+                .into_iter()
+                .collect(),
+            related_locations: self
+                .sarif_related(analyzer)
+                .into_iter()
+                .enumerate()
+                .map(|(i, (span, msg))| SarifLocation {
+                    id: Some(i),
+                    physical_location: SarifPhysicalLocation::new(&span, sources),
+                    message: Some(SarifMessage {
+                        text: strip_ansi(&msg),
+                    }),
+                })
+                .collect(),
+        }
+    }
+
+    /// A result for each report that [`ReportDisplay::reports`] would print
+    fn sarif_results(
+        &self,
+        analyzer: &(impl AnalyzerLike + Search),
+        sources: &BTreeMap<String, String>,
+    ) -> Vec<SarifResult> {
+        vec![self.sarif_result(analyzer, sources)]
+    }
+}
+
+impl SarifDisplay for PanicAnalysis {
+    fn sarif_location(&self) -> Option<LocStrSpan> {
+        Some(self.ctx_loc.clone())
+    }
+
+    fn sarif_related(&self, _analyzer: &(impl AnalyzerLike + Search)) -> Vec<(LocStrSpan, String)> {
+        self.panics
+            .iter()
+            .map(|(span, (code, _array, paths))| {
+                (
+                    span.clone(),
+                    format!("{code} is reachable, via: {}", paths.join(", ")),
+                )
+            })
+            .collect()
+    }
+
+    fn sarif_results(
+        &self,
+        analyzer: &(impl AnalyzerLike + Search),
+        sources: &BTreeMap<String, String>,
+    ) -> Vec<SarifResult> {
+        if self.panics.is_empty() {
+            return vec![];
+        }
+        vec![self.sarif_result(analyzer, sources)]
+    }
+}
+
+impl SarifDisplay for UnsupportedAnalysis {
+    fn sarif_location(&self) -> Option<LocStrSpan> {
+        Some(self.ctx_loc.clone())
+    }
+
+    fn sarif_related(&self, _analyzer: &(impl AnalyzerLike + Search)) -> Vec<(LocStrSpan, String)> {
+        self.constructs
+            .iter()
+            .map(|(span, (reason, paths))| {
+                (span.clone(), format!("{reason}, via: {}", paths.join(", ")))
+            })
+            .collect()
+    }
+
+    fn sarif_results(
+        &self,
+        analyzer: &(impl AnalyzerLike + Search),
+        sources: &BTreeMap<String, String>,
+    ) -> Vec<SarifResult> {
+        if self.constructs.is_empty() {
+            return vec![];
+        }
+        vec![self.sarif_result(analyzer, sources)]
+    }
+}
+
+impl SarifDisplay for UnresolvedImport {
+    fn sarif_location(&self) -> Option<LocStrSpan> {
+        Some(self.span.clone())
+    }
+
+    fn sarif_related(&self, _analyzer: &(impl AnalyzerLike + Search)) -> Vec<(LocStrSpan, String)> {
+        vec![(self.span.clone(), self.searched())]
+    }
+}
+
+impl SarifDisplay for SyntaxError {
+    fn sarif_location(&self) -> Option<LocStrSpan> {
+        Some(self.span.clone())
+    }
+
+    fn sarif_related(&self, _analyzer: &(impl AnalyzerLike + Search)) -> Vec<(LocStrSpan, String)> {
+        vec![]
+    }
+}
+
+impl SarifDisplay for BoundAnalysis {
+    fn sarif_location(&self) -> Option<LocStrSpan> {
+        Some(self.var_def.0.clone())
+    }
+
+    fn sarif_related(&self, analyzer: &(impl AnalyzerLike + Search)) -> Vec<(LocStrSpan, String)> {
+        let init = if self.report_config.show_initial_bounds {
+            self.init_item(analyzer)
+        } else {
+            None
+        };
+        let mut related = init
+            .map(|item| {
+                (
+                    item.loc.clone(),
+                    StrippedAnalysisItem::from(item).to_string(),
+                )
+            })
+            .into_iter()
+            .collect::<Vec<_>>();
+        related.extend(self.bound_changes.iter().map(|(span, range)| {
+            let (parts, unsat) = self.range_parts(analyzer, range);
+            let item = StrippedAnalysisItem {
+                init: false,
+                order: 0,
+                name: self.var_display_name.clone(),
+                loc: LocSpan(span.1),
+                ctx: self.ctx,
+                ctx_conditionals: vec![],
+                parts,
+                unsat,
+            };
+            (span.clone(), item.to_string())
+        }));
+        if let Some(killed_span) = &self.ctx_killed {
+            related.push((
+                killed_span.clone(),
+                "Execution guaranteed to revert here!".to_string(),
+            ));
+        }
+        related
+    }
+
+    fn sarif_results(
+        &self,
+        analyzer: &(impl AnalyzerLike + Search),
+        sources: &BTreeMap<String, String>,
+    ) -> Vec<SarifResult> {
+        let mut results = vec![self.sarif_result(analyzer, sources)];
+        if self.report_config.show_subctxs {
+            results.extend(
+                self.sub_ctxs
+                    .iter()
+                    .flat_map(|analysis| analysis.sarif_results(analyzer, sources)),
+            );
+        }
+        results
+    }
+}
+
+impl SarifDisplay for StorageRangeReport {
+    fn sarif_location(&self) -> Option<LocStrSpan> {
+        Some(self.analysis.var_def.0.clone())
+    }
+
+    fn sarif_related(&self, analyzer: &(impl AnalyzerLike + Search)) -> Vec<(LocStrSpan, String)> {
+        self.analysis.sarif_related(analyzer)
+    }
+
+    fn sarif_results(
+        &self,
+        analyzer: &(impl AnalyzerLike + Search),
+        sources: &BTreeMap<String, String>,
+    ) -> Vec<SarifResult> {
+        let mut results = vec![self.sarif_result(analyzer, sources)];
+        if self.analysis.report_config.show_subctxs {
+            results.extend(
+                self.analysis
+                    .sub_ctxs
+                    .iter()
+                    .flat_map(|analysis| analysis.sarif_results(analyzer, sources)),
+            );
+        }
+        results
+    }
+}
+
+impl SarifDisplay for AccessStorageWriteReport {
+    fn sarif_location(&self) -> Option<LocStrSpan> {
+        self.writes.first().map(|write| write.loc.clone())
+    }
+
+    fn sarif_related(&self, _analyzer: &(impl AnalyzerLike + Search)) -> Vec<(LocStrSpan, String)> {
+        self.writes
+            .iter()
+            .map(|write| {
+                (
+                    write.func_loc.clone(),
+                    format!("written in function \"{}\"", write.func_name),
+                )
+            })
+            .collect()
+    }
+
+    /// A result for each write, anchored where it happens
+    fn sarif_results(
+        &self,
+        analyzer: &(impl AnalyzerLike + Search),
+        sources: &BTreeMap<String, String>,
+    ) -> Vec<SarifResult> {
+        if self.writes.is_empty() {
+            return vec![self.sarif_result(analyzer, sources)];
+        }
+        self.msgs
+            .iter()
+            .zip(self.writes.iter())
+            .map(|(msg, write)| {
+                AccessStorageWriteReport::new(vec![msg.clone()], vec![write.clone()])
+                    .sarif_result(analyzer, sources)
+            })
+            .collect()
+    }
+}
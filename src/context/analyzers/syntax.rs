@@ -0,0 +1,74 @@
+use crate::analyzers::{LocStrSpan, ReportDisplay};
+use shared::analyzer::{AnalyzerLike, Search};
+
+use ariadne::{Cache, Color, Config, Label, Report, ReportKind, Span};
+use solang_parser::diagnostics::Diagnostic;
+use std::collections::BTreeMap;
+
+/// A file that failed to parse, which the analysis went on without
+#[derive(Debug, Clone)]
+pub struct SyntaxError {
+    pub span: LocStrSpan,
+    pub diagnostic: Diagnostic,
+}
+
+impl SyntaxError {
+    pub fn new(file_mapping: &BTreeMap<usize, String>, diagnostic: &Diagnostic) -> Self {
+        Self {
+            span: LocStrSpan::new(file_mapping, diagnostic.loc),
+            diagnostic: diagnostic.clone(),
+        }
+    }
+}
+
+impl ReportDisplay for SyntaxError {
+    fn report_kind(&self) -> ReportKind<'_> {
+        ReportKind::Custom("Syntax error", Color::Red)
+    }
+
+    fn msg(&self, _analyzer: &(impl AnalyzerLike + Search)) -> String {
+        format!("Could not parse: {}", self.diagnostic.message)
+    }
+
+    fn labels(&self, _analyzer: &(impl AnalyzerLike + Search)) -> Vec<Label<LocStrSpan>> {
+        vec![Label::new(self.span.clone())
+            .with_message(self.diagnostic.message.clone())
+            .with_color(Color::Red)]
+    }
+
+    fn reports(&self, analyzer: &(impl AnalyzerLike + Search)) -> Vec<Report<'_, LocStrSpan>> {
+        let mut report = Report::build(self.report_kind(), self.span.source(), self.span.start())
+            .with_message(self.msg(analyzer))
+            .with_config(
+                Config::default()
+                    .with_cross_gap(false)
+                    .with_underlines(true)
+                    .with_tab_width(4),
+            );
+
+        report.add_labels(self.labels(analyzer));
+        vec![report.finish()]
+    }
+
+    fn print_reports(
+        &self,
+        mut src: &mut impl Cache<String>,
+        analyzer: &(impl AnalyzerLike + Search),
+    ) {
+        let reports = self.reports(analyzer);
+        reports.into_iter().for_each(|report| {
+            report.print(&mut src).unwrap();
+        });
+    }
+
+    fn eprint_reports(
+        &self,
+        mut src: &mut impl Cache<String>,
+        analyzer: &(impl AnalyzerLike + Search),
+    ) {
+        let reports = self.reports(analyzer);
+        reports.into_iter().for_each(|report| {
+            report.eprint(&mut src).unwrap();
+        });
+    }
+}
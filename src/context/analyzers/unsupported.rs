@@ -0,0 +1,124 @@
+use crate::analyzers::{LocStrSpan, ReportDisplay};
+use shared::{
+    analyzer::{AnalyzerError, AnalyzerLike, Search},
+    context::*,
+};
+
+use ariadne::{Cache, Color, Config, Fmt, Label, Report, ReportKind, Span};
+use std::collections::BTreeMap;
+
+/// The constructs the analyzer couldn't handle in a function, past which its variables are
+/// unconstrained
+#[derive(Debug, Clone)]
+pub struct UnsupportedAnalysis {
+    pub ctx_loc: LocStrSpan,
+    pub ctx: ContextNode,
+    /// Each unsupported construct, why it isn't supported, and the paths that reach it
+    pub constructs: BTreeMap<LocStrSpan, (String, Vec<String>)>,
+    /// Whether the analyzer recovered from an internal error in the function, rather than only
+    /// skipping constructs it doesn't support
+    pub internal_error: bool,
+}
+
+impl ReportDisplay for UnsupportedAnalysis {
+    fn report_kind(&self) -> ReportKind<'_> {
+        ReportKind::Custom("Unsupported", Color::Yellow)
+    }
+
+    fn msg(&self, analyzer: &(impl AnalyzerLike + Search)) -> String {
+        let func = format!("function {}", self.ctx.associated_fn_name(analyzer));
+        format!(
+            "Analysis was incomplete in function: {}",
+            func.fg(Color::Cyan)
+        )
+    }
+
+    fn labels(&self, _analyzer: &(impl AnalyzerLike + Search)) -> Vec<Label<LocStrSpan>> {
+        self.constructs
+            .iter()
+            .map(|(span, (reason, paths))| {
+                Label::new(span.clone())
+                    .with_message(format!("{reason}, via: {}", paths.join(", ")).fg(Color::Yellow))
+                    .with_color(Color::Yellow)
+            })
+            .collect()
+    }
+
+    fn reports(&self, analyzer: &(impl AnalyzerLike + Search)) -> Vec<Report<'_, LocStrSpan>> {
+        if self.constructs.is_empty() {
+            return vec![];
+        }
+
+        let mut report = Report::build(
+            self.report_kind(),
+            self.ctx_loc.source(),
+            self.ctx_loc.start(),
+        )
+        .with_message(self.msg(analyzer))
+        .with_config(
+            Config::default()
+                .with_cross_gap(false)
+                .with_underlines(true)
+                .with_tab_width(4),
+        );
+
+        report.add_labels(self.labels(analyzer));
+        vec![report.finish()]
+    }
+
+    fn print_reports(
+        &self,
+        mut src: &mut impl Cache<String>,
+        analyzer: &(impl AnalyzerLike + Search),
+    ) {
+        let reports = self.reports(analyzer);
+        reports.into_iter().for_each(|report| {
+            report.print(&mut src).unwrap();
+        });
+    }
+
+    fn eprint_reports(
+        &self,
+        mut src: &mut impl Cache<String>,
+        analyzer: &(impl AnalyzerLike + Search),
+    ) {
+        let reports = self.reports(analyzer);
+        reports.into_iter().for_each(|report| {
+            report.eprint(&mut src).unwrap();
+        });
+    }
+}
+
+impl<T> UnsupportedAnalyzer for T where T: Search + AnalyzerLike + Sized {}
+pub trait UnsupportedAnalyzer: Search + AnalyzerLike + Sized {
+    /// Collects every construct under `ctx` that the analyzer marked as unsupported, along with
+    /// any internal error it recovered from while analyzing the function
+    fn unsupported_constructs(
+        &self,
+        file_mapping: &BTreeMap<usize, String>,
+        ctx: ContextNode,
+    ) -> UnsupportedAnalysis {
+        let mut constructs: BTreeMap<LocStrSpan, (String, Vec<String>)> = BTreeMap::default();
+        let mut internal_error = false;
+        let mut stack = vec![ctx];
+        while let Some(curr) = stack.pop() {
+            curr.unsupported(self).into_iter().for_each(|err| {
+                internal_error |= matches!(err, AnalyzerError::Internal(..));
+                let entry = constructs
+                    .entry(LocStrSpan::new(file_mapping, err.loc()))
+                    .or_insert((err.to_string(), vec![]));
+                entry.1.push(curr.path(self));
+            });
+            let underlying = curr.underlying(self);
+            stack.extend(underlying.forks.iter());
+            stack.extend(underlying.children.iter());
+        }
+
+        UnsupportedAnalysis {
+            ctx_loc: LocStrSpan::new(file_mapping, ctx.underlying(self).loc),
+            ctx,
+            constructs,
+            internal_error,
+        }
+    }
+}
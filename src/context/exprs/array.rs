@@ -2,26 +2,72 @@ use crate::{
     context::exprs::{member_access::MemberAccess, require::Require},
     Builtin, ContextBuilder, Edge, ExprRet, Node, VarType,
 };
-use shared::{analyzer::AnalyzerLike, context::*, range::elem::RangeOp};
+use shared::{
+    analyzer::AnalyzerLike,
+    context::*,
+    range::{
+        elem::{RangeElem, RangeOp},
+        elem_ty::{Dynamic, Elem},
+        Range,
+    },
+};
 
-use solang_parser::pt::{Expression, Loc};
+use solang_parser::pt::{CodeLocation, Expression, Loc};
+use std::cmp::Ordering;
 
 impl<T> Array for T where T: AnalyzerLike<Expr = Expression> + Sized {}
 pub trait Array: AnalyzerLike<Expr = Expression> + Sized {
     /// Gets the array type
     fn array_ty(&mut self, ty_expr: &Expression, ctx: ContextNode) -> ExprRet {
-        let (ctx, inner_ty) = self.parse_ctx_expr(ty_expr, ctx).expect_single();
+        let (ctx, inner_ty) = match self.parse_ctx_expr(ty_expr, ctx) {
+            e @ ExprRet::Unsupported(..) => return e,
+            e => e.expect_single(),
+        };
         if let Some(var_type) = VarType::try_from_idx(self, inner_ty) {
             let dyn_b = Builtin::Array(var_type);
             if let Some(idx) = self.builtins().get(&dyn_b) {
                 ExprRet::Single((ctx, *idx))
             } else {
-                let idx = self.add_node(Node::Builtin(dyn_b.clone()));
+                let idx = self.add_node(Node::Builtin(Box::new(dyn_b.clone())));
                 self.builtins_mut().insert(dyn_b, idx);
                 ExprRet::Single((ctx, idx))
             }
         } else {
-            panic!("Expected to be able to convert to a var type from an index to determine array type. This is a bug. Please report it at github.com/nascentxyz/pyrometer.")
+            self.unsupported(ctx, ty_expr.loc(), "array of a non-type")
+        }
+    }
+
+    /// Gets the fixed size array type, i.e. `uint256[3]`
+    fn sized_array_ty(
+        &mut self,
+        ty_expr: &Expression,
+        size_expr: &Expression,
+        ctx: ContextNode,
+    ) -> ExprRet {
+        let (ctx, inner_ty) = match self.parse_ctx_expr(ty_expr, ctx) {
+            ExprRet::Single(inner) | ExprRet::SingleLiteral(inner) => inner,
+            e @ ExprRet::Unsupported(..) | e @ ExprRet::CtxKilled => return e,
+            _ => return self.unsupported(ctx, ty_expr.loc(), "array of more than one type"),
+        };
+        let size = match self.parse_ctx_expr(size_expr, ctx) {
+            e @ ExprRet::Unsupported(..) | e @ ExprRet::CtxKilled => return e,
+            ExprRet::Single((_, size)) | ExprRet::SingleLiteral((_, size)) => {
+                match self.node(size) {
+                    Node::ContextVar(_) => ContextVarNode::from(size)
+                        .maybe_concrete(self)
+                        .and_then(|c| c.uint_val()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        match (VarType::try_from_idx(self, inner_ty), size) {
+            (Some(var_type), Some(size)) => {
+                let idx = self.builtin_or_add(Builtin::SizedArray(size, Box::new(var_type)));
+                ExprRet::Single((ctx, idx))
+            }
+            (_, None) => self.unsupported(ctx, size_expr.loc(), "non-constant array size"),
+            (None, _) => self.unsupported(ctx, ty_expr.loc(), "array of a non-type"),
         }
     }
 
@@ -34,10 +80,142 @@ pub trait Array: AnalyzerLike<Expr = Expression> + Sized {
         ctx: ContextNode,
     ) -> ExprRet {
         let inner_tys = self.parse_ctx_expr(ty_expr, ctx);
+        if let ExprRet::Single((_, inner_ty)) = inner_tys {
+            if !matches!(self.node(inner_ty), Node::ContextVar(_)) {
+                // a fixed size array type, i.e. `uint256[3]`
+                return self.sized_array_ty(ty_expr, index_expr, ctx);
+            }
+        }
         let index_tys = self.parse_ctx_expr(index_expr, ctx);
+        let (inner_tys, index_tys) = self.bounds_check_fork(loc, inner_tys, index_tys);
         self.index_into_array_inner(loc, inner_tys, index_tys)
     }
 
+    /// Forks the context on an index access into a non-mapping. One side of the fork requires
+    /// `index >= length` and reverts with `Panic(0x32)` if it is reachable, the other is the in-bounds
+    /// side that the index access continues in. Returns the paths retargeted to the in-bounds fork.
+    #[tracing::instrument(level = "trace", skip_all)]
+    fn bounds_check_fork(
+        &mut self,
+        loc: Loc,
+        inner_paths: ExprRet,
+        index_paths: ExprRet,
+    ) -> (ExprRet, ExprRet) {
+        match (&inner_paths, &index_paths) {
+            (ExprRet::Single((ctx, parent)), ExprRet::Single((_, index)))
+            | (ExprRet::Single((ctx, parent)), ExprRet::SingleLiteral((_, index))) => {
+                let ctx = *ctx;
+                let parent = ContextVarNode::from(*parent).first_version(self);
+                if parent.is_mapping(self) {
+                    return (inner_paths, index_paths);
+                }
+
+                // the length is made before forking so both forks constrain their own version of
+                // it, rather than one fork finding the other's through their parent
+                let len_var = self.tmp_length(parent, ctx, loc).latest_version(self);
+                match self.index_in_bounds(ctx, ContextVarNode::from(*index), len_var) {
+                    Some(true) => {
+                        // only the in-bounds side is reachable, so there is nothing to fork
+                        self.handle_require_inner(
+                            loc,
+                            &ExprRet::Single((ctx, len_var.into())),
+                            &index_paths,
+                            RangeOp::Gt,
+                            RangeOp::Lt,
+                            (RangeOp::Lte, RangeOp::Gte),
+                        );
+                        if ctx.is_killed(self) {
+                            return (ExprRet::CtxKilled, index_paths);
+                        }
+                        return (
+                            ExprRet::Single((ctx, parent.latest_version(self).into())),
+                            index_paths,
+                        );
+                    }
+                    Some(false) => {
+                        tracing::trace!("out-of-bounds index access is certain");
+                        ctx.panic(self, loc, PanicCode::IndexOutOfBounds, parent);
+                        return (ExprRet::CtxKilled, index_paths);
+                    }
+                    None => {}
+                }
+                let (oob_subctx, in_bounds_subctx) = self.fork_ctx(loc, ctx);
+
+                let oob_index_paths = match index_paths {
+                    ExprRet::SingleLiteral((_, index)) => {
+                        ExprRet::SingleLiteral((oob_subctx, index))
+                    }
+                    _ => ExprRet::Single((oob_subctx, *index)),
+                };
+                self.handle_require_inner(
+                    loc,
+                    &ExprRet::Single((oob_subctx, len_var.into())),
+                    &oob_index_paths,
+                    RangeOp::Lte,
+                    RangeOp::Gte,
+                    (RangeOp::Gt, RangeOp::Lt),
+                );
+                if !oob_subctx.is_killed(self) {
+                    tracing::trace!("out-of-bounds index access is reachable");
+                    oob_subctx.panic(self, loc, PanicCode::IndexOutOfBounds, parent);
+                }
+
+                let in_bounds_index_paths = match index_paths {
+                    ExprRet::SingleLiteral((_, index)) => {
+                        ExprRet::SingleLiteral((in_bounds_subctx, index))
+                    }
+                    _ => ExprRet::Single((in_bounds_subctx, *index)),
+                };
+                self.handle_require_inner(
+                    loc,
+                    &ExprRet::Single((in_bounds_subctx, len_var.into())),
+                    &in_bounds_index_paths,
+                    RangeOp::Gt,
+                    RangeOp::Lt,
+                    (RangeOp::Lte, RangeOp::Gte),
+                );
+                if in_bounds_subctx.is_killed(self) {
+                    return (ExprRet::CtxKilled, in_bounds_index_paths);
+                }
+
+                (
+                    ExprRet::Single((in_bounds_subctx, parent.latest_version(self).into())),
+                    in_bounds_index_paths,
+                )
+            }
+            _ => (inner_paths, index_paths),
+        }
+    }
+
+    /// Whether `index < len` is known from the relational facts of the context or the bounds of
+    /// the two, if it is
+    fn index_in_bounds(
+        &self,
+        ctx: ContextNode,
+        index: ContextVarNode,
+        len: ContextVarNode,
+    ) -> Option<bool> {
+        let index = index.latest_version(self);
+        if let (Some(index), Some(len)) = (zone_term(index, self), zone_term(len, self)) {
+            if let Some(known) = ctx.zone(self).implies(RangeOp::Lt, index, len) {
+                return Some(known);
+            }
+        }
+        let index_range = index.range(self)?;
+        let len_range = len.range(self)?;
+        let below = index_range
+            .evaled_range_max(self)
+            .range_ord(&len_range.evaled_range_min(self));
+        let above = index_range
+            .evaled_range_min(self)
+            .range_ord(&len_range.evaled_range_max(self));
+        match (below, above) {
+            (Some(Ordering::Less), _) => Some(true),
+            (_, Some(Ordering::Greater | Ordering::Equal)) => Some(false),
+            _ => None,
+        }
+    }
+
     #[tracing::instrument(level = "trace", skip_all)]
     fn index_into_array_inner(
         &mut self,
@@ -46,24 +224,13 @@ pub trait Array: AnalyzerLike<Expr = Expression> + Sized {
         index_paths: ExprRet,
     ) -> ExprRet {
         match (inner_paths, index_paths) {
+            (e @ ExprRet::Unsupported(..), _) | (_, e @ ExprRet::Unsupported(..)) => e,
             (_, ExprRet::CtxKilled) => ExprRet::CtxKilled,
             (ExprRet::CtxKilled, _) => ExprRet::CtxKilled,
-            (ExprRet::Single((ctx, parent)), ExprRet::Single((_rhs_ctx, index))) | (ExprRet::Single((ctx, parent)), ExprRet::SingleLiteral((_rhs_ctx, index))) => {
-
+            (ExprRet::Single((ctx, parent)), ExprRet::Single((_rhs_ctx, index)))
+            | (ExprRet::Single((ctx, parent)), ExprRet::SingleLiteral((_rhs_ctx, index))) => {
                 let index = ContextVarNode::from(index).latest_version(self);
                 let parent = ContextVarNode::from(parent).first_version(self);
-                let idx = self.advance_var_in_ctx(index, loc, ctx);
-                if !parent.is_mapping(self) {
-                    let len_var = self.tmp_length(parent, ctx, loc).latest_version(self);
-                    self.handle_require_inner(
-                        loc,
-                        &ExprRet::Single((ctx, len_var.latest_version(self).into())),
-                        &ExprRet::Single((ctx, idx.latest_version(self).into())),
-                        RangeOp::Gt,
-                        RangeOp::Lt,
-                        (RangeOp::Lte, RangeOp::Gte),
-                    );
-                }
 
                 let name = format!("{}[{}]", parent.name(self), index.name(self));
                 tracing::trace!("indexing: {}", name);
@@ -96,7 +263,72 @@ pub trait Array: AnalyzerLike<Expr = Expression> + Sized {
                     ExprRet::Single((ctx, idx_node))
                 }
             }
-            e => panic!("Expected single expr evaluation of index expression, but was: {e:?}. This is a bug. Please report it at github.com/nascentxyz/pyrometer."),
+            (ExprRet::SingleLiteral(inner), index_paths) => {
+                self.index_into_array_inner(loc, ExprRet::Single(inner), index_paths)
+            }
+            (ExprRet::Fork(w1, w2), index_paths) => ExprRet::Fork(
+                Box::new(self.index_into_array_inner(loc, *w1, index_paths.clone())),
+                Box::new(self.index_into_array_inner(loc, *w2, index_paths)),
+            ),
+            (inner_paths, ExprRet::Fork(w1, w2)) => ExprRet::Fork(
+                Box::new(self.index_into_array_inner(loc, inner_paths.clone(), *w1)),
+                Box::new(self.index_into_array_inner(loc, inner_paths, *w2)),
+            ),
+            (ExprRet::Multi(inner), index_paths) => ExprRet::Multi(
+                inner
+                    .into_iter()
+                    .map(|i| self.index_into_array_inner(loc, i, index_paths.clone()))
+                    .collect(),
+            ),
+            (inner_paths, ExprRet::Multi(inner)) => ExprRet::Multi(
+                inner
+                    .into_iter()
+                    .map(|i| self.index_into_array_inner(loc, inner_paths.clone(), i))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// After a write to `arr[key]`, updates every other index access into the same array
+    /// whose key may be equal to `key`. If the keys must be equal, the aliased access takes
+    /// on the written value. If they only may be equal, its range is widened to include the
+    /// written value.
+    #[tracing::instrument(level = "trace", skip_all)]
+    fn update_index_aliases(
+        &mut self,
+        loc: Loc,
+        written: ContextVarNode,
+        rhs: ContextVarNode,
+        ctx: ContextNode,
+    ) {
+        if let Some((arr, key)) = written.index_access_parts(self) {
+            let written_name = written.name(self);
+
+            // compare against the keys as they were when each access was made, not their
+            // current values
+            let accesses = ctx
+                .index_access_vars(self, arr)
+                .into_iter()
+                .filter_map(|access| Some((access, access.index_access_parts(self)?.1)))
+                .filter(|(access, other_key)| *other_key != key && access.range(self).is_some())
+                .collect::<Vec<_>>();
+
+            accesses.into_iter().for_each(|(access, other_key)| {
+                let rhs_elem = Elem::Dynamic(Dynamic::new(rhs.into(), loc));
+                if key.must_alias(&other_key, self) {
+                    tracing::trace!("{} must alias {}", written_name, access.name(self));
+                    let new_access = self.advance_var_in_ctx(access, loc, ctx);
+                    let _ = new_access.try_set_range_min(self, rhs_elem.clone());
+                    let _ = new_access.try_set_range_max(self, rhs_elem);
+                } else if key.may_alias(&other_key, self) {
+                    tracing::trace!("{} may alias {}", written_name, access.name(self));
+                    let new_access = self.advance_var_in_ctx(access, loc, ctx);
+                    let prev_elem = Elem::Dynamic(Dynamic::new(access.into(), loc));
+                    let _ =
+                        new_access.try_set_range_min(self, prev_elem.clone().min(rhs_elem.clone()));
+                    let _ = new_access.try_set_range_max(self, prev_elem.max(rhs_elem));
+                }
+            });
         }
     }
 }
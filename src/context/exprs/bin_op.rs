@@ -1,6 +1,5 @@
 use crate::{context::ContextBuilder, ExprRet};
 use ethers_core::types::{I256, U256};
-use shared::analyzer::AsDotStr;
 use shared::range::elem::RangeElem;
 use shared::{
     analyzer::AnalyzerLike,
@@ -8,7 +7,9 @@ use shared::{
     nodes::{BuiltInNode, Builtin, Concrete, VarType},
     range::{
         elem::RangeOp,
-        elem_ty::{Dynamic, Elem},
+        elem_ty::{Dynamic, Elem, RangeConcrete},
+        interval_set::IntervalSet,
+        range_ops::{RangeCongruence, RangeDiv, RangeLift},
         Range, RangeEval, SolcRange,
     },
     Edge, Node,
@@ -42,6 +43,7 @@ pub trait BinOp: AnalyzerLike<Expr = Expression> + Sized {
         assign: bool,
     ) -> ExprRet {
         match (lhs_paths, rhs_paths) {
+            (e @ ExprRet::Unsupported(..), _) | (_, e @ ExprRet::Unsupported(..)) => e.clone(),
             (ExprRet::SingleLiteral((lhs_ctx, lhs)), ExprRet::SingleLiteral((_rhs_ctx, rhs))) => {
                 let lhs_cvar = ContextVarNode::from(*lhs).latest_version(self);
                 let rhs_cvar = ContextVarNode::from(*rhs).latest_version(self);
@@ -88,20 +90,39 @@ pub trait BinOp: AnalyzerLike<Expr = Expression> + Sized {
                     })
                     .collect(),
             ),
-            (ExprRet::Multi(lhs_sides), ExprRet::Multi(rhs_sides)) => {
-                todo!("here multi: {lhs_sides:?} {rhs_sides:?}")
+            (ExprRet::Multi(lhs_sides), ExprRet::Multi(rhs_sides))
+                if lhs_sides.len() == rhs_sides.len() =>
+            {
+                ExprRet::Multi(
+                    lhs_sides
+                        .iter()
+                        .zip(rhs_sides.iter())
+                        .map(|(lhs, rhs)| self.op_match(loc, lhs, rhs, op, assign))
+                        .collect(),
+                )
             }
             (_, ExprRet::CtxKilled) => ExprRet::CtxKilled,
             (ExprRet::CtxKilled, _) => ExprRet::CtxKilled,
-            (ExprRet::Fork(world1, world2), rhs @ ExprRet::Single(..)) => ExprRet::Fork(
+            (ExprRet::Fork(world1, world2), rhs) => ExprRet::Fork(
                 Box::new(self.op_match(loc, world1, rhs, op, assign)),
                 Box::new(self.op_match(loc, world2, rhs, op, assign)),
             ),
-            (lhs @ ExprRet::Single(..), ExprRet::Fork(world1, world2)) => ExprRet::Fork(
+            (lhs, ExprRet::Fork(world1, world2)) => ExprRet::Fork(
                 Box::new(self.op_match(loc, lhs, world1, op, assign)),
                 Box::new(self.op_match(loc, lhs, world2, op, assign)),
             ),
-            (l, r) => todo!("here other: {l:?} {r:?}"),
+            (ExprRet::Multi(lhs_sides), rhs) => ExprRet::Multi(
+                lhs_sides
+                    .iter()
+                    .map(|lhs| self.op_match(loc, lhs, rhs, op, assign))
+                    .collect(),
+            ),
+            (lhs, ExprRet::Multi(rhs_sides)) => ExprRet::Multi(
+                rhs_sides
+                    .iter()
+                    .map(|rhs| self.op_match(loc, lhs, rhs, op, assign))
+                    .collect(),
+            ),
         }
     }
 
@@ -177,8 +198,22 @@ pub trait BinOp: AnalyzerLike<Expr = Expression> + Sized {
                             ctx.kill(self, loc);
                             return ExprRet::CtxKilled;
                         }
+
+                        // `type(intN).min / -1` overflows
+                        if matches!(op, RangeOp::Div) && lhs_cvar.is_const(self) {
+                            if let (Some(lhs), Some(rhs)) = (
+                                lhs_cvar.evaled_range_min(self),
+                                new_rhs.evaled_range_min(self),
+                            ) {
+                                if lhs.range_div(&rhs).is_none() {
+                                    ctx.kill(self, loc);
+                                    return ExprRet::CtxKilled;
+                                }
+                            }
+                        }
                     } else if new_rhs.is_symbolic(self) {
                         let tmp_rhs = self.advance_var_in_ctx(new_rhs, loc, ctx);
+                        ctx.alias_var(tmp_rhs, new_rhs, self);
                         let zero_node = self.add_node(Node::Concrete(Concrete::from(U256::zero())));
                         let zero_node = self.add_node(Node::ContextVar(
                             ContextVar::new_from_concrete(Loc::Implicit, zero_node.into(), self),
@@ -205,7 +240,15 @@ pub trait BinOp: AnalyzerLike<Expr = Expression> + Sized {
                         let cvar = ContextVarNode::from(self.add_node(Node::ContextVar(tmp_var)));
                         ctx.add_ctx_dep(cvar, self);
 
-                        let range = tmp_rhs.range(self).expect("No range?");
+                        let (range, range_min) =
+                            match (tmp_rhs.range(self), tmp_rhs.range_min(self)) {
+                                (Some(range), Some(range_min)) => (range, range_min),
+                                _ => {
+                                    let reason =
+                                        format!("division by `{}`", tmp_rhs.display_name(self));
+                                    return self.unsupported(ctx, loc, reason);
+                                }
+                            };
                         if range.min_is_negative(self) {
                             let mut range_excls = range.range_exclusions();
                             let excl = Elem::from(Concrete::from(I256::zero()));
@@ -214,11 +257,8 @@ pub trait BinOp: AnalyzerLike<Expr = Expression> + Sized {
                         } else {
                             // the new min is max(1, rhs.min)
                             let min = Elem::max(
-                                tmp_rhs.range_min(self).unwrap_or_else(|| {
-                                    panic!("No range minimum: {:?}", tmp_rhs.underlying(self))
-                                }),
-                                Elem::from(Concrete::from(U256::from(1)))
-                                    .cast(tmp_rhs.range_min(self).expect("No range minimum?")),
+                                range_min.clone(),
+                                Elem::from(Concrete::from(U256::from(1))).cast(range_min),
                             );
 
                             tmp_rhs.set_range_min(self, min);
@@ -246,13 +286,18 @@ pub trait BinOp: AnalyzerLike<Expr = Expression> + Sized {
                         }
                     } else if lhs_cvar.is_symbolic(self) {
                         let tmp_lhs = self.advance_var_in_ctx(lhs_cvar, loc, ctx);
+                        ctx.alias_var(tmp_lhs, lhs_cvar, self);
                         // the new min is max(lhs.min, rhs.min)
-                        let min = Elem::max(
-                            tmp_lhs.range_min(self).unwrap_or_else(|| {
-                                panic!("No range minimum: {:?}", tmp_lhs.ty(self).as_dot_str(self))
-                            }),
-                            Elem::Dynamic(Dynamic::new(rhs_cvar.into(), loc)),
-                        );
+                        let lhs_min = match tmp_lhs.range_min(self) {
+                            Some(lhs_min) => lhs_min,
+                            None => {
+                                let reason =
+                                    format!("subtraction from `{}`", tmp_lhs.display_name(self));
+                                return self.unsupported(ctx, loc, reason);
+                            }
+                        };
+                        let min =
+                            Elem::max(lhs_min, Elem::Dynamic(Dynamic::new(rhs_cvar.into(), loc)));
                         tmp_lhs.set_range_min(self, min);
 
                         let tmp_var = ContextVar {
@@ -290,6 +335,7 @@ pub trait BinOp: AnalyzerLike<Expr = Expression> + Sized {
                     let lhs_cvar = lhs_cvar.latest_version(self);
                     if lhs_cvar.is_symbolic(self) {
                         let tmp_lhs = self.advance_var_in_ctx(lhs_cvar, loc, ctx);
+                        ctx.alias_var(tmp_lhs, lhs_cvar, self);
 
                         // the new max is min(lhs.max, (2**256 - rhs.min))
                         let max = Elem::min(
@@ -344,6 +390,7 @@ pub trait BinOp: AnalyzerLike<Expr = Expression> + Sized {
                     let lhs_cvar = lhs_cvar.latest_version(self);
                     if lhs_cvar.is_symbolic(self) {
                         let tmp_lhs = self.advance_var_in_ctx(lhs_cvar, loc, ctx);
+                        ctx.alias_var(tmp_lhs, lhs_cvar, self);
 
                         // the new max is min(lhs.max, (2**256 / max(1, rhs.min)))
                         let max = Elem::min(
@@ -411,6 +458,7 @@ pub trait BinOp: AnalyzerLike<Expr = Expression> + Sized {
                         }
                     } else if new_rhs.is_symbolic(self) {
                         let tmp_rhs = self.advance_var_in_ctx(rhs_cvar, loc, ctx);
+                        ctx.alias_var(tmp_rhs, rhs_cvar, self);
                         // the new min is max(lhs.min, rhs.min)
                         let min = Elem::max(
                             tmp_rhs.range_min(self).expect("No range minimum?"),
@@ -464,6 +512,108 @@ pub trait BinOp: AnalyzerLike<Expr = Expression> + Sized {
         new_lhs.set_range_min(self, new_range.range_min());
         new_lhs.set_range_max(self, new_range.range_max());
 
+        // lift the operation over any disjoint intervals the operands are known to be in
+        let lhs_intervals = lhs_cvar.range_intervals(self);
+        let rhs_intervals = new_rhs.range_intervals(self);
+        if lhs_intervals.is_some() || rhs_intervals.is_some() {
+            let as_intervals = |cvar: ContextVarNode, intervals: Option<IntervalSet>| {
+                intervals.or_else(|| {
+                    Some(IntervalSet::new(
+                        cvar.evaled_range_min(self)?,
+                        cvar.evaled_range_max(self)?,
+                    ))
+                })
+            };
+            let lifted = as_intervals(lhs_cvar, lhs_intervals)
+                .zip(as_intervals(new_rhs, rhs_intervals))
+                .and_then(|(lhs, rhs)| lhs.range_lift(op, &rhs));
+            if let Some(lifted) = lifted.filter(|lifted| !lifted.is_empty()) {
+                new_lhs.narrow_to_intervals(self, lifted);
+            }
+        }
+
+        // bitwise operations aren't monotonic, so applying them to the bounds says little about
+        // the result. Track which bits are known instead and bound the result by those
+        if matches!(
+            op,
+            RangeOp::BitAnd | RangeOp::BitOr | RangeOp::BitXor | RangeOp::Shl | RangeOp::Shr
+        ) {
+            let bits = lhs_cvar
+                .known_bits(self)
+                .zip(new_rhs.known_bits(self))
+                .and_then(|(lhs, rhs)| lhs.apply(op, &rhs));
+            let size = match new_lhs
+                .evaled_range_min(self)
+                .and_then(|min| min.maybe_concrete())
+            {
+                Some(RangeConcrete {
+                    val: Concrete::Uint(size, _),
+                    ..
+                }) => Some(size),
+                _ => None,
+            };
+            if let (Some(bits), Some(size)) = (bits, size) {
+                let bits = bits.truncate(size);
+                let uint = |elem: Option<Elem<Concrete>>| {
+                    elem.and_then(|elem| elem.maybe_concrete())
+                        .and_then(|elem| elem.val.uint_val())
+                };
+                // the bits only tighten what the operands already say: setting bits never makes
+                // a value smaller and clearing them never makes it larger
+                let mut min = bits.min_value();
+                let mut max = bits.max_value();
+                [lhs_cvar, new_rhs].into_iter().for_each(|cvar| match op {
+                    RangeOp::BitOr => {
+                        if let Some(side_min) = uint(cvar.evaled_range_min(self)) {
+                            min = min.max(side_min);
+                        }
+                    }
+                    RangeOp::BitAnd => {
+                        if let Some(side_max) = uint(cvar.evaled_range_max(self)) {
+                            max = max.min(side_max);
+                        }
+                    }
+                    _ => {}
+                });
+                new_lhs.set_range_min(self, Elem::from(Concrete::Uint(size, min)));
+                new_lhs.set_range_max(self, Elem::from(Concrete::Uint(size, max)));
+                new_lhs.set_range_known_bits(self, Some(bits));
+            }
+        }
+
+        // track what the result is congruent to, which tightens its evaluated bounds
+        if let (Some(lhs), Some(rhs)) = (lhs_cvar.congruence(self), new_rhs.congruence(self)) {
+            if let Some(RangeConcrete {
+                val: Concrete::Uint(size, _),
+                ..
+            }) = new_lhs
+                .evaled_range_min(self)
+                .and_then(|min| min.maybe_concrete())
+            {
+                if let Some(congruence) = lhs.range_congruence(op, &rhs, size) {
+                    new_lhs.set_range_congruence(self, Some(congruence));
+                }
+            }
+        }
+
+        // adding or subtracting a constant keeps a fixed distance to the variable
+        if let (Some(lhs), Some(rhs)) = (zone_term(lhs_cvar, self), zone_term(new_rhs, self)) {
+            let term = match (op, lhs, rhs) {
+                (RangeOp::Add, (ZoneVar::Var(_), _), (ZoneVar::Zero, c)) => Some((lhs.0, c)),
+                (RangeOp::Sub, (ZoneVar::Var(_), _), (ZoneVar::Zero, c)) => {
+                    c.checked_neg().map(|c| (lhs.0, c))
+                }
+                (RangeOp::Add, (ZoneVar::Zero, c), (ZoneVar::Var(_), _)) => Some((rhs.0, c)),
+                _ => None,
+            };
+            if let Some(term) = term {
+                if !ctx.add_relation(RangeOp::Eq, (ZoneVar::Var(new_lhs), 0), term, self) {
+                    ctx.kill(self, loc);
+                    return ExprRet::CtxKilled;
+                }
+            }
+        }
+
         // last ditch effort to prevent exponentiation from having a minimum of 1 instead of 0.
         // if the lhs is 0 check if the rhs is also 0, otherwise set minimum to 0.
         if matches!(op, RangeOp::Exp) {
@@ -7,6 +7,8 @@ use shared::{
     range::{
         elem::{RangeElem, RangeOp},
         elem_ty::{Elem, RangeConcrete, RangeExpr},
+        interval_set::IntervalSet,
+        range_ops::RangeLift,
         Range, SolcRange,
     },
     Node,
@@ -25,7 +27,7 @@ pub trait Cmp: AnalyzerLike<Expr = Expression> + Sized {
     #[tracing::instrument(level = "trace", skip_all)]
     fn not_inner(&mut self, loc: Loc, lhs_expr: ExprRet) -> ExprRet {
         match lhs_expr {
-            ExprRet::CtxKilled => lhs_expr,
+            ExprRet::CtxKilled | ExprRet::Unsupported(..) => lhs_expr,
             ExprRet::Single((ctx, lhs)) | ExprRet::SingleLiteral((ctx, lhs)) => {
                 let lhs_cvar = ContextVarNode::from(lhs);
 
@@ -49,9 +51,12 @@ pub trait Cmp: AnalyzerLike<Expr = Expression> + Sized {
 
                 ExprRet::Single((ctx, self.add_node(Node::ContextVar(out_var))))
             }
-            ExprRet::Multi(f) => {
-                panic!("not: {f:?}")
-            }
+            ExprRet::Multi(inner) => ExprRet::Multi(
+                inner
+                    .into_iter()
+                    .map(|expr_ret| self.not_inner(loc, expr_ret))
+                    .collect(),
+            ),
             ExprRet::Fork(world1, world2) => ExprRet::Fork(
                 Box::new(self.not_inner(loc, *world1)),
                 Box::new(self.not_inner(loc, *world2)),
@@ -72,6 +77,30 @@ pub trait Cmp: AnalyzerLike<Expr = Expression> + Sized {
         self.cmp_inner(loc, &lhs_paths, op, &rhs_paths)
     }
 
+    /// Whether a comparison is decided by the disjoint intervals either side is known to be in
+    fn intervals_cmp(
+        &self,
+        lhs_cvar: ContextVarNode,
+        op: RangeOp,
+        rhs_cvar: ContextVarNode,
+    ) -> Option<bool> {
+        let lhs_intervals = lhs_cvar.range_intervals(self);
+        let rhs_intervals = rhs_cvar.range_intervals(self);
+        if lhs_intervals.is_none() && rhs_intervals.is_none() {
+            return None;
+        }
+        let as_intervals = |cvar: ContextVarNode, intervals: Option<IntervalSet>| {
+            intervals.or_else(|| {
+                Some(IntervalSet::new(
+                    cvar.evaled_range_min(self)?,
+                    cvar.evaled_range_max(self)?,
+                ))
+            })
+        };
+        as_intervals(lhs_cvar, lhs_intervals)?
+            .range_lift_cmp(op, &as_intervals(rhs_cvar, rhs_intervals)?)
+    }
+
     #[tracing::instrument(level = "trace", skip_all)]
     fn cmp_inner(
         &mut self,
@@ -81,6 +110,7 @@ pub trait Cmp: AnalyzerLike<Expr = Expression> + Sized {
         rhs_paths: &ExprRet,
     ) -> ExprRet {
         match (lhs_paths, rhs_paths) {
+            (e @ ExprRet::Unsupported(..), _) | (_, e @ ExprRet::Unsupported(..)) => e.clone(),
             (ExprRet::SingleLiteral((_ctx, lhs)), ExprRet::Single((rhs_ctx, rhs))) => {
                 ContextVarNode::from(*lhs).literal_cast_from(&ContextVarNode::from(*rhs), self);
                 self.cmp_inner(loc, &ExprRet::Single((*rhs_ctx, *rhs)), op, rhs_paths)
@@ -92,7 +122,15 @@ pub trait Cmp: AnalyzerLike<Expr = Expression> + Sized {
             (ExprRet::Single((ctx, lhs)), ExprRet::Single((_rhs_ctx, rhs))) => {
                 let lhs_cvar = ContextVarNode::from(*lhs);
                 let rhs_cvar = ContextVarNode::from(*rhs);
-                let range = {
+                let known = match (zone_term(lhs_cvar, self), zone_term(rhs_cvar, self)) {
+                    (Some(lhs), Some(rhs)) => ctx.zone(self).implies(op, lhs, rhs),
+                    _ => None,
+                }
+                .or_else(|| self.intervals_cmp(lhs_cvar, op, rhs_cvar));
+                let range = if let Some(known) = known {
+                    // the relational facts already decide the comparison
+                    known.into()
+                } else {
                     let elem = Elem::Expr(RangeExpr {
                         lhs: Box::new(Elem::Dynamic(Dynamic::new(lhs_cvar.into(), loc))),
                         op,
@@ -101,8 +139,8 @@ pub trait Cmp: AnalyzerLike<Expr = Expression> + Sized {
 
                     let exclusions = lhs_cvar
                         .range(self)
-                        .expect("No lhs rnage")
-                        .range_exclusions();
+                        .map(|range| range.range_exclusions())
+                        .unwrap_or_default();
                     SolcRange::new(elem.clone(), elem, exclusions)
                 };
 
@@ -198,7 +236,30 @@ pub trait Cmp: AnalyzerLike<Expr = Expression> + Sized {
                 Box::new(self.cmp_inner(loc, m, op, world1)),
                 Box::new(self.cmp_inner(loc, m, op, world2)),
             ),
-            (e, f) => todo!("any: {:?} {:?}", e, f),
+            (ExprRet::CtxKilled, _) | (_, ExprRet::CtxKilled) => ExprRet::CtxKilled,
+            (ExprRet::SingleLiteral((lhs_ctx, lhs)), r @ ExprRet::SingleLiteral(_)) => {
+                self.cmp_inner(loc, &ExprRet::Single((*lhs_ctx, *lhs)), op, r)
+            }
+            (ExprRet::Fork(world1, world2), r) => ExprRet::Fork(
+                Box::new(self.cmp_inner(loc, world1, op, r)),
+                Box::new(self.cmp_inner(loc, world2, op, r)),
+            ),
+            (l, ExprRet::Fork(world1, world2)) => ExprRet::Fork(
+                Box::new(self.cmp_inner(loc, l, op, world1)),
+                Box::new(self.cmp_inner(loc, l, op, world2)),
+            ),
+            (ExprRet::Multi(lhs_sides), r) => ExprRet::Multi(
+                lhs_sides
+                    .iter()
+                    .map(|expr_ret| self.cmp_inner(loc, expr_ret, op, r))
+                    .collect(),
+            ),
+            (l, ExprRet::Multi(rhs_sides)) => ExprRet::Multi(
+                rhs_sides
+                    .iter()
+                    .map(|expr_ret| self.cmp_inner(loc, l, op, expr_ret))
+                    .collect(),
+            ),
         }
     }
 
@@ -377,7 +438,8 @@ pub trait Cmp: AnalyzerLike<Expr = Expression> + Sized {
                             return false.into();
                         }
                     }
-                    e => unreachable!("Cmp with strange op: {:?}", e),
+                    // anything else isn't a comparison, so nothing is known of it
+                    _ => {}
                 }
                 SolcRange::default_bool()
             } else {
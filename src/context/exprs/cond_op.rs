@@ -91,7 +91,7 @@ pub trait CondOp: AnalyzerLike<Expr = Expression> + Require + Sized {
 
     fn match_true(&mut self, loc: Loc, true_cvars: &ExprRet, if_expr: &Expression) {
         match true_cvars {
-            ExprRet::CtxKilled => {}
+            ExprRet::CtxKilled | ExprRet::Unsupported(..) => {}
             ExprRet::Single((fork_ctx, _true_cvar))
             | ExprRet::SingleLiteral((fork_ctx, _true_cvar)) => {
                 self.true_fork_if_cvar(loc, if_expr.clone(), *fork_ctx);
@@ -109,7 +109,7 @@ pub trait CondOp: AnalyzerLike<Expr = Expression> + Require + Sized {
 
     fn match_false(&mut self, loc: Loc, false_cvars: &ExprRet, if_expr: &Expression) {
         match false_cvars {
-            ExprRet::CtxKilled => {}
+            ExprRet::CtxKilled | ExprRet::Unsupported(..) => {}
             ExprRet::Single((fork_ctx, _false_cvar))
             | ExprRet::SingleLiteral((fork_ctx, _false_cvar)) => {
                 self.false_fork_if_cvar(loc, if_expr.clone(), *fork_ctx);
@@ -80,7 +80,7 @@ pub trait List: AnalyzerLike<Expr = Expression> + Sized {
                 Box::new(self.match_ty(loc, w1, input)),
                 Box::new(self.match_ty(loc, w2, input)),
             ),
-            ExprRet::CtxKilled => ExprRet::CtxKilled,
+            ExprRet::CtxKilled | ExprRet::Unsupported(..) => ty_ret.clone(),
         }
     }
 }
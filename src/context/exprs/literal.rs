@@ -1,4 +1,4 @@
-use crate::ExprRet;
+use crate::{ContextBuilder, ExprRet};
 use ethers_core::types::H256;
 use ethers_core::types::I256;
 use shared::{
@@ -7,15 +7,15 @@ use shared::{
     nodes::{Concrete, ConcreteNode},
     Edge, Node,
 };
-use solang_parser::pt::HexLiteral;
+use solang_parser::pt::{Expression, HexLiteral};
 
 use ethers_core::types::{Address, U256};
 use solang_parser::pt::Loc;
 use std::str::FromStr;
 
-impl<T> Literal for T where T: AnalyzerLike + Sized {}
+impl<T> Literal for T where T: AnalyzerLike<Expr = Expression> + Sized {}
 
-pub trait Literal: AnalyzerLike + Sized {
+pub trait Literal: AnalyzerLike<Expr = Expression> + Sized {
     fn number_literal(
         &mut self,
         ctx: ContextNode,
@@ -97,7 +97,7 @@ pub trait Literal: AnalyzerLike + Sized {
             self.add_edge(node, ctx, Edge::Context(ContextEdge::Variable));
             ExprRet::SingleLiteral((ctx, node))
         } else {
-            todo!("hexes: {:?}", hexes);
+            self.unsupported(ctx, hexes[0].loc, "concatenated hex literals")
         }
     }
 
@@ -115,8 +115,10 @@ pub trait Literal: AnalyzerLike + Sized {
     fn string_literal(&mut self, ctx: ContextNode, loc: Loc, s: &str) -> ExprRet {
         let concrete_node =
             ConcreteNode::from(self.add_node(Node::Concrete(Concrete::String(s.to_string()))));
-        let ccvar = Node::ContextVar(ContextVar::new_from_concrete(loc, concrete_node, self));
-        let node = self.add_node(ccvar);
+        let mut var = ContextVar::new_from_concrete(loc, concrete_node, self);
+        // quoted, so that the literal isn't found in place of a variable named like its contents
+        var.name = format!("\"{s}\"");
+        let node = self.add_node(Node::ContextVar(var));
         self.add_edge(node, ctx, Edge::Context(ContextEdge::Variable));
         ExprRet::Single((ctx, node))
     }
@@ -55,7 +55,7 @@ pub trait MemberAccess: AnalyzerLike<Expr = Expression> + Sized {
                 .possible_library_funcs(ctx, member_idx)
                 .into_iter()
                 .collect::<Vec<_>>(),
-            _ => todo!(),
+            _ => vec![],
         }
     }
 
@@ -71,7 +71,10 @@ pub trait MemberAccess: AnalyzerLike<Expr = Expression> + Sized {
         if ident.name == "length" {
             return self.length(loc, member_expr, ctx);
         }
-        let (_, member_idx) = self.parse_ctx_expr(member_expr, ctx).expect_single();
+        let member_idx = match self.parse_ctx_expr(member_expr, ctx) {
+            e @ ExprRet::Unsupported(..) => return e,
+            e => e.expect_single().1,
+        };
         match self.node(member_idx) {
             Node::ContextVar(cvar) => match &cvar.ty {
                 VarType::User(TypeNode::Struct(struct_node), _) => {
@@ -98,6 +101,12 @@ pub trait MemberAccess: AnalyzerLike<Expr = Expression> + Sized {
                             );
                             self.add_edge(fc_node, ctx, Edge::Context(ContextEdge::Variable));
                             return ExprRet::Single((ctx, fc_node));
+                        } else {
+                            return self.unsupported(
+                                ctx,
+                                loc,
+                                format!("unresolved type of field `{}`", ident.name),
+                            );
                         }
                     } else if let Some(ret) =
                         self.library_func_search(ctx, struct_node.0.into(), ident)
@@ -159,16 +168,12 @@ pub trait MemberAccess: AnalyzerLike<Expr = Expression> + Sized {
                             return ExprRet::Single((ctx, fn_node));
                         }
                     } else {
-                        panic!(
-                            "No function with name {:?} in contract: {:?}. Functions: [{:#?}]",
+                        let reason = format!(
+                            "member `{}` of contract {}",
                             ident.name,
-                            con_node.name(self),
-                            con_node
-                                .funcs(self)
-                                .iter()
-                                .map(|func| func.name(self))
-                                .collect::<Vec<_>>()
-                        )
+                            con_node.name(self)
+                        );
+                        return self.unsupported(ctx, loc, reason);
                     }
                 }
                 VarType::BuiltIn(bn, _) => {
@@ -180,7 +185,7 @@ pub trait MemberAccess: AnalyzerLike<Expr = Expression> + Sized {
                         ident,
                     );
                 }
-                e => todo!("member access: {:?}, {:?}", e, ident),
+                _ => return self.unsupported(ctx, loc, format!("member access `{}`", ident.name)),
             },
             Node::Msg(_msg) => {
                 let name = format!("msg.{}", ident.name);
@@ -319,7 +324,13 @@ pub trait MemberAccess: AnalyzerLike<Expr = Expression> + Sized {
                                 return ExprRet::Single((ctx, cvar));
                             }
                         }
-                        e => panic!("unknown msg attribute: {e:?}"),
+                        _ => {
+                            return self.unsupported(
+                                ctx,
+                                loc,
+                                format!("member access `{}`", ident.name),
+                            )
+                        }
                     };
 
                     let mut var = ContextVar::new_from_concrete(loc, node, self);
@@ -510,7 +521,13 @@ pub trait MemberAccess: AnalyzerLike<Expr = Expression> + Sized {
                                 return ExprRet::Single((ctx, cvar));
                             }
                         }
-                        e => panic!("unknown block attribute: {e:?}"),
+                        _ => {
+                            return self.unsupported(
+                                ctx,
+                                loc,
+                                format!("member access `{}`", ident.name),
+                            )
+                        }
                     };
                     let mut var = ContextVar::new_from_concrete(loc, node, self);
                     var.name = name.clone();
@@ -531,7 +548,7 @@ pub trait MemberAccess: AnalyzerLike<Expr = Expression> + Sized {
                     ident,
                 );
             }
-            e => todo!("{:?}", e),
+            _ => return self.unsupported(ctx, loc, format!("member access `{}`", ident.name)),
         }
         ExprRet::Single((ctx, member_idx))
     }
@@ -573,21 +590,27 @@ pub trait MemberAccess: AnalyzerLike<Expr = Expression> + Sized {
                             });
                             ExprRet::Single((ctx, func_node))
                         }
-                        _ => panic!("Unknown member access on address: {:?}", ident.name),
+                        _ => self.unsupported(
+                            ctx,
+                            loc,
+                            format!("member `{}` of address", ident.name),
+                        ),
                     }
                 }
-                Builtin::Bool => panic!("Unknown member access on bool: {:?}", ident.name),
+                Builtin::Bool => {
+                    self.unsupported(ctx, loc, format!("member `{}` of bool", ident.name))
+                }
                 Builtin::String => {
-                    panic!("Unknown member access on string: {:?}", ident.name)
+                    self.unsupported(ctx, loc, format!("member `{}` of string", ident.name))
                 }
                 Builtin::Bytes(size) => {
-                    panic!("Unknown member access on bytes{}: {:?}", size, ident.name)
+                    self.unsupported(ctx, loc, format!("member `{}` of bytes{size}", ident.name))
                 }
                 Builtin::Rational => {
-                    panic!("Unknown member access on rational: {:?}", ident.name)
+                    self.unsupported(ctx, loc, format!("member `{}` of rational", ident.name))
                 }
                 Builtin::DynamicBytes => {
-                    panic!("Unknown member access on bytes[]: {:?}", ident.name)
+                    self.unsupported(ctx, loc, format!("member `{}` of bytes[]", ident.name))
                 }
                 Builtin::Array(_) => {
                     if ident.name.starts_with("push") {
@@ -596,17 +619,40 @@ pub trait MemberAccess: AnalyzerLike<Expr = Expression> + Sized {
                             let fn_node = FunctionNode::from(self.add_node(as_fn.clone()));
                             ExprRet::Single((ctx, fn_node.into()))
                         } else {
-                            panic!("Trying to push to nonstorage variable is not supported");
+                            self.unsupported(ctx, loc, "push to a non-storage array")
+                        }
+                    } else if ident.name.starts_with("pop") {
+                        if is_storage {
+                            let as_fn = self.builtin_fns().get("pop").unwrap();
+                            let fn_node = FunctionNode::from(self.add_node(as_fn.clone()));
+                            ExprRet::Single((ctx, fn_node.into()))
+                        } else {
+                            self.unsupported(ctx, loc, "pop from a non-storage array")
                         }
                     } else {
-                        panic!("Unknown member access on array[]: {:?}", ident.name)
+                        self.unsupported(ctx, loc, format!("member `{}` of array[]", ident.name))
                     }
                 }
+                Builtin::SizedArray(size, _) => match &*ident.name {
+                    "length" => {
+                        let c = Concrete::from(size);
+                        let node = self.add_node(Node::Concrete(c)).into();
+                        let mut var = ContextVar::new_from_concrete(loc, node, self);
+                        var.name = format!("array[{size}].length");
+                        var.display_name = var.name.clone();
+                        var.is_tmp = true;
+                        var.is_symbolic = false;
+                        let cvar = self.add_node(Node::ContextVar(var));
+                        self.add_edge(cvar, ctx, Edge::Context(ContextEdge::Variable));
+                        ExprRet::Single((ctx, cvar))
+                    }
+                    e => self.unsupported(ctx, loc, format!("member `{e}` of array[{size}]")),
+                },
                 Builtin::Mapping(_, _) => {
-                    panic!("Unknown member access on mapping: {:?}", ident.name)
+                    self.unsupported(ctx, loc, format!("member `{}` of mapping", ident.name))
                 }
                 Builtin::Func(_, _) => {
-                    panic!("Unknown member access on func: {:?}", ident.name)
+                    self.unsupported(ctx, loc, format!("member `{}` of func", ident.name))
                 }
                 Builtin::Int(size) => {
                     let max = if size == 256 {
@@ -628,7 +674,7 @@ pub trait MemberAccess: AnalyzerLike<Expr = Expression> + Sized {
                             ExprRet::Single((ctx, cvar))
                         }
                         "min" => {
-                            let min = max * I256::from(-1i32);
+                            let min = max * I256::from(-1i32) - I256::from(1);
                             let c = Concrete::from(min);
                             let node = self.add_node(Node::Concrete(c)).into();
                             let mut var = ContextVar::new_from_concrete(loc, node, self);
@@ -640,7 +686,7 @@ pub trait MemberAccess: AnalyzerLike<Expr = Expression> + Sized {
                             self.add_edge(cvar, ctx, Edge::Context(ContextEdge::Variable));
                             ExprRet::Single((ctx, cvar))
                         }
-                        e => panic!("Unknown type attribute on int{size}: {e:?}"),
+                        e => self.unsupported(ctx, loc, format!("member `{e}` of int{size}")),
                     }
                 }
                 Builtin::Uint(size) => match &*ident.name {
@@ -675,7 +721,7 @@ pub trait MemberAccess: AnalyzerLike<Expr = Expression> + Sized {
                         self.add_edge(cvar, ctx, Edge::Context(ContextEdge::Variable));
                         ExprRet::Single((ctx, cvar))
                     }
-                    e => panic!("Unknown type attribute on uint{size}: {e:?}"),
+                    e => self.unsupported(ctx, loc, format!("member `{e}` of uint{size}")),
                 },
             }
         }
@@ -839,7 +885,8 @@ pub trait MemberAccess: AnalyzerLike<Expr = Expression> + Sized {
                 self.add_edge(*idx, idx_node, Edge::Context(ContextEdge::Index));
                 ExprRet::Single((ctx, idx_node))
             }
-            _ => todo!("here"),
+            e @ ExprRet::Unsupported(..) => e.clone(),
+            _ => self.unsupported(ctx, loc, "index access by a non-variable index"),
         }
     }
 
@@ -848,6 +895,14 @@ pub trait MemberAccess: AnalyzerLike<Expr = Expression> + Sized {
         self.match_length(loc, elem, true)
     }
 
+    /// The range of a new length variable of `arr`, which is fixed for a fixed size array
+    fn length_range(&self, arr: ContextVarNode) -> Option<SolcRange> {
+        match arr.underlying(self).ty.fixed_len(self) {
+            Some(len) => SolcRange::from(Concrete::from(len)),
+            None => SolcRange::try_from_builtin(&Builtin::Uint(256)),
+        }
+    }
+
     #[tracing::instrument(level = "trace", skip_all)]
     fn tmp_length(
         &mut self,
@@ -871,7 +926,7 @@ pub trait MemberAccess: AnalyzerLike<Expr = Expression> + Sized {
                 is_symbolic: true,
                 ty: VarType::BuiltIn(
                     BuiltInNode::from(self.builtin_or_add(Builtin::Uint(256))),
-                    SolcRange::try_from_builtin(&Builtin::Uint(256)),
+                    self.length_range(arr),
                 ),
             };
             let len_node = self.add_node(Node::ContextVar(len_var));
@@ -905,6 +960,9 @@ pub trait MemberAccess: AnalyzerLike<Expr = Expression> + Sized {
         match elem_path {
             ExprRet::CtxKilled => ExprRet::CtxKilled,
             ExprRet::Single((array_ctx, arr)) => {
+                if !matches!(self.node(arr), Node::ContextVar(_)) {
+                    return self.unsupported(array_ctx, loc, "length of a non-variable");
+                }
                 let next_arr = self.advance_var_in_ctx(
                     ContextVarNode::from(arr).latest_version(self),
                     loc,
@@ -944,7 +1002,7 @@ pub trait MemberAccess: AnalyzerLike<Expr = Expression> + Sized {
                         is_symbolic: true,
                         ty: VarType::BuiltIn(
                             BuiltInNode::from(self.builtin_or_add(Builtin::Uint(256))),
-                            SolcRange::try_from_builtin(&Builtin::Uint(256)),
+                            self.length_range(arr),
                         ),
                     };
                     let len_node = self.add_node(Node::ContextVar(len_var));
@@ -971,7 +1029,20 @@ pub trait MemberAccess: AnalyzerLike<Expr = Expression> + Sized {
                     ExprRet::Single((array_ctx, len_node))
                 }
             }
-            _ => todo!("here"),
+            ExprRet::SingleLiteral(inner) => {
+                self.match_length(loc, ExprRet::Single(inner), update_len_bound)
+            }
+            ExprRet::Multi(inner) => ExprRet::Multi(
+                inner
+                    .into_iter()
+                    .map(|i| self.match_length(loc, i, update_len_bound))
+                    .collect(),
+            ),
+            ExprRet::Fork(w1, w2) => ExprRet::Fork(
+                Box::new(self.match_length(loc, *w1, update_len_bound)),
+                Box::new(self.match_length(loc, *w2, update_len_bound)),
+            ),
+            e @ ExprRet::Unsupported(..) => e,
         }
     }
 }
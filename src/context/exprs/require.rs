@@ -3,22 +3,27 @@ use crate::{
     exprs::{BinOp, Variable},
     AnalyzerLike, Concrete, ConcreteNode, ContextBuilder, ExprRet, Node,
 };
-use shared::range::range_string::ToRangeString;
+#[cfg(feature = "smt")]
+use shared::range::smt::{SmtResult, SmtScript};
 use shared::{
     context::*,
     nodes::{BuiltInNode, Builtin, VarType},
     range::{
+        congruence::Congruence,
         elem::{RangeElem, RangeOp},
         elem_ty::{Dynamic, Elem, RangeConcrete},
+        interval_set::IntervalSet,
+        range_ops::{RangeAdd, RangeSub},
         Range, RangeEval, SolcRange,
     },
     Edge,
 };
 use solang_parser::helpers::CodeLocation;
 
-use ethers_core::types::I256;
+use ethers_core::types::{I256, U256};
 use solang_parser::pt::{Expression, Loc};
 use std::cmp::Ordering;
+use std::collections::BTreeSet;
 
 impl<T> Require for T where T: Variable + BinOp + Sized + AnalyzerLike {}
 pub trait Require: AnalyzerLike + Variable + BinOp + Sized {
@@ -135,7 +140,10 @@ pub trait Require: AnalyzerLike + Variable + BinOp + Sized {
                     (RangeOp::Neq, RangeOp::Eq),
                 );
             }
-            Expression::Or(loc, lhs, rhs) => {
+            or @ Expression::Or(loc, lhs, rhs) => {
+                if self.require_disjunction(*loc, or, ctx) {
+                    return;
+                }
                 let lhs_paths = self.cmp(*loc, lhs, RangeOp::Or, rhs, ctx);
                 let cnode = ConcreteNode::from(self.add_node(Node::Concrete(Concrete::Bool(true))));
                 let tmp_true =
@@ -181,6 +189,7 @@ pub trait Require: AnalyzerLike + Variable + BinOp + Sized {
         recursion_ops: (RangeOp, RangeOp),
     ) {
         match (lhs_paths, rhs_paths) {
+            (ExprRet::Unsupported(..), _) | (_, ExprRet::Unsupported(..)) => {}
             (_, ExprRet::CtxKilled) => {}
             (ExprRet::CtxKilled, _) => {}
             (ExprRet::SingleLiteral((lhs_ctx, lhs)), ExprRet::Single((_rhs_ctx, rhs))) => {
@@ -210,6 +219,8 @@ pub trait Require: AnalyzerLike + Variable + BinOp + Sized {
                 let rhs_cvar = ContextVarNode::from(*rhs).latest_version(self);
                 let new_lhs = self.advance_var_in_ctx(lhs_cvar, loc, *lhs_ctx);
                 let new_rhs = self.advance_var_in_ctx(rhs_cvar, loc, *lhs_ctx);
+                lhs_ctx.alias_var(new_lhs, lhs_cvar, self);
+                lhs_ctx.alias_var(new_rhs, rhs_cvar, self);
 
                 self.require(new_lhs, new_rhs, *lhs_ctx, loc, op, rhs_op, recursion_ops);
                 // if lhs_ctx != rhs_ctx {
@@ -283,7 +294,19 @@ pub trait Require: AnalyzerLike + Variable + BinOp + Sized {
                 self.handle_require_inner(loc, m, world1, op, rhs_op, recursion_ops);
                 self.handle_require_inner(loc, m, world2, op, rhs_op, recursion_ops);
             }
-            (e, f) => todo!("any: {:?} {:?}", e, f),
+            (ExprRet::Fork(world1, world2), m @ ExprRet::Multi(_)) => {
+                self.handle_require_inner(loc, world1, m, op, rhs_op, recursion_ops);
+                self.handle_require_inner(loc, world2, m, op, rhs_op, recursion_ops);
+            }
+            (ExprRet::SingleLiteral((lhs_ctx, lhs)), r @ ExprRet::SingleLiteral(_)) => self
+                .handle_require_inner(
+                    loc,
+                    &ExprRet::Single((*lhs_ctx, *lhs)),
+                    r,
+                    op,
+                    rhs_op,
+                    recursion_ops,
+                ),
         }
     }
 
@@ -307,16 +330,35 @@ pub trait Require: AnalyzerLike + Variable + BinOp + Sized {
             op.to_string(),
             new_rhs.display_name(self)
         );
+        if !matches!(
+            op,
+            RangeOp::Eq | RangeOp::Neq | RangeOp::Lt | RangeOp::Gt | RangeOp::Lte | RangeOp::Gte
+        ) {
+            self.unsupported(ctx, loc, format!("requirement with `{}`", op.to_string()));
+            return None;
+        }
         let mut any_unsat = false;
         let mut tmp_cvar = None;
 
         if let Some(mut lhs_range) = new_lhs.underlying(self).ty.range(self) {
+            let lhs_intervals = lhs_range.intervals.clone();
+            let rhs_intervals = new_rhs.range_intervals(self);
+            let lhs_bits = lhs_range.known_bits;
+            let rhs_bits = new_rhs.range_known_bits(self);
+            let lhs_congruence = lhs_range.congruence;
+            let rhs_congruence = new_rhs.range_congruence(self);
             let lhs_range_fn = SolcRange::dyn_fn_from_op(op);
+            // updating the dependencies resets the facts the bounds were tightened by, which still
+            // hold for the same value
             lhs_range.update_deps(ctx, self);
+            lhs_range.known_bits = lhs_bits;
+            lhs_range.congruence = lhs_congruence;
             let mut new_var_range = lhs_range_fn(lhs_range.clone(), new_rhs, loc);
 
             if let Some(mut rhs_range) = new_rhs.range(self) {
                 rhs_range.update_deps(ctx, self);
+                rhs_range.known_bits = rhs_bits;
+                rhs_range.congruence = rhs_congruence;
                 let lhs_is_const = new_lhs.is_const(self);
                 let rhs_is_const = new_rhs.is_const(self);
                 match (lhs_is_const, rhs_is_const) {
@@ -352,10 +394,48 @@ pub trait Require: AnalyzerLike + Variable + BinOp + Sized {
                     }
                 }
             } else {
-                todo!(
-                    "Require: right hand side didnt have a range, likely invalid solidity - {:?}",
-                    new_rhs.underlying(self)
-                );
+                let reason = format!("requirement on `{}`", new_rhs.display_name(self));
+                self.unsupported(ctx, loc, reason);
+                return None;
+            }
+
+            // the requirement only tightened the bounds, so the sides are still in their intervals,
+            // still have the bits they were known to have and are still congruent to the same values
+            let in_intervals = [(new_lhs, lhs_intervals), (new_rhs, rhs_intervals)]
+                .into_iter()
+                .all(|(cvar, intervals)| match intervals {
+                    Some(intervals) => cvar.narrow_to_intervals(self, intervals),
+                    None => true,
+                });
+            let bits_hold = [(new_lhs, lhs_bits), (new_rhs, rhs_bits)].into_iter().all(
+                |(cvar, bits)| match bits {
+                    Some(bits) => {
+                        cvar.set_range_known_bits(self, Some(bits));
+                        !cvar
+                            .known_bits(self)
+                            .map(|bits| bits.is_conflicting())
+                            .unwrap_or(false)
+                    }
+                    None => true,
+                },
+            );
+            let congruences_hold = [(new_lhs, lhs_congruence), (new_rhs, rhs_congruence)]
+                .into_iter()
+                .all(|(cvar, congruence)| match congruence {
+                    Some(congruence) => {
+                        cvar.set_range_congruence(self, Some(congruence));
+                        cvar.range(self).map(|r| r.sat(self)).unwrap_or(true)
+                    }
+                    None => true,
+                });
+            if !in_intervals || !bits_hold || !congruences_hold {
+                ctx.kill(self, loc);
+                return None;
+            }
+
+            if self.require_relation(new_lhs, new_rhs, ctx, loc, op) {
+                ctx.kill(self, loc);
+                return None;
             }
 
             if let Some(backing_arr) = new_lhs.len_var_to_array(self) {
@@ -455,6 +535,23 @@ pub trait Require: AnalyzerLike + Variable + BinOp + Sized {
             }
 
             ctx.add_ctx_dep(cvar, self);
+
+            // intervals can't capture correlations between variables (i.e. `x < y && y < x`),
+            // so give the solver a chance to prove the path infeasible when the requirement
+            // relates more than one of them
+            #[cfg(feature = "smt")]
+            if self.relates_variables(new_lhs, new_rhs) {
+                let script = SmtScript::for_path(ctx, self);
+                let solver = &self.settings().solver;
+                let res = match self.smt_cache() {
+                    Some(cache) => cache.check(&script, solver),
+                    None => script.check(solver),
+                };
+                if res == SmtResult::Unsat {
+                    ctx.kill(self, loc);
+                    return None;
+                }
+            }
         }
 
         if let Some(tmp) = new_lhs.tmp_of(self) {
@@ -465,9 +562,194 @@ pub trait Require: AnalyzerLike + Variable + BinOp + Sized {
             }
         }
 
+        if matches!(op, RangeOp::Eq) && self.require_congruence(new_lhs, new_rhs, ctx, loc) {
+            ctx.kill(self, loc);
+            return None;
+        }
+
         tmp_cvar
     }
 
+    /// Narrows a variable to the disjoint intervals a disjunction of comparisons against literals
+    /// allows, i.e. `x < 10 || x > 100`. Returns whether the context was killed
+    fn require_disjunction(&mut self, loc: Loc, expr: &Expression, ctx: ContextNode) -> bool {
+        let (name, disjuncts) = match disjuncts(expr) {
+            Some(disjuncts) => disjuncts,
+            None => return false,
+        };
+        let cvar = match ctx.var_by_name_or_recurse(self, &name) {
+            Some(cvar) => cvar.latest_version(self),
+            None => return false,
+        };
+        let builtin = match &cvar.underlying(self).ty {
+            VarType::BuiltIn(bn, _) => bn.underlying(self).clone(),
+            _ => return false,
+        };
+        // a one of the variable's own type, to step past a strict bound
+        let one = match builtin {
+            Builtin::Int(size) => Elem::from(Concrete::Int(size, I256::from(1))),
+            Builtin::Uint(size) => Elem::from(Concrete::Uint(size, U256::from(1))),
+            _ => return false,
+        };
+        let ty_range = match SolcRange::try_from_builtin(&builtin) {
+            Some(range) => range,
+            None => return false,
+        };
+        let (ty_min, ty_max) = (ty_range.min, ty_range.max);
+
+        let mut intervals = vec![];
+        for (op, literal) in disjuncts {
+            let val = match self.parse_ctx_expr(literal, ctx).flatten() {
+                ExprRet::Single((_, idx)) | ExprRet::SingleLiteral((_, idx)) => {
+                    match ContextVarNode::from(idx)
+                        .evaled_range_min(self)
+                        .and_then(|min| min.maybe_concrete())
+                        .and_then(|c| c.val.cast(builtin.clone()))
+                    {
+                        Some(val) => Elem::from(val),
+                        None => return false,
+                    }
+                }
+                _ => return false,
+            };
+            let interval = match op {
+                RangeOp::Eq => Some((val.clone(), val)),
+                RangeOp::Gte => Some((val, ty_max.clone())),
+                RangeOp::Lte => Some((ty_min.clone(), val)),
+                RangeOp::Gt if matches!(val.range_ord(&ty_max), Some(Ordering::Less)) => {
+                    val.range_add(&one).map(|min| (min, ty_max.clone()))
+                }
+                RangeOp::Lt if matches!(val.range_ord(&ty_min), Some(Ordering::Greater)) => {
+                    val.range_sub(&one).map(|max| (ty_min.clone(), max))
+                }
+                RangeOp::Gt | RangeOp::Lt => continue,
+                _ => return false,
+            };
+            match interval {
+                Some(interval) => intervals.push(interval),
+                None => return false,
+            }
+        }
+
+        let new_cvar = self.advance_var_in_ctx(cvar, loc, ctx);
+        ctx.alias_var(new_cvar, cvar, self);
+        if !new_cvar.narrow_to_intervals(self, IntervalSet::from_intervals(intervals)) {
+            ctx.kill(self, loc);
+            return true;
+        }
+        false
+    }
+
+    /// Records the congruence a requirement like `x % 4 == 0` puts on `x`. Returns whether no
+    /// value of `x` satisfies it
+    fn require_congruence(
+        &mut self,
+        new_lhs: ContextVarNode,
+        new_rhs: ContextVarNode,
+        ctx: ContextNode,
+        loc: Loc,
+    ) -> bool {
+        let (tmp, remainder) = match (new_lhs.tmp_of(self), new_rhs.tmp_of(self)) {
+            (Some(tmp), _) if new_rhs.is_const(self) => (tmp, new_rhs),
+            (_, Some(tmp)) if new_lhs.is_const(self) => (tmp, new_lhs),
+            _ => return false,
+        };
+        let divisor = match (tmp.op, tmp.rhs) {
+            (RangeOp::Mod, Some(divisor)) if divisor.is_const(self) => divisor,
+            _ => return false,
+        };
+        if tmp.lhs.is_tmp(self) || tmp.lhs.is_const(self) {
+            return false;
+        }
+        let (divisor, remainder) = match (divisor.congruence(self), remainder.congruence(self)) {
+            (Some(divisor), Some(remainder))
+                if divisor.is_constant()
+                    && remainder.is_constant()
+                    && !divisor.residue.is_zero() =>
+            {
+                (divisor.residue, remainder.residue)
+            }
+            _ => return false,
+        };
+        if remainder >= divisor {
+            return true;
+        }
+
+        let cvar = match ctx.var_by_name_or_recurse(self, &tmp.lhs.name(self)) {
+            Some(cvar) => cvar.latest_version(self),
+            None => return false,
+        };
+        let congruence = Congruence::new(divisor, remainder);
+        let congruence = match cvar.range_congruence(self) {
+            Some(existing) => match existing.meet(&congruence) {
+                Some(congruence) => congruence,
+                None => return true,
+            },
+            None => congruence,
+        };
+        let new_cvar = self.advance_var_in_ctx(cvar, loc, ctx);
+        ctx.alias_var(new_cvar, cvar, self);
+        new_cvar.set_range_congruence(self, Some(congruence));
+        !new_cvar.range(self).map(|r| r.sat(self)).unwrap_or(true)
+    }
+
+    /// Adds the requirement to the relational facts of the context and narrows any variable the
+    /// new facts say something about. Returns whether the facts became unsatisfiable
+    fn require_relation(
+        &mut self,
+        new_lhs: ContextVarNode,
+        new_rhs: ContextVarNode,
+        ctx: ContextNode,
+        loc: Loc,
+        op: RangeOp,
+    ) -> bool {
+        let (lhs, rhs) = match (zone_term(new_lhs, self), zone_term(new_rhs, self)) {
+            (Some(lhs), Some(rhs)) => (lhs, rhs),
+            _ => return false,
+        };
+        if !ctx.add_relation(op, lhs, rhs, self) {
+            return true;
+        }
+
+        self.narrow_from_zone(new_lhs, loc, ctx, true);
+        self.narrow_from_zone(new_rhs, loc, ctx, true);
+
+        // propagate to the latest version of every other variable the facts are about
+        let zone = ctx.zone(self);
+        let names: BTreeSet<String> = zone
+            .vars()
+            .into_iter()
+            .flat_map(|rep| zone.aliases_of(rep))
+            .filter(|cvar| !cvar.is_tmp(self))
+            .map(|cvar| cvar.name(self))
+            .collect();
+        names.iter().for_each(|name| {
+            if let Some(cvar) = ctx.var_by_name_or_recurse(self, name) {
+                let cvar = cvar.latest_version(self);
+                if cvar != new_lhs
+                    && cvar != new_rhs
+                    && ctx.zone(self).vars().contains(&ctx.zone(self).rep(cvar))
+                {
+                    self.narrow_from_zone(cvar, loc, ctx, false);
+                }
+            }
+        });
+        false
+    }
+
+    /// Whether a requirement depends on more than one non-constant variable, the only case the
+    /// bounds of each variable on their own may not decide
+    fn relates_variables(&self, new_lhs: ContextVarNode, new_rhs: ContextVarNode) -> bool {
+        let vars: BTreeSet<ContextVarNode> = new_lhs
+            .dependent_on(self, true)
+            .into_iter()
+            .chain(new_rhs.dependent_on(self, true))
+            .filter(|cvar| !cvar.is_const(self))
+            .map(|cvar| cvar.first_version(self))
+            .collect();
+        vars.len() > 1
+    }
+
     /// Checks and returns whether the require statement is killable (i.e. impossible)
     fn const_killable(&mut self, op: RangeOp, lhs_range: SolcRange, rhs_range: SolcRange) -> bool {
         // check that the op is satisfied, return it as a bool
@@ -510,7 +792,8 @@ pub trait Require: AnalyzerLike + Variable + BinOp + Sized {
                     Some(Ordering::Greater)
                 )
             }
-            e => todo!("Non-comparator in require, {e:?}"),
+            // `require` only passes comparators
+            _ => false,
         }
     }
 
@@ -637,7 +920,8 @@ pub trait Require: AnalyzerLike + Variable + BinOp + Sized {
                 nonconst_var.set_range_max(self, elem);
                 false
             }
-            e => todo!("Non-comparator in require, {e:?}"),
+            // `require` only passes comparators
+            _ => false,
         }
     }
 
@@ -723,15 +1007,15 @@ pub trait Require: AnalyzerLike + Variable + BinOp + Sized {
                     return true;
                 }
 
-                let max_conc = max.maybe_concrete().unwrap_or_else(|| {
-                    self.open_dot();
-                    panic!("Was not concrete: {}", max.to_range_string(true, self).s)
-                });
-                let one = Concrete::one(&max_conc.val).expect("Cannot decrement range elem by one");
+                // without a concrete max there is no one to step by, so the bounds are kept
+                let one = match max.maybe_concrete().and_then(|max| Concrete::one(&max.val)) {
+                    Some(one) => one,
+                    None => return false,
+                };
 
                 // we add/sub one to the element because its strict >
                 new_lhs.set_range_min(self, rhs_elem + one.clone().into());
-                new_rhs.set_range_max(self, lhs_range.range_min() - one.into());
+                new_rhs.set_range_max(self, lhs_range.range_max() - one.into());
                 false
             }
             RangeOp::Gte => {
@@ -749,7 +1033,7 @@ pub trait Require: AnalyzerLike + Variable + BinOp + Sized {
                 }
 
                 new_lhs.set_range_min(self, rhs_elem);
-                new_rhs.set_range_max(self, lhs_range.range_min());
+                new_rhs.set_range_max(self, lhs_range.range_max());
                 false
             }
             RangeOp::Lt => {
@@ -770,7 +1054,7 @@ pub trait Require: AnalyzerLike + Variable + BinOp + Sized {
                 let one = Concrete::one(&min_conc.val).expect("Cannot decrement range elem by one");
 
                 new_lhs.set_range_max(self, rhs_elem - one.clone().into());
-                new_rhs.set_range_min(self, lhs_range.range_max() + one.into());
+                new_rhs.set_range_min(self, lhs_range.range_min() + one.into());
                 false
             }
             RangeOp::Lte => {
@@ -787,10 +1071,11 @@ pub trait Require: AnalyzerLike + Variable + BinOp + Sized {
                 }
 
                 new_lhs.set_range_max(self, rhs_elem);
-                new_rhs.set_range_max(self, lhs_range.range_min());
+                new_rhs.set_range_min(self, lhs_range.range_min());
                 false
             }
-            e => todo!("Non-comparator in require, {e:?}"),
+            // `require` only passes comparators
+            _ => false,
         }
     }
 
@@ -824,7 +1109,13 @@ pub trait Require: AnalyzerLike + Variable + BinOp + Sized {
                             }),
                         );
                     }
-                    _ => todo!(),
+                    _ => {
+                        if let Some(r) = new_underlying_lhs.underlying(self).ty.default_range(self)
+                        {
+                            new_underlying_lhs.set_range_min(self, r.min);
+                            new_underlying_lhs.set_range_max(self, r.max);
+                        }
+                    }
                 }
             }
         }
@@ -922,7 +1213,11 @@ pub trait Require: AnalyzerLike + Variable + BinOp + Sized {
                         );
                         (false, new_rhs)
                     }
-                    e => panic!("here {e:?}"),
+                    e => {
+                        let reason = format!("requirement through `{}`", e.to_string());
+                        self.unsupported(ctx, loc, reason);
+                        return;
+                    }
                 };
 
                 let new_underlying_rhs =
@@ -962,3 +1257,51 @@ pub trait Require: AnalyzerLike + Variable + BinOp + Sized {
         }
     }
 }
+
+/// Splits a disjunction of comparisons between a single variable and literals into the
+/// comparisons, oriented so the variable is on the left
+fn disjuncts(expr: &Expression) -> Option<(String, Vec<(RangeOp, &Expression)>)> {
+    let (op, lhs, rhs) = match expr {
+        Expression::Or(_, lhs, rhs) => {
+            let (lhs_name, mut lhs_disjuncts) = disjuncts(lhs)?;
+            let (rhs_name, rhs_disjuncts) = disjuncts(rhs)?;
+            if lhs_name != rhs_name {
+                return None;
+            }
+            lhs_disjuncts.extend(rhs_disjuncts);
+            return Some((lhs_name, lhs_disjuncts));
+        }
+        Expression::Parenthesis(_, inner) => return disjuncts(inner),
+        Expression::Less(_, lhs, rhs) => (RangeOp::Lt, lhs, rhs),
+        Expression::LessEqual(_, lhs, rhs) => (RangeOp::Lte, lhs, rhs),
+        Expression::More(_, lhs, rhs) => (RangeOp::Gt, lhs, rhs),
+        Expression::MoreEqual(_, lhs, rhs) => (RangeOp::Gte, lhs, rhs),
+        Expression::Equal(_, lhs, rhs) => (RangeOp::Eq, lhs, rhs),
+        _ => return None,
+    };
+
+    let is_literal = |expr: &Expression| match expr {
+        Expression::NumberLiteral(..) | Expression::HexNumberLiteral(..) => true,
+        Expression::Negate(_, inner) => matches!(
+            **inner,
+            Expression::NumberLiteral(..) | Expression::HexNumberLiteral(..)
+        ),
+        _ => false,
+    };
+    match (&**lhs, &**rhs) {
+        (Expression::Variable(ident), literal) if is_literal(literal) => {
+            Some((ident.name.clone(), vec![(op, literal)]))
+        }
+        (literal, Expression::Variable(ident)) if is_literal(literal) => {
+            let flipped = match op {
+                RangeOp::Lt => RangeOp::Gt,
+                RangeOp::Lte => RangeOp::Gte,
+                RangeOp::Gt => RangeOp::Lt,
+                RangeOp::Gte => RangeOp::Lte,
+                op => op,
+            };
+            Some((ident.name.clone(), vec![(flipped, literal)]))
+        }
+        _ => None,
+    }
+}
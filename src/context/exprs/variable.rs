@@ -15,6 +15,8 @@ pub trait Variable: AnalyzerLike<Expr = Expression> + Sized {
         tracing::trace!("Getting variable: {}, loc: {:?}", &ident.name, ident.loc);
         if let Some(cvar) = ctx.latest_var_by_name(self, &ident.name) {
             let var = self.advance_var_in_ctx(cvar, ident.loc, ctx);
+            ctx.alias_var(var, cvar, self);
+            let var = self.narrow_from_zone(var, ident.loc, ctx, true);
             ExprRet::Single((ctx, var.0.into()))
         } else if let Some(env) = self.env_variable(ident, ctx) {
             env
@@ -28,7 +30,9 @@ pub trait Variable: AnalyzerLike<Expr = Expression> + Sized {
                 Node::ContextVar(_) => {
                     let cvar = ContextVarNode::from(cvar).latest_version(self);
                     let mut ctx_cvar = self.advance_var_in_ctx(cvar, ident.loc, ctx);
+                    ctx.alias_var(ctx_cvar, cvar, self);
                     ctx_cvar.update_deps(ctx, self);
+                    let ctx_cvar = self.narrow_from_zone(ctx_cvar, ident.loc, ctx, true);
                     ExprRet::Single((ctx, ctx_cvar.0.into()))
                 }
                 _ => ExprRet::Single((ctx, cvar)),
@@ -36,11 +40,13 @@ pub trait Variable: AnalyzerLike<Expr = Expression> + Sized {
         } else if let Some(idx) = self.user_types().get(&ident.name) {
             let var = match ContextVar::maybe_from_user_ty(self, ident.loc, *idx) {
                 Some(v) => v,
-                None => panic!(
-                    "Could not create context variable from user type: {:?}, {:#?}",
-                    self.node(*idx),
-                    self.user_types()
-                ),
+                None => {
+                    return self.unsupported(
+                        ctx,
+                        ident.loc,
+                        format!("unresolved type `{}`", ident.name),
+                    )
+                }
             };
 
             // We assume a storage variable is 0 to start with
@@ -54,9 +60,23 @@ pub trait Variable: AnalyzerLike<Expr = Expression> + Sized {
             //         }
             //     }
             // }
+            // a constant is its initializer, evaluated in the context it is first read in
+            let init = match self.node(*idx) {
+                Node::Var(var) if var.is_const() => var.initializer_expr.clone(),
+                _ => None,
+            };
             let new_cvarnode = self.add_node(Node::ContextVar(var));
             self.add_edge(new_cvarnode, ctx, Edge::Context(ContextEdge::Variable));
-            ExprRet::Single((ctx, new_cvarnode))
+            if let Some(init) = init {
+                let rhs_paths = self.parse_ctx_expr(&init, ctx);
+                self.match_assign_sides(
+                    ident.loc,
+                    &ExprRet::Single((ctx, new_cvarnode)),
+                    &rhs_paths,
+                )
+            } else {
+                ExprRet::Single((ctx, new_cvarnode))
+            }
         } else if let Some(func) = self.builtin_fns().get(&ident.name) {
             let (inputs, outputs) = self
                 .builtin_fn_inputs()
@@ -78,7 +98,11 @@ pub trait Variable: AnalyzerLike<Expr = Expression> + Sized {
             .iter()
             .find(|func| func.name(self) == ident.name)
         {
-            panic!("here");
+            self.unsupported(
+                ctx,
+                ident.loc,
+                format!("function `{}` as a value", ident.name),
+            )
         } else {
             // println!("{}", self.dot_str());
             // self.open_dot();
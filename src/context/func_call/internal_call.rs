@@ -75,7 +75,11 @@ pub trait InternalFuncCaller: AnalyzerLike<Expr = Expression> + Sized + GraphLik
                 .copied()
                 .collect::<Vec<_>>();
             if possible_structs.is_empty() {
-                panic!("No functions or structs found for Named Function Call");
+                self.unsupported(
+                    ctx,
+                    *loc,
+                    format!("call of unknown function `{}`", ident.name),
+                )
             } else if possible_structs.len() == 1 {
                 let strukt = possible_structs[0];
                 let var = ContextVar::new_from_struct(*loc, strukt, ctx, self);
@@ -104,7 +108,8 @@ pub trait InternalFuncCaller: AnalyzerLike<Expr = Expression> + Sized + GraphLik
                 });
                 ExprRet::Single((ctx, cvar))
             } else {
-                todo!("Disambiguate struct construction");
+                let reason = format!("ambiguous struct construction `{}`", ident.name);
+                self.unsupported(ctx, *loc, reason)
             }
         } else if possible_funcs.len() == 1 {
             let func = possible_funcs[0];
@@ -125,7 +130,8 @@ pub trait InternalFuncCaller: AnalyzerLike<Expr = Expression> + Sized + GraphLik
             );
             self.setup_fn_call(&ident.loc, &inputs, func.into(), ctx, None)
         } else {
-            todo!("Disambiguate named function call");
+            let reason = format!("ambiguous named argument call of `{}`", ident.name);
+            self.unsupported(ctx, *loc, reason)
         }
     }
 
@@ -153,10 +159,13 @@ pub trait InternalFuncCaller: AnalyzerLike<Expr = Expression> + Sized + GraphLik
         if possible_funcs.is_empty() {
             // this is a builtin, cast, or unknown function?
             let (func_ctx, func_idx) = match self.parse_ctx_expr(func_expr, ctx) {
-                ExprRet::Single((ctx, idx)) => (ctx, idx),
+                ExprRet::Single((ctx, idx)) | ExprRet::SingleLiteral((ctx, idx)) => (ctx, idx),
                 m @ ExprRet::Multi(_) => m.expect_single(),
                 ExprRet::CtxKilled => return ExprRet::CtxKilled,
-                e => todo!("got fork in func call: {:?}", e),
+                e @ ExprRet::Unsupported(..) => return e,
+                ExprRet::Fork(..) => {
+                    return self.unsupported(ctx, *loc, "call of a function that forks")
+                }
             };
             self.intrinsic_func_call(loc, input_exprs, func_idx, func_ctx)
         } else if possible_funcs.len() == 1 {
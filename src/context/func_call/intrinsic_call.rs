@@ -5,17 +5,24 @@ use crate::{
     },
     ExprRet,
 };
+use ethers_core::{
+    types::{H256, U256},
+    utils::{id, keccak256},
+};
 use shared::{
     analyzer::{AnalyzerLike, GraphLike},
     context::*,
-    nodes::{Builtin, VarType},
+    nodes::{Builtin, Concrete, TypeNode, VarType},
     range::{
+        elem::RangeOp,
         elem_ty::{Dynamic, Elem},
+        range_ops::RangeLift,
         Range, SolcRange,
     },
     Edge, Node, NodeIdx,
 };
 
+use sha2::{Digest, Sha256};
 use solang_parser::pt::{Expression, Loc};
 
 impl<T> IntrinsicFuncCaller for T where T: AnalyzerLike<Expr = Expression> + Sized + GraphLike {}
@@ -34,65 +41,163 @@ pub trait IntrinsicFuncCaller: AnalyzerLike<Expr = Expression> + Sized + GraphLi
                 if let Some(func_name) = &underlying.name {
                     match &*func_name.name {
                         "abi.decode" => {
-                            // we skip the first because that is what is being decoded.
                             // TODO: check if we have a concrete bytes value
+                            let (data_ctx, data) =
+                                self.parse_ctx_expr(&input_exprs[0], ctx).expect_single();
+                            let data_len = match self.node(data) {
+                                Node::ContextVar(_) => Some(self.tmp_length(
+                                    ContextVarNode::from(data).latest_version(self),
+                                    data_ctx,
+                                    *loc,
+                                )),
+                                _ => None,
+                            };
                             let ret = self.parse_ctx_expr(&input_exprs[1], ctx);
                             fn match_decode(
                                 loc: &Loc,
                                 ret: ExprRet,
-                                analyzer: &mut (impl GraphLike + AnalyzerLike),
+                                data_len: Option<ContextVarNode>,
+                                analyzer: &mut impl ContextBuilder,
                             ) -> ExprRet {
                                 match ret {
-                                    ExprRet::Single((ctx, expect_builtin)) => {
-                                        match analyzer.node(expect_builtin) {
-                                            Node::Builtin(_) => {
-                                                let var = ContextVar::new_from_builtin(
-                                                    *loc,
-                                                    expect_builtin.into(),
-                                                    analyzer,
-                                                );
-                                                let node = analyzer.add_node(Node::ContextVar(var));
-                                                analyzer.add_edge(
-                                                    node,
+                                    ExprRet::Single((ctx, expect_ty))
+                                    | ExprRet::SingleLiteral((ctx, expect_ty)) => {
+                                        let mut var = match analyzer.node(expect_ty) {
+                                            Node::Builtin(_) => ContextVar::new_from_builtin(
+                                                *loc,
+                                                expect_ty.into(),
+                                                analyzer,
+                                            ),
+                                            Node::ContextVar(cvar) => match cvar.ty.clone() {
+                                                VarType::User(TypeNode::Struct(struct_node), _) => {
+                                                    ContextVar::new_from_struct(
+                                                        *loc,
+                                                        struct_node,
+                                                        ctx,
+                                                        analyzer,
+                                                    )
+                                                }
+                                                VarType::User(ty_node, _) => {
+                                                    match ContextVar::maybe_from_user_ty(
+                                                        analyzer,
+                                                        *loc,
+                                                        ty_node.into(),
+                                                    ) {
+                                                        Some(var) => var,
+                                                        None => {
+                                                            let reason = format!(
+                                                                "abi.decode into {}",
+                                                                ty_node.as_string(analyzer)
+                                                            );
+                                                            return analyzer
+                                                                .unsupported(ctx, *loc, reason);
+                                                        }
+                                                    }
+                                                }
+                                                ty => {
+                                                    let bn = analyzer
+                                                        .builtin_or_add(ty.as_builtin(analyzer))
+                                                        .into();
+                                                    ContextVar::new_from_builtin(*loc, bn, analyzer)
+                                                }
+                                            },
+                                            _ => {
+                                                return analyzer.unsupported(
                                                     ctx,
-                                                    Edge::Context(ContextEdge::Variable),
-                                                );
-                                                ExprRet::Single((ctx, node))
-                                            }
-                                            Node::ContextVar(cvar) => {
-                                                let bn = analyzer
-                                                    .builtin_or_add(cvar.ty.as_builtin(analyzer))
-                                                    .into();
-                                                let var = ContextVar::new_from_builtin(
-                                                    *loc, bn, analyzer,
-                                                );
-                                                let node = analyzer.add_node(Node::ContextVar(var));
-                                                analyzer.add_edge(
-                                                    node,
-                                                    ctx,
-                                                    Edge::Context(ContextEdge::Variable),
-                                                );
-                                                ExprRet::Single((ctx, node))
+                                                    *loc,
+                                                    "abi.decode into a non-type",
+                                                )
                                             }
-                                            e => todo!("Unhandled type in abi.decode: {e:?}"),
+                                        };
+                                        // give each decoded value its own name so that attributes
+                                        // (i.e. `.length`) of different decoded values are not shared
+                                        var.name = format!(
+                                            "tmp_decoded_{}_{}",
+                                            ctx.new_tmp(analyzer),
+                                            var.display_name
+                                        );
+                                        var.is_tmp = true;
+                                        // the length of a fixed size array is already known
+                                        let is_dyn = var.ty.is_dyn_builtin(analyzer)
+                                            && !var.ty.is_mapping(analyzer)
+                                            && var.ty.fixed_len(analyzer).is_none();
+                                        let node = analyzer.add_node(Node::ContextVar(var));
+                                        analyzer.add_edge(
+                                            node,
+                                            ctx,
+                                            Edge::Context(ContextEdge::Variable),
+                                        );
+
+                                        // the length of a decoded `bytes`, `string` or array
+                                        // cannot exceed the length of the input
+                                        if let (true, Some(data_len)) = (is_dyn, data_len) {
+                                            let len = analyzer.tmp_length(node.into(), ctx, *loc);
+                                            len.set_range_max(
+                                                analyzer,
+                                                Elem::Dynamic(Dynamic::new(
+                                                    data_len.latest_version(analyzer).into(),
+                                                    *loc,
+                                                )),
+                                            );
                                         }
+                                        ExprRet::Single((ctx, node))
                                     }
                                     ExprRet::Multi(inner) => ExprRet::Multi(
                                         inner
                                             .iter()
-                                            .map(|i| match_decode(loc, i.clone(), analyzer))
+                                            .map(|i| {
+                                                match_decode(loc, i.clone(), data_len, analyzer)
+                                            })
                                             .collect(),
                                     ),
-                                    e => panic!("This is invalid solidity: {:?}", e),
+                                    ExprRet::Fork(w1, w2) => ExprRet::Fork(
+                                        Box::new(match_decode(loc, *w1, data_len, analyzer)),
+                                        Box::new(match_decode(loc, *w2, data_len, analyzer)),
+                                    ),
+                                    e @ (ExprRet::CtxKilled | ExprRet::Unsupported(..)) => e,
                                 }
                             }
-                            match_decode(loc, ret, self)
+                            match_decode(loc, ret, data_len, self)
                         }
                         "abi.encode"
                         | "abi.encodePacked"
-                        | "abi.encodeCall"
+                        | "abi.encodeWithSelector"
                         | "abi.encodeWithSignature" => {
-                            // currently we dont support concrete abi encoding, TODO
+                            let func_name = func_name.name.clone();
+                            let encoded =
+                                self.parse_concrete_inputs(input_exprs, ctx)
+                                    .and_then(|vals| match &*func_name {
+                                        "abi.encode" => Some(Concrete::abi_encode(&vals)),
+                                        "abi.encodePacked" => Concrete::abi_encode_packed(&vals),
+                                        "abi.encodeWithSelector" => match vals.split_first()? {
+                                            (Concrete::Bytes(4, selector), args) => {
+                                                let mut encoded = selector.0[..4].to_vec();
+                                                encoded.extend(Concrete::abi_encode(args));
+                                                Some(encoded)
+                                            }
+                                            _ => None,
+                                        },
+                                        _ => match vals.split_first()? {
+                                            (Concrete::String(sig), args) => {
+                                                let mut encoded = id(sig).to_vec();
+                                                encoded.extend(Concrete::abi_encode(args));
+                                                Some(encoded)
+                                            }
+                                            _ => None,
+                                        },
+                                    });
+                            if let Some(encoded) = encoded {
+                                self.concrete_var(*loc, ctx, Concrete::DynBytes(encoded))
+                            } else {
+                                let bn = self.builtin_or_add(Builtin::DynamicBytes);
+                                let cvar = ContextVar::new_from_builtin(*loc, bn.into(), self);
+                                let node = self.add_node(Node::ContextVar(cvar));
+                                self.add_edge(node, ctx, Edge::Context(ContextEdge::Variable));
+                                ExprRet::Single((ctx, node))
+                            }
+                        }
+                        "abi.encodeCall" => {
+                            // currently we dont support concrete abi encoding of function calls, TODO
                             let bn = self.builtin_or_add(Builtin::DynamicBytes);
                             let cvar = ContextVar::new_from_builtin(*loc, bn.into(), self);
                             let node = self.add_node(Node::ContextVar(cvar));
@@ -123,6 +228,15 @@ pub trait IntrinsicFuncCaller: AnalyzerLike<Expr = Expression> + Sized + GraphLi
                             let len = self.tmp_length(arr, arr_ctx, *loc);
 
                             let len_as_idx = len.as_tmp(*loc, ctx, self);
+                            // increment the length
+                            let len = len.latest_version(self);
+                            let elem = Elem::Dynamic(Dynamic::new(len.into(), *loc));
+                            let one = Elem::from(Concrete::from(U256::from(1))).cast(elem);
+                            if let Some(r) = len.range(self) {
+                                let new_len = self.advance_var_in_ctx(len, *loc, arr_ctx);
+                                new_len.set_range_min(self, r.min + one.clone());
+                                new_len.set_range_max(self, r.max + one);
+                            }
                             // set length as index
                             let index = self.index_into_array_inner(
                                 *loc,
@@ -133,15 +247,104 @@ pub trait IntrinsicFuncCaller: AnalyzerLike<Expr = Expression> + Sized + GraphLi
                             let new_elem = self.parse_ctx_expr(&input_exprs[1], ctx);
                             self.match_assign_sides(*loc, &index, &new_elem)
                         }
-                        "keccak256" => {
-                            self.parse_ctx_expr(&input_exprs[0], ctx).expect_single();
-                            let var = ContextVar::new_from_builtin(
+                        "pop" => {
+                            let (arr_ctx, arr) = match input_exprs {
+                                [arr_expr] => match self.parse_ctx_expr(arr_expr, ctx) {
+                                    ExprRet::Single(arr) | ExprRet::SingleLiteral(arr) => arr,
+                                    e @ (ExprRet::Unsupported(..) | ExprRet::CtxKilled) => {
+                                        return e
+                                    }
+                                    _ => {
+                                        return self.unsupported(
+                                            ctx,
+                                            *loc,
+                                            "pop on more than one array",
+                                        )
+                                    }
+                                },
+                                _ => return self.unsupported(ctx, *loc, "pop with arguments"),
+                            };
+                            let arr = ContextVarNode::from(arr).latest_version(self);
+                            // get length
+                            let len = self.tmp_length(arr, arr_ctx, *loc);
+
+                            // fork on whether the array is empty, comparing against a zero made
+                            // in each fork so none is left behind in the parent
+                            let (empty_subctx, nonempty_subctx) = self.fork_ctx(*loc, arr_ctx);
+                            let zero =
+                                self.concrete_var(*loc, empty_subctx, Concrete::from(U256::zero()));
+                            self.handle_require_inner(
                                 *loc,
-                                self.builtin_or_add(Builtin::Bytes(32)).into(),
-                                self,
+                                &ExprRet::Single((empty_subctx, len.latest_version(self).into())),
+                                &zero,
+                                RangeOp::Eq,
+                                RangeOp::Neq,
+                                (RangeOp::Neq, RangeOp::Eq),
                             );
-                            let cvar = self.add_node(Node::ContextVar(var));
-                            ExprRet::Single((ctx, cvar))
+                            if !empty_subctx.is_killed(self) {
+                                tracing::trace!("pop on empty array is reachable");
+                                empty_subctx.panic(self, *loc, PanicCode::EmptyArrayPop, arr);
+                            }
+
+                            let zero = self.concrete_var(
+                                *loc,
+                                nonempty_subctx,
+                                Concrete::from(U256::zero()),
+                            );
+                            self.handle_require_inner(
+                                *loc,
+                                &ExprRet::Single((
+                                    nonempty_subctx,
+                                    len.latest_version(self).into(),
+                                )),
+                                &zero,
+                                RangeOp::Gt,
+                                RangeOp::Lt,
+                                (RangeOp::Lte, RangeOp::Gte),
+                            );
+                            if nonempty_subctx.is_killed(self) {
+                                return ExprRet::CtxKilled;
+                            }
+
+                            // decrement the length
+                            let len = self.tmp_length(arr, nonempty_subctx, *loc);
+                            let elem = Elem::Dynamic(Dynamic::new(len.into(), *loc));
+                            let one = Elem::from(Concrete::from(U256::from(1))).cast(elem);
+                            if let Some(r) = len.range(self) {
+                                let new_len = self.advance_var_in_ctx(len, *loc, nonempty_subctx);
+                                new_len.set_range_min(self, r.min - one.clone());
+                                new_len.set_range_max(self, r.max - one);
+                            }
+                            ExprRet::Multi(vec![])
+                        }
+                        "keccak256" | "sha256" => {
+                            let func_name = func_name.name.clone();
+                            let hashed = self
+                                .parse_concrete_inputs(input_exprs, ctx)
+                                .and_then(|vals| match &vals[..] {
+                                    [Concrete::DynBytes(bytes)] => Some(bytes.clone()),
+                                    [Concrete::String(s)] => Some(s.as_bytes().to_vec()),
+                                    _ => None,
+                                })
+                                .map(|bytes| {
+                                    if func_name == "keccak256" {
+                                        H256::from(keccak256(bytes))
+                                    } else {
+                                        H256::from_slice(&Sha256::digest(bytes))
+                                    }
+                                });
+                            if let Some(hash) = hashed {
+                                self.concrete_var(*loc, ctx, Concrete::Bytes(32, hash))
+                            } else {
+                                let var = ContextVar::new_from_builtin(
+                                    *loc,
+                                    self.builtin_or_add(Builtin::Bytes(32)).into(),
+                                    self,
+                                );
+                                let cvar = self.add_node(Node::ContextVar(var));
+                                self.add_edge(cvar, ctx, Edge::Context(ContextEdge::Variable));
+                                ExprRet::Single((ctx, cvar))
+                            }
                         }
                         "ecrecover" => {
                             input_exprs.iter().for_each(|expr| {
@@ -156,13 +359,16 @@ pub trait IntrinsicFuncCaller: AnalyzerLike<Expr = Expression> + Sized + GraphLi
                             let cvar = self.add_node(Node::ContextVar(var));
                             ExprRet::Single((ctx, cvar))
                         }
-                        e => todo!("builtin function: {:?}", e),
+                        e => {
+                            let reason = format!("builtin function `{e}`");
+                            self.unsupported(ctx, *loc, reason)
+                        }
                     }
                 } else {
-                    panic!("unnamed builtin?")
+                    self.unsupported(ctx, *loc, "unnamed builtin function")
                 }
             }
-            Node::Builtin(Builtin::Array(_)) => {
+            Node::Builtin(ty) if matches!(**ty, Builtin::Array(_)) => {
                 // create a new list
                 let (ctx, len_cvar) = self.parse_ctx_expr(&input_exprs[0], ctx).expect_single();
                 let ty = VarType::try_from_idx(self, func_idx);
@@ -216,16 +422,17 @@ pub trait IntrinsicFuncCaller: AnalyzerLike<Expr = Expression> + Sized + GraphLi
             }
             Node::Builtin(ty) => {
                 // it is a cast
-                let ty = ty.clone();
+                let ty = *ty.clone();
                 fn cast_match(
                     loc: &Loc,
-                    analyzer: &mut (impl GraphLike + AnalyzerLike),
+                    analyzer: &mut impl ContextBuilder,
                     ty: Builtin,
                     ret: ExprRet,
                     func_idx: NodeIdx,
                 ) -> ExprRet {
                     match ret {
                         ExprRet::CtxKilled => ExprRet::CtxKilled,
+                        e @ ExprRet::Unsupported(..) => e,
                         ExprRet::Single((ctx, cvar)) | ExprRet::SingleLiteral((ctx, cvar)) => {
                             let new_var = ContextVarNode::from(cvar).as_cast_tmp(
                                 *loc,
@@ -238,8 +445,13 @@ pub trait IntrinsicFuncCaller: AnalyzerLike<Expr = Expression> + Sized + GraphLi
                                 VarType::try_from_idx(analyzer, func_idx).expect("");
                             // cast the ranges
                             if let Some(r) = ContextVarNode::from(cvar).range(analyzer) {
-                                let curr_range =
-                                    SolcRange::try_from_builtin(&ty).expect("No default range");
+                                let curr_range = match SolcRange::try_from_builtin(&ty) {
+                                    Some(range) => range,
+                                    None => {
+                                        let reason = format!("cast to {}", ty.as_string(analyzer));
+                                        return analyzer.unsupported(ctx, *loc, reason);
+                                    }
+                                };
                                 new_var.set_range_min(
                                     analyzer,
                                     r.range_min().cast(curr_range.range_min()),
@@ -254,6 +466,16 @@ pub trait IntrinsicFuncCaller: AnalyzerLike<Expr = Expression> + Sized + GraphLi
                                     *range = range.clone().cast(curr_range.range_min());
                                 });
                                 new_var.set_range_exclusions(analyzer, exclusions);
+                                // a cast that keeps every value keeps the disjoint intervals too
+                                let intervals = r.intervals.and_then(|intervals| {
+                                    intervals.range_lift_cast(
+                                        &curr_range.range_min(),
+                                        &curr_range.range_max(),
+                                    )
+                                });
+                                if let Some(intervals) = intervals {
+                                    new_var.narrow_to_intervals(analyzer, intervals);
+                                }
                             }
 
                             ExprRet::Single((ctx, new_var.into()))
@@ -293,7 +515,40 @@ pub trait IntrinsicFuncCaller: AnalyzerLike<Expr = Expression> + Sized + GraphLi
 
                 ExprRet::Single((ctx, func_idx))
             }
-            e => todo!("{:?}", e),
+            _ => self.unsupported(ctx, *loc, "call of a non-function expression"),
         }
     }
+
+    /// Parses the inputs to a builtin function, returning their values if all of them are concrete
+    fn parse_concrete_inputs(
+        &mut self,
+        input_exprs: &[Expression],
+        ctx: ContextNode,
+    ) -> Option<Vec<Concrete>> {
+        let inputs: Vec<_> = input_exprs
+            .iter()
+            .map(|expr| self.parse_ctx_expr(expr, ctx).flatten())
+            .collect();
+        inputs
+            .into_iter()
+            .map(|input| match input {
+                ExprRet::Single((_, idx)) | ExprRet::SingleLiteral((_, idx)) => {
+                    match self.node(idx) {
+                        Node::ContextVar(_) => ContextVarNode::from(idx).maybe_concrete(self),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Adds a variable with a concrete value to the context
+    fn concrete_var(&mut self, loc: Loc, ctx: ContextNode, concrete: Concrete) -> ExprRet {
+        let concrete_node = self.add_node(Node::Concrete(concrete)).into();
+        let var = ContextVar::new_from_concrete(loc, concrete_node, self);
+        let node = self.add_node(Node::ContextVar(var));
+        self.add_edge(node, ctx, Edge::Context(ContextEdge::Variable));
+        ExprRet::Single((ctx, node))
+    }
 }
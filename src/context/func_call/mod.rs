@@ -20,6 +20,18 @@ pub mod internal_call;
 pub mod intrinsic_call;
 pub mod namespaced_call;
 
+/// A call into a function, with the inputs it is given
+pub struct FuncCall {
+    pub func_node: FunctionNode,
+    pub loc: Loc,
+    pub inputs: Vec<ContextVarNode>,
+    pub params: Vec<FunctionParamNode>,
+    /// Where the call is in running the modifiers of the function, if it is in them
+    pub modifier_state: Option<ModifierState>,
+    /// How the call reads in the source, to name the context it is made in
+    pub func_call_str: Option<String>,
+}
+
 impl<T> FuncCaller for T where T: AnalyzerLike<Expr = Expression> + Sized + GraphLike {}
 pub trait FuncCaller: GraphLike + AnalyzerLike<Expr = Expression> + Sized {
     #[tracing::instrument(level = "trace", skip_all)]
@@ -36,9 +48,7 @@ pub trait FuncCaller: GraphLike + AnalyzerLike<Expr = Expression> + Sized {
                 self.call_name_spaced_named_func(ctx, loc, member_expr, ident, input_exprs)
             }
             Variable(ident) => self.call_internal_named_func(ctx, loc, ident, input_exprs),
-            e => {
-                panic!("Cannot call intrinsic functions with named arguments. Call: {e:?}")
-            }
+            _ => self.unsupported(ctx, *loc, "builtin function call with named arguments"),
         }
     }
     #[tracing::instrument(level = "trace", skip_all)]
@@ -57,10 +67,13 @@ pub trait FuncCaller: GraphLike + AnalyzerLike<Expr = Expression> + Sized {
             Variable(ident) => self.call_internal_func(ctx, loc, ident, func_expr, input_exprs),
             _ => {
                 let (func_ctx, func_idx) = match self.parse_ctx_expr(func_expr, ctx) {
-                    ExprRet::Single((ctx, idx)) => (ctx, idx),
+                    ExprRet::Single((ctx, idx)) | ExprRet::SingleLiteral((ctx, idx)) => (ctx, idx),
                     m @ ExprRet::Multi(_) => m.expect_single(),
                     ExprRet::CtxKilled => return ExprRet::CtxKilled,
-                    e => todo!("got fork in func call: {:?}", e),
+                    e @ ExprRet::Unsupported(..) => return e,
+                    ExprRet::Fork(..) => {
+                        return self.unsupported(ctx, *loc, "call of a function that forks")
+                    }
                 };
                 self.intrinsic_func_call(loc, input_exprs, func_idx, func_ctx)
             }
@@ -78,9 +91,11 @@ pub trait FuncCaller: GraphLike + AnalyzerLike<Expr = Expression> + Sized {
         // try to find the function based on naive signature
         // This doesnt do type inference on NumberLiterals (i.e. 100 could be uintX or intX, and there could
         // be a function that takes an int256 but we evaled as uint256)
-        let fn_sig = format!("{}{}", fn_name, input_paths.try_as_func_input_str(self));
-        if let Some(func) = funcs.iter().find(|func| func.name(self) == fn_sig) {
-            return Some(*func);
+        if let Some(input_str) = input_paths.try_as_func_input_str(self) {
+            let fn_sig = format!("{fn_name}{input_str}");
+            if let Some(func) = funcs.iter().find(|func| func.name(self) == fn_sig) {
+                return Some(*func);
+            }
         }
 
         // filter by input len
@@ -145,10 +160,7 @@ pub trait FuncCaller: GraphLike + AnalyzerLike<Expr = Expression> + Sized {
         // if we have a single match thats our function
         let var = match ContextVar::maybe_from_user_ty(self, *loc, func_idx) {
             Some(v) => v,
-            None => panic!(
-                "Could not create context variable from user type: {:?}",
-                self.node(func_idx)
-            ),
+            None => return self.unsupported(ctx, *loc, "call of a non-function"),
         };
 
         // TODO: this is probably wrong
@@ -166,7 +178,7 @@ pub trait FuncCaller: GraphLike + AnalyzerLike<Expr = Expression> + Sized {
         if let Some(func_node) = ContextVarNode::from(new_cvarnode).ty(self).func_node(self) {
             self.func_call(ctx, *loc, inputs, func_node, func_call_str)
         } else {
-            unreachable!()
+            self.unsupported(ctx, *loc, "call of a non-function")
         }
     }
 
@@ -181,19 +193,25 @@ pub trait FuncCaller: GraphLike + AnalyzerLike<Expr = Expression> + Sized {
     ) -> ExprRet {
         let params = func.params(self);
         let input_paths = input_paths.clone().flatten();
+        if let Some(e) = input_paths.maybe_unsupported() {
+            return e.clone();
+        }
         match input_paths {
             ExprRet::Single((ctx, input_var)) | ExprRet::SingleLiteral((ctx, input_var)) => {
                 // if we get a single var, we expect the func to only take a single
                 // variable
+                let inputs = vec![ContextVarNode::from(input_var).latest_version(self)];
                 self.func_call_inner(
                     false,
                     ctx,
-                    func,
-                    loc,
-                    vec![ContextVarNode::from(input_var).latest_version(self)],
-                    params,
-                    None,
-                    func_call_str,
+                    FuncCall {
+                        func_node: func,
+                        loc,
+                        inputs,
+                        params,
+                        modifier_state: None,
+                        func_call_str,
+                    },
                 )
             }
             ExprRet::Multi(ref inputs) => {
@@ -211,45 +229,47 @@ pub trait FuncCaller: GraphLike + AnalyzerLike<Expr = Expression> + Sized {
                         self.func_call_inner(
                             false,
                             ctx,
-                            func,
-                            loc,
-                            input_vars,
-                            params,
-                            None,
-                            func_call_str,
+                            FuncCall {
+                                func_node: func,
+                                loc,
+                                inputs: input_vars,
+                                params,
+                                modifier_state: None,
+                                func_call_str,
+                            },
                         )
                     } else {
-                        panic!(
-                            "input has fork - need to flatten, {:?}, {:?}",
-                            params,
-                            input_paths.clone().flatten()
-                        )
+                        self.unsupported(ctx, loc, "call with forked inputs")
                     }
                 } else {
-                    panic!("Length mismatch: {inputs:?} {params:?}");
+                    let reason = format!(
+                        "call of `{}` with {} inputs for {} parameters",
+                        func.name(self),
+                        inputs.len(),
+                        params.len()
+                    );
+                    self.unsupported(ctx, loc, reason)
                 }
             }
             ExprRet::Fork(w1, w2) => ExprRet::Fork(
                 Box::new(self.func_call(ctx, loc, &w1, func, func_call_str.clone())),
                 Box::new(self.func_call(ctx, loc, &w2, func, func_call_str)),
             ),
-            e => todo!("here: {:?}", e),
+            e @ (ExprRet::CtxKilled | ExprRet::Unsupported(..)) => e,
         }
     }
 
     /// Checks if there are any modifiers and executes them prior to executing the function
     #[tracing::instrument(level = "trace", skip_all)]
-    fn func_call_inner(
-        &mut self,
-        entry_call: bool,
-        ctx: ContextNode,
-        func_node: FunctionNode,
-        loc: Loc,
-        inputs: Vec<ContextVarNode>,
-        params: Vec<FunctionParamNode>,
-        modifier_state: Option<ModifierState>,
-        func_call_str: Option<String>,
-    ) -> ExprRet {
+    fn func_call_inner(&mut self, entry_call: bool, ctx: ContextNode, call: FuncCall) -> ExprRet {
+        let FuncCall {
+            func_node,
+            loc,
+            inputs,
+            params,
+            modifier_state,
+            func_call_str,
+        } = call;
         let fn_ext = ctx.is_fn_ext(func_node, self);
         let callee_ctx = if entry_call {
             ctx
@@ -661,7 +681,7 @@ pub trait FuncCaller: GraphLike + AnalyzerLike<Expr = Expression> + Sized {
                                 let ret = self.parse_ctx_expr(expr, ctx);
                                 ret.try_as_func_input_str(self)
                             })
-                            .collect::<Vec<_>>()
+                            .collect::<Option<Vec<_>>>()?
                             .join(", ");
                         let _ = write!(mod_name, "{args_str}");
                     }
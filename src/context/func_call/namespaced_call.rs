@@ -12,6 +12,7 @@ use shared::{
     analyzer::{AnalyzerLike, GraphLike},
     context::ContextNode,
     nodes::*,
+    Node,
 };
 use solang_parser::pt::{Expression, Identifier, Loc, NamedArgument};
 
@@ -21,14 +22,13 @@ pub trait NameSpaceFuncCaller: AnalyzerLike<Expr = Expression> + Sized + GraphLi
     fn call_name_spaced_named_func(
         &mut self,
         ctx: ContextNode,
-        _loc: &Loc,
+        loc: &Loc,
         member_expr: &Expression,
         _ident: &Identifier,
         _input_args: &[NamedArgument],
     ) -> ExprRet {
-        let (_mem_ctx, _member) = self.parse_ctx_expr(member_expr, ctx).expect_single();
-
-        todo!("here");
+        let _ = self.parse_ctx_expr(member_expr, ctx);
+        self.unsupported(ctx, *loc, "member function call with named arguments")
     }
 
     #[tracing::instrument(level = "trace", skip_all)]
@@ -45,16 +45,26 @@ pub trait NameSpaceFuncCaller: AnalyzerLike<Expr = Expression> + Sized + GraphLi
         if let Variable(Identifier { name, .. }) = member_expr {
             if name == "abi" {
                 let func_name = format!("abi.{}", ident.name);
-                let as_fn = self
-                    .builtin_fns()
-                    .get(&func_name)
-                    .unwrap_or_else(|| panic!("No builtin function with name {func_name}"));
-                let fn_node = FunctionNode::from(self.add_node(as_fn.clone()));
+                let as_fn = match self.builtin_fns().get(&func_name) {
+                    Some(as_fn) => as_fn.clone(),
+                    None => {
+                        let reason = format!("builtin function `{func_name}`");
+                        return self.unsupported(ctx, *loc, reason);
+                    }
+                };
+                let fn_node = FunctionNode::from(self.add_node(as_fn));
                 return self.intrinsic_func_call(loc, input_exprs, fn_node.into(), ctx);
             }
         }
 
-        let (mem_ctx, member) = self.parse_ctx_expr(member_expr, ctx).expect_single();
+        let (mem_ctx, member) = match self.parse_ctx_expr(member_expr, ctx) {
+            e @ ExprRet::Unsupported(..) => return e,
+            e => e.expect_single(),
+        };
+        if let Node::Unresolved(unresolved) = self.node(member) {
+            let reason = format!("call on unresolved `{}`", unresolved.name);
+            return self.unsupported(ctx, *loc, reason);
+        }
         tracing::trace!(
             "namespaced function call: {:?}.{:?}(..)",
             ContextVarNode::from(member).display_name(self),
@@ -78,7 +88,17 @@ pub trait NameSpaceFuncCaller: AnalyzerLike<Expr = Expression> + Sized + GraphLi
         );
 
         if possible_funcs.is_empty() {
-            let as_input_str = ExprRet::Multi(inputs).try_as_func_input_str(self);
+            let inputs = ExprRet::Multi(inputs);
+            if let Some(e) = inputs.maybe_unsupported() {
+                return e.clone();
+            }
+            let as_input_str = match inputs.try_as_func_input_str(self) {
+                Some(as_input_str) => as_input_str,
+                None => {
+                    let reason = format!("call of `{}` with untyped inputs", ident.name);
+                    return self.unsupported(ctx, *loc, reason);
+                }
+            };
             let (func_ctx, func_idx) = match self
                 .parse_ctx_expr(
                     &MemberAccess(
@@ -93,10 +113,13 @@ pub trait NameSpaceFuncCaller: AnalyzerLike<Expr = Expression> + Sized + GraphLi
                 )
                 .flatten()
             {
-                ExprRet::Single((ctx, idx)) => (ctx, idx),
+                ExprRet::Single((ctx, idx)) | ExprRet::SingleLiteral((ctx, idx)) => (ctx, idx),
                 m @ ExprRet::Multi(_) => m.expect_single(),
                 ExprRet::CtxKilled => return ExprRet::CtxKilled,
-                e => todo!("got fork in func call: {:?}", e),
+                e @ ExprRet::Unsupported(..) => return e,
+                ExprRet::Fork(..) => {
+                    return self.unsupported(ctx, *loc, "call of a function that forks")
+                }
             };
             let mut modifierd_input_exprs = vec![member_expr.clone()];
             modifierd_input_exprs.extend(input_exprs.to_vec());
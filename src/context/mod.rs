@@ -5,13 +5,19 @@ use shared::context::*;
 
 use shared::range::elem_ty::Dynamic;
 
+use shared::range::elem::RangeElem;
 use shared::range::elem_ty::Elem;
 use shared::range::Range;
 use solang_parser::pt::VariableDeclaration;
 
 use crate::VarType;
 use petgraph::{visit::EdgeRef, Direction};
-use shared::{analyzer::AnalyzerLike, nodes::*, range::elem::RangeOp, Edge, Node, NodeIdx};
+use shared::{
+    analyzer::{AnalyzerError, AnalyzerLike},
+    nodes::*,
+    range::elem::RangeOp,
+    Edge, Node, NodeIdx,
+};
 use solang_parser::pt::{Expression, Loc, Statement};
 
 // pub mod func;
@@ -31,6 +37,9 @@ pub mod queries;
 #[derive(Debug, Clone)]
 pub enum ExprRet {
     CtxKilled,
+    /// A construct the analyzer doesn't handle, and why. The context it was hit in has been
+    /// marked and havocked, and the expressions depending on it are skipped
+    Unsupported(Loc, String),
     Single((ContextNode, NodeIdx)),
     SingleLiteral((ContextNode, NodeIdx)),
     Multi(Vec<ExprRet>),
@@ -60,6 +69,7 @@ impl ExprRet {
                 format!("({} || {})", w1.debug_str(analyzer), w2.debug_str(analyzer))
             }
             ExprRet::CtxKilled => "CtxKilled".to_string(),
+            ExprRet::Unsupported(_, reason) => format!("Unsupported({reason})"),
         }
     }
 
@@ -108,22 +118,36 @@ impl ExprRet {
         }
     }
 
-    pub fn try_as_func_input_str(&self, analyzer: &(impl GraphLike + AnalyzerLike)) -> String {
+    /// The parameter list of a function signature the return would be passed to, if every
+    /// part of it has a type
+    pub fn try_as_func_input_str(
+        &self,
+        analyzer: &(impl GraphLike + AnalyzerLike),
+    ) -> Option<String> {
         match self {
             ExprRet::Single(inner) | ExprRet::SingleLiteral(inner) => {
                 let (_, idx) = inner;
-                let var_ty = VarType::try_from_idx(analyzer, *idx)
-                    .unwrap_or_else(|| panic!("Non-typeable as type: {:?}", analyzer.node(*idx)));
-                format!("({})", var_ty.as_dot_str(analyzer))
+                let var_ty = VarType::try_from_idx(analyzer, *idx)?;
+                Some(format!("({})", var_ty.as_dot_str(analyzer)))
             }
             ExprRet::Multi(inner) if !self.has_fork() => {
                 let mut strs = vec![];
                 for ret in inner.iter() {
-                    strs.push(ret.try_as_func_input_str(analyzer).replace(['(', ')'], ""));
+                    strs.push(ret.try_as_func_input_str(analyzer)?.replace(['(', ')'], ""));
                 }
-                format!("({})", strs.join(", "))
+                Some(format!("({})", strs.join(", ")))
             }
-            _ => todo!("here"),
+            _ => None,
+        }
+    }
+
+    /// The first unsupported construct the return holds, if any
+    pub fn maybe_unsupported(&self) -> Option<&ExprRet> {
+        match self {
+            ExprRet::Unsupported(..) => Some(self),
+            ExprRet::Multi(inner) => inner.iter().find_map(ExprRet::maybe_unsupported),
+            ExprRet::Fork(w1, w2) => w1.maybe_unsupported().or_else(|| w2.maybe_unsupported()),
+            _ => None,
         }
     }
 
@@ -270,10 +294,14 @@ pub trait ContextBuilder: AnalyzerLike<Expr = Expression> + Sized + ExprParser {
                         // ctx_node
                         parent.into()
                     }
-                    e => todo!(
-                        "Expected a context to be created by a function or context but got: {:?}",
-                        e
-                    ),
+                    e => {
+                        // there is no context to mark, so the block is skipped
+                        tracing::error!(
+                            "Expected a context to be created by a function or context but got: {:?}",
+                            e
+                        );
+                        return;
+                    }
                 };
 
                 // optionally add named input and named outputs into context
@@ -298,6 +326,17 @@ pub trait ContextBuilder: AnalyzerLike<Expr = Expression> + Sized + ExprParser {
 
                             Some((*param_node, ContextVarNode::from(cvar_node)))
                         } else {
+                            if let (Some(name), Node::Unresolved(_)) =
+                                (&func_param.name, self.node(func_param.ty))
+                            {
+                                ContextNode::from(ctx_node).mark_unsupported(
+                                    self,
+                                    AnalyzerError::Unsupported(
+                                        func_param.loc,
+                                        format!("unresolved type of parameter `{}`", name.name),
+                                    ),
+                                );
+                            }
                             None
                         }
                     })
@@ -332,12 +371,14 @@ pub trait ContextBuilder: AnalyzerLike<Expr = Expression> + Sized + ExprParser {
                         .func_call_inner(
                             true,
                             ctx_node.into(),
-                            parent.into().into(),
-                            fn_loc,
-                            inputs,
-                            params,
-                            None,
-                            None,
+                            FuncCall {
+                                func_node: parent.into().into(),
+                                loc: fn_loc,
+                                inputs,
+                                params,
+                                modifier_state: None,
+                                func_call_str: None,
+                            },
                         )
                         .is_killed()
                     {
@@ -436,8 +477,11 @@ pub trait ContextBuilder: AnalyzerLike<Expr = Expression> + Sized + ExprParser {
                     );
                 }
             }
-            DoWhile(_loc, _while_stmt, _while_expr) => {
-                todo!("do while not supported");
+            DoWhile(loc, _while_stmt, _while_expr) => {
+                tracing::trace!("parsing do while");
+                if let Some(parent) = parent_ctx {
+                    self.unsupported(parent.into().into(), *loc, "do while loop");
+                }
             }
             Continue(_loc) => {
                 tracing::trace!("parsing continue");
@@ -461,16 +505,7 @@ pub trait ContextBuilder: AnalyzerLike<Expr = Expression> + Sized + ExprParser {
                         .expect("No context for variable definition?")
                         .into(),
                 );
-                let vars = ctx.local_vars(self);
-                vars.iter().for_each(|var| {
-                    // widen to max range
-                    let latest_var = var.latest_version(self);
-                    if let Some(r) = latest_var.underlying(self).ty.default_range(self) {
-                        let new_var = self.advance_var_in_ctx(latest_var, *loc, ctx);
-                        new_var.set_range_min(self, r.min);
-                        new_var.set_range_max(self, r.max);
-                    }
-                });
+                self.havoc_vars(ctx, *loc);
             }
             Return(loc, maybe_ret_expr) => {
                 tracing::trace!("parsing return");
@@ -513,9 +548,11 @@ pub trait ContextBuilder: AnalyzerLike<Expr = Expression> + Sized + ExprParser {
                     }
                 }
             }
-            RevertNamedArgs(_loc, _maybe_err_path, _named_args) => {
+            RevertNamedArgs(loc, _maybe_err_path, _named_args) => {
                 tracing::trace!("parsing named revert");
-                todo!("revert named args")
+                if let Some(parent) = parent_ctx {
+                    self.unsupported(parent.into().into(), *loc, "revert with named arguments");
+                }
             }
             Emit(_loc, _emit_expr) => {}
             Try(_loc, _try_expr, _maybe_returns, _clauses) => {}
@@ -556,7 +593,7 @@ pub trait ContextBuilder: AnalyzerLike<Expr = Expression> + Sized + ExprParser {
 
     fn return_match(&mut self, loc: &Loc, paths: &ExprRet) {
         match paths {
-            ExprRet::CtxKilled => {}
+            ExprRet::CtxKilled | ExprRet::Unsupported(..) => {}
             ExprRet::Single((ctx, expr)) | ExprRet::SingleLiteral((ctx, expr)) => {
                 self.add_edge(
                     ContextVarNode::from(*expr).latest_version(self),
@@ -585,7 +622,17 @@ pub trait ContextBuilder: AnalyzerLike<Expr = Expression> + Sized + ExprParser {
         rhs_paths: Option<&ExprRet>,
     ) -> bool {
         match (lhs_paths, rhs_paths) {
+            // an unsupported type leaves nothing to declare, and an unsupported value leaves the
+            // variable unconstrained
+            (ExprRet::Unsupported(..), _) => false,
+            (l, Some(ExprRet::Unsupported(..))) => self.match_var_def(var_decl, loc, l, None),
             (ExprRet::CtxKilled, _) | (_, Some(ExprRet::CtxKilled)) => true,
+            (ExprRet::Single((lhs_ctx, ty)), _) if VarType::try_from_idx(self, *ty).is_none() => {
+                let name = var_decl.name.as_ref().map(|name| name.name.as_str());
+                let reason = format!("variable `{}` of unknown type", name.unwrap_or("_"));
+                self.unsupported(*lhs_ctx, loc, reason);
+                false
+            }
             (ExprRet::Single((_lhs_ctx, ty)), Some(ExprRet::SingleLiteral((rhs_ctx, rhs)))) => {
                 let ty = VarType::try_from_idx(self, *ty).expect("Not a known type");
                 let rhs_cvar = ContextVarNode::from(*rhs).latest_version(self);
@@ -675,13 +722,22 @@ pub trait ContextBuilder: AnalyzerLike<Expr = Expression> + Sized + ExprParser {
                 self.match_var_def(var_decl, loc, m, Some(world1))
                     && self.match_var_def(var_decl, loc, m, Some(world2))
             }
-            (e, f) => todo!("any: {:?} {:?}", e, f),
+            (ExprRet::Fork(world1, world2), r) => {
+                self.match_var_def(var_decl, loc, world1, r)
+                    && self.match_var_def(var_decl, loc, world2, r)
+            }
+            (ExprRet::Multi(lhs_sides), r) => lhs_sides
+                .iter()
+                .all(|expr_ret| self.match_var_def(var_decl, loc, expr_ret, r)),
+            (ExprRet::SingleLiteral(inner), r) => {
+                self.match_var_def(var_decl, loc, &ExprRet::Single(*inner), r)
+            }
         }
     }
 
     fn match_expr(&mut self, paths: &ExprRet) {
         match paths {
-            ExprRet::CtxKilled => {}
+            ExprRet::CtxKilled | ExprRet::Unsupported(..) => {}
             ExprRet::Single((ctx, expr)) | ExprRet::SingleLiteral((ctx, expr)) => {
                 self.add_edge(*expr, *ctx, Edge::Context(ContextEdge::Call));
             }
@@ -734,15 +790,17 @@ pub trait ContextBuilder: AnalyzerLike<Expr = Expression> + Sized + ExprParser {
             BoolLiteral(loc, b) => self.bool_literal(ctx, *loc, *b),
             HexNumberLiteral(loc, b, _unit) => self.hex_num_literal(ctx, *loc, b, false),
             HexLiteral(hexes) => self.hex_literals(ctx, hexes),
-            RationalNumberLiteral(_, _, _, _, _) => todo!("Rational literal"),
+            RationalNumberLiteral(loc, ..) => {
+                self.unsupported(ctx, *loc, "rational number literal")
+            }
             Negate(_loc, expr) => match &**expr {
                 NumberLiteral(loc, int, exp, _unit) => {
                     self.number_literal(ctx, *loc, int, exp, true)
                 }
                 HexNumberLiteral(loc, b, _unit) => self.hex_num_literal(ctx, *loc, b, true),
-                e => todo!("UnaryMinus unexpected rhs: {e:?}"),
+                _ => self.unsupported(ctx, *_loc, "negation of a non-literal"),
             },
-            UnaryPlus(_loc, e) => todo!("UnaryPlus unexpected rhs: {e:?}"),
+            UnaryPlus(loc, _e) => self.unsupported(ctx, *loc, "unary plus"),
 
             // Binary ops
             Power(loc, lhs_expr, rhs_expr) => {
@@ -813,8 +871,8 @@ pub trait ContextBuilder: AnalyzerLike<Expr = Expression> + Sized + ExprParser {
             AssignOr(loc, lhs_expr, rhs_expr) => {
                 self.op_expr(*loc, lhs_expr, rhs_expr, ctx, RangeOp::BitOr, true)
             }
-            BitwiseNot(_loc, _lhs_expr) => {
-                todo!("Bitwise not")
+            BitwiseNot(loc, _lhs_expr) => {
+                self.unsupported(ctx, *loc, "bitwise not")
                 // self.op_expr(*loc, lhs_expr, rhs_expr, ctx, RangeOp::BitNot, false)
             }
 
@@ -826,8 +884,10 @@ pub trait ContextBuilder: AnalyzerLike<Expr = Expression> + Sized + ExprParser {
             ArraySubscript(loc, ty_expr, Some(index_expr)) => {
                 self.index_into_array(*loc, ty_expr, index_expr, ctx)
             }
-            ArraySlice(_loc, _lhs_expr, _maybe_middle_expr, _maybe_rhs) => todo!("Array slice"),
-            ArrayLiteral(_, _) => todo!("Array literal"),
+            ArraySlice(loc, _lhs_expr, _maybe_middle_expr, _maybe_rhs) => {
+                self.unsupported(ctx, *loc, "array slice")
+            }
+            ArrayLiteral(loc, _) => self.unsupported(ctx, *loc, "array literal"),
 
             // Comparator
             Equal(loc, lhs, rhs) => self.cmp(*loc, lhs, RangeOp::Eq, rhs, ctx),
@@ -843,7 +903,9 @@ pub trait ContextBuilder: AnalyzerLike<Expr = Expression> + Sized + ExprParser {
             Or(loc, lhs, rhs) => self.cmp(*loc, lhs, RangeOp::Or, rhs, ctx),
 
             // Function calls
-            FunctionCallBlock(_loc, _func_expr, _input_exprs) => todo!("Function call block"),
+            FunctionCallBlock(loc, _func_expr, _input_exprs) => {
+                self.unsupported(ctx, *loc, "function call with options, e.g. `{value: ..}`")
+            }
             NamedFunctionCall(loc, func_expr, input_args) => {
                 self.named_fn_call_expr(ctx, loc, func_expr, input_args)
             }
@@ -866,14 +928,60 @@ pub trait ContextBuilder: AnalyzerLike<Expr = Expression> + Sized + ExprParser {
                 let ret = self.parse_ctx_expr(expr, ctx);
                 fn delete_match(
                     loc: &Loc,
-                    analyzer: &mut (impl GraphLike + AnalyzerLike<Expr = Expression>),
+                    analyzer: &mut impl ContextBuilder,
                     ret: ExprRet,
                 ) -> ExprRet {
                     match ret {
                         ExprRet::CtxKilled => ExprRet::CtxKilled,
+                        e @ ExprRet::Unsupported(..) => e,
                         ExprRet::Single((ctx, cvar)) | ExprRet::SingleLiteral((ctx, cvar)) => {
-                            let mut new_var = analyzer.advance_var_in_ctx(cvar.into(), *loc, ctx);
+                            let cvar = ContextVarNode::from(cvar).latest_version(analyzer);
+                            // `delete arr` on a dynamic array also resets its length, while a
+                            // fixed size array keeps its own
+                            let ty = &cvar.underlying(analyzer).ty;
+                            let len = if ty.is_dyn_builtin(analyzer)
+                                && ty.fixed_len(analyzer).is_none()
+                                && !cvar.is_mapping(analyzer)
+                            {
+                                Some(analyzer.tmp_length(cvar, ctx, *loc))
+                            } else {
+                                None
+                            };
+
+                            let mut new_var = analyzer.advance_var_in_ctx(
+                                cvar.latest_version(analyzer),
+                                *loc,
+                                ctx,
+                            );
                             new_var.sol_delete_range(analyzer);
+
+                            // as does every element read from the array so far
+                            if new_var.underlying(analyzer).ty.is_dyn_builtin(analyzer) {
+                                ctx.index_access_vars(analyzer, new_var)
+                                    .into_iter()
+                                    .for_each(|access| {
+                                        let mut new_access =
+                                            analyzer.advance_var_in_ctx(access, *loc, ctx);
+                                        new_access.sol_delete_range(analyzer);
+                                    });
+                            }
+
+                            if let Some(len) = len {
+                                let new_len = analyzer.advance_var_in_ctx(
+                                    len.latest_version(analyzer),
+                                    *loc,
+                                    ctx,
+                                );
+                                let zero = Elem::from(Concrete::from(U256::zero()));
+                                new_len.set_range_min(analyzer, zero.clone());
+                                new_len.set_range_max(analyzer, zero);
+                            }
+
+                            // `delete arr[i]` zeroes the element, so keys that alias `i` are
+                            // updated as if zero had been written
+                            if new_var.index_to_array(analyzer).is_some() {
+                                analyzer.update_index_aliases(*loc, new_var, new_var, ctx);
+                            }
                             ExprRet::Single((ctx, new_var.into()))
                         }
                         ExprRet::Multi(inner) => ExprRet::Multi(
@@ -911,17 +1019,17 @@ pub trait ContextBuilder: AnalyzerLike<Expr = Expression> + Sized + ExprParser {
 
             // Misc.
             Variable(ident) => self.variable(ident, ctx),
-            Type(_loc, ty) => {
+            Type(loc, ty) => {
                 if let Some(builtin) = Builtin::try_from_ty(ty.clone(), self) {
                     if let Some(idx) = self.builtins().get(&builtin) {
                         ExprRet::Single((ctx, *idx))
                     } else {
-                        let idx = self.add_node(Node::Builtin(builtin.clone()));
+                        let idx = self.add_node(Node::Builtin(Box::new(builtin.clone())));
                         self.builtins_mut().insert(builtin, idx);
                         ExprRet::Single((ctx, idx))
                     }
                 } else {
-                    todo!("??")
+                    self.unsupported(ctx, *loc, format!("type {ty:?}"))
                 }
             }
             Parenthesis(_loc, expr) => self.parse_ctx_expr(expr, ctx),
@@ -936,7 +1044,7 @@ pub trait ContextBuilder: AnalyzerLike<Expr = Expression> + Sized + ExprParser {
         rhs: &ExprRet,
     ) -> ExprRet {
         match rhs {
-            ExprRet::CtxKilled => ExprRet::CtxKilled,
+            ExprRet::CtxKilled | ExprRet::Unsupported(..) => rhs.clone(),
             ExprRet::SingleLiteral((ctx, var)) => {
                 ContextVarNode::from(*var).try_increase_size(self);
                 self.match_in_de_crement(pre, increment, loc, &ExprRet::Single((*ctx, *var)))
@@ -970,7 +1078,7 @@ pub trait ContextBuilder: AnalyzerLike<Expr = Expression> + Sized + ExprParser {
                         ExprRet::Single((*ctx, cvar.into()))
                     }
                 } else {
-                    panic!("No range in post-increment")
+                    self.unsupported(*ctx, loc, "increment of a variable without a range")
                 }
             }
             ExprRet::Multi(inner) => ExprRet::Multi(
@@ -1005,6 +1113,7 @@ pub trait ContextBuilder: AnalyzerLike<Expr = Expression> + Sized + ExprParser {
         rhs_paths: &ExprRet,
     ) -> ExprRet {
         match (lhs_paths, rhs_paths) {
+            (e @ ExprRet::Unsupported(..), _) | (_, e @ ExprRet::Unsupported(..)) => e.clone(),
             (ExprRet::CtxKilled, _) | (_, ExprRet::CtxKilled) => ExprRet::CtxKilled,
             (ExprRet::Single((_lhs_ctx, lhs)), ExprRet::SingleLiteral((rhs_ctx, rhs))) => {
                 let lhs_cvar = ContextVarNode::from(*lhs).latest_version(self);
@@ -1072,7 +1181,19 @@ pub trait ContextBuilder: AnalyzerLike<Expr = Expression> + Sized + ExprParser {
                 Box::new(self.match_assign_sides(loc, m, world1)),
                 Box::new(self.match_assign_sides(loc, m, world2)),
             ),
-            (e, f) => todo!("any: {:?} {:?}", e, f),
+            (ExprRet::SingleLiteral(lhs), r) => {
+                self.match_assign_sides(loc, &ExprRet::Single(*lhs), r)
+            }
+            (ExprRet::Fork(world1, world2), r) => ExprRet::Fork(
+                Box::new(self.match_assign_sides(loc, world1, r)),
+                Box::new(self.match_assign_sides(loc, world2, r)),
+            ),
+            (ExprRet::Multi(lhs_sides), r) => ExprRet::Multi(
+                lhs_sides
+                    .iter()
+                    .map(|expr_ret| self.match_assign_sides(loc, expr_ret, r))
+                    .collect(),
+            ),
         }
     }
 
@@ -1091,17 +1212,29 @@ pub trait ContextBuilder: AnalyzerLike<Expr = Expression> + Sized + ExprParser {
 
         let new_lhs = self.advance_var_in_ctx(lhs_cvar.latest_version(self), loc, ctx);
         if !lhs_cvar.ty_eq(&rhs_cvar, self) {
-            let cast_to_min = lhs_cvar
-                .range_min(self)
-                .unwrap_or_else(|| panic!("No range during cast? {:?}", lhs_cvar.underlying(self)));
-            let cast_to_max = lhs_cvar
-                .range_max(self)
-                .unwrap_or_else(|| panic!("No range during cast? {:?}", lhs_cvar.underlying(self)));
+            let (cast_to_min, cast_to_max) =
+                match (lhs_cvar.range_min(self), lhs_cvar.range_max(self)) {
+                    (Some(min), Some(max)) => (min, max),
+                    _ => {
+                        return self.unsupported(
+                            ctx,
+                            loc,
+                            format!("assignment to `{}`", lhs_cvar.display_name(self)),
+                        )
+                    }
+                };
             let _ = new_lhs.try_set_range_min(self, new_lower_bound.cast(cast_to_min));
             let _ = new_lhs.try_set_range_max(self, new_upper_bound.cast(cast_to_max));
         } else {
             let _ = new_lhs.try_set_range_min(self, new_lower_bound);
             let _ = new_lhs.try_set_range_max(self, new_upper_bound);
+            ctx.alias_var(new_lhs, rhs_cvar.latest_version(self), self);
+            let intervals = rhs_cvar.latest_version(self).range_intervals(self);
+            new_lhs.set_range_intervals(self, intervals);
+            let known_bits = rhs_cvar.latest_version(self).range_known_bits(self);
+            new_lhs.set_range_known_bits(self, known_bits);
+            let congruence = rhs_cvar.latest_version(self).range_congruence(self);
+            new_lhs.set_range_congruence(self, congruence);
         }
         if let Some(rhs_range) = rhs_cvar.range(self) {
             new_lhs.try_set_range_exclusions(self, rhs_range.exclusions);
@@ -1133,9 +1266,73 @@ pub trait ContextBuilder: AnalyzerLike<Expr = Expression> + Sized + ExprParser {
                     }
                 }
             }
+
+            self.update_index_aliases(loc, new_lhs, rhs_cvar.latest_version(self), ctx);
         }
 
-        ExprRet::Single((ctx, new_lhs.into()))
+        // carry over the length of a dynamic rhs, if it has been constrained
+        if rhs_cvar.underlying(self).ty.is_dyn_builtin(self) && !rhs_cvar.is_mapping(self) {
+            let rhs_len_name = format!("{}.length", rhs_cvar.first_version(self).name(self));
+            if let Some(rhs_len) = ctx.var_by_name_or_recurse(self, &rhs_len_name) {
+                let lhs_len = self.tmp_length(new_lhs, ctx, loc).latest_version(self);
+                let new_lhs_len = self.advance_var_in_ctx(lhs_len, loc, ctx);
+                let rhs_len_elem =
+                    Elem::Dynamic(Dynamic::new(rhs_len.latest_version(self).into(), loc));
+                new_lhs_len.set_range_min(self, rhs_len_elem.clone());
+                new_lhs_len.set_range_max(self, rhs_len_elem);
+            }
+        }
+
+        ExprRet::Single((ctx, new_lhs.latest_version(self).into()))
+    }
+
+    /// Marks `ctx` as having hit a construct the analyzer doesn't handle and havocs its
+    /// variables, so that the analysis can carry on past it knowing nothing about them
+    fn unsupported(&mut self, ctx: ContextNode, loc: Loc, reason: impl Into<String>) -> ExprRet {
+        let reason = reason.into();
+        tracing::debug!("unsupported in {}: {reason}", ctx.path(self));
+        ctx.mark_unsupported(self, AnalyzerError::Unsupported(loc, reason.clone()));
+        self.havoc_vars(ctx, loc);
+        ExprRet::Unsupported(loc, reason)
+    }
+
+    /// Widens every variable of `ctx` to the full range of its type
+    fn havoc_vars(&mut self, ctx: ContextNode, loc: Loc) {
+        let vars = ctx.local_vars(self);
+        vars.iter().for_each(|var| {
+            // widen to max range
+            let latest_var = var.latest_version(self);
+            if let Some(r) = latest_var.underlying(self).ty.default_range(self) {
+                let new_var = self.advance_var_in_ctx(latest_var, loc, ctx);
+                new_var.set_range_min(self, r.min);
+                new_var.set_range_max(self, r.max);
+            }
+        });
+    }
+
+    /// Forks `ctx` into two subcontexts at `loc`, returning the pair of forks
+    fn fork_ctx(&mut self, loc: Loc, ctx: ContextNode) -> (ContextNode, ContextNode) {
+        let lhs_subctx = ContextNode::from(self.add_node(Node::Context(Context::new_subctx(
+            ctx, loc, true, None, false, self, None,
+        ))));
+        ctx.add_fork(lhs_subctx, self);
+        let rhs_subctx = ContextNode::from(self.add_node(Node::Context(Context::new_subctx(
+            ctx, loc, true, None, false, self, None,
+        ))));
+        ctx.add_fork(rhs_subctx, self);
+        let ctx_fork = self.add_node(Node::ContextFork);
+        self.add_edge(ctx_fork, ctx, Edge::Context(ContextEdge::ContextFork));
+        self.add_edge(
+            NodeIdx::from(lhs_subctx.0),
+            ctx_fork,
+            Edge::Context(ContextEdge::Subcontext),
+        );
+        self.add_edge(
+            NodeIdx::from(rhs_subctx.0),
+            ctx_fork,
+            Edge::Context(ContextEdge::Subcontext),
+        );
+        (lhs_subctx, rhs_subctx)
     }
 
     fn advance_var_in_ctx(
@@ -1153,6 +1350,11 @@ pub trait ContextBuilder: AnalyzerLike<Expr = Expression> + Sized + ExprParser {
         if let Some(old_ctx) = cvar_node.maybe_ctx(self) {
             if old_ctx != ctx {
                 self.add_edge(new_cvarnode, ctx, Edge::Context(ContextEdge::Variable));
+                // an index access keeps the array and key version it was made with
+                if let Some((arr, key)) = cvar_node.index_access_parts(self) {
+                    self.add_edge(new_cvarnode, arr, Edge::Context(ContextEdge::IndexAccess));
+                    self.add_edge(key, new_cvarnode, Edge::Context(ContextEdge::Index));
+                }
             } else {
                 self.add_edge(new_cvarnode, cvar_node.0, Edge::Context(ContextEdge::Prev));
             }
@@ -1163,6 +1365,97 @@ pub trait ContextBuilder: AnalyzerLike<Expr = Expression> + Sized + ExprParser {
         ContextVarNode::from(new_cvarnode)
     }
 
+    /// Narrows the range of `cvar` with the relational facts `ctx` knows about it. If `in_place`
+    /// is false, the narrowed range is put on a new version of the variable instead
+    fn narrow_from_zone(
+        &mut self,
+        cvar: ContextVarNode,
+        loc: Loc,
+        ctx: ContextNode,
+        in_place: bool,
+    ) -> ContextVarNode {
+        let zone = ctx.zone(self);
+        if zone.is_empty() {
+            return cvar;
+        }
+        let rep = zone.rep(cvar);
+        if !zone.vars().contains(&rep) {
+            return cvar;
+        }
+        let (min, max) = zone.bounds_of(rep);
+        let neqs = zone.neqs_of(rep);
+
+        let builtin = match &cvar.underlying(self).ty {
+            VarType::BuiltIn(bn, _) => bn.underlying(self).clone(),
+            _ => return cvar,
+        };
+        let range = match cvar.range(self) {
+            Some(range) => range,
+            None => return cvar,
+        };
+
+        let new_min = min
+            .and_then(|min| i128_to_concrete(min, &builtin))
+            .map(Elem::from)
+            .filter(|min| {
+                matches!(
+                    min.range_ord(&range.evaled_range_min(self)),
+                    Some(std::cmp::Ordering::Greater)
+                )
+            });
+        let new_max = max
+            .and_then(|max| i128_to_concrete(max, &builtin))
+            .map(Elem::from)
+            .filter(|max| {
+                matches!(
+                    max.range_ord(&range.evaled_range_max(self)),
+                    Some(std::cmp::Ordering::Less)
+                )
+            });
+        let new_excls: Vec<_> = neqs
+            .into_iter()
+            .filter_map(|c| i128_to_concrete(c, &builtin))
+            .map(Elem::from)
+            .filter(|excl| !range.exclusions.contains(excl))
+            .collect();
+
+        if new_min.is_none() && new_max.is_none() && new_excls.is_empty() {
+            return cvar;
+        }
+
+        let new_cvar = if in_place {
+            cvar
+        } else {
+            let new_cvar = self.advance_var_in_ctx(cvar.latest_version(self), loc, ctx);
+            ctx.alias_var(new_cvar, cvar, self);
+            new_cvar
+        };
+        let intervals = range.intervals.clone();
+        let known_bits = range.known_bits;
+        let congruence = range.congruence;
+        if let Some(min) = new_min {
+            new_cvar.set_range_min(self, min);
+        }
+        if let Some(max) = new_max {
+            new_cvar.set_range_max(self, max);
+        }
+        if !new_excls.is_empty() {
+            let mut excls = range.exclusions;
+            excls.extend(new_excls);
+            new_cvar.set_range_exclusions(self, excls);
+        }
+        if let Some(intervals) = intervals {
+            new_cvar.narrow_to_intervals(self, intervals);
+        }
+        if known_bits.is_some() {
+            new_cvar.set_range_known_bits(self, known_bits);
+        }
+        if congruence.is_some() {
+            new_cvar.set_range_congruence(self, congruence);
+        }
+        new_cvar
+    }
+
     fn advance_var_underlying(&mut self, cvar_node: ContextVarNode, loc: Loc) -> &mut ContextVar {
         assert_eq!(None, cvar_node.next_version(self));
         let mut new_cvar = cvar_node.latest_version(self).underlying(self).clone();
@@ -13,11 +13,21 @@ use std::collections::BTreeMap;
 #[derive(Debug, Clone)]
 pub struct AccessStorageWriteReport {
     pub msgs: Vec<String>,
+    /// Where the storage var is written, one for each message if there are any writes
+    pub writes: Vec<StorageWrite>,
+}
+
+/// A write of a storage var, and the function it is written in
+#[derive(Debug, Clone)]
+pub struct StorageWrite {
+    pub loc: LocStrSpan,
+    pub func_name: String,
+    pub func_loc: LocStrSpan,
 }
 
 impl AccessStorageWriteReport {
-    pub fn new(msgs: Vec<String>) -> Self {
-        Self { msgs }
+    pub fn new(msgs: Vec<String>, writes: Vec<StorageWrite>) -> Self {
+        Self { msgs, writes }
     }
 }
 
@@ -126,19 +136,23 @@ pub trait AccessStorageWriteQuery: BoundAnalyzer + Search + AnalyzerLike + Sized
                         }
                     }
 
-                    write_ctxs.push(analysis.ctx);
+                    let write_loc = analysis.bound_changes.last().unwrap().0.clone();
+                    write_ctxs.push((analysis.ctx, write_loc));
                 }
             }
         }
 
         if write_ctxs.is_empty() {
-            AccessStorageWriteReport::new(vec![format!(
-                "No write access for storage var \"{storage_var_name}\" after constructor"
-            )])
+            AccessStorageWriteReport::new(
+                vec![format!(
+                    "No write access for storage var \"{storage_var_name}\" after constructor"
+                )],
+                vec![],
+            )
         } else {
             let msgs = write_ctxs
                 .iter()
-                .map(|ctx| {
+                .map(|(ctx, _)| {
                     let bounds_string = ctx
                         .ctx_deps(self)
                         .iter()
@@ -200,7 +214,18 @@ pub trait AccessStorageWriteQuery: BoundAnalyzer + Search + AnalyzerLike + Sized
                     }
                 })
                 .collect();
-            AccessStorageWriteReport::new(msgs)
+            let writes = write_ctxs
+                .into_iter()
+                .map(|(ctx, loc)| {
+                    let func = ctx.associated_fn(self);
+                    StorageWrite {
+                        loc,
+                        func_name: func.name(self),
+                        func_loc: LocStrSpan::new(file_mapping, func.underlying(self).loc),
+                    }
+                })
+                .collect();
+            AccessStorageWriteReport::new(msgs, writes)
         }
     }
 
@@ -0,0 +1,191 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{Document, Item};
+
+/// The parts of a Foundry project's `foundry.toml` that decide which sources are analyzed
+/// and how their imports resolve
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoundryConfig {
+    /// The project root, i.e. the directory holding `foundry.toml`
+    pub root: PathBuf,
+    /// The sources directory, relative to the root
+    pub src: PathBuf,
+    /// The library directories, relative to the root
+    pub libs: Vec<PathBuf>,
+    /// Remappings set in `foundry.toml`, as `(prefix, target)`
+    pub remappings: Vec<(String, String)>,
+    pub auto_detect_remappings: bool,
+}
+
+impl FoundryConfig {
+    /// Foundry's defaults for a project at `root`
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            src: PathBuf::from("src"),
+            libs: vec![PathBuf::from("lib")],
+            remappings: vec![],
+            auto_detect_remappings: true,
+        }
+    }
+
+    /// Reads `foundry.toml` in `root`, using the `default` profile overlaid with the one named
+    /// by `FOUNDRY_PROFILE`. Without a `foundry.toml`, Foundry's defaults are used
+    pub fn load(root: &Path) -> Result<Self, String> {
+        let mut config = Self::new(root.to_path_buf());
+        let path = root.join("foundry.toml");
+        if !path.is_file() {
+            return Ok(config);
+        }
+        let doc = fs::read_to_string(&path)
+            .map_err(|err| format!("Could not read {path:?}: {err}"))?
+            .parse::<Document>()
+            .map_err(|err| format!("Invalid {path:?}: {err}"))?;
+
+        let mut profiles = vec!["default".to_string()];
+        if let Ok(profile) = std::env::var("FOUNDRY_PROFILE") {
+            if profile != "default" {
+                profiles.push(profile);
+            }
+        }
+        profiles
+            .iter()
+            .filter_map(|name| doc.get("profile")?.get(name))
+            .for_each(|profile| config.apply_profile(profile));
+        Ok(config)
+    }
+
+    fn apply_profile(&mut self, profile: &Item) {
+        if let Some(src) = profile.get("src").and_then(Item::as_str) {
+            self.src = PathBuf::from(src);
+        }
+        if let Some(libs) = profile.get("libs").and_then(Item::as_array) {
+            self.libs = libs
+                .iter()
+                .filter_map(|lib| lib.as_str())
+                .map(PathBuf::from)
+                .collect();
+        }
+        if let Some(remappings) = profile.get("remappings").and_then(Item::as_array) {
+            self.remappings = remappings
+                .iter()
+                .filter_map(|remapping| parse_remapping(remapping.as_str()?))
+                .collect();
+        }
+        if let Some(auto_detect) = profile
+            .get("auto_detect_remappings")
+            .and_then(Item::as_bool)
+        {
+            self.auto_detect_remappings = auto_detect;
+        }
+    }
+
+    /// Every remapping of the project, in the order forge resolves them: those set in
+    /// `foundry.toml`, then `remappings.txt`, then those detected in the libraries. The first
+    /// remapping of a prefix wins, and longer prefixes are listed first so that they take
+    /// precedence over shorter ones they extend
+    pub fn resolved_remappings(&self) -> Vec<(String, String)> {
+        let mut remappings = self.remappings.clone();
+        if let Ok(file) = fs::read_to_string(self.root.join("remappings.txt")) {
+            remappings.extend(file.lines().filter_map(parse_remapping));
+        }
+        if self.auto_detect_remappings {
+            remappings.extend(self.detected_remappings());
+        }
+
+        let mut resolved: Vec<(String, String)> = vec![];
+        remappings.into_iter().for_each(|(name, path)| {
+            if !resolved.iter().any(|(existing, _)| *existing == name) {
+                resolved.push((name, path));
+            }
+        });
+        resolved.sort_by_key(|(name, _)| std::cmp::Reverse(name.len()));
+        resolved
+    }
+
+    /// Remappings for the libraries installed in `libs`, e.g. `forge-std/` to
+    /// `lib/forge-std/src/`, including the `remappings.txt` of each library. Libraries nested
+    /// in other libraries are visited after the top level ones, so those take priority
+    fn detected_remappings(&self) -> Vec<(String, String)> {
+        let mut remappings = vec![];
+        let mut queue: VecDeque<PathBuf> = self.libs.iter().cloned().collect();
+        while let Some(lib_dir) = queue.pop_front() {
+            let mut libs = match fs::read_dir(self.root.join(&lib_dir)) {
+                Ok(entries) => entries
+                    .filter_map(|entry| entry.ok())
+                    .filter(|entry| entry.path().is_dir())
+                    .map(|entry| entry.file_name().to_string_lossy().to_string())
+                    .filter(|name| !name.starts_with('.'))
+                    .collect::<Vec<_>>(),
+                Err(_) => continue,
+            };
+            libs.sort();
+            libs.into_iter().for_each(|name| {
+                let lib = lib_dir.join(&name);
+                let target = ["src", "contracts"]
+                    .iter()
+                    .map(|dir| lib.join(dir))
+                    .find(|dir| self.root.join(dir).is_dir())
+                    .unwrap_or_else(|| lib.clone());
+                remappings.push((format!("{name}/"), dir_string(&target)));
+
+                if let Ok(file) = fs::read_to_string(self.root.join(&lib).join("remappings.txt")) {
+                    remappings.extend(
+                        file.lines()
+                            .filter_map(parse_remapping)
+                            .map(|(name, path)| (name, dir_string(&lib.join(path)))),
+                    );
+                }
+                queue.push_back(lib.join("lib"));
+            });
+        }
+        remappings
+    }
+
+    /// Every Solidity file under the sources directory, sorted by path
+    pub fn sources(&self) -> Vec<PathBuf> {
+        let mut sources = vec![];
+        let mut dirs = vec![self.root.join(&self.src)];
+        while let Some(dir) = dirs.pop() {
+            let entries = match fs::read_dir(&dir) {
+                Ok(entries) => entries,
+                Err(_) => continue,
+            };
+            entries.filter_map(|entry| entry.ok()).for_each(|entry| {
+                let path = entry.path();
+                if path.is_dir() {
+                    dirs.push(path);
+                } else if path.extension().is_some_and(|ext| ext == "sol") {
+                    sources.push(path);
+                }
+            });
+        }
+        sources.sort();
+        sources
+    }
+}
+
+/// Parses a remapping such as `@openzeppelin/=lib/openzeppelin-contracts/contracts/`,
+/// dropping any `context:` prefix since remappings apply to every file here
+pub fn parse_remapping(remapping: &str) -> Option<(String, String)> {
+    let (name, path) = remapping.trim().split_once('=')?;
+    let name = match name.split_once(':') {
+        Some((_context, name)) => name,
+        None => name,
+    };
+    if name.is_empty() {
+        return None;
+    }
+    Some((name.to_string(), path.to_string()))
+}
+
+/// A directory as a remapping target, which always ends in `/`
+fn dir_string(dir: &Path) -> String {
+    let dir = dir.to_string_lossy();
+    if dir.ends_with('/') {
+        dir.to_string()
+    } else {
+        format!("{dir}/")
+    }
+}
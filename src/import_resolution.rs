@@ -0,0 +1,73 @@
+use crate::Analyzer;
+use solang_parser::pt::Loc;
+use std::path::{Path, PathBuf};
+
+/// An import that matched no file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingImport {
+    /// The location of the import's path in the importing file
+    pub loc: Loc,
+    /// The path as written in the import
+    pub path: String,
+    /// Every path that was tried, in order
+    pub searched: Vec<PathBuf>,
+}
+
+impl Analyzer {
+    /// The paths an import could refer to, in the order they are tried:
+    ///  1. the first remapping whose prefix the import starts with
+    ///  2. for imports starting with `./` or `../`, the importing file's directory only
+    ///  3. otherwise the importing file's directory, the root and each include path, then
+    ///     `node_modules` in the importing file's directory and each of its ancestors, followed
+    ///     by the root's, as npm and Hardhat resolve packages such as `@openzeppelin/...`
+    pub fn import_candidates(&self, import: &str, current_path: &Path) -> Vec<PathBuf> {
+        let mut candidates = vec![];
+        if let Some((name, path)) = self
+            .remappings
+            .iter()
+            .find(|(name, _)| import.starts_with(name))
+        {
+            candidates.push(
+                self.root
+                    .join(path)
+                    .join(import.replacen(name, "", 1).trim_start_matches('/')),
+            );
+        }
+
+        let dir = current_path.parent().unwrap_or(Path::new(""));
+        candidates.push(dir.join(import));
+        if import.starts_with("./") || import.starts_with("../") {
+            return candidates;
+        }
+
+        candidates.push(self.root.join(import));
+        candidates.extend(self.include_paths.iter().map(|path| path.join(import)));
+        dir.ancestors()
+            .chain(self.root.ancestors())
+            .map(|dir| dir.join("node_modules").join(import))
+            .for_each(|path| {
+                if !candidates.contains(&path) {
+                    candidates.push(path)
+                }
+            });
+        candidates
+    }
+
+    /// The first of an import's candidates that is a file, or the missing import
+    pub fn resolve_import(
+        &self,
+        import: &str,
+        loc: Loc,
+        current_path: &Path,
+    ) -> Result<PathBuf, MissingImport> {
+        let searched = self.import_candidates(import, current_path);
+        match searched.iter().find(|path| path.is_file()) {
+            Some(path) => Ok(path.clone()),
+            None => Err(MissingImport {
+                loc,
+                path: import.to_string(),
+                searched,
+            }),
+        }
+    }
+}
@@ -1,16 +1,24 @@
 use ethers_core::types::U256;
 use shared::analyzer::*;
 use shared::nodes::*;
+use shared::range::eval_cache::EvalCache;
+use shared::range::smt::SmtCache;
 use shared::{Edge, Node, NodeIdx};
+use solang_parser::diagnostics::Diagnostic;
 use solang_parser::pt::Import;
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::ffi::OsString;
 use std::path::Path;
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+};
 
+use solang_parser::helpers::CodeLocation;
 use solang_parser::pt::{
     ContractDefinition, ContractPart, EnumDefinition, ErrorDefinition, Expression,
-    FunctionDefinition, FunctionTy, SourceUnit, SourceUnitPart, StructDefinition, TypeDefinition,
-    Using, UsingList, VariableDefinition,
+    FunctionDefinition, FunctionTy, Identifier, Loc, SourceUnit, SourceUnitPart, StructDefinition,
+    TypeDefinition, Using, UsingList, VariableDefinition,
 };
 use std::path::PathBuf;
 use std::{collections::HashMap, fs};
@@ -19,9 +27,16 @@ use petgraph::{graph::*, Directed};
 
 mod builtin_fns;
 
+pub mod config;
+pub mod foundry;
+pub mod import_resolution;
+
 pub mod context;
 // pub mod range;
+use config::PyrometerConfig;
 use context::*;
+use foundry::FoundryConfig;
+use import_resolution::MissingImport;
 pub use shared;
 
 #[derive(Debug, Clone)]
@@ -29,6 +44,14 @@ pub struct Analyzer {
     pub root: PathBuf,
     pub remappings: Vec<(String, String)>,
     pub imported_srcs: BTreeSet<OsString>,
+    /// Directories searched for imports that aren't relative, like solc's `--include-path`
+    pub include_paths: Vec<PathBuf>,
+    /// Imports that matched no file, which are skipped
+    pub missing_imports: Vec<MissingImport>,
+    /// Why files failed to parse, which are skipped
+    pub parse_errors: Vec<Diagnostic>,
+    /// The contents of each parsed file by its number, to quote source text in reports
+    pub sources: BTreeMap<usize, String>,
     pub final_pass_items: Vec<(
         Vec<FunctionNode>,
         Vec<(Using, NodeIdx)>,
@@ -43,6 +66,9 @@ pub struct Analyzer {
     pub user_types: HashMap<String, NodeIdx>,
     pub builtin_fns: HashMap<String, Function>,
     pub builtin_fn_inputs: HashMap<String, (Vec<FunctionParam>, Vec<FunctionReturn>)>,
+    pub eval_cache: EvalCache,
+    pub smt_cache: SmtCache,
+    pub settings: AnalysisSettings,
 }
 
 impl Default for Analyzer {
@@ -51,6 +77,10 @@ impl Default for Analyzer {
             root: Default::default(),
             remappings: Default::default(),
             imported_srcs: Default::default(),
+            include_paths: Default::default(),
+            missing_imports: Default::default(),
+            parse_errors: Default::default(),
+            sources: Default::default(),
             final_pass_items: Default::default(),
             file_no: 0,
             msg: MsgNode(0),
@@ -61,6 +91,9 @@ impl Default for Analyzer {
             user_types: Default::default(),
             builtin_fns: builtin_fns::builtin_fns(),
             builtin_fn_inputs: Default::default(),
+            eval_cache: Default::default(),
+            smt_cache: Default::default(),
+            settings: Default::default(),
         };
         a.builtin_fn_inputs = builtin_fns::builtin_fns_inputs(&mut a);
 
@@ -84,6 +117,10 @@ impl GraphLike for Analyzer {
     fn graph(&self) -> &Graph<Node, Edge, Directed, usize> {
         &self.graph
     }
+
+    fn eval_cache(&self) -> Option<&EvalCache> {
+        Some(&self.eval_cache)
+    }
 }
 
 impl AnalyzerLike for Analyzer {
@@ -92,6 +129,14 @@ impl AnalyzerLike for Analyzer {
         self.entry
     }
 
+    fn settings(&self) -> &AnalysisSettings {
+        &self.settings
+    }
+
+    fn smt_cache(&self) -> Option<&SmtCache> {
+        Some(&self.smt_cache)
+    }
+
     fn msg(&mut self) -> MsgNode {
         self.msg
     }
@@ -130,12 +175,12 @@ impl AnalyzerLike for Analyzer {
                     if let Some(idx) = self.builtins.get(&builtin) {
                         *idx
                     } else {
-                        let idx = self.add_node(Node::Builtin(builtin.clone()));
+                        let idx = self.add_node(Node::Builtin(Box::new(builtin.clone())));
                         self.builtins.insert(builtin, idx);
                         idx
                     }
                 } else {
-                    0.into()
+                    self.add_unresolved(expr)
                 }
             }
             Variable(ident) => {
@@ -154,31 +199,43 @@ impl AnalyzerLike for Analyzer {
                     if let Some(idx) = self.builtins.get(&dyn_b) {
                         *idx
                     } else {
-                        let idx = self.add_node(Node::Builtin(dyn_b.clone()));
+                        let idx = self.add_node(Node::Builtin(Box::new(dyn_b.clone())));
                         self.builtins.insert(dyn_b, idx);
                         idx
                     }
                 } else {
-                    todo!("???")
+                    self.add_unresolved(expr)
                 }
             }
-            ArraySubscript(_loc, ty_expr, Some(index_expr)) => {
-                let _inner_ty = self.parse_expr(ty_expr);
-                let _index_ty = self.parse_expr(index_expr);
-                // println!("here: {:?}", index_expr);
-                // if let Some(var_type) = VarType::try_from_idx(self, inner_ty) {
-                //     let dyn_b = DynBuiltin::Array(var_type);
-                //     if let Some(idx) = self.dyn_builtins.get(&dyn_b) {
-                //         *idx
-                //     } else {
-                //         let idx = self.add_node(Node::DynBuiltin(dyn_b.clone()));
-                //         self.dyn_builtins.insert(dyn_b, idx);
-                //         idx
-                //     }
-                // } else {
-                //     todo!("???")
-                // }
-                0.into()
+            ArraySubscript(loc, ty_expr, Some(index_expr)) => {
+                let inner_ty = self.parse_expr(ty_expr);
+                // like in a function body, only a literal size is known. Anything else, i.e. a
+                // constant, is left unresolved so variables of the type are reported as unsupported
+                let size = match &**index_expr {
+                    NumberLiteral(..) => {
+                        let size = self.parse_expr(index_expr);
+                        match self.node(size) {
+                            Node::Concrete(Concrete::Uint(_, size)) => Some(*size),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                };
+                match (VarType::try_from_idx(self, inner_ty), size) {
+                    (Some(var_type), Some(size)) => {
+                        self.builtin_or_add(Builtin::SizedArray(size, Box::new(var_type)))
+                    }
+                    (Some(var_type), None) => {
+                        let size = self
+                            .source_text(index_expr.loc())
+                            .unwrap_or_else(|| "non-literal size".to_string());
+                        self.add_node(Node::Unresolved(Identifier {
+                            loc: *loc,
+                            name: format!("{}[{size}]", var_type.as_dot_str(self)),
+                        }))
+                    }
+                    (None, _) => self.add_unresolved(expr),
+                }
             }
             NumberLiteral(_loc, int, exp, _unit) => {
                 let int = U256::from_dec_str(int).unwrap();
@@ -190,12 +247,31 @@ impl AnalyzerLike for Analyzer {
                 };
                 self.add_node(Node::Concrete(Concrete::Uint(256, val)))
             }
-            _ => 0.into(),
+            _ => self.add_unresolved(expr),
         }
     }
 }
 
 impl Analyzer {
+    /// The source text at a location, if its file was parsed
+    pub fn source_text(&self, loc: Loc) -> Option<String> {
+        match loc {
+            Loc::File(file_no, start, end) => self.sources.get(&file_no)?.get(start..end),
+            _ => None,
+        }
+        .map(str::to_string)
+    }
+
+    /// Adds a type that couldn't be resolved, named by its source, so that variables of it are
+    /// reported as unsupported
+    fn add_unresolved(&mut self, expr: &Expression) -> NodeIdx {
+        let loc = expr.loc();
+        let name = self
+            .source_text(loc)
+            .unwrap_or_else(|| "unknown type".to_string());
+        self.add_node(Node::Unresolved(Identifier { loc, name }))
+    }
+
     pub fn set_remappings_and_root(&mut self, remappings_path: String) {
         self.root = PathBuf::from(&remappings_path)
             .parent()
@@ -214,6 +290,57 @@ impl Analyzer {
             .collect();
     }
 
+    /// Roots the analyzer at a Foundry project, resolving imports with its remappings
+    pub fn set_foundry_project(&mut self, config: &FoundryConfig) {
+        self.root = config.root.clone();
+        self.remappings = config.resolved_remappings();
+    }
+
+    /// Applies the environment assumptions and import settings of a `pyrometer.toml`. Its
+    /// remappings and include paths are tried after any already set
+    pub fn set_config(&mut self, config: &PyrometerConfig) {
+        *self.node_mut(self.msg) = Node::Msg(config.msg.clone());
+        *self.node_mut(self.block) = Node::Block(config.block.clone());
+        self.remappings.extend(config.remappings.iter().cloned());
+        self.include_paths
+            .extend(config.include_paths.iter().cloned());
+    }
+
+    /// Parses each of `paths` as an entry, returning every parsed source like [`Self::parse`]
+    /// does, entries included. A file is parsed once however many others import it, and files
+    /// already parsed as an import aren't parsed again. Fails if an entry can't be read
+    #[allow(clippy::type_complexity)]
+    pub fn parse_project(
+        &mut self,
+        paths: &[PathBuf],
+    ) -> Result<Vec<(Option<NodeIdx>, String, String, usize)>, String> {
+        let mut all_sources = vec![];
+        for path in paths {
+            let canonical = fs::canonicalize(path)
+                .map_err(|err| format!("Could not find file {path:?}: {err}"))?;
+            if !self.imported_srcs.insert(canonical.into_os_string()) {
+                continue;
+            }
+            let sol = fs::read_to_string(path)
+                .map_err(|err| format!("Could not read file {path:?}: {err}"))?;
+            // `parse` leaves `file_no` at the last file it numbered
+            if !all_sources.is_empty() {
+                self.file_no += 1;
+            }
+            let file_no = self.file_no;
+            let (maybe_entry, sources) = self.parse(&sol, path, false);
+            all_sources.extend(sources);
+            all_sources.push((
+                maybe_entry,
+                path.to_string_lossy().to_string(),
+                sol,
+                file_no,
+            ));
+        }
+        self.final_pass();
+        Ok(all_sources)
+    }
+
     #[tracing::instrument(level = "trace", skip_all)]
     pub fn parse(
         &mut self,
@@ -227,6 +354,7 @@ impl Analyzer {
         // tracing::trace!("parsing: {:?}", current_path);
         let file_no = self.file_no;
         let mut imported = vec![];
+        self.sources.insert(file_no, src.to_string());
         match solang_parser::parse(src, file_no) {
             Ok((source_unit, _comments)) => {
                 let parent = self.add_node(Node::SourceUnit(file_no));
@@ -245,10 +373,40 @@ impl Analyzer {
 
                 (Some(parent), imported)
             }
-            Err(e) => panic!("FAIL to parse, {e:?}"),
+            Err(errors) => {
+                tracing::warn!("Could not parse: {:?}", current_path);
+                self.parse_errors.extend(errors);
+                (None, imported)
+            }
         }
     }
 
+    /// Parses and analyzes only the files `src` imports, numbering the locations of its import
+    /// directives as file 0. The imported functions are left out of later final passes, so a
+    /// clone can go on to analyze `src` itself without analyzing them again
+    pub fn parse_imports(
+        &mut self,
+        src: &str,
+        current_path: &Path,
+    ) -> Vec<(Option<NodeIdx>, String, String, usize)> {
+        let source_unit = match solang_parser::parse(src, 0) {
+            Ok((source_unit, _comments)) => source_unit,
+            // the syntax errors are reported when `src` itself is parsed
+            Err(_) => return vec![],
+        };
+        let imported = source_unit
+            .0
+            .iter()
+            .flat_map(|part| match part {
+                SourceUnitPart::ImportDirective(import) => self.parse_import(import, current_path),
+                _ => vec![],
+            })
+            .collect();
+        self.final_pass();
+        self.final_pass_items.clear();
+        imported
+    }
+
     pub fn final_pass(&mut self) {
         let elems = self.final_pass_items.clone();
         elems.iter().for_each(|(funcs, usings, inherits)| {
@@ -267,12 +425,35 @@ impl Analyzer {
         elems.into_iter().for_each(|(funcs, _usings, _inherits)| {
             funcs.into_iter().for_each(|func| {
                 if let Some(body) = &func.underlying(self).body.clone() {
-                    self.parse_ctx_statement(body, false, Some(func));
+                    // a bug in the analysis of one function shouldn't take down the rest, so
+                    // a panic is recorded against the function's context instead
+                    let res = panic::catch_unwind(AssertUnwindSafe(|| {
+                        self.parse_ctx_statement(body, false, Some(func));
+                    }));
+                    if let Err(payload) = res {
+                        self.record_panic(func, payload);
+                    }
                 }
             });
         });
     }
 
+    /// Marks the body context of a function whose analysis panicked with the panic's message
+    fn record_panic(&mut self, func: FunctionNode, payload: Box<dyn Any + Send>) {
+        let msg = if let Some(msg) = payload.downcast_ref::<&str>() {
+            msg.to_string()
+        } else if let Some(msg) = payload.downcast_ref::<String>() {
+            msg.clone()
+        } else {
+            "unknown panic".to_string()
+        };
+        let loc = func.underlying(self).loc;
+        match func.maybe_body_ctx(self) {
+            Some(ctx) => ctx.mark_unsupported(self, AnalyzerError::Internal(loc, msg)),
+            None => tracing::error!("analysis of {} panicked: {msg}", func.name(self)),
+        }
+    }
+
     #[tracing::instrument(level = "trace", skip_all)]
     pub fn parse_source_unit(
         &mut self,
@@ -370,10 +551,9 @@ impl Analyzer {
                 let node = self.parse_ty_def(def);
                 self.add_edge(node, sup_node, Edge::Ty);
             }
-            EventDefinition(_def) => todo!(),
-            Annotation(_anno) => todo!(),
             Using(using) => usings.push((*using.clone(), parent)),
-            StraySemicolon(_loc) => todo!(),
+            // nothing here affects the analysis
+            EventDefinition(_) | Annotation(_) | StraySemicolon(_) => {}
             PragmaDirective(_, _, _) => {}
             ImportDirective(import) => imported.extend(self.parse_import(import, current_path)),
         }
@@ -389,91 +569,64 @@ impl Analyzer {
         match import {
             Import::Plain(import_path, _) => {
                 tracing::trace!("parse_import, path: {:?}", import_path);
-                let remapping = self
-                    .remappings
-                    .iter()
-                    .find(|x| import_path.string.starts_with(&x.0));
-
-                let remapped = if let Some((name, path)) = remapping {
-                    self.root.join(path).join(
-                        import_path
-                            .string
-                            .replacen(name, "", 1)
-                            .trim_start_matches('/'),
-                    )
-                } else {
-                    current_path
-                        .parent()
-                        .unwrap()
-                        .join(import_path.string.clone())
-                };
-
-                let canonical = fs::canonicalize(&remapped)
-                    .unwrap_or_else(|_| panic!("Could not find file: {remapped:?}"));
-                let canonical_str_path = canonical.as_os_str();
-                if self.imported_srcs.contains(canonical_str_path) {
-                    return vec![];
-                }
-                self.imported_srcs.insert(canonical_str_path.into());
-
-                let sol = fs::read_to_string(&canonical).unwrap_or_else(|_| {
-                    panic!("Could not find file for dependency: {canonical:?}")
-                });
-                self.file_no += 1;
-                let file_no = self.file_no;
-                let (maybe_entry, mut inner_sources) = self.parse(&sol, &remapped, false);
-                inner_sources.push((
-                    maybe_entry,
-                    remapped.to_str().unwrap().to_owned(),
-                    sol.to_string(),
-                    file_no,
-                ));
-                inner_sources
+                self.parse_import_path(&import_path.string, import_path.loc, current_path)
             }
             Import::Rename(import_path, _elems, _) => {
                 tracing::trace!("parse_import, path: {:?}, Rename", import_path);
-                let remapping = self
-                    .remappings
-                    .iter()
-                    .find(|x| import_path.string.starts_with(&x.0));
-
-                let remapped = if let Some((name, path)) = remapping {
-                    self.root.join(path).join(
-                        import_path
-                            .string
-                            .replacen(name, "", 1)
-                            .trim_start_matches('/'),
-                    )
-                } else {
-                    current_path
-                        .parent()
-                        .unwrap()
-                        .join(import_path.string.clone())
-                };
+                self.parse_import_path(&import_path.string, import_path.loc, current_path)
+            }
+            Import::GlobalSymbol(import_path, _alias, _) => {
+                tracing::trace!("parse_import, path: {:?}, GlobalSymbol", import_path);
+                self.parse_import_path(&import_path.string, import_path.loc, current_path)
+            }
+        }
+    }
 
-                let canonical = fs::canonicalize(&remapped).unwrap();
-                let canonical_str_path = canonical.as_os_str();
-                if self.imported_srcs.contains(canonical_str_path) {
-                    return vec![];
+    /// Parses the file an import resolves to, unless it was already parsed. An import that
+    /// resolves to no file is recorded in `missing_imports` and skipped
+    fn parse_import_path(
+        &mut self,
+        import: &str,
+        loc: Loc,
+        current_path: &Path,
+    ) -> Vec<(Option<NodeIdx>, String, String, usize)> {
+        let resolved = self
+            .resolve_import(import, loc, current_path)
+            .and_then(|remapped| {
+                // the file may still be gone or unreadable by the time it is read
+                let unreadable = || MissingImport {
+                    loc,
+                    path: import.to_string(),
+                    searched: vec![remapped.clone()],
+                };
+                let canonical = fs::canonicalize(&remapped).map_err(|_| unreadable())?;
+                if self.imported_srcs.contains(canonical.as_os_str()) {
+                    return Ok(None);
                 }
-                self.imported_srcs.insert(canonical_str_path.into());
-
-                let sol = fs::read_to_string(&canonical).unwrap_or_else(|_| {
-                    panic!("Could not find file for dependency: {canonical:?}")
-                });
-                self.file_no += 1;
-                let file_no = self.file_no;
-                let (maybe_entry, mut inner_sources) = self.parse(&sol, &remapped, false);
-                inner_sources.push((
-                    maybe_entry,
-                    remapped.to_str().unwrap().to_owned(),
-                    sol.to_string(),
-                    file_no,
-                ));
-                inner_sources
+                let sol = fs::read_to_string(&canonical).map_err(|_| unreadable())?;
+                Ok(Some((remapped, canonical, sol)))
+            });
+        let (remapped, canonical, sol) = match resolved {
+            Ok(Some(resolved)) => resolved,
+            Ok(None) => return vec![],
+            Err(missing) => {
+                tracing::warn!("Could not resolve import: {:?}", missing.path);
+                self.missing_imports.push(missing);
+                return vec![];
             }
-            e => todo!("import {:?}", e),
-        }
+        };
+        self.imported_srcs.insert(canonical.into_os_string());
+
+        self.file_no += 1;
+        let file_no = self.file_no;
+        let (maybe_entry, mut inner_sources) = self.parse(&sol, &remapped, false);
+        inner_sources.push((
+            maybe_entry,
+            remapped.to_str().unwrap().to_owned(),
+            sol.to_string(),
+            file_no,
+        ));
+        inner_sources
     }
 
     // #[tracing::instrument(name = "parse_contract_def", skip_all, fields(name = format!("{:?}", contract_def.name)))]
@@ -537,10 +690,9 @@ impl Analyzer {
                 let node = self.parse_ty_def(def);
                 self.add_edge(node, con_node, Edge::Ty);
             }
-            EventDefinition(_def) => {}
-            Annotation(_anno) => todo!(),
             Using(using) => usings.push((*using.clone(), con_node.0.into())),
-            StraySemicolon(_loc) => todo!(),
+            // nothing here affects the analysis
+            EventDefinition(_) | Annotation(_) | StraySemicolon(_) => {}
         });
         self.user_types
             .insert(con_node.name(self), con_node.0.into());
@@ -550,12 +702,13 @@ impl Analyzer {
     #[tracing::instrument(level = "trace", skip_all)]
     pub fn parse_using(&mut self, using_def: &Using, scope_node: NodeIdx) {
         tracing::trace!("Parsing \"using\" {:?}", using_def);
-        let ty_idx = self.parse_expr(
-            &using_def
-                .ty
-                .clone()
-                .expect("No type defined for using statement"),
-        );
+        let ty_idx = match &using_def.ty {
+            Some(ty) => self.parse_expr(ty),
+            None => {
+                tracing::warn!("Skipping unsupported `using` for all types");
+                return;
+            }
+        };
         match &using_def.list {
             UsingList::Library(ident_paths) => {
                 ident_paths.identifiers.iter().for_each(|ident| {
@@ -566,7 +719,7 @@ impl Analyzer {
                             Edge::LibraryContract(scope_node),
                         );
                     } else {
-                        panic!("Cannot find library contract {}", ident.name);
+                        tracing::warn!("Cannot find library contract {}", ident.name);
                     }
                 });
             }
@@ -586,14 +739,14 @@ impl Analyzer {
                             {
                                 self.add_edge(*func, ty_idx, Edge::LibraryFunction(scope_node));
                             } else {
-                                panic!(
+                                tracing::warn!(
                                     "Cannot find library function {}.{}",
                                     ident_paths.path.identifiers[0].name,
                                     ident_paths.path.identifiers[1].name
                                 );
                             }
                         } else {
-                            panic!(
+                            tracing::warn!(
                                 "Cannot find library contract {}",
                                 ident_paths.path.identifiers[0].name
                             );
@@ -615,7 +768,7 @@ impl Analyzer {
                         }) {
                             self.add_edge(*func, ty_idx, Edge::LibraryFunction(scope_node));
                         } else {
-                            panic!(
+                            tracing::warn!(
                                 "Cannot find library function {}",
                                 ident_paths.path.identifiers[0].name
                             );
@@ -623,7 +776,8 @@ impl Analyzer {
                     }
                 });
             }
-            UsingList::Error => todo!(),
+            // the parser already recovered from it
+            UsingList::Error => {}
         }
     }
 
@@ -0,0 +1,58 @@
+use ethers_core::types::U256;
+use pyrometer::Analyzer;
+use shared::range::Range;
+use std::env;
+mod helpers;
+use helpers::*;
+
+#[test]
+fn test_abi_decode_ranges() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/abi_decode.sol");
+    let sol = include_str!("./test_data/abi_decode.sol");
+    let mut analyzer = Analyzer::default();
+    let ctxs = body_ctxs(&mut analyzer, path_str, sol);
+    let bounds = |func: &str, var: &str| {
+        let range = ctxs[func]
+            .latest_var_by_name(&analyzer, var)
+            .unwrap_or_else(|| panic!("no `{var}` in {func}"))
+            .range(&analyzer)
+            .unwrap();
+        let val = |elem: shared::range::elem_ty::Elem<_>| {
+            elem.maybe_concrete().unwrap().val.uint_val().unwrap()
+        };
+        (
+            val(range.evaled_range_min(&analyzer)),
+            val(range.evaled_range_max(&analyzer)),
+        )
+    };
+
+    // decoded values take the range of their type
+    assert_eq!(
+        bounds("decode_builtins", "b"),
+        (U256::zero(), U256::from(255))
+    );
+    assert_eq!(bounds("decode_builtins", "a"), (U256::zero(), U256::MAX));
+    assert_eq!(
+        bounds("decode_nested", "kind"),
+        (U256::zero(), U256::from(255))
+    );
+
+    // a decoded `bytes` or array is no longer than the input
+    assert_eq!(
+        bounds("decode_bytes", "inner_len"),
+        (U256::zero(), U256::from(99))
+    );
+    assert_eq!(
+        bounds("decode_arrays", "xs_len"),
+        (U256::zero(), U256::from(99))
+    );
+    // while the length of a fixed size array is fixed
+    assert_eq!(
+        bounds("decode_arrays", "ys_len"),
+        (U256::from(3), U256::from(3))
+    );
+
+    ctxs.values()
+        .for_each(|ctx| assert!(ctx.killed_loc(&analyzer).is_none()));
+}
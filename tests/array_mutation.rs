@@ -0,0 +1,53 @@
+use ethers_core::types::U256;
+use pyrometer::Analyzer;
+use shared::nodes::Concrete;
+use shared::range::{elem_ty::Elem, Range};
+use std::env;
+mod helpers;
+use helpers::*;
+
+#[test]
+fn test_array_mutation() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/array_mutation.sol");
+    let sol = include_str!("./test_data/array_mutation.sol");
+    let mut analyzer = Analyzer::default();
+    let ctxs = body_ctxs(&mut analyzer, path_str, sol);
+    // the bounds on the one path of each function that doesn't revert
+    let bounds = |func: &str, var: &str| {
+        let live = ctxs[func]
+            .terminal_child_list(&analyzer)
+            .into_iter()
+            .filter(|ctx| ctx.killed_loc(&analyzer).is_none())
+            .collect::<Vec<_>>();
+        assert_eq!(live.len(), 1, "{func}");
+        let range = live[0]
+            .latest_var_by_name(&analyzer, var)
+            .unwrap()
+            .range(&analyzer)
+            .unwrap();
+        (
+            range.evaled_range_min(&analyzer),
+            range.evaled_range_max(&analyzer),
+        )
+    };
+
+    assert_eq!(bounds("push_pop", "stack.length"), (uint(0), uint(0)));
+    assert_eq!(
+        bounds("checked_pop", "stack.length"),
+        (uint(2), Elem::from(Concrete::from(U256::MAX - 1)))
+    );
+    assert_eq!(bounds("delete_array", "stack.length"), (uint(0), uint(0)));
+    assert_eq!(bounds("delete_elem", "stack[i]"), (uint(0), uint(0)));
+    // a fixed size array keeps its length when deleted
+    assert_eq!(
+        bounds("delete_fixed", "fixedStack.length"),
+        (uint(3), uint(3))
+    );
+    assert_eq!(bounds("delete_fixed", "fixedStack[2]"), (uint(0), uint(0)));
+    // `pop` compares the length against a zero made in each fork, not in the context it forks
+    assert!(ctxs["unchecked_pop"]
+        .local_vars(&analyzer)
+        .iter()
+        .all(|var| !var.is_const(&analyzer)));
+}
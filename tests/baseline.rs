@@ -0,0 +1,155 @@
+use pyrometer::context::analyzers::{
+    baseline::{Baseline, Findings, Suppressions},
+    panics::PanicAnalyzer,
+};
+use pyrometer::Analyzer;
+use shared::analyzer::Search;
+use shared::{nodes::FunctionNode, Edge};
+use std::collections::BTreeMap;
+use std::env;
+use std::path::PathBuf;
+
+/// The fingerprint ids of the reachable panics of each function in `sol` that no comment
+/// suppresses, by function name
+fn panic_ids(path_str: String, sol: &str) -> BTreeMap<String, Vec<String>> {
+    let mut analyzer = Analyzer::default();
+    let (maybe_entry, _) = analyzer.parse(sol, &PathBuf::from(path_str.clone()), true);
+    let entry = maybe_entry.unwrap();
+    let file_mapping: BTreeMap<_, _> = vec![(0usize, path_str.clone())].into_iter().collect();
+    let sources: BTreeMap<_, _> = vec![(path_str, sol.to_string())].into_iter().collect();
+    let suppressions = Suppressions::new(&sources);
+    analyzer
+        .search_children(entry, &Edge::Func)
+        .into_iter()
+        .filter_map(|func| {
+            let func = FunctionNode::from(func);
+            let ctx = func.maybe_body_ctx(&analyzer)?;
+            let ids = analyzer
+                .reachable_panics(&file_mapping, ctx)
+                .retain_findings(&analyzer, &sources, |fingerprint, span| {
+                    !suppressions.suppresses(&fingerprint.rule, span, &sources)
+                })
+                .map(|panics| {
+                    panics
+                        .findings(&analyzer, &sources)
+                        .into_iter()
+                        .map(|(fingerprint, _span)| fingerprint.id())
+                        .collect()
+                })
+                .unwrap_or_default();
+            let name = func.name(&analyzer);
+            let name = name.split('(').next().unwrap_or_default().to_string();
+            Some((name, ids))
+        })
+        .collect()
+}
+
+#[test]
+fn test_suppressions() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/baseline.sol");
+    let sol = include_str!("./test_data/baseline.sol");
+    let ids = panic_ids(path_str, sol);
+    assert_eq!(ids["known"].len(), 1);
+    assert!(ids["suppressed"].is_empty());
+    // comments only suppress the rules they name
+    assert_eq!(ids["otherRule"].len(), 1);
+}
+
+#[test]
+fn test_suppression_comments() {
+    let src = [
+        r#"string a = "// pyrometer-disable-next-line";"#,
+        "uint256 b = 1;",
+        r#"string c = "x\" // pyrometer-disable-next-line"; // pyrometer-disable-next-line panic"#,
+        "uint256 d = 1;",
+        "/* // pyrometer-disable-next-line */",
+        "uint256 e = 1;",
+        "/* start",
+        "// pyrometer-disable-next-line */",
+        "uint256 f = 1;",
+        "x = 1; // pyrometer-disable-next-line panic, unsupported",
+        "uint256 g = 1;",
+    ]
+    .join("\n");
+    let sources: BTreeMap<_, _> = vec![("A.sol".to_string(), src)].into_iter().collect();
+    // only comments outside string literals and block comments count, each line counting from 1
+    assert_eq!(
+        Suppressions::new(&sources).lines,
+        vec![
+            (("A.sol".to_string(), 4), vec!["panic".to_string()]),
+            (
+                ("A.sol".to_string(), 11),
+                vec!["panic".to_string(), "unsupported".to_string()]
+            ),
+        ]
+        .into_iter()
+        .collect()
+    );
+}
+
+#[test]
+fn test_fingerprints_are_stable() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/baseline.sol");
+    let sol = include_str!("./test_data/baseline.sol");
+    let ids = panic_ids(path_str.clone(), sol);
+    // the same findings of functions with the same body still differ by function
+    assert_ne!(ids["known"], ids["otherRule"]);
+
+    // moving a finding around its file, or reformatting it, doesn't change its fingerprint
+    let moved = sol
+        .replacen(
+            "contract Baseline {",
+            "contract Baseline {\n    uint256 added;\n",
+            1,
+        )
+        .replacen(
+            "return values[i];",
+            "return values[\n            i\n        ];",
+            1,
+        );
+    assert_eq!(panic_ids(path_str, &moved)["known"], ids["known"]);
+}
+
+#[test]
+fn test_baseline() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/baseline.sol");
+    let sol = include_str!("./test_data/baseline.sol");
+    let mut analyzer = Analyzer::default();
+    let (maybe_entry, _) = analyzer.parse(sol, &PathBuf::from(path_str.clone()), true);
+    let entry = maybe_entry.unwrap();
+    let file_mapping: BTreeMap<_, _> = vec![(0usize, path_str.clone())].into_iter().collect();
+    let sources: BTreeMap<_, _> = vec![(path_str, sol.to_string())].into_iter().collect();
+    let known = analyzer
+        .search_children(entry, &Edge::Func)
+        .into_iter()
+        .map(FunctionNode::from)
+        .find(|func| func.name(&analyzer).starts_with("known"))
+        .and_then(|func| func.maybe_body_ctx(&analyzer))
+        .unwrap();
+    let panics = analyzer.reachable_panics(&file_mapping, known);
+    let findings = panics.findings(&analyzer, &sources);
+    assert_eq!(findings.len(), 1);
+    let fingerprint = &findings[0].0;
+    assert_eq!(fingerprint.rule, "panic");
+    assert_eq!(fingerprint.contract.as_deref(), Some("Baseline"));
+    assert_eq!(fingerprint.function.as_deref(), Some("known(uint256)"));
+    assert_eq!(fingerprint.variable.as_deref(), Some("values"));
+    assert_eq!(fingerprint.span, "values[i]");
+
+    let baseline = Baseline::new(vec![fingerprint.clone()]);
+    let json = serde_json::to_string(&baseline).unwrap();
+    assert_eq!(serde_json::from_str::<Baseline>(&json).unwrap(), baseline);
+
+    // a baselined finding isn't reported, but each entry only accepts one copy of it
+    let mut baseline = baseline;
+    assert!(baseline.accept(fingerprint));
+    assert!(!baseline.accept(fingerprint));
+    let mut baseline = Baseline::new(vec![fingerprint.clone()]);
+    assert!(panics
+        .retain_findings(&analyzer, &sources, |fingerprint, _span| !baseline
+            .accept(fingerprint))
+        .is_none());
+}
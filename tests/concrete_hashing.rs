@@ -0,0 +1,123 @@
+use ethers_core::types::H256;
+use pyrometer::Analyzer;
+use shared::nodes::Concrete;
+use std::env;
+mod helpers;
+use helpers::*;
+
+/// A `bytesN` with the given leading bytes
+fn bytes(size: u8, hex: &str) -> Concrete {
+    let mut h = H256::zero();
+    let val = hex::decode(hex).unwrap();
+    h.0[..val.len()].copy_from_slice(&val);
+    Concrete::Bytes(size, h)
+}
+
+#[test]
+fn test_concrete_hashing() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/concrete_hashing.sol");
+    let sol = include_str!("./test_data/concrete_hashing.sol");
+    let mut analyzer = Analyzer::default();
+    let ctxs = body_ctxs(&mut analyzer, path_str, sol);
+    let concrete = |func: &str, var: &str| {
+        ctxs[func]
+            .latest_var_by_name(&analyzer, var)
+            .unwrap_or_else(|| panic!("no `{var}` in {func}"))
+            .maybe_concrete(&analyzer)
+    };
+
+    assert_eq!(
+        concrete("role", "r"),
+        Some(bytes(
+            32,
+            "9f2df0fed2c77648de5860a4cc508cd0818c85b8b8a1ab4ceeef8d981c8956a6"
+        ))
+    );
+    assert_eq!(
+        concrete("sha", "h"),
+        Some(bytes(
+            32,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        ))
+    );
+    // as is a constant initialized with one
+    assert_eq!(concrete("role", "m"), concrete("role", "r"));
+    assert_eq!(concrete("selector", "sel"), Some(bytes(4, "a9059cbb")));
+    assert_eq!(
+        concrete("encode", "h"),
+        Some(bytes(
+            32,
+            "cc69885fda6bcc1a4ace058b4a62bf5e179ea78fd58a1ccd71c22cc9b688792f"
+        ))
+    );
+    assert_eq!(
+        concrete("encode", "p"),
+        Some(bytes(
+            32,
+            "3a7280a17d85377210c21299b6f82c9b18998fc585f906f6417dd342244bcbf5"
+        ))
+    );
+}
+
+#[test]
+fn test_encode_calldata() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/concrete_hashing.sol");
+    let sol = include_str!("./test_data/concrete_hashing.sol");
+    let mut analyzer = Analyzer::default();
+    let ctxs = body_ctxs(&mut analyzer, path_str, sol);
+    let returned = |func: &str| {
+        let rets = ctxs[func].return_nodes(&analyzer);
+        assert_eq!(rets.len(), 1, "{func} should return once");
+        rets[0].1.maybe_concrete(&analyzer)
+    };
+
+    // `transfer(address(1), 2)`: the selector of `transfer(address,uint256)` followed by each
+    // argument padded to a word
+    let calldata = hex::decode(concat!(
+        "a9059cbb",
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "0000000000000000000000000000000000000000000000000000000000000002",
+    ))
+    .unwrap();
+    assert_eq!(
+        returned("withSignature"),
+        Some(Concrete::DynBytes(calldata.clone()))
+    );
+    assert_eq!(returned("withSelector"), Some(Concrete::DynBytes(calldata)));
+}
+
+#[test]
+fn test_bytes_literal() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/concrete_hashing.sol");
+    let sol = include_str!("./test_data/concrete_hashing.sol");
+    let mut analyzer = Analyzer::default();
+    let ctxs = body_ctxs(&mut analyzer, path_str, sol);
+    let concrete = |var: &str| {
+        ctxs["literal"]
+            .latest_var_by_name(&analyzer, var)
+            .unwrap()
+            .maybe_concrete(&analyzer)
+    };
+
+    // a hex literal assigned to a `bytesN` keeps its big endian byte order, left aligned
+    assert_eq!(concrete("transfer"), Some(bytes(4, "a9059cbb")));
+    assert_eq!(concrete("short"), Some(bytes(2, "0102")));
+}
+
+#[test]
+fn test_selector_comparison() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/concrete_hashing.sol");
+    let sol = include_str!("./test_data/concrete_hashing.sol");
+    let mut analyzer = Analyzer::default();
+    let ctxs = body_ctxs(&mut analyzer, path_str, sol);
+
+    // the computed selector equals `transfer`'s, whether compared to a variable or a literal
+    assert!(ctxs["role"].killed_loc(&analyzer).is_none());
+    assert!(ctxs["selector"].killed_loc(&analyzer).is_none());
+    // but not `transferFrom`'s
+    assert!(ctxs["wrongSelector"].killed_loc(&analyzer).is_some());
+}
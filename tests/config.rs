@@ -0,0 +1,188 @@
+use ethers_core::types::U256;
+use pyrometer::config::{glob_match, AnalyzerSettings, PyrometerConfig, Severity};
+use pyrometer::context::analyzers::ReportConfig;
+use pyrometer::Analyzer;
+use shared::analyzer::Search;
+use shared::nodes::Concrete;
+use shared::range::{elem_ty::Elem, Range};
+use shared::{nodes::FunctionNode, Edge};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+fn config_dir() -> PathBuf {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    PathBuf::from(format!("{manifest_dir}/tests/test_data/config"))
+}
+
+#[test]
+fn test_load_config() {
+    let dir = config_dir();
+    // found from a directory below the one holding it
+    let path = PyrometerConfig::find(&dir.join("src")).unwrap();
+    assert_eq!(path, dir.join("pyrometer.toml"));
+    let config = PyrometerConfig::load(&path).unwrap();
+    assert_eq!(config.root, dir);
+
+    assert_eq!(config.report.show_consts, Some(true));
+    assert_eq!(config.report.show_tmps, None);
+    assert_eq!(config.contracts.include, vec!["Vault*".to_string()]);
+    assert_eq!(config.contracts.exclude, vec!["*Mock".to_string()]);
+    assert_eq!(config.functions.exclude, vec!["test*".to_string()]);
+
+    assert_eq!(config.msg.value, Some(U256::zero()));
+    assert!(config.msg.sender.is_some());
+    assert_eq!(config.msg.origin, None);
+    assert_eq!(config.block.number, Some(U256::from(100)));
+    assert_eq!(config.block.timestamp, Some(U256::from(100)));
+
+    assert_eq!(config.enabled("panic"), Some(true));
+    assert_eq!(config.enabled("bounds"), Some(false));
+    assert_eq!(
+        config.analyzers.get("unsupported"),
+        Some(&AnalyzerSettings {
+            enabled: None,
+            severity: Some(Severity::Error),
+        })
+    );
+    assert_eq!(config.enabled("unresolved-import"), None);
+
+    assert_eq!(
+        config.remappings,
+        vec![(
+            "maths/".to_string(),
+            dir.join("lib/math/").to_string_lossy().to_string()
+        )]
+    );
+    assert_eq!(config.include_paths, vec![dir.join("lib")]);
+}
+
+#[test]
+fn test_invalid_config() {
+    let path = env::temp_dir().join(format!("pyrometer-{}-invalid.toml", std::process::id()));
+    fs::write(&path, "[analyzers]\npanic = { severity = \"fatal\" }\n").unwrap();
+    let err = PyrometerConfig::load(&path).unwrap_err();
+    assert!(err.contains("Unknown severity `fatal`"), "{err}");
+    fs::write(&path, "[env.msg]\nvalue = -1\n").unwrap();
+    let err = PyrometerConfig::load(&path).unwrap_err();
+    // errors point at the line they are on
+    assert!(err.contains("value = -1"), "{err}");
+    assert!(err.contains("expected an unsigned integer"), "{err}");
+
+    // unknown and mistyped keys are rejected rather than ignored
+    let errs = [
+        ("[reports]\nshow_consts = true\n", "unknown field `reports`"),
+        (
+            "[report]\nshow_const = true\n",
+            "unknown field `show_const`",
+        ),
+        ("[report]\nshow_consts = \"yes\"\n", "expected a boolean"),
+        ("[env.msg]\nvalues = 0\n", "unknown field `values`"),
+        (
+            "[analyzers]\npanic = { enable = true }\n",
+            "unknown field `enable`",
+        ),
+        (
+            "[analyzers]\npanic = \"on\"\n",
+            "expected a boolean or a table",
+        ),
+        ("[contracts]\ninclude = \"Vault*\"\n", "expected a sequence"),
+        (
+            "[contracts]\ninclude = [\"Va[ult\"]\n",
+            "Invalid glob `Va[ult`",
+        ),
+        ("[env.block]\ncoinbase = \"0x12\"\n", "expected an address"),
+        (
+            "[imports]\nremappings = [\"maths/\"]\n",
+            "Invalid remapping `maths/`",
+        ),
+    ];
+    for (toml, expected) in errs {
+        fs::write(&path, toml).unwrap();
+        let err = PyrometerConfig::load(&path).unwrap_err();
+        assert!(err.contains(expected), "{err}");
+    }
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_selection() {
+    assert!(glob_match("Vault*", "Vault"));
+    assert!(glob_match("Vault*", "VaultV2"));
+    assert!(glob_match("*Mock", "VaultMock"));
+    assert!(glob_match("set?", "setA"));
+    assert!(glob_match("*a*b", "xaab"));
+    assert!(!glob_match("Vault*", "MyVault"));
+    assert!(!glob_match("set?", "set"));
+    // character classes, and their negations
+    assert!(glob_match("Vault[0-9]", "Vault2"));
+    assert!(!glob_match("Vault[0-9]", "VaultV"));
+    assert!(glob_match("Vault[!0-9]", "VaultV"));
+    // `**` is a path wildcard, only valid on its own, where it matches any name
+    assert!(glob_match("**", "VaultMock"));
+    assert!(!glob_match("**Mock", "VaultMock"));
+    // an invalid glob matches nothing
+    assert!(!glob_match("Va[ult", "Va[ult"));
+
+    let config = PyrometerConfig::load(&config_dir().join("pyrometer.toml")).unwrap();
+    assert!(config.analyzes(Some("Vault"), "deposit()"));
+    assert!(!config.analyzes(Some("Vault"), "testDeposit()"));
+    assert!(!config.analyzes(Some("VaultMock"), "deposit()"));
+    assert!(!config.analyzes(Some("Other"), "deposit()"));
+    // free functions are only picked by name
+    assert!(config.analyzes(None, "half(uint256)"));
+}
+
+#[test]
+fn test_report_settings() {
+    let config = PyrometerConfig::load(&config_dir().join("pyrometer.toml")).unwrap();
+    let report = config.report.apply(ReportConfig::default());
+    assert!(report.show_consts);
+    assert!(report.simplify_bounds);
+    // simplified bounds aren't evaluated unless asked to be
+    assert!(!report.eval_bounds);
+    assert_eq!(report.show_tmps, ReportConfig::default().show_tmps);
+}
+
+#[test]
+fn test_env_and_imports() {
+    let dir = config_dir();
+    let config = PyrometerConfig::load(&dir.join("pyrometer.toml")).unwrap();
+    let path = dir.join("src/Vault.sol");
+    let sol = fs::read_to_string(&path).unwrap();
+    let mut analyzer = Analyzer::default();
+    analyzer.set_config(&config);
+    let (maybe_entry, _) = analyzer.parse(&sol, &path, true);
+    let entry = maybe_entry.unwrap();
+    // `maths/Half.sol` resolves with the file's remapping
+    assert!(analyzer.missing_imports.is_empty());
+
+    let deposit = analyzer
+        .search_children(entry, &Edge::Func)
+        .into_iter()
+        .map(FunctionNode::from)
+        .find(|func| {
+            func.name(&analyzer) == "deposit()"
+                && func
+                    .maybe_associated_contract(&analyzer)
+                    .is_some_and(|contract| contract.name(&analyzer) == "Vault")
+        })
+        .unwrap();
+    let ctx = deposit.body_ctx(&analyzer);
+    // the values are concrete, however far the bounds are evaluated
+    let bounds = |var: &str| {
+        let range = ctx
+            .latest_var_by_name(&analyzer, var)
+            .unwrap()
+            .range(&analyzer)
+            .unwrap();
+        let val = |elem: Elem<Concrete>| elem.maybe_concrete().unwrap().val;
+        (
+            val(range.evaled_range_min(&analyzer)),
+            val(range.evaled_range_max(&analyzer)),
+        )
+    };
+    let uint = |val: u64| Concrete::Uint(256, U256::from(val));
+    assert_eq!(bounds("value"), (uint(0), uint(0)));
+    assert_eq!(bounds("number"), (uint(100), uint(100)));
+}
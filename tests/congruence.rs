@@ -0,0 +1,73 @@
+use ethers_core::types::U256;
+use pyrometer::Analyzer;
+use shared::range::congruence::Congruence;
+use shared::range::Range;
+use std::env;
+mod helpers;
+use helpers::*;
+
+#[test]
+fn test_congruence_ops() {
+    let four = Congruence::new(U256::from(4), U256::zero());
+    assert_eq!(four.round_up(U256::from(6)), U256::from(8));
+    assert_eq!(four.round_down(U256::from(29)), U256::from(28));
+
+    let odd = Congruence::new(U256::from(2), U256::one());
+    assert_eq!(four.meet(&odd), None);
+    assert_eq!(
+        four.meet(&Congruence::new(U256::from(8), U256::from(4))),
+        Some(Congruence::new(U256::from(8), U256::from(4)))
+    );
+
+    let scaled = Congruence::unknown()
+        .mul(&Congruence::constant(U256::from(32)))
+        .unwrap();
+    assert_eq!(scaled, Congruence::new(U256::from(32), U256::zero()));
+    assert_eq!(scaled.div(&Congruence::constant(U256::from(8))), Some(four));
+    assert_eq!(
+        scaled.add(&Congruence::constant(U256::from(3))),
+        Some(Congruence::new(U256::from(32), U256::from(3)))
+    );
+    assert_eq!(
+        Congruence::unknown().shl(&Congruence::constant(U256::from(5)), 256),
+        Some(Congruence::new(U256::from(32), U256::zero()))
+    );
+}
+
+#[test]
+fn test_congruence() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/congruence.sol");
+    let sol = include_str!("./test_data/congruence.sol");
+    let mut analyzer = Analyzer::default();
+    let ctxs = body_ctxs(&mut analyzer, path_str, sol);
+    let bounds = |func: &str| {
+        let range = ctxs[func]
+            .latest_var_by_name(&analyzer, "a")
+            .unwrap()
+            .range(&analyzer)
+            .unwrap();
+        (
+            range.evaled_range_min(&analyzer),
+            range.evaled_range_max(&analyzer),
+        )
+    };
+
+    assert_eq!(bounds("scaled"), (uint(64), uint(3168)));
+    assert_eq!(bounds("aligned"), (uint(8), uint(28)));
+    assert_eq!(bounds("shifted"), (uint(32), uint(96)));
+    assert_eq!(bounds("divided").0, uint(4));
+
+    // 6 is not a multiple of 4
+    assert!(ctxs["misaligned"].killed_loc(&analyzer).is_some());
+    [
+        "scaled",
+        "aligned",
+        "shifted",
+        "divided",
+        "offset",
+        "uncheckedOffset",
+    ]
+    .iter()
+    .for_each(|func| assert!(ctxs[*func].killed_loc(&analyzer).is_none()));
+}
@@ -0,0 +1,133 @@
+use pyrometer::Analyzer;
+use shared::nodes::Concrete;
+use shared::range::elem_ty::Elem;
+use shared::range::range_string::ToRangeString;
+use shared::range::Range;
+use std::env;
+mod helpers;
+use helpers::*;
+
+#[test]
+fn test_disjunctive() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/disjunctive.sol");
+    let sol = include_str!("./test_data/disjunctive.sol");
+    let mut analyzer = Analyzer::default();
+    let ctxs = body_ctxs(&mut analyzer, path_str, sol);
+    let range = |func: &str, var: &str| {
+        ctxs[func]
+            .latest_var_by_name(&analyzer, var)
+            .unwrap()
+            .range(&analyzer)
+            .unwrap()
+    };
+
+    // both sides of the `||` survive
+    let split = range("split", "x").intervals.unwrap();
+    assert_eq!(
+        split.to_range_string(true, &analyzer).s,
+        "[0, 9] ∪ [101, 2**256 - 1]"
+    );
+
+    // a later requirement drops the lower interval entirely
+    let narrowed = range("narrowed", "x");
+    assert_eq!(narrowed.evaled_range_min(&analyzer), uint(101));
+    assert!(narrowed.intervals.is_none());
+
+    // arithmetic is applied to each interval
+    let lifted = range("lifted", "y").intervals.unwrap();
+    assert_eq!(
+        lifted.intervals(),
+        &[(uint(1), uint(10)), (uint(102), uint(1000))]
+    );
+
+    // as are assignment operations and casts that keep every value
+    let assigned = range("assign_lifted", "x").intervals.unwrap();
+    assert_eq!(
+        assigned.intervals(),
+        &[(uint(0), uint(18)), (uint(202), uint(1998))]
+    );
+    let cast = range("cast_lifted", "y").intervals.unwrap();
+    assert_eq!(
+        cast.to_range_string(true, &analyzer).s,
+        "[0, 9] ∪ [101, 999]"
+    );
+    // a truncating cast could wrap an interval around, so drops them
+    assert!(range("truncating_cast", "y").intervals.is_none());
+
+    // comparisons are decided when every interval agrees
+    let cmp = |var: &str| {
+        let range = range("cmp_lifted", var);
+        (
+            range.evaled_range_min(&analyzer),
+            range.evaled_range_max(&analyzer),
+        )
+    };
+    let always = |b: bool| (Elem::from(Concrete::Bool(b)), Elem::from(Concrete::Bool(b)));
+    assert_eq!(cmp("inGap"), always(false));
+    assert_eq!(cmp("outside"), always(true));
+    assert_ne!(cmp("unknown"), always(true));
+    assert_ne!(cmp("unknown"), always(false));
+
+    // `20 < x < 50` falls in the gap
+    assert!(ctxs["excluded"].killed_loc(&analyzer).is_some());
+    [
+        "split",
+        "narrowed",
+        "lifted",
+        "capped",
+        "signed_split",
+        "signed_div",
+        "assign_lifted",
+        "cast_lifted",
+        "truncating_cast",
+        "cmp_lifted",
+    ]
+    .iter()
+    .for_each(|func| assert!(ctxs[*func].killed_loc(&analyzer).is_none()));
+
+    // signed types step by a signed one
+    let signed = range("signed_split", "x").intervals.unwrap();
+    assert_eq!(
+        signed.to_range_string(true, &analyzer).s,
+        "[-1 * 2**255, -11] ∪ [11, 2**255 - 1]"
+    );
+
+    // a divisor that may be zero isn't lifted over the intervals
+    assert!(range("signed_div", "y").intervals.is_none());
+
+    // past the cap, the closest intervals are merged
+    let capped = range("capped", "x").intervals.unwrap();
+    assert_eq!(
+        capped.intervals(),
+        &[
+            (uint(1), uint(3)),
+            (uint(5), uint(5)),
+            (uint(7), uint(7)),
+            (uint(50), uint(50))
+        ]
+    );
+}
+
+#[test]
+fn test_max_intervals() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/disjunctive.sol");
+    let sol = include_str!("./test_data/disjunctive.sol");
+    let mut analyzer = Analyzer::default();
+    analyzer.settings.max_intervals = 2;
+    let ctxs = body_ctxs(&mut analyzer, path_str, sol);
+
+    // the cap is per analyzer
+    let capped = ctxs["capped"]
+        .latest_var_by_name(&analyzer, "x")
+        .unwrap()
+        .range(&analyzer)
+        .unwrap()
+        .intervals
+        .unwrap();
+    assert_eq!(
+        capped.intervals(),
+        &[(uint(1), uint(7)), (uint(50), uint(50))]
+    );
+}
@@ -0,0 +1,74 @@
+use ethers_core::types::U256;
+use pyrometer::Analyzer;
+use shared::analyzer::{AnalyzerLike, GraphLike};
+use shared::context::{ContextVar, ContextVarNode};
+use shared::nodes::{BuiltInNode, Builtin, Concrete, VarType};
+use shared::range::elem::{RangeElem, RangeOp};
+use shared::range::elem_ty::{Dynamic, Elem, RangeExpr};
+use shared::range::SolcRange;
+use shared::Node;
+use solang_parser::pt::Loc;
+use std::env;
+use std::path::PathBuf;
+
+fn uint(val: u64) -> Elem<Concrete> {
+    Elem::from(Concrete::from(U256::from(val)))
+}
+
+/// Adds a `uint256` variable ranging over `[min, max]` to the graph
+fn var(
+    analyzer: &mut Analyzer,
+    name: &str,
+    min: Elem<Concrete>,
+    max: Elem<Concrete>,
+) -> ContextVarNode {
+    let builtin = BuiltInNode::from(analyzer.builtin_or_add(Builtin::Uint(256)));
+    let var = ContextVar {
+        loc: Some(Loc::Implicit),
+        name: name.to_string(),
+        display_name: name.to_string(),
+        storage: None,
+        is_tmp: false,
+        tmp_of: None,
+        is_symbolic: true,
+        ty: VarType::BuiltIn(builtin, Some(SolcRange::new(min, max, vec![]))),
+    };
+    ContextVarNode::from(analyzer.add_node(Node::ContextVar(var)))
+}
+
+fn dynamic(var: ContextVarNode) -> Elem<Concrete> {
+    Elem::from(Dynamic::new(var.into(), Loc::Implicit))
+}
+
+#[test]
+fn test_eval_cache_invalidation() {
+    let mut analyzer = Analyzer::default();
+    let x = var(&mut analyzer, "x", uint(0), uint(10));
+    let plus_one = |var| Elem::Expr(RangeExpr::new(dynamic(var), RangeOp::Add, uint(1)));
+    let y = var(&mut analyzer, "y", plus_one(x), plus_one(x));
+    let z = var(&mut analyzer, "z", plus_one(y), plus_one(y));
+
+    assert_eq!(dynamic(z).maximize(&analyzer), uint(12));
+    let stats = analyzer.eval_cache.stats();
+    assert_eq!(dynamic(z).maximize(&analyzer), uint(12));
+    assert_eq!(analyzer.eval_cache.stats().hits, stats.hits + 1);
+
+    // changing `x` drops the bounds of everything evaluated from it
+    x.set_range_max(&mut analyzer, uint(20));
+    assert!(analyzer.eval_cache.stats().invalidations >= 3);
+    assert_eq!(dynamic(z).maximize(&analyzer), uint(22));
+    assert_eq!(dynamic(z).minimize(&analyzer), uint(2));
+}
+
+#[test]
+fn test_eval_cache_hits() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/math.sol");
+    let sol = include_str!("./test_data/math.sol");
+    let mut analyzer = Analyzer::default();
+    let _ = analyzer.parse(sol, &PathBuf::from(path_str), true);
+    let stats = analyzer.eval_cache.stats();
+    assert!(stats.misses > 0);
+    assert!(stats.hits > stats.misses, "{stats}");
+    assert!(stats.entries > 0);
+}
@@ -0,0 +1,99 @@
+//! Reference EVM arithmetic on two's complement words, independent of the range ops
+
+use ethers_core::types::{I256, U256};
+
+/// The min and max of an `int{size}`
+pub fn bounds(size: u16) -> (I256, I256) {
+    if size == 256 {
+        (I256::MIN, I256::MAX)
+    } else {
+        let half = I256::from(1) << (size as usize - 1);
+        (-half, half - I256::from(1))
+    }
+}
+
+/// Truncates a word to `size` bits, sign extending it if the type is signed
+pub fn truncate(signed: bool, size: u16, word: U256) -> U256 {
+    if size == 256 {
+        return word;
+    }
+    let mask = (U256::one() << size as usize) - 1;
+    let word = word & mask;
+    if signed && word.bit(size as usize - 1) {
+        word | !mask
+    } else {
+        word
+    }
+}
+
+/// Truncates a word to `size` bits and sign extends it, like `SIGNEXTEND`
+pub fn sign_extend(word: U256, size: u16) -> I256 {
+    I256::from_raw(truncate(true, size, word))
+}
+
+/// `SDIV`
+pub fn sdiv(a: I256, b: I256) -> I256 {
+    if b.is_zero() {
+        I256::zero()
+    } else if a == I256::MIN && b == I256::minus_one() {
+        I256::MIN
+    } else {
+        let (a_neg, a_abs) = (a.is_negative(), a.unsigned_abs());
+        let (b_neg, b_abs) = (b.is_negative(), b.unsigned_abs());
+        let quot = I256::from_raw(a_abs / b_abs);
+        if a_neg != b_neg {
+            I256::zero().overflowing_sub(quot).0
+        } else {
+            quot
+        }
+    }
+}
+
+/// `SMOD`, taking the sign of the dividend
+pub fn smod(a: I256, b: I256) -> I256 {
+    if b.is_zero() {
+        return I256::zero();
+    }
+    let rem = I256::from_raw(a.unsigned_abs() % b.unsigned_abs());
+    if a.is_negative() {
+        I256::zero().overflowing_sub(rem).0
+    } else {
+        rem
+    }
+}
+
+/// `SHL`
+pub fn shl(word: U256, shift: U256) -> U256 {
+    if shift >= U256::from(256) {
+        U256::zero()
+    } else {
+        word << shift.as_usize()
+    }
+}
+
+/// `SHR`
+pub fn shr(word: U256, shift: U256) -> U256 {
+    if shift >= U256::from(256) {
+        U256::zero()
+    } else {
+        word >> shift.as_usize()
+    }
+}
+
+/// `SAR`, filling with the sign bit
+pub fn sar(a: I256, shift: U256) -> I256 {
+    if shift >= U256::from(256) {
+        return if a.is_negative() {
+            I256::minus_one()
+        } else {
+            I256::zero()
+        };
+    }
+    let shift = shift.as_usize();
+    let shifted = a.into_raw() >> shift;
+    if a.is_negative() && shift > 0 {
+        I256::from_raw(shifted | !(U256::MAX >> shift))
+    } else {
+        I256::from_raw(shifted)
+    }
+}
@@ -0,0 +1,109 @@
+use pyrometer::foundry::{parse_remapping, FoundryConfig};
+use pyrometer::Analyzer;
+use shared::analyzer::Search;
+use shared::{nodes::FunctionNode, Edge};
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+fn project_root() -> PathBuf {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    PathBuf::from(format!("{manifest_dir}/tests/test_data/foundry_project"))
+}
+
+#[test]
+fn test_foundry_config() {
+    let root = project_root();
+    let config = FoundryConfig::load(&root).unwrap();
+    assert_eq!(config.src, PathBuf::from("src"));
+    assert_eq!(config.libs, vec![PathBuf::from("lib")]);
+    assert_eq!(
+        config.remappings,
+        vec![("maths/".to_string(), "src/math/".to_string())]
+    );
+    assert!(config.auto_detect_remappings);
+
+    let remappings = config.resolved_remappings();
+    let expected = [
+        ("maths/", "src/math/"),
+        // libraries resolve to their `src`, else `contracts`, else the library itself
+        ("dep/", "lib/dep/src/"),
+        ("inner/", "lib/dep/lib/inner/contracts/"),
+        ("other/", "lib/other/"),
+    ];
+    expected.iter().for_each(|(name, path)| {
+        assert!(
+            remappings.contains(&(name.to_string(), path.to_string())),
+            "missing {name}={path} in {remappings:?}"
+        );
+    });
+    assert_eq!(remappings.len(), expected.len());
+    // the analyzer takes the first remapping matching an import, so longer prefixes go first
+    assert!(remappings
+        .windows(2)
+        .all(|pair| pair[0].0.len() >= pair[1].0.len()));
+
+    let sources = config
+        .sources()
+        .iter()
+        .map(|path| path.strip_prefix(&root).unwrap().to_path_buf())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        sources,
+        vec![
+            PathBuf::from("src/A.sol"),
+            PathBuf::from("src/B.sol"),
+            PathBuf::from("src/math/Half.sol"),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_remapping() {
+    assert_eq!(
+        parse_remapping("@oz/=lib/openzeppelin-contracts/contracts/"),
+        Some((
+            "@oz/".to_string(),
+            "lib/openzeppelin-contracts/contracts/".to_string()
+        ))
+    );
+    assert_eq!(
+        parse_remapping("src/:ds-test/=lib/ds-test/src/"),
+        Some(("ds-test/".to_string(), "lib/ds-test/src/".to_string()))
+    );
+    assert_eq!(parse_remapping("not a remapping"), None);
+}
+
+#[test]
+fn test_parse_project() {
+    let config = FoundryConfig::load(&project_root()).unwrap();
+    let mut analyzer = Analyzer::default();
+    analyzer.set_foundry_project(&config);
+    let all_sources = analyzer.parse_project(&config.sources()).unwrap();
+
+    // `Dep.sol` is imported by both `A.sol` and `B.sol`, and `Half.sol` is imported by `A.sol`
+    // before being reached as an entry, yet every file is parsed once
+    let files = all_sources
+        .iter()
+        .map(|(_entry, name, _src, _num)| fs::canonicalize(name).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(files.len(), 5);
+    assert_eq!(files.iter().collect::<BTreeSet<_>>().len(), files.len());
+    let nums = all_sources
+        .iter()
+        .map(|(_entry, _name, _src, num)| *num)
+        .collect::<BTreeSet<_>>();
+    assert_eq!(nums.len(), files.len());
+
+    let funcs = all_sources
+        .iter()
+        .filter_map(|(entry, _name, _src, _num)| *entry)
+        .flat_map(|entry| analyzer.search_children(entry, &Edge::Func))
+        .map(|func| FunctionNode::from(func).name(&analyzer))
+        .map(|name| name.split('(').next().unwrap_or_default().to_string())
+        .collect::<BTreeSet<_>>();
+    ["a", "b", "half", "clamp", "limit"]
+        .iter()
+        .for_each(|func| assert!(funcs.contains(*func), "missing {func} in {funcs:?}"));
+}
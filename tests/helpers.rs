@@ -1,7 +1,15 @@
+// each test crate only uses some of these
+#![allow(dead_code)]
+
+use ethers_core::types::U256;
 use pyrometer::Analyzer;
 use shared::analyzer::Search;
+use shared::context::ContextNode;
+use shared::nodes::Concrete;
+use shared::range::elem_ty::Elem;
 use shared::NodeIdx;
 use shared::{nodes::FunctionNode, Edge};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 pub fn assert_no_ctx_killed(path_str: String, sol: &str) {
@@ -37,3 +45,27 @@ pub fn no_ctx_killed(analyzer: Analyzer, entry: NodeIdx) {
         }
     }
 }
+
+/// The body context of each function, keyed by the function's name without its parameters
+pub fn body_ctxs(
+    analyzer: &mut Analyzer,
+    path_str: String,
+    sol: &str,
+) -> BTreeMap<String, ContextNode> {
+    let (maybe_entry, _) = analyzer.parse(sol, &PathBuf::from(path_str), true);
+    let entry = maybe_entry.unwrap();
+    analyzer
+        .search_children(entry, &Edge::Func)
+        .into_iter()
+        .filter_map(|func| {
+            let func = FunctionNode::from(func);
+            let name = func.name(analyzer);
+            let name = name.split('(').next().unwrap_or_default().to_string();
+            Some((name, func.maybe_body_ctx(analyzer)?))
+        })
+        .collect()
+}
+
+pub fn uint(val: u64) -> Elem<Concrete> {
+    Elem::from(Concrete::from(U256::from(val)))
+}
@@ -0,0 +1,60 @@
+use pyrometer::context::analyzers::{
+    bounds::FunctionVarsBoundAnalyzer, html::HtmlReport, ReportConfig,
+};
+use pyrometer::Analyzer;
+use std::collections::BTreeMap;
+use std::env;
+mod helpers;
+use helpers::*;
+
+#[test]
+fn test_html_report() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/html_report.sol");
+    let sol = include_str!("./test_data/html_report.sol");
+    let mut analyzer = Analyzer::default();
+    let ctxs = body_ctxs(&mut analyzer, path_str.clone(), sol);
+    let file_mapping: BTreeMap<_, _> = vec![(0usize, path_str.clone())].into_iter().collect();
+    let sources: BTreeMap<_, _> = vec![(path_str, sol.to_string())].into_iter().collect();
+    let config = ReportConfig {
+        show_consts: true,
+        ..Default::default()
+    };
+
+    let mut report = HtmlReport::new(sources);
+    let analysis = analyzer
+        .bounds_for_all(&file_mapping, ctxs["branch"], config)
+        .as_cli_compat(&file_mapping);
+    report.add_function(&analysis, &analyzer);
+
+    let func = &report.functions[0];
+    assert_eq!(func.function, "branch(uint256)");
+    assert_eq!((func.span.line, func.end_line), (3, 11));
+    let tree = &func.tree;
+    assert!(tree.killed_at.is_none());
+    assert_eq!(tree.forks.len(), 2);
+
+    let (taken, not_taken) = (&tree.forks[0], &tree.forks[1]);
+    assert_eq!(taken.path, "branch(uint256).fork-0");
+    assert_eq!(taken.killed_at.as_ref().unwrap().line, 5);
+    assert_eq!(taken.conditions, vec!["(x > 10) == true"]);
+    assert!(not_taken.killed_at.is_none());
+    assert_eq!(not_taken.conditions, vec!["(x <= 10) == true"]);
+    let last = not_taken.bounds.last().unwrap();
+    assert_eq!(last.span.line, 8);
+    assert_eq!(
+        (last.min.as_deref(), last.max.as_deref()),
+        (Some("1"), Some("11"))
+    );
+    let ret = &not_taken.returns[0];
+    assert_eq!(ret.span.line, 9);
+    assert_eq!(
+        (ret.min.as_deref(), ret.max.as_deref()),
+        (Some("1"), Some("11"))
+    );
+
+    let html = report.to_html();
+    assert!(html.contains("\"branch(uint256).fork-1\""));
+    assert!(!html.contains("/*REPORT_DATA*/"));
+    assert_eq!(html.matches("</script>").count(), 1);
+}
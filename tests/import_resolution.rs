@@ -0,0 +1,124 @@
+use pyrometer::context::analyzers::{imports::UnresolvedImport, sarif::SarifDisplay};
+use pyrometer::Analyzer;
+use shared::analyzer::Search;
+use shared::{nodes::FunctionNode, Edge};
+use solang_parser::pt::Loc;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn project_dir() -> PathBuf {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    PathBuf::from(format!("{manifest_dir}/tests/test_data/npm_imports"))
+}
+
+fn func_names(analyzer: &Analyzer) -> Vec<String> {
+    analyzer
+        .search_children(analyzer.entry, &Edge::Func)
+        .into_iter()
+        .map(|func| FunctionNode::from(func).name(analyzer))
+        .map(|name| name.split('(').next().unwrap_or_default().to_string())
+        .collect()
+}
+
+#[test]
+fn test_node_modules_and_include_paths() {
+    let dir = project_dir();
+    let path = dir.join("contracts/token/Token.sol");
+    let sol = fs::read_to_string(&path).unwrap();
+    let mut analyzer = Analyzer {
+        include_paths: vec![dir.join("include")],
+        ..Default::default()
+    };
+    let (maybe_entry, sources) = analyzer.parse(&sol, &path, true);
+    assert!(maybe_entry.is_some());
+    assert!(analyzer.missing_imports.is_empty());
+
+    // `node_modules` is found two directories up from the importing file
+    let imported = sources
+        .iter()
+        .map(|(_entry, name, _src, _num)| fs::canonicalize(name).unwrap())
+        .collect::<Vec<_>>();
+    assert!(imported.contains(
+        &fs::canonicalize(dir.join("node_modules/@acme/contracts/utils/Math.sol")).unwrap()
+    ));
+    assert!(imported.contains(&fs::canonicalize(dir.join("include/shared/Cap.sol")).unwrap()));
+    let funcs = func_names(&analyzer);
+    ["mint", "min", "cap"]
+        .iter()
+        .for_each(|func| assert!(funcs.contains(&func.to_string()), "missing {func}"));
+}
+
+#[test]
+fn test_import_candidates() {
+    let analyzer = Analyzer {
+        root: PathBuf::from("/project"),
+        remappings: vec![("@acme/".to_string(), "lib/acme/".to_string())],
+        include_paths: vec![PathBuf::from("/include")],
+        ..Default::default()
+    };
+    let current = Path::new("/project/contracts/A.sol");
+
+    // relative imports are only looked for next to the importing file
+    assert_eq!(
+        analyzer.import_candidates("./B.sol", current),
+        vec![PathBuf::from("/project/contracts/./B.sol")]
+    );
+
+    let candidates = analyzer.import_candidates("@acme/X.sol", current);
+    assert_eq!(
+        candidates,
+        vec![
+            PathBuf::from("/project/lib/acme/X.sol"),
+            PathBuf::from("/project/contracts/@acme/X.sol"),
+            PathBuf::from("/project/@acme/X.sol"),
+            PathBuf::from("/include/@acme/X.sol"),
+            PathBuf::from("/project/contracts/node_modules/@acme/X.sol"),
+            PathBuf::from("/project/node_modules/@acme/X.sol"),
+            PathBuf::from("/node_modules/@acme/X.sol"),
+        ]
+    );
+}
+
+#[test]
+fn test_missing_import() {
+    let path = project_dir().join("contracts/Missing.sol");
+    let path_str = path.to_string_lossy().to_string();
+    let sol = fs::read_to_string(&path).unwrap();
+    let mut analyzer = Analyzer::default();
+    let (maybe_entry, _) = analyzer.parse(&sol, &path, true);
+    assert!(maybe_entry.is_some());
+    assert!(func_names(&analyzer).contains(&"f".to_string()));
+
+    assert_eq!(analyzer.missing_imports.len(), 2);
+    // `import * as` is resolved like the other forms
+    assert_eq!(analyzer.missing_imports[1].path, "@acme/contracts/Gone.sol");
+    let missing = &analyzer.missing_imports[0];
+    assert_eq!(missing.path, "@acme/contracts/Missing.sol");
+    let import_start = sol.find("\"@acme").unwrap();
+    assert_eq!(
+        missing.loc,
+        Loc::File(
+            0,
+            import_start,
+            import_start + "\"@acme/contracts/Missing.sol\"".len()
+        )
+    );
+    assert!(missing
+        .searched
+        .iter()
+        .any(|path| path.ends_with("npm_imports/node_modules/@acme/contracts/Missing.sol")));
+
+    let file_mapping: BTreeMap<_, _> = vec![(0usize, path_str.clone())].into_iter().collect();
+    let sources: BTreeMap<_, _> = vec![(path_str, sol.clone())].into_iter().collect();
+    let report = UnresolvedImport::new(&file_mapping, missing);
+    let results = report.sarif_results(&analyzer, &sources);
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].rule_id, "unresolved-import");
+    assert_eq!(results[0].level, "error");
+    assert_eq!(
+        results[0].message.text,
+        "Could not find a file for import: \"@acme/contracts/Missing.sol\""
+    );
+}
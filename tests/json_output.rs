@@ -0,0 +1,87 @@
+use ethers_core::types::U256;
+use pyrometer::context::analyzers::{bounds::FunctionVarsBoundAnalyzer, ReportConfig};
+use pyrometer::Analyzer;
+use std::collections::BTreeMap;
+use std::env;
+mod helpers;
+use helpers::*;
+
+#[test]
+fn test_json_output() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/json_output.sol");
+    let sol = include_str!("./test_data/json_output.sol");
+    let mut analyzer = Analyzer::default();
+    let ctxs = body_ctxs(&mut analyzer, path_str.clone(), sol);
+    let file_mapping: BTreeMap<_, _> = vec![(0usize, path_str.clone())].into_iter().collect();
+    let sources: BTreeMap<_, _> = vec![(path_str.clone(), sol.to_string())]
+        .into_iter()
+        .collect();
+    let config = ReportConfig {
+        show_consts: true,
+        ..Default::default()
+    };
+    let json = |func: &str| {
+        analyzer
+            .bounds_for_all(&file_mapping, ctxs[func], config)
+            .as_cli_compat(&file_mapping)
+            .to_json(&analyzer, &sources)
+    };
+
+    let bounded = json("bounded");
+    assert_eq!(bounded.function, "bounded(uint256)");
+    assert_eq!((bounded.span.line, bounded.span.column), (2, 40));
+    let ctx = &bounded.contexts[0];
+    assert_eq!(ctx.path, "bounded(uint256)");
+    assert!(!ctx.killed);
+    assert!(ctx.killed_at.is_none());
+    let init = &ctx.bounds[0];
+    assert!(init.init);
+    assert_eq!(init.name, "x");
+    // bounds are integers, next to the form the text reports show them in
+    assert_eq!(init.min.as_deref(), Some("0"));
+    assert_eq!(init.max, Some(U256::MAX.to_string()));
+    assert_eq!(
+        (init.min_pretty.as_str(), init.max_pretty.as_str()),
+        ("0", "2**256 - 1")
+    );
+    let last = ctx.bounds.last().unwrap();
+    assert!(!last.init);
+    assert_eq!(last.span.file, path_str);
+    assert_eq!((last.span.line, last.span.column), (4, 17));
+    assert_eq!(&sol[last.span.start..last.span.end], "x != 10");
+    assert_eq!(last.min.as_deref(), Some("6"));
+    assert_eq!(last.exclusions, vec![Some("10".to_string())]);
+    assert_eq!(last.exclusions_pretty, vec!["10"]);
+    assert!(!last.unsat);
+
+    let reverts = json("reverts");
+    let ctx = &reverts.contexts[0];
+    assert!(ctx.killed);
+    let killed_at = ctx.killed_at.as_ref().unwrap();
+    assert_eq!((killed_at.line, killed_at.column), (9, 17));
+
+    let returned = json("returned");
+    let ret = &returned.contexts[0].returns[0];
+    assert_eq!((ret.span.line, ret.span.column), (14, 9));
+    assert_eq!(
+        (ret.min.as_deref(), ret.max.as_deref()),
+        (Some("1"), Some("10"))
+    );
+    assert!(bounded.contexts[0].returns.is_empty());
+
+    // signed bounds keep their sign, and `bytesN` are in hex
+    let encoded = json("encoded");
+    let bounds = &encoded.contexts[0].bounds;
+    let y = bounds.iter().rfind(|bound| bound.name == "y").unwrap();
+    assert_eq!(
+        (y.min.as_deref(), y.max.as_deref()),
+        (Some("-128"), Some("-4"))
+    );
+    let sel = bounds.iter().rfind(|bound| bound.name == "sel").unwrap();
+    assert_eq!(sel.max.as_deref(), Some("0xa9059cbb"));
+
+    let value = serde_json::to_value(&reverts).unwrap();
+    assert_eq!(value["contexts"][0]["killed"], true);
+    assert_eq!(value["contexts"][0]["bounds"][0]["name"], "x");
+}
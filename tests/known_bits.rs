@@ -0,0 +1,68 @@
+use ethers_core::types::U256;
+use pyrometer::Analyzer;
+use shared::nodes::Concrete;
+use shared::range::elem_ty::Elem;
+use shared::range::known_bits::KnownBits;
+use shared::range::Range;
+use std::env;
+mod helpers;
+use helpers::*;
+
+#[test]
+fn test_known_bits_ops() {
+    let low_nibble = KnownBits::from_interval(U256::zero(), U256::from(15));
+    assert_eq!(low_nibble.min_value(), U256::zero());
+    assert_eq!(low_nibble.max_value(), U256::from(15));
+
+    let xored = low_nibble.xor(&KnownBits::constant(U256::from(0xff)));
+    assert_eq!(xored.min_value(), U256::from(0xf0));
+    assert_eq!(xored.max_value(), U256::from(0xff));
+
+    let shifted = low_nibble.shl(U256::from(4));
+    assert_eq!(shifted.min_value(), U256::zero());
+    assert_eq!(shifted.max_value(), U256::from(0xf0));
+    assert_eq!(shifted.shr(U256::from(4)), low_nibble);
+
+    assert!(KnownBits::constant(U256::from(5))
+        .intersect(&KnownBits::constant(U256::from(4)))
+        .is_conflicting());
+}
+
+#[test]
+fn test_known_bits() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/known_bits.sol");
+    let sol = include_str!("./test_data/known_bits.sol");
+    let mut analyzer = Analyzer::default();
+    let ctxs = body_ctxs(&mut analyzer, path_str, sol);
+    let bounds = |func: &str| {
+        let range = ctxs[func]
+            .latest_var_by_name(&analyzer, "a")
+            .unwrap()
+            .range(&analyzer)
+            .unwrap();
+        (
+            range.evaled_range_min(&analyzer),
+            range.evaled_range_max(&analyzer),
+        )
+    };
+
+    assert_eq!(bounds("packed"), (uint(0), uint(0xffff)));
+    assert_eq!(bounds("xored"), (uint(0xf0), uint(0xff)));
+    assert_eq!(bounds("shifted"), (uint(0xff), uint(0xffff)));
+    // `x & 7` for `x <= 8` can be anything up to 7, not just `8 & 7`
+    assert_eq!(bounds("masked"), (uint(0), uint(7)));
+    // clearing bits keeps `x < 100`
+    assert_eq!(bounds("anded"), (uint(0), uint(99)));
+    // setting a bit keeps `x > 100`
+    assert_eq!(
+        bounds("ored"),
+        (uint(101), Elem::from(Concrete::from(U256::MAX)))
+    );
+
+    // bit 0 of `x & 0xf0` is always zero
+    assert!(ctxs["odd"].killed_loc(&analyzer).is_some());
+    ["packed", "xored", "shifted", "masked", "anded", "ored"]
+        .iter()
+        .for_each(|func| assert!(ctxs[*func].killed_loc(&analyzer).is_none()));
+}
@@ -0,0 +1,73 @@
+use ethers_core::types::U256;
+use pyrometer::Analyzer;
+use shared::nodes::Concrete;
+use shared::range::{elem_ty::Elem, Range};
+use std::env;
+mod helpers;
+use helpers::*;
+
+#[test]
+fn test_mapping_keys() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/mapping_keys.sol");
+    let sol = include_str!("./test_data/mapping_keys.sol");
+    let mut analyzer = Analyzer::default();
+    let ctxs = body_ctxs(&mut analyzer, path_str, sol);
+    let bounds = |func: &str, var: &str| {
+        let range = ctxs[func]
+            .latest_var_by_name(&analyzer, var)
+            .unwrap()
+            .range(&analyzer)
+            .unwrap();
+        let val = |elem: Elem<Concrete>| elem.maybe_concrete().unwrap().val.uint_val().unwrap();
+        (
+            val(range.evaled_range_min(&analyzer)),
+            val(range.evaled_range_max(&analyzer)),
+        )
+    };
+
+    // reading a key back gives the value written to it
+    assert_eq!(
+        bounds("read_after_write", "y"),
+        (U256::from(0), U256::from(99))
+    );
+
+    // `a == 1`, so writing `vals[a]` overwrites `vals[1]`
+    assert_eq!(bounds("must_alias", "a"), (U256::from(1), U256::from(1)));
+    assert_eq!(
+        bounds("must_alias", "vals[1]"),
+        (U256::from(10), U256::from(10))
+    );
+
+    // `a` and `b` may be the same key, so `vals[a]` is either value
+    assert_eq!(
+        bounds("may_alias", "vals[a]"),
+        (U256::from(5), U256::from(10))
+    );
+    assert_eq!(
+        bounds("may_alias", "vals[b]"),
+        (U256::from(10), U256::from(10))
+    );
+
+    // `a > 10` can't be key 1, so `vals[a]` keeps its value
+    assert_eq!(bounds("no_alias", "a").0, U256::from(11));
+    assert_eq!(
+        bounds("no_alias", "vals[a]"),
+        (U256::from(5), U256::from(5))
+    );
+    assert_eq!(
+        bounds("no_alias", "vals[1]"),
+        (U256::from(10), U256::from(10))
+    );
+
+    // keys are compared as they were when each access was made, so `vals[a]` was made with
+    // `a == 11` and is overwritten, while `vals[b]` was made with `b == 3` and is not
+    assert_eq!(
+        bounds("reassigned_key", "vals[a]"),
+        (U256::from(2), U256::from(2))
+    );
+    assert_eq!(
+        bounds("reassigned_key", "vals[b]"),
+        (U256::from(1), U256::from(1))
+    );
+}
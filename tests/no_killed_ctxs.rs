@@ -26,6 +26,30 @@ fn test_dyn_types() {
     assert_no_ctx_killed(path_str, sol);
 }
 
+#[test]
+fn test_mapping_keys() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/mapping_keys.sol");
+    let sol = include_str!("./test_data/mapping_keys.sol");
+    assert_no_ctx_killed(path_str, sol);
+}
+
+#[test]
+fn test_array_mutation() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/array_mutation.sol");
+    let sol = include_str!("./test_data/array_mutation.sol");
+    assert_no_ctx_killed(path_str, sol);
+}
+
+#[test]
+fn test_abi_decode() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/abi_decode.sol");
+    let sol = include_str!("./test_data/abi_decode.sol");
+    assert_no_ctx_killed(path_str, sol);
+}
+
 #[test]
 fn test_env() {
     let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
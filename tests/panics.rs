@@ -0,0 +1,86 @@
+use pyrometer::context::analyzers::panics::PanicAnalyzer;
+use pyrometer::Analyzer;
+use shared::analyzer::Search;
+use shared::context::PanicCode;
+use shared::{nodes::FunctionNode, Edge};
+use std::collections::BTreeMap;
+use std::env;
+use std::path::PathBuf;
+mod helpers;
+use helpers::*;
+
+fn reachable_panics(path_str: String, sol: &str) -> BTreeMap<String, Vec<PanicCode>> {
+    let mut analyzer = Analyzer::default();
+    let (maybe_entry, _) = analyzer.parse(sol, &PathBuf::from(path_str.clone()), true);
+    let entry = maybe_entry.unwrap();
+    let file_mapping: BTreeMap<usize, String> = vec![(0usize, path_str)].into_iter().collect();
+    analyzer
+        .search_children(entry, &Edge::Func)
+        .into_iter()
+        .filter_map(|func| {
+            let func = FunctionNode::from(func);
+            let ctx = func.maybe_body_ctx(&analyzer)?;
+            let codes = analyzer
+                .reachable_panics(&file_mapping, ctx)
+                .panics
+                .into_values()
+                .map(|(code, _array, _paths)| code)
+                .collect();
+            let name = func.name(&analyzer);
+            let name = name.split('(').next().unwrap_or_default().to_string();
+            Some((name, codes))
+        })
+        .collect()
+}
+
+#[test]
+fn test_index_bounds() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/index_bounds.sol");
+    let sol = include_str!("./test_data/index_bounds.sol");
+    let panics = reachable_panics(path_str, sol);
+    assert_eq!(
+        panics.get("unchecked_index").unwrap(),
+        &vec![PanicCode::IndexOutOfBounds]
+    );
+    assert!(panics.get("checked_index").unwrap().is_empty());
+    assert!(panics.get("fixed_index").unwrap().is_empty());
+    // the length of a fixed size array is known
+    assert!(panics.get("sized_index").unwrap().is_empty());
+    assert_eq!(
+        panics.get("sized_out_of_bounds").unwrap(),
+        &vec![PanicCode::IndexOutOfBounds]
+    );
+}
+
+#[test]
+fn test_array_pop() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/array_mutation.sol");
+    let sol = include_str!("./test_data/array_mutation.sol");
+    let panics = reachable_panics(path_str, sol);
+    assert_eq!(
+        panics.get("unchecked_pop").unwrap(),
+        &vec![PanicCode::EmptyArrayPop]
+    );
+    assert!(panics.get("checked_pop").unwrap().is_empty());
+    assert!(panics.get("delete_array").unwrap().is_empty());
+}
+
+#[test]
+fn test_index_bounds_forks() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/index_bounds.sol");
+    let sol = include_str!("./test_data/index_bounds.sol");
+    let mut analyzer = Analyzer::default();
+    let ctxs = body_ctxs(&mut analyzer, path_str, sol);
+    let forks = |name: &str| ctxs[name].underlying(&analyzer).forks.len();
+    // only an access that may or may not be in bounds forks
+    assert_eq!(forks("unchecked_index"), 2);
+    assert_eq!(forks("checked_index"), 0);
+    assert_eq!(forks("fixed_index"), 0);
+    assert_eq!(forks("sized_index"), 0);
+    // an access that is certainly out of bounds reverts in place
+    assert_eq!(forks("sized_out_of_bounds"), 0);
+    assert!(ctxs["sized_out_of_bounds"].panicked(&analyzer).is_some());
+}
@@ -0,0 +1,364 @@
+use ethers_core::types::{I256, U256};
+use proptest::prelude::*;
+use pyrometer::Analyzer;
+use shared::analyzer::{AnalyzerLike, GraphLike};
+use shared::context::{ContextVar, ContextVarNode};
+use shared::nodes::{BuiltInNode, Builtin, Concrete, VarType};
+use shared::range::elem::{RangeElem, RangeOp};
+use shared::range::elem_ty::{Dynamic, Elem, RangeConcrete, RangeExpr};
+use shared::range::range_ops::{RangeAdd, RangeDiv, RangeExp, RangeMod, RangeMul, RangeSub};
+use shared::range::{Range, SolcRange};
+use shared::Node;
+use solang_parser::pt::Loc;
+mod evm;
+
+/// The integer types ranges are drawn from
+const TYPES: [(bool, u16); 6] = [
+    (false, 8),
+    (false, 64),
+    (false, 256),
+    (true, 8),
+    (true, 64),
+    (true, 256),
+];
+
+/// An integer value of some type, kept as its two's complement word
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Val {
+    signed: bool,
+    size: u16,
+    word: U256,
+}
+
+impl Val {
+    fn new(signed: bool, size: u16, word: U256) -> Self {
+        Self {
+            signed,
+            size,
+            word: evm::truncate(signed, size, word),
+        }
+    }
+
+    fn is_negative(&self) -> bool {
+        self.signed && I256::from_raw(self.word).is_negative()
+    }
+
+    /// A key ordering values numerically, regardless of sign
+    fn key(&self) -> (bool, U256) {
+        (!self.is_negative(), self.word)
+    }
+
+    fn concrete(&self) -> Concrete {
+        if self.signed {
+            Concrete::Int(self.size, I256::from_raw(self.word))
+        } else {
+            Concrete::Uint(self.size, self.word)
+        }
+    }
+}
+
+/// The numeric key of a concrete range bound, comparable with [`Val::key`]
+fn key(concrete: &Concrete) -> Option<(bool, U256)> {
+    match concrete {
+        Concrete::Uint(_, val) => Some((true, *val)),
+        Concrete::Int(_, val) => Some((!val.is_negative(), val.into_raw())),
+        _ => None,
+    }
+}
+
+/// The min and max of the type of `val`
+fn type_bounds(val: Val) -> (Val, Val) {
+    if val.signed {
+        let (min, max) = evm::bounds(val.size);
+        (
+            Val::new(true, val.size, min.into_raw()),
+            Val::new(true, val.size, max.into_raw()),
+        )
+    } else {
+        (
+            Val::new(false, val.size, U256::zero()),
+            Val::new(false, val.size, U256::MAX),
+        )
+    }
+}
+
+/// Concrete EVM semantics of an operation, which is `None` when it reverts. Outside of
+/// `unchecked` blocks arithmetic reverts on overflow, inside it wraps like the rest (shifts and
+/// bitwise) always do. Dividing by zero reverts either way
+fn exec(op: RangeOp, lhs: Val, rhs: Val, unchecked: bool) -> Option<Val> {
+    let signed = |val: Val| evm::sign_extend(val.word, val.size);
+    let wrapped = |word: U256| Some(Val::new(lhs.signed, lhs.size, word));
+    let checked_signed = |res: Option<I256>| {
+        let res = res?;
+        let wrapped = Val::new(true, lhs.size, res.into_raw());
+        (signed(wrapped) == res).then_some(wrapped)
+    };
+    let checked_unsigned = |res: Option<U256>| {
+        let res = res?;
+        let wrapped = Val::new(false, lhs.size, res);
+        (wrapped.word == res).then_some(wrapped)
+    };
+    let (l, r) = (signed(lhs), signed(rhs));
+    match (op, lhs.signed) {
+        // the low bits of two's complement arithmetic don't depend on the sign
+        (RangeOp::Add, _) if unchecked => wrapped(lhs.word.overflowing_add(rhs.word).0),
+        (RangeOp::Sub, _) if unchecked => wrapped(lhs.word.overflowing_sub(rhs.word).0),
+        (RangeOp::Mul, _) if unchecked => wrapped(lhs.word.overflowing_mul(rhs.word).0),
+        (RangeOp::Div, true) if unchecked && !r.is_zero() => wrapped(evm::sdiv(l, r).into_raw()),
+        (RangeOp::Exp, false) if unchecked => wrapped(lhs.word.overflowing_pow(rhs.word).0),
+        (RangeOp::Add, true) => checked_signed(l.checked_add(r)),
+        (RangeOp::Add, false) => checked_unsigned(lhs.word.checked_add(rhs.word)),
+        (RangeOp::Sub, true) => checked_signed(l.checked_sub(r)),
+        (RangeOp::Sub, false) => checked_unsigned(lhs.word.checked_sub(rhs.word)),
+        (RangeOp::Mul, true) => checked_signed(l.checked_mul(r)),
+        (RangeOp::Mul, false) => checked_unsigned(lhs.word.checked_mul(rhs.word)),
+        (RangeOp::Div, true) => checked_signed(l.checked_div(r)),
+        (RangeOp::Div, false) => checked_unsigned(lhs.word.checked_div(rhs.word)),
+        (RangeOp::Mod, true) if r.is_zero() => None,
+        (RangeOp::Mod, true) => Some(Val::new(true, lhs.size, evm::smod(l, r).into_raw())),
+        (RangeOp::Mod, false) => checked_unsigned(lhs.word.checked_rem(rhs.word)),
+        (RangeOp::Exp, false) => checked_unsigned(lhs.word.checked_pow(rhs.word)),
+        (RangeOp::Shl, _) => Some(Val::new(lhs.signed, lhs.size, evm::shl(lhs.word, rhs.word))),
+        (RangeOp::Shr, true) => Some(Val::new(true, lhs.size, evm::sar(l, rhs.word).into_raw())),
+        (RangeOp::Shr, false) => Some(Val::new(false, lhs.size, evm::shr(lhs.word, rhs.word))),
+        (RangeOp::BitAnd, _) => Some(Val::new(lhs.signed, lhs.size, lhs.word & rhs.word)),
+        (RangeOp::BitOr, _) => Some(Val::new(lhs.signed, lhs.size, lhs.word | rhs.word)),
+        (RangeOp::BitXor, _) => Some(Val::new(lhs.signed, lhs.size, lhs.word ^ rhs.word)),
+        (RangeOp::Min, _) => Some(if lhs.key() <= rhs.key() { lhs } else { rhs }),
+        (RangeOp::Max, _) => Some(if lhs.key() >= rhs.key() { lhs } else { rhs }),
+        _ => None,
+    }
+}
+
+/// Adds a variable ranging over `[min, max]` to the graph
+fn var(analyzer: &mut Analyzer, min: Val, max: Val) -> ContextVarNode {
+    let builtin = if min.signed {
+        Builtin::Int(min.size)
+    } else {
+        Builtin::Uint(min.size)
+    };
+    let builtin = BuiltInNode::from(analyzer.builtin_or_add(builtin));
+    let range = SolcRange::new(
+        Elem::from(min.concrete()),
+        Elem::from(max.concrete()),
+        vec![],
+    );
+    let var = ContextVar {
+        loc: Some(Loc::Implicit),
+        name: "x".to_string(),
+        display_name: "x".to_string(),
+        storage: None,
+        is_tmp: false,
+        tmp_of: None,
+        is_symbolic: true,
+        ty: VarType::BuiltIn(builtin, Some(range)),
+    };
+    ContextVarNode::from(analyzer.add_node(Node::ContextVar(var)))
+}
+
+/// An element referring to `var`
+fn dynamic(var: ContextVarNode) -> Elem<Concrete> {
+    Elem::Dynamic(Dynamic::new(var.into(), Loc::Implicit))
+}
+
+prop_compose! {
+    fn word()(
+        word in prop::array::uniform4(any::<u64>()),
+        small in -300i64..300,
+        kind in 0u8..4,
+    ) -> U256 {
+        match kind {
+            0 => U256(word),
+            1 => U256::MAX,
+            2 => U256::one() << 255,
+            // small values, positive or negative, exercise the boundaries around zero
+            _ => I256::from(small).into_raw(),
+        }
+    }
+}
+
+prop_compose! {
+    /// A value sampled from inside a range of the given type
+    fn ranged(signed: bool, size: u16)(words in prop::array::uniform3(word())) -> (Val, Val, Val) {
+        let mut vals = words.map(|word| Val::new(signed, size, word));
+        vals.sort_by_key(|val| val.key());
+        (vals[0], vals[1], vals[2])
+    }
+}
+
+fn ty() -> impl Strategy<Value = (bool, u16)> {
+    prop::sample::select(TYPES.to_vec())
+}
+
+/// Checks that the result of `lhs op rhs` lies within the bounds the range engine computes,
+/// whenever it has one, both for the expression and for the range `BinOp::op` builds from the
+/// lhs's range with [`SolcRange::dyn_fn_from_op`]
+fn check_sound(
+    op: RangeOp,
+    lhs: (Val, Val, Val),
+    rhs: (Val, Val, Val),
+) -> Result<(), TestCaseError> {
+    let (lhs_min, lhs_val, lhs_max) = lhs;
+    let (rhs_min, rhs_val, rhs_max) = rhs;
+    let res = match exec(op, lhs_val, rhs_val, false) {
+        Some(res) => res,
+        // reverting executions produce no value
+        None => return Ok(()),
+    };
+
+    let mut analyzer = Analyzer::default();
+    let lhs_var = var(&mut analyzer, lhs_min, lhs_max);
+    let rhs_var = var(&mut analyzer, rhs_min, rhs_max);
+    let expr = Elem::Expr(RangeExpr::new(dynamic(lhs_var), op, dynamic(rhs_var)));
+    let lhs_range = lhs_var.range(&analyzer).unwrap();
+    let range = SolcRange::dyn_fn_from_op(op)(lhs_range, rhs_var, Loc::Implicit);
+
+    for (built, min, max) in [
+        ("expr", expr.minimize(&analyzer), expr.maximize(&analyzer)),
+        (
+            "dyn_fn_from_op",
+            range.evaled_range_min(&analyzer),
+            range.evaled_range_max(&analyzer),
+        ),
+    ] {
+        if let Some(min) = min.maybe_concrete().and_then(|min| key(&min.val)) {
+            prop_assert!(
+                min <= res.key(),
+                "{op:?} ({built}): {lhs_val:?} in [{lhs_min:?}, {lhs_max:?}], {rhs_val:?} in [{rhs_min:?}, {rhs_max:?}] gave {res:?}, below the min {min:?}"
+            );
+        }
+        if let Some(max) = max.maybe_concrete().and_then(|max| key(&max.val)) {
+            prop_assert!(
+                max >= res.key(),
+                "{op:?} ({built}): {lhs_val:?} in [{lhs_min:?}, {lhs_max:?}], {rhs_val:?} in [{rhs_min:?}, {rhs_max:?}] gave {res:?}, above the max {max:?}"
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Applies an operation to two concrete values with the range ops
+fn range_exec(op: RangeOp, lhs: Val, rhs: Val) -> Option<Val> {
+    let concrete = |val: Val| RangeConcrete {
+        val: val.concrete(),
+        loc: Loc::Implicit,
+    };
+    let (l, r) = (concrete(lhs), concrete(rhs));
+    let res = match op {
+        RangeOp::Add => l.range_add(&r),
+        RangeOp::Sub => l.range_sub(&r),
+        RangeOp::Mul => l.range_mul(&r),
+        RangeOp::Div => l.range_div(&r),
+        RangeOp::Mod => l.range_mod(&r),
+        RangeOp::Exp => l.range_exp(&r),
+        _ => unreachable!("{op:?} isn't checked arithmetic"),
+    }?;
+    let val = res.maybe_concrete().expect("Not concrete").val;
+    Some(match val {
+        Concrete::Uint(size, word) => Val::new(false, size, word),
+        Concrete::Int(size, val) => Val::new(true, size, val.into_raw()),
+        _ => panic!("{val:?} isn't an integer"),
+    })
+}
+
+/// Compares the range ops on concrete values with unchecked arithmetic. They agree whenever
+/// the operation doesn't overflow, otherwise the range ops saturate at a bound of the type (or
+/// revert) where unchecked arithmetic wraps around
+fn check_unchecked(op: RangeOp, lhs: Val, rhs: Val) -> Result<(), TestCaseError> {
+    let res = range_exec(op, lhs, rhs);
+    let wrapped = exec(op, lhs, rhs, true);
+    match exec(op, lhs, rhs, false) {
+        Some(checked) => {
+            prop_assert_eq!(wrapped, Some(checked), "{:?}: {:?}, {:?}", op, lhs, rhs);
+            prop_assert_eq!(res, Some(checked), "{:?}: {:?}, {:?}", op, lhs, rhs);
+        }
+        None if wrapped.is_none() => {
+            prop_assert_eq!(res, None, "{:?}: {:?}, {:?}", op, lhs, rhs);
+        }
+        None => {
+            let (min, max) = type_bounds(lhs);
+            prop_assert!(
+                res.is_none() || res == Some(min) || res == Some(max),
+                "{op:?}: {lhs:?}, {rhs:?} wrapped to {wrapped:?}, but the range ops gave {res:?}"
+            );
+        }
+    }
+    Ok(())
+}
+
+fn arith_op() -> impl Strategy<Value = RangeOp> {
+    prop::sample::select(vec![
+        RangeOp::Add,
+        RangeOp::Sub,
+        RangeOp::Mul,
+        RangeOp::Div,
+        RangeOp::Mod,
+        RangeOp::Min,
+        RangeOp::Max,
+        RangeOp::BitAnd,
+        RangeOp::BitOr,
+        RangeOp::BitXor,
+    ])
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(2000))]
+
+    #[test]
+    fn prop_binary_ops_sound(
+        op in arith_op(),
+        (lhs, rhs) in ty().prop_flat_map(|(signed, size)| (ranged(signed, size), ranged(signed, size))),
+    ) {
+        check_sound(op, lhs, rhs)?;
+    }
+
+    #[test]
+    fn prop_shifts_sound(
+        op in prop::sample::select(vec![RangeOp::Shl, RangeOp::Shr]),
+        lhs in ty().prop_flat_map(|(signed, size)| ranged(signed, size)),
+        shifts in prop::array::uniform3(0u64..300),
+    ) {
+        let mut shifts = shifts.map(|shift| Val::new(false, 256, U256::from(shift)));
+        shifts.sort_by_key(|val| val.key());
+        check_sound(op, lhs, (shifts[0], shifts[1], shifts[2]))?;
+    }
+
+    #[test]
+    fn prop_unchecked_ops(
+        op in prop::sample::select(vec![
+            RangeOp::Add,
+            RangeOp::Sub,
+            RangeOp::Mul,
+            RangeOp::Div,
+            RangeOp::Mod,
+        ]),
+        ((_, lhs, _), (_, rhs, _)) in ty().prop_flat_map(|(signed, size)| (ranged(signed, size), ranged(signed, size))),
+    ) {
+        check_unchecked(op, lhs, rhs)?;
+    }
+
+    #[test]
+    fn prop_unchecked_exp(base in 0u64..1000, exp in 0u64..80) {
+        check_unchecked(
+            RangeOp::Exp,
+            Val::new(false, 256, U256::from(base)),
+            Val::new(false, 256, U256::from(exp)),
+        )?;
+    }
+
+    #[test]
+    fn prop_exp_sound(
+        bases in prop::array::uniform3(0u64..1000),
+        exps in prop::array::uniform3(0u64..80),
+    ) {
+        let mut bases = bases.map(|base| Val::new(false, 256, U256::from(base)));
+        bases.sort_by_key(|val| val.key());
+        let mut exps = exps.map(|exp| Val::new(false, 256, U256::from(exp)));
+        exps.sort_by_key(|val| val.key());
+        check_sound(
+            RangeOp::Exp,
+            (bases[0], bases[1], bases[2]),
+            (exps[0], exps[1], exps[2]),
+        )?;
+    }
+}
@@ -0,0 +1,71 @@
+use pyrometer::Analyzer;
+use shared::nodes::Concrete;
+use shared::range::{elem_ty::Elem, Range};
+use std::env;
+mod helpers;
+use helpers::*;
+
+#[test]
+fn test_relational() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/relational.sol");
+    let sol = include_str!("./test_data/relational.sol");
+    let mut analyzer = Analyzer::default();
+    analyzer.settings.relational = true;
+    let ctxs = body_ctxs(&mut analyzer, path_str, sol);
+    let range = |func: &str, var: &str| {
+        ctxs[func]
+            .latest_var_by_name(&analyzer, var)
+            .unwrap()
+            .range(&analyzer)
+            .unwrap()
+    };
+
+    // `x < y` and `y < 10` imply `x < 9`
+    assert_eq!(
+        range("transitive", "x").evaled_range_max(&analyzer),
+        uint(8)
+    );
+
+    // requirements on `x` carry over to its copy
+    let copied = range("copied", "y");
+    assert_eq!(copied.evaled_range_max(&analyzer), uint(99));
+    assert!(copied.range_exclusions().contains(&uint(0)));
+
+    // `y` stays 5 away from `x`
+    assert_eq!(range("offset", "y").evaled_range_max(&analyzer), uint(14));
+
+    // `x <= y` already decides `x > y`
+    let decided = range("decided", "b");
+    assert_eq!(
+        decided.evaled_range_min(&analyzer),
+        Elem::from(Concrete::Bool(false))
+    );
+    assert_eq!(
+        decided.evaled_range_max(&analyzer),
+        Elem::from(Concrete::Bool(false))
+    );
+
+    assert!(ctxs["contradiction"].killed_loc(&analyzer).is_some());
+    ["transitive", "copied", "offset", "decided"]
+        .iter()
+        .for_each(|func| assert!(ctxs[*func].killed_loc(&analyzer).is_none()));
+}
+
+#[test]
+fn test_relational_off_by_default() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/relational.sol");
+    let sol = include_str!("./test_data/relational.sol");
+    let mut analyzer = Analyzer::default();
+    let ctxs = body_ctxs(&mut analyzer, path_str, sol);
+
+    // without relations, the copy doesn't learn about requirements on `x`
+    let copied = ctxs["copied"]
+        .latest_var_by_name(&analyzer, "y")
+        .unwrap()
+        .range(&analyzer)
+        .unwrap();
+    assert!(!copied.range_exclusions().contains(&uint(0)));
+    assert!(ctxs.values().all(|ctx| ctx.zone(&analyzer).is_empty()));
+}
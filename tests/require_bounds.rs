@@ -0,0 +1,44 @@
+use ethers_core::types::U256;
+use pyrometer::Analyzer;
+use shared::nodes::Concrete;
+use shared::range::{elem::RangeElem, elem_ty::Elem, Range};
+use std::cmp::Ordering;
+use std::env;
+mod helpers;
+use helpers::*;
+
+#[test]
+fn test_require_rhs_bounds() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/require_bounds.sol");
+    let sol = include_str!("./test_data/require_bounds.sol");
+    let mut analyzer = Analyzer::default();
+    let ctxs = body_ctxs(&mut analyzer, path_str, sol);
+    let range = |func: &str, var: &str| {
+        ctxs[func]
+            .latest_var_by_name(&analyzer, var)
+            .unwrap()
+            .range(&analyzer)
+            .unwrap()
+    };
+
+    // `x > y` only bounds `y` by the largest `x`
+    assert_eq!(range("gt", "y").evaled_range_max(&analyzer), uint(98));
+    assert_eq!(range("gte", "y").evaled_range_max(&analyzer), uint(100));
+
+    // `x < y` only bounds `y` by the smallest `x`, so `x == 1, y == 6` is still possible
+    assert!(matches!(
+        range("lt", "y")
+            .evaled_range_min(&analyzer)
+            .range_ord(&uint(6)),
+        Some(Ordering::Less) | Some(Ordering::Equal)
+    ));
+    assert_eq!(
+        range("lte", "y").evaled_range_max(&analyzer),
+        Elem::from(Concrete::from(U256::MAX))
+    );
+
+    ["gt", "gte", "lt", "lte"]
+        .iter()
+        .for_each(|func| assert!(ctxs[*func].killed_loc(&analyzer).is_none()));
+}
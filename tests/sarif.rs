@@ -0,0 +1,130 @@
+use ariadne::{Color, ReportKind};
+use pyrometer::context::analyzers::panics::PanicAnalyzer;
+use pyrometer::context::analyzers::sarif::{
+    level, rule_id, strip_ansi, SarifArtifactLocation, SarifDisplay, SarifLog, SRCROOT,
+};
+use pyrometer::context::analyzers::ReportConfig;
+use pyrometer::context::queries::storage_write::AccessStorageWriteQuery;
+use pyrometer::Analyzer;
+use std::collections::BTreeMap;
+use std::env;
+use std::path::{Path, PathBuf};
+mod helpers;
+use helpers::*;
+
+#[test]
+fn test_sarif_panics() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/index_bounds.sol");
+    let sol = include_str!("./test_data/index_bounds.sol");
+    let mut analyzer = Analyzer::default();
+    let ctxs = body_ctxs(&mut analyzer, path_str.clone(), sol);
+    let file_mapping: BTreeMap<_, _> = vec![(0usize, path_str.clone())].into_iter().collect();
+    let sources: BTreeMap<_, _> = vec![(path_str.clone(), sol.to_string())]
+        .into_iter()
+        .collect();
+
+    let checked = analyzer.reachable_panics(&file_mapping, ctxs["checked_index"]);
+    assert!(checked.sarif_results(&analyzer, &sources).is_empty());
+
+    let unchecked = analyzer.reachable_panics(&file_mapping, ctxs["unchecked_index"]);
+    let results = unchecked.sarif_results(&analyzer, &sources);
+    assert_eq!(results.len(), 1);
+    let result = &results[0];
+    assert_eq!(result.rule_id, "panic");
+    assert_eq!(result.level, "error");
+    assert!(!result.message.text.contains('\u{1b}'));
+    assert!(result.message.text.contains("unchecked_index"));
+    let location = &result.locations[0].physical_location;
+    assert_eq!(location.artifact_location.uri, path_str);
+    assert_eq!(location.region.start_line, Some(4));
+    let related = &result.related_locations[0];
+    let region = &related.physical_location.region;
+    assert_eq!(
+        &sol[region.byte_offset..region.byte_offset + region.byte_length],
+        "x[i]"
+    );
+    assert_eq!(
+        (region.start_line, region.start_column),
+        (Some(5), Some(16))
+    );
+    assert!(related
+        .message
+        .as_ref()
+        .unwrap()
+        .text
+        .starts_with("Panic(0x32)"));
+
+    let log = serde_json::to_value(SarifLog::new(results, Path::new(&manifest_dir))).unwrap();
+    let uri = &log["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"];
+    assert_eq!(uri["uri"], "tests/test_data/index_bounds.sol");
+    assert_eq!(uri["uriBaseId"], SRCROOT);
+    assert_eq!(log["version"], "2.1.0");
+    assert!(log["$schema"].is_string());
+    assert_eq!(log["runs"][0]["tool"]["driver"]["rules"][0]["id"], "panic");
+    assert_eq!(log["runs"][0]["results"][0]["ruleId"], "panic");
+    assert!(log["runs"][0]["results"][0].get("ruleName").is_none());
+}
+
+#[test]
+fn test_sarif_access() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/access.sol");
+    let sol = include_str!("./test_data/access.sol");
+    let mut analyzer = Analyzer::default();
+    let (maybe_entry, _) = analyzer.parse(sol, &PathBuf::from(path_str.clone()), true);
+    let file_mapping: BTreeMap<_, _> = vec![(0usize, path_str.clone())].into_iter().collect();
+    let sources: BTreeMap<_, _> = vec![(path_str.clone(), sol.to_string())]
+        .into_iter()
+        .collect();
+
+    let report = analyzer.access_query(
+        maybe_entry.unwrap(),
+        &file_mapping,
+        ReportConfig::default(),
+        "Access".to_string(),
+        "x".to_string(),
+    );
+    let results = report.sarif_results(&analyzer, &sources);
+    assert_eq!(results.len(), 1);
+    let region = &results[0].locations[0].physical_location.region;
+    assert_eq!(
+        &sol[region.byte_offset..region.byte_offset + region.byte_length],
+        "x = v"
+    );
+    let related = &results[0].related_locations[0];
+    assert_eq!(related.physical_location.region.start_line, Some(4));
+    assert!(related.message.as_ref().unwrap().text.contains("setX"));
+}
+
+#[test]
+fn test_sarif_kinds() {
+    assert_eq!(
+        rule_id(&ReportKind::Custom("Storage Write Query", Color::Green)),
+        "storage-write-query"
+    );
+    assert_eq!(level(&ReportKind::Custom("Panic", Color::Red)), "error");
+    assert_eq!(level(&ReportKind::Warning), "warning");
+    assert_eq!(level(&ReportKind::Custom("Bounds", Color::Cyan)), "note");
+    assert_eq!(
+        strip_ansi("\u{1b}[36mfunction f\u{1b}[0m()"),
+        "function f()"
+    );
+}
+
+#[test]
+fn test_sarif_uris() {
+    let root = Path::new("/work/my project");
+    let inside = SarifArtifactLocation::from_path("/work/my project/src/A.sol", root);
+    assert_eq!(inside.uri, "src/A.sol");
+    assert_eq!(inside.uri_base_id.as_deref(), Some(SRCROOT));
+    let outside = SarifArtifactLocation::from_path("/lib/b c.sol", root);
+    assert_eq!(outside.uri, "file:///lib/b%20c.sol");
+    assert!(outside.uri_base_id.is_none());
+
+    let log = serde_json::to_value(SarifLog::new(vec![], root)).unwrap();
+    assert_eq!(
+        log["runs"][0]["originalUriBaseIds"][SRCROOT]["uri"],
+        "file:///work/my%20project/"
+    );
+}
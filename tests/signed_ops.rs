@@ -0,0 +1,308 @@
+use ethers_core::types::{I256, U256};
+use proptest::prelude::*;
+use pyrometer::Analyzer;
+use shared::nodes::Concrete;
+use shared::range::elem_ty::{Elem, RangeConcrete};
+use shared::range::range_ops::{RangeAdd, RangeDiv, RangeMod, RangeMul, RangeShift, RangeSub};
+use shared::range::Range;
+use solang_parser::pt::Loc;
+use std::env;
+mod evm;
+mod helpers;
+use helpers::body_ctxs;
+
+/// What checked Solidity arithmetic does with an operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Expected {
+    /// The operation succeeds with this value
+    Value(I256),
+    /// The operation overflows, so a range bound saturates at the type's min or max
+    Overflow { negative: bool },
+    /// The operation always reverts, e.g. dividing by zero
+    Revert,
+}
+
+/// Checked Solidity arithmetic on top of the reference EVM operations
+mod checked {
+    use super::*;
+
+    fn checked(val: Option<I256>, size: u16, negative: bool) -> Expected {
+        let (min, max) = evm::bounds(size);
+        match val {
+            Some(val) if val >= min && val <= max => Expected::Value(val),
+            _ => Expected::Overflow { negative },
+        }
+    }
+
+    pub fn add(a: I256, b: I256, size: u16) -> Expected {
+        checked(a.checked_add(b), size, a.is_negative())
+    }
+
+    pub fn sub(a: I256, b: I256, size: u16) -> Expected {
+        checked(a.checked_sub(b), size, a.is_negative())
+    }
+
+    pub fn mul(a: I256, b: I256, size: u16) -> Expected {
+        checked(a.checked_mul(b), size, a.is_negative() != b.is_negative())
+    }
+
+    pub fn div(a: I256, b: I256, size: u16) -> Expected {
+        if b.is_zero() {
+            return Expected::Revert;
+        }
+        let (min, _) = evm::bounds(size);
+        if a == min && b == I256::minus_one() {
+            // the result doesn't fit, which checked arithmetic reverts on
+            return Expected::Revert;
+        }
+        Expected::Value(evm::sdiv(a, b))
+    }
+
+    pub fn rem(a: I256, b: I256) -> Expected {
+        if b.is_zero() {
+            return Expected::Revert;
+        }
+        Expected::Value(evm::smod(a, b))
+    }
+
+    pub fn shl(a: I256, shift: U256, size: u16) -> Expected {
+        if shift >= U256::from(size) {
+            return Expected::Value(I256::zero());
+        }
+        let wrapped = evm::sign_extend(evm::shl(a.into_raw(), shift), size);
+        if evm::sar(wrapped, shift) == a {
+            Expected::Value(wrapped)
+        } else {
+            Expected::Overflow {
+                negative: a.is_negative(),
+            }
+        }
+    }
+}
+
+fn int(size: u16, val: I256) -> RangeConcrete<Concrete> {
+    RangeConcrete {
+        val: Concrete::Int(size, val),
+        loc: Loc::Implicit,
+    }
+}
+
+fn uint(size: u16, val: U256) -> RangeConcrete<Concrete> {
+    RangeConcrete {
+        val: Concrete::Uint(size, val),
+        loc: Loc::Implicit,
+    }
+}
+
+fn concrete(elem: Option<Elem<Concrete>>) -> Option<Concrete> {
+    elem.map(|elem| elem.maybe_concrete().expect("Not concrete").val)
+}
+
+fn check(size: u16, actual: Option<Elem<Concrete>>, expected: Expected) {
+    let (min, max) = evm::bounds(size);
+    let expected = match expected {
+        Expected::Value(val) => Some(Concrete::Int(size, val)),
+        Expected::Overflow { negative: true } => Some(Concrete::Int(size, min)),
+        Expected::Overflow { negative: false } => Some(Concrete::Int(size, max)),
+        Expected::Revert => None,
+    };
+    assert_eq!(concrete(actual), expected);
+}
+
+prop_compose! {
+    fn sized_int()(
+        size in prop::sample::select(vec![8u16, 16, 64, 128, 256]),
+        word in prop::array::uniform4(any::<u64>()),
+        edge in prop::sample::select(vec![None, Some(0u8), Some(1), Some(2), Some(3), Some(4)]),
+    ) -> (u16, I256) {
+        let (min, max) = evm::bounds(size);
+        let val = match edge {
+            Some(0) => min,
+            Some(1) => max,
+            Some(2) => I256::minus_one(),
+            Some(3) => I256::zero(),
+            Some(4) => I256::one(),
+            _ => evm::sign_extend(U256(word), size),
+        };
+        (size, val)
+    }
+}
+
+prop_compose! {
+    fn int_pair()((size, a) in sized_int(), (_, b) in sized_int()) -> (u16, I256, I256) {
+        let (min, max) = evm::bounds(size);
+        // bring `b` into `a`'s type
+        let b = if b < min || b > max { evm::sign_extend(b.into_raw(), size) } else { b };
+        (size, a, b)
+    }
+}
+
+proptest! {
+    #[test]
+    fn prop_signed_add((size, a, b) in int_pair()) {
+        check(size, int(size, a).range_add(&int(size, b)), checked::add(a, b, size));
+    }
+
+    #[test]
+    fn prop_signed_sub((size, a, b) in int_pair()) {
+        check(size, int(size, a).range_sub(&int(size, b)), checked::sub(a, b, size));
+    }
+
+    #[test]
+    fn prop_signed_mul((size, a, b) in int_pair()) {
+        check(size, int(size, a).range_mul(&int(size, b)), checked::mul(a, b, size));
+    }
+
+    #[test]
+    fn prop_signed_div((size, a, b) in int_pair()) {
+        check(size, int(size, a).range_div(&int(size, b)), checked::div(a, b, size));
+    }
+
+    #[test]
+    fn prop_signed_mod((size, a, b) in int_pair()) {
+        check(size, int(size, a).range_mod(&int(size, b)), checked::rem(a, b));
+    }
+
+    #[test]
+    fn prop_signed_shr((size, a) in sized_int(), shift in 0u64..300) {
+        let shift = U256::from(shift);
+        check(
+            size,
+            int(size, a).range_shr(&uint(256, shift)),
+            Expected::Value(evm::sar(a, shift)),
+        );
+    }
+
+    #[test]
+    fn prop_signed_shl((size, a) in sized_int(), shift in 0u64..300) {
+        let shift = U256::from(shift);
+        check(
+            size,
+            int(size, a).range_shl(&uint(256, shift)),
+            checked::shl(a, shift, size),
+        );
+    }
+
+    #[test]
+    fn prop_unsigned_ops(a in any::<u128>(), b in any::<u128>(), shift in 0u64..300) {
+        let (a, b) = (U256::from(a) << 128, U256::from(b));
+        let shift = U256::from(shift);
+        let lhs = uint(256, a);
+        let rhs = uint(256, b);
+        prop_assert_eq!(
+            concrete(lhs.range_add(&rhs)),
+            Some(Concrete::Uint(256, a.checked_add(b).unwrap_or(U256::MAX)))
+        );
+        // a bound below zero saturates at it
+        prop_assert_eq!(
+            concrete(lhs.range_sub(&rhs)),
+            Some(Concrete::Uint(256, a.checked_sub(b).unwrap_or(U256::zero())))
+        );
+        prop_assert_eq!(
+            concrete(rhs.range_sub(&lhs)),
+            Some(Concrete::Uint(256, b.checked_sub(a).unwrap_or(U256::zero())))
+        );
+        prop_assert_eq!(
+            concrete(lhs.range_mul(&rhs)),
+            Some(Concrete::Uint(256, a.checked_mul(b).unwrap_or(U256::MAX)))
+        );
+        prop_assert_eq!(
+            concrete(lhs.range_div(&rhs)),
+            a.checked_div(b).map(|val| Concrete::Uint(256, val))
+        );
+        prop_assert_eq!(
+            concrete(lhs.range_mod(&rhs)),
+            a.checked_rem(b).map(|val| Concrete::Uint(256, val))
+        );
+        prop_assert_eq!(
+            concrete(lhs.range_shr(&uint(256, shift))),
+            Some(Concrete::Uint(256, evm::shr(a, shift)))
+        );
+    }
+}
+
+#[test]
+fn test_signed_edge_cases() {
+    let min = I256::MIN;
+    let neg_one = I256::minus_one();
+
+    // `type(int256).min / -1` overflows
+    assert_eq!(int(256, min).range_div(&int(256, neg_one)), None);
+    // `type(int256).min % -1` doesn't
+    assert_eq!(
+        concrete(int(256, min).range_mod(&int(256, neg_one))),
+        Some(Concrete::Int(256, I256::zero()))
+    );
+    // the remainder takes the sign of the dividend
+    assert_eq!(
+        concrete(int(256, I256::from(-7)).range_mod(&int(256, I256::from(3)))),
+        Some(Concrete::Int(256, I256::from(-1)))
+    );
+    assert_eq!(
+        concrete(int(256, I256::from(7)).range_mod(&int(256, I256::from(-3)))),
+        Some(Concrete::Int(256, I256::from(1)))
+    );
+    // shifting right rounds towards negative infinity
+    assert_eq!(
+        concrete(int(256, I256::from(-7)).range_shr(&uint(256, U256::one()))),
+        Some(Concrete::Int(256, I256::from(-4)))
+    );
+    // smaller types saturate at their own two's complement bounds
+    assert_eq!(
+        concrete(int(8, I256::from(-100)).range_sub(&int(8, I256::from(100)))),
+        Some(Concrete::Int(8, I256::from(-128)))
+    );
+    assert_eq!(
+        concrete(int(8, I256::from(-128)).range_div(&int(8, neg_one))),
+        None
+    );
+}
+
+#[test]
+fn test_unsigned_sub() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/unsigned_sub.sol");
+    let sol = include_str!("./test_data/unsigned_sub.sol");
+    let mut analyzer = Analyzer::default();
+    let ctxs = body_ctxs(&mut analyzer, path_str, sol);
+    let z = ctxs["f"]
+        .latest_var_by_name(&analyzer, "z")
+        .unwrap()
+        .range(&analyzer)
+        .unwrap();
+    // `x - y` reverts below zero, so an unsigned result never goes negative
+    assert_eq!(
+        concrete(Some(z.evaled_range_min(&analyzer))),
+        Some(Concrete::Uint(256, U256::zero()))
+    );
+    assert_eq!(
+        concrete(Some(z.evaled_range_max(&analyzer))),
+        Some(Concrete::Uint(256, U256::from(9)))
+    );
+}
+
+#[test]
+fn test_negative_factor() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/negative_factor.sol");
+    let sol = include_str!("./test_data/negative_factor.sol");
+    let mut analyzer = Analyzer::default();
+    let ctxs = body_ctxs(&mut analyzer, path_str, sol);
+    let bounds = |func: &str| {
+        let y = ctxs[func]
+            .latest_var_by_name(&analyzer, "y")
+            .unwrap()
+            .range(&analyzer)
+            .unwrap();
+        (
+            concrete(Some(y.evaled_range_min(&analyzer))),
+            concrete(Some(y.evaled_range_max(&analyzer))),
+        )
+    };
+    let int = |val: i32| Some(Concrete::Int(256, I256::from(val)));
+
+    // `-10 < x < 10`, so a negative divisor or factor swaps which end is the min
+    assert_eq!(bounds("div"), (int(-4), int(4)));
+    assert_eq!(bounds("mul"), (int(-18), int(18)));
+    assert_eq!(bounds("mulVar"), (int(-18), int(18)));
+}
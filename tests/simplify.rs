@@ -0,0 +1,89 @@
+use ethers_core::types::U256;
+use pyrometer::Analyzer;
+use shared::nodes::Concrete;
+use shared::range::elem::RangeOp;
+use shared::range::elem_ty::{Dynamic, Elem, RangeExpr};
+use shared::range::range_string::ToRangeString;
+use shared::range::simplify::simplify_expr;
+use shared::range::Range;
+use solang_parser::pt::Loc;
+use std::env;
+mod helpers;
+use helpers::*;
+
+fn expr(lhs: Elem<Concrete>, op: RangeOp, rhs: Elem<Concrete>) -> Elem<Concrete> {
+    Elem::Expr(RangeExpr::new(lhs, op, rhs))
+}
+
+#[test]
+fn test_simplify() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/simplify.sol");
+    let sol = include_str!("./test_data/simplify.sol");
+    let mut analyzer = Analyzer::default();
+    let ctxs = body_ctxs(&mut analyzer, path_str, sol);
+    let simplified = |func: &str| {
+        let range = ctxs[func]
+            .latest_var_by_name(&analyzer, "a")
+            .unwrap()
+            .range(&analyzer)
+            .unwrap();
+        (
+            range
+                .simplified_range_min(&analyzer)
+                .to_range_string(false, &analyzer)
+                .s,
+            range
+                .simplified_range_max(&analyzer)
+                .to_range_string(true, &analyzer)
+                .s,
+        )
+    };
+
+    for (func, expected) in [
+        ("canceled", "x"),
+        ("gathered", "x + 5"),
+        ("offset", "x - 2"),
+        ("scaled", "x * 6"),
+        ("annihilated", "0"),
+        ("commuted", "x + 7"),
+    ] {
+        let (min, max) = simplified(func);
+        assert_eq!(min, expected, "{func}");
+        assert_eq!(max, expected, "{func}");
+    }
+}
+
+#[test]
+fn test_simplify_rules() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/simplify.sol");
+    let sol = include_str!("./test_data/simplify.sol");
+    let mut analyzer = Analyzer::default();
+    let ctxs = body_ctxs(&mut analyzer, path_str, sol);
+    let x_var = ctxs["gathered"].var_by_name(&analyzer, "x").unwrap();
+    let x = Elem::from(Dynamic::new(x_var.into(), Loc::Implicit));
+    let simplify = |lhs, op, rhs| simplify_expr(RangeExpr::new(lhs, op, rhs), true, &analyzer);
+
+    assert_eq!(simplify(uint(2), RangeOp::Add, uint(3)), uint(5));
+    assert_eq!(simplify(x.clone(), RangeOp::Min, x.clone()), x);
+    assert_eq!(simplify(x.clone(), RangeOp::Mod, uint(1)), uint(0));
+    assert_eq!(
+        simplify(
+            expr(x.clone(), RangeOp::Sub, uint(2)),
+            RangeOp::Sub,
+            uint(3)
+        ),
+        expr(x.clone(), RangeOp::Sub, uint(5))
+    );
+    // gathering constants that overflow would change the value, so they are left apart
+    let near_max = expr(
+        x.clone(),
+        RangeOp::Add,
+        Elem::from(Concrete::from(U256::MAX)),
+    );
+    assert_eq!(
+        simplify(near_max.clone(), RangeOp::Add, uint(1)),
+        expr(near_max, RangeOp::Add, uint(1))
+    );
+}
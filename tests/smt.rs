@@ -0,0 +1,176 @@
+use ethers_core::types::H256;
+use pyrometer::Analyzer;
+use shared::analyzer::Search;
+use shared::range::smt::{SmtScript, SmtSort, ToSmt};
+use shared::{
+    nodes::{Concrete, FunctionNode},
+    Edge,
+};
+use std::env;
+use std::path::PathBuf;
+
+fn smt_scripts(path_str: String, sol: &str) -> Vec<String> {
+    let mut analyzer = Analyzer::default();
+    let (maybe_entry, _) = analyzer.parse(sol, &PathBuf::from(path_str), true);
+    let entry = maybe_entry.unwrap();
+    analyzer
+        .search_children(entry, &Edge::Func)
+        .into_iter()
+        .filter_map(|func| FunctionNode::from(func).maybe_body_ctx(&analyzer))
+        .flat_map(|ctx| SmtScript::for_all_paths(ctx, &analyzer))
+        .map(|mut script| {
+            script.get_model = true;
+            script.to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn test_smt_export() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/smt_export.sol");
+    let sol = include_str!("./test_data/smt_export.sol");
+    let scripts = smt_scripts(path_str, sol);
+    // one script for each side of the `if`
+    assert_eq!(scripts.len(), 2);
+    scripts.iter().for_each(|script| {
+        assert!(script.contains("(set-logic QF_BV)"));
+        assert!(script.trim_end().ends_with("(check-sat)\n(get-model)"));
+        assert_eq!(script.matches('(').count(), script.matches(')').count());
+        // widths come from the solidity types
+        assert!(script.contains("|x@") && script.contains("| (_ BitVec 8))"));
+        assert!(script.contains("| (_ BitVec 16))"));
+        // `int16` comparisons are signed, `uint8` comparisons are not
+        assert!(script.contains("(bvslt |y@"));
+        assert!(script.contains("(bvugt |x@"));
+    });
+}
+
+#[test]
+fn test_smt_bytes() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/smt_bytes.sol");
+    let sol = include_str!("./test_data/smt_bytes.sol");
+    let scripts = smt_scripts(path_str, sol);
+    assert_eq!(scripts.len(), 1);
+    let script = &scripts[0];
+    assert_eq!(script.matches('(').count(), script.matches(')').count());
+    // `bytesN` keep their leading bytes in the high bits when narrowed or widened
+    assert!(script.contains("((_ extract 255 224) "));
+    assert!(script.contains("(_ bv0 224))"));
+    assert!(script.contains("| (_ BitVec 32))"));
+    assert!(script.contains("| (_ BitVec 256))"));
+
+    // a concrete `bytes4` compared as a `bytes32` is padded on the right
+    let analyzer = Analyzer::default();
+    let mut h = H256::default();
+    h.0[..4].copy_from_slice(&[0xa9, 0x05, 0x9c, 0xbb]);
+    let sel = Concrete::Bytes(4, h);
+    assert_eq!(
+        sel.to_smt(SmtSort::Bytes(32), &analyzer).unwrap(),
+        "(concat (_ bv2835717307 32) (_ bv0 224))"
+    );
+    assert_eq!(
+        sel.to_smt(SmtSort::Bytes(4), &analyzer).unwrap(),
+        "(_ bv2835717307 32)"
+    );
+}
+
+#[test]
+fn test_smt_no_model() {
+    // a model is only asked for when requested, as asking for one is an error on `unsat`
+    let script = SmtScript::default().to_string();
+    assert!(script.trim_end().ends_with("(check-sat)"));
+    assert!(!script.contains("(get-model)"));
+}
+
+/// Analyzes `smt_prune.sol` checking paths with `solver`, returning whether the body of each
+/// function was killed
+#[cfg(feature = "smt")]
+fn killed_by_pruning(solver: &str) -> (Analyzer, std::collections::BTreeMap<String, bool>) {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/smt_prune.sol");
+    let sol = include_str!("./test_data/smt_prune.sol");
+    let mut analyzer = Analyzer::default();
+    analyzer.settings.solver = solver.to_string();
+    let (maybe_entry, _) = analyzer.parse(sol, &PathBuf::from(path_str), true);
+    let entry = maybe_entry.unwrap();
+    let killed = analyzer
+        .search_children(entry, &Edge::Func)
+        .into_iter()
+        .map(FunctionNode::from)
+        .map(|func| {
+            let name = func.name(&analyzer);
+            let name = name.split('(').next().unwrap_or_default().to_string();
+            (
+                name,
+                func.body_ctx(&analyzer).killed_loc(&analyzer).is_some(),
+            )
+        })
+        .collect();
+    (analyzer, killed)
+}
+
+#[cfg(feature = "smt")]
+#[test]
+#[ignore = "needs a local SMT solver, see `DEFAULT_SOLVER`"]
+fn test_smt_prune() {
+    use shared::range::smt::{SmtResult, DEFAULT_SOLVER};
+    assert_eq!(
+        SmtScript::default().check(DEFAULT_SOLVER),
+        SmtResult::Sat,
+        "no SMT solver could be run"
+    );
+    let (_, killed) = killed_by_pruning(DEFAULT_SOLVER);
+    assert!(killed["correlated"]);
+    assert!(!killed["feasible"]);
+}
+
+#[cfg(all(feature = "smt", unix))]
+#[test]
+fn test_smt_prune_stub_solver() {
+    use shared::range::smt::SmtResult;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    // a solver that only finds the path through `require(y > x)` after `require(x > y)`
+    // unsatisfiable, reading all of its input first so pyrometer can write the script. Each
+    // run is logged, to count how often pyrometer asked
+    let solver = env::temp_dir().join(format!("pyrometer-stub-solver-{}", std::process::id()));
+    let log = solver.with_extension("log");
+    let _ = fs::remove_file(&log);
+    fs::write(
+        &solver,
+        format!(
+            "#!/bin/sh\necho run >> {}\nif grep -F '(y > x) value' > /dev/null; then echo unsat; else echo sat; fi\n",
+            log.display()
+        ),
+    )
+    .unwrap();
+    fs::set_permissions(&solver, fs::Permissions::from_mode(0o755)).unwrap();
+    let runs = || fs::read_to_string(&log).unwrap_or_default().lines().count();
+
+    let solver_cmd = solver.to_str().unwrap();
+    let (analyzer, killed) = killed_by_pruning(solver_cmd);
+    assert!(killed["correlated"]);
+    assert!(!killed["feasible"]);
+    // only the requirements between `x` and `y` are checked, `x < 100` is decided by its range
+    assert_eq!(runs(), 3);
+
+    // a path is only solved again once its constraints change
+    let script = SmtScript::default();
+    assert_eq!(
+        analyzer.smt_cache.check(&script, solver_cmd),
+        SmtResult::Sat
+    );
+    assert_eq!(
+        analyzer.smt_cache.check(&script, solver_cmd),
+        SmtResult::Sat
+    );
+    assert_eq!(runs(), 4);
+    assert_eq!(analyzer.smt_cache.hits(), 1);
+    assert_eq!(analyzer.smt_cache.entries(), 4);
+
+    fs::remove_file(&solver).unwrap();
+    fs::remove_file(&log).unwrap();
+}
@@ -0,0 +1,28 @@
+use ariadne::Span;
+use pyrometer::context::analyzers::{sarif::SarifDisplay, syntax::SyntaxError};
+use pyrometer::Analyzer;
+use std::collections::BTreeMap;
+use std::env;
+use std::path::PathBuf;
+
+#[test]
+fn test_syntax_error() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/syntax_error.sol");
+    let sol = include_str!("./test_data/syntax_error.sol");
+    let mut analyzer = Analyzer::default();
+    let (maybe_entry, _) = analyzer.parse(sol, &PathBuf::from(path_str.clone()), true);
+    // the file is skipped rather than failing the analysis
+    assert!(maybe_entry.is_none());
+    assert!(!analyzer.parse_errors.is_empty());
+
+    let file_mapping: BTreeMap<_, _> = vec![(0usize, path_str.clone())].into_iter().collect();
+    let sources: BTreeMap<_, _> = vec![(path_str, sol.to_string())].into_iter().collect();
+    let report = SyntaxError::new(&file_mapping, &analyzer.parse_errors[0]);
+    assert_eq!(report.span.start(), sol.find(';').unwrap());
+    let results = report.sarif_results(&analyzer, &sources);
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].rule_id, "syntax-error");
+    assert_eq!(results[0].level, "error");
+    assert!(results[0].message.text.starts_with("Could not parse: "));
+}
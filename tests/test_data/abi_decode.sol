@@ -0,0 +1,43 @@
+contract AbiDecode {
+    struct Call {
+        address target;
+        bytes data;
+    }
+
+    struct Order {
+        Call call;
+        uint256[] amounts;
+        uint8 kind;
+    }
+
+    function decode_builtins(bytes memory data) public {
+        (uint256 a, uint8 b) = abi.decode(data, (uint256, uint8));
+    }
+
+    function decode_bytes(bytes memory data) public {
+        require(data.length < 100);
+        (bytes memory inner, string memory name) = abi.decode(data, (bytes, string));
+        uint256 inner_len = inner.length;
+        require(inner.length <= data.length);
+        require(bytes(name).length <= data.length);
+    }
+
+    function decode_struct(bytes memory data) public {
+        Call memory call = abi.decode(data, (Call));
+        require(call.target == address(0));
+    }
+
+    function decode_nested(bytes memory data) public {
+        (Order memory order, uint256 x) = abi.decode(data, (Order, uint256));
+        uint8 kind = order.kind;
+        require(x >= 0);
+    }
+
+    function decode_arrays(bytes memory data) public {
+        require(data.length < 100);
+        (uint256[] memory xs, uint256[3] memory ys) = abi.decode(data, (uint256[], uint256[3]));
+        uint256 xs_len = xs.length;
+        uint256 ys_len = ys.length;
+        require(xs.length <= data.length);
+    }
+}
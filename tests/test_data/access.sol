@@ -0,0 +1,8 @@
+contract Access {
+    uint256 x;
+
+    function setX(uint256 v) public {
+        require(v < 100);
+        x = v;
+    }
+}
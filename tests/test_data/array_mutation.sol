@@ -0,0 +1,39 @@
+contract ArrayMutation {
+    uint256[] stack;
+    uint256[3] fixedStack;
+
+    function push_pop() public {
+        require(stack.length == 0);
+        stack.push(5);
+        stack.pop();
+        require(stack.length == 0);
+    }
+
+    function checked_pop() public {
+        require(stack.length > 2);
+        stack.pop();
+        require(stack.length > 1);
+    }
+
+    function delete_array() public {
+        stack.push(5);
+        delete stack;
+        require(stack.length == 0);
+    }
+
+    function delete_fixed() public {
+        fixedStack[2] = 5;
+        delete fixedStack;
+        require(fixedStack[2] == 0);
+    }
+
+    function delete_elem(uint256 i) public {
+        stack[i] = 5;
+        delete stack[i];
+        require(stack[i] == 0);
+    }
+
+    function unchecked_pop() public {
+        stack.pop();
+    }
+}
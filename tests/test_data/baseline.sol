@@ -0,0 +1,17 @@
+contract Baseline {
+    uint256[] public values;
+
+    function known(uint256 i) public view returns (uint256) {
+        return values[i];
+    }
+
+    function suppressed(uint256 i) public view returns (uint256) {
+        // pyrometer-disable-next-line panic
+        return values[i];
+    }
+
+    function otherRule(uint256 i) public view returns (uint256) {
+        // pyrometer-disable-next-line unsupported
+        return values[i];
+    }
+}
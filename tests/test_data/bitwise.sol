@@ -138,20 +138,20 @@ contract Bitwise {
         int256 a12 = int_shr(-100, 2);
         require(a12 == -25);
         int256 a13 = int_shr(-100, 4);
-        require(a13 == -6);
+        require(a13 == -7);
         int256 a14 = int_shr(-100, 8);
         require(a14 == -1);
         int256 a15 = int_shr(-1000000000, 8);
         require(a15 == -3906250);
         int256 a16 = int_shr(-1000000000, 16);
-        require(a16 == -15258);
+        require(a16 == -15259);
         int256 a17 = int_shr(-10000000000, 32);
-        require(a17 == -2);
+        require(a17 == -3);
         int256 a18 = int_shr(-100000000000000000000, 64);
-        require(a18 == -5);
+        require(a18 == -6);
         int256 a19 = int_shr(-1000000000000000000000000000000000000000, 128);
-        require(a19 == -2);
+        require(a19 == -3);
         int256 a20 = int_shr(-1000000000000000000000000000000000000000000000000000000000000000000000000000, 248);
-        require(a20 == -2);
+        require(a20 == -3);
     }
 }
@@ -0,0 +1,49 @@
+contract ConcreteHashing {
+    bytes32 constant MINTER_ROLE = keccak256("MINTER_ROLE");
+
+    function role() public {
+        bytes32 r = keccak256("MINTER_ROLE");
+        require(MINTER_ROLE == r);
+        bytes32 m = MINTER_ROLE;
+    }
+
+    function sha() public {
+        bytes32 h = sha256("abc");
+    }
+
+    function selector() public {
+        bytes4 sel = bytes4(keccak256("transfer(address,uint256)"));
+        bytes4 transfer = 0xa9059cbb;
+        require(sel == transfer);
+        require(sel == 0xa9059cbb);
+    }
+
+    function wrongSelector() public {
+        bytes4 sel = bytes4(keccak256("transfer(address,uint256)"));
+        require(sel == 0x23b872dd);
+    }
+
+    function encode() public {
+        bytes32 h = keccak256(abi.encode(uint256(1), true));
+        bytes32 p = keccak256(abi.encodePacked(uint8(1), uint16(2)));
+    }
+
+    function calldata_() public {
+        bytes memory sig = abi.encodeWithSignature("transfer(address,uint256)", address(1), uint256(2));
+        bytes memory sel = abi.encodeWithSelector(bytes4(0xa9059cbb), address(1), uint256(2));
+    }
+
+    function withSignature() public returns (bytes memory) {
+        return abi.encodeWithSignature("transfer(address,uint256)", address(1), uint256(2));
+    }
+
+    function withSelector() public returns (bytes memory) {
+        bytes4 sel = bytes4(keccak256("transfer(address,uint256)"));
+        return abi.encodeWithSelector(sel, address(1), uint256(2));
+    }
+
+    function literal() public {
+        bytes4 transfer = 0xa9059cbb;
+        bytes2 short = 0x0102;
+    }
+}
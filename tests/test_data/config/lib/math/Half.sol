@@ -0,0 +1,3 @@
+function half(uint256 x) pure returns (uint256) {
+    return x / 2;
+}
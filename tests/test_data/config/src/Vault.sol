@@ -0,0 +1,19 @@
+import "maths/Half.sol";
+
+contract Vault {
+    function deposit() public payable returns (uint256) {
+        uint256 value = msg.value;
+        uint256 number = block.number;
+        return half(number);
+    }
+
+    function testDeposit() public {}
+}
+
+contract VaultMock {
+    function deposit() public {}
+}
+
+contract Other {
+    function deposit() public {}
+}
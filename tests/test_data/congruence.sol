@@ -0,0 +1,49 @@
+contract Congruence {
+    function scaled(uint256 y) public returns (uint256) {
+        require(y < 100);
+        uint256 a = y * 32;
+        require(a > 40);
+        return a;
+    }
+
+    function aligned(uint256 a) public returns (uint256) {
+        require(a % 4 == 0);
+        require(a > 5);
+        require(a < 30);
+        return a;
+    }
+
+    function shifted(uint256 y) public returns (uint256) {
+        uint256 a = y << 5;
+        require(a >= 1);
+        require(a < 100);
+        return a;
+    }
+
+    function divided(uint256 y) public returns (uint256) {
+        require(y % 64 == 0);
+        uint256 a = y / 16;
+        require(a > 1);
+        return a;
+    }
+
+    function misaligned(uint256 a) public returns (uint256) {
+        require(a % 4 == 0);
+        require(a == 6);
+        return a;
+    }
+
+    function offset(uint256 x) public returns (uint256) {
+        uint256 a = x * 4 + 1;
+        require(a % 4 == 1);
+        return a;
+    }
+
+    function uncheckedOffset(uint256 x) public returns (uint256) {
+        unchecked {
+            uint256 a = x * 4 + 1;
+            require(a % 4 == 1);
+            return a;
+        }
+    }
+}
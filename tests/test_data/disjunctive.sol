@@ -0,0 +1,75 @@
+contract Disjunctive {
+    function split(uint256 x) public returns (uint256) {
+        require(x < 10 || x > 100);
+        return x;
+    }
+
+    function narrowed(uint256 x) public returns (uint256) {
+        require(x < 10 || x > 100);
+        require(x > 50);
+        return x;
+    }
+
+    function lifted(uint256 x) public returns (uint256) {
+        require(x < 10 || x > 100);
+        require(x < 1000);
+        uint256 y = x + 1;
+        return y;
+    }
+
+    function excluded(uint256 x) public returns (uint256) {
+        require(x < 10 || x > 100);
+        require(x > 20);
+        require(x < 50);
+        return x;
+    }
+
+    function capped(uint256 x) public returns (uint256) {
+        require(x == 1 || x == 3 || x == 5 || x == 7 || x == 50);
+        return x;
+    }
+
+    function signed_div(int256 x, int256 d) public returns (int256) {
+        require(x < -100 || x > 100);
+        require(x > -1000);
+        require(x < 1000);
+        require(d > -3);
+        require(d < 3);
+        int256 y = x / d;
+        return y;
+    }
+
+    function signed_split(int256 x) public returns (int256) {
+        require(x < -10 || x > 10);
+        return x;
+    }
+
+    function assign_lifted(uint256 x) public returns (uint256) {
+        require(x < 10 || x > 100);
+        require(x < 1000);
+        x *= 2;
+        return x;
+    }
+
+    function cast_lifted(uint256 x) public returns (uint16) {
+        require(x < 10 || x > 100);
+        require(x < 1000);
+        uint16 y = uint16(x);
+        return y;
+    }
+
+    function truncating_cast(uint256 x) public returns (uint8) {
+        require(x < 10 || x > 100);
+        require(x < 1000);
+        uint8 y = uint8(x);
+        return y;
+    }
+
+    function cmp_lifted(uint256 x) public returns (bool) {
+        require(x < 10 || x > 100);
+        bool inGap = x == 50;
+        bool outside = x != 50;
+        bool unknown = x > 5;
+        return inGap;
+    }
+}
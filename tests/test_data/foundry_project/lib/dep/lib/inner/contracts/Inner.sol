@@ -0,0 +1,7 @@
+pragma solidity ^0.8.0;
+
+library Inner {
+    function limit() internal pure returns (uint256) {
+        return 100;
+    }
+}
@@ -0,0 +1,12 @@
+pragma solidity ^0.8.0;
+
+import "inner/Inner.sol";
+
+library Dep {
+    function clamp(uint256 x) internal pure returns (uint256) {
+        if (x > Inner.limit()) {
+            return Inner.limit();
+        }
+        return x;
+    }
+}
@@ -0,0 +1,12 @@
+pragma solidity ^0.8.0;
+
+import "dep/Dep.sol";
+import "maths/Half.sol";
+
+contract A {
+    function a(uint256 x) public pure returns (uint256) {
+        uint256 y = Dep.clamp(x);
+        require(y > 200);
+        return Half.half(y);
+    }
+}
@@ -0,0 +1,11 @@
+pragma solidity ^0.8.0;
+
+import "dep/Dep.sol";
+
+contract B {
+    uint256 public stored;
+
+    function b(uint256 x) public {
+        stored = Dep.clamp(x);
+    }
+}
@@ -0,0 +1,7 @@
+pragma solidity ^0.8.0;
+
+library Half {
+    function half(uint256 x) internal pure returns (uint256) {
+        return x / 2;
+    }
+}
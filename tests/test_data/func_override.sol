@@ -6,7 +6,7 @@ contract B {
 
 
 contract A is B {
-	function a(uint256 x) internal override returns (uint256) {
+	function a(uint256 x) internal virtual override returns (uint256) {
 		return 100;
 	}
 
@@ -15,4 +15,15 @@ contract A is B {
 		// uint256 x = 100;
 		// require(x == 100);
 	}
-}
\ No newline at end of file
+}
+
+contract C is A {
+	function a(uint256 x) internal override returns (uint256) {
+		return 300;
+	}
+
+	function c() public {
+		uint256 y = a(5);
+		require(y == 300);
+	}
+}
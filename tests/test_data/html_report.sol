@@ -0,0 +1,12 @@
+// </script> in a comment must not end the report's script early
+contract HtmlReport {
+    function branch(uint256 x) public returns (uint256) {
+        if (x > 10) {
+            require(x < 5);
+            return x;
+        } else {
+            x += 1;
+            return x;
+        }
+    }
+}
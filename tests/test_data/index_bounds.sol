@@ -0,0 +1,25 @@
+contract IndexBounds {
+    uint256[] storeVar;
+
+    function unchecked_index(uint256[] calldata x, uint256 i) public returns (uint256) {
+        return x[i];
+    }
+
+    function checked_index(uint256[] calldata x, uint256 i) public returns (uint256) {
+        require(i < x.length);
+        return x[i];
+    }
+
+    function fixed_index(uint256[] calldata x) public returns (uint256) {
+        require(x.length > 5);
+        return x[5];
+    }
+
+    function sized_index(uint256[3] memory x) public returns (uint256) {
+        return x[2];
+    }
+
+    function sized_out_of_bounds(uint256[3] memory x) public returns (uint256) {
+        return x[3];
+    }
+}
@@ -0,0 +1,21 @@
+contract JsonOutput {
+    function bounded(uint256 x) public {
+        require(x > 5);
+        require(x != 10);
+    }
+
+    function reverts(uint256 x) public {
+        require(x > 5);
+        require(x < 3);
+    }
+
+    function returned(uint256 x) public returns (uint256) {
+        require(x < 10);
+        return x + 1;
+    }
+
+    function encoded(int8 y, bytes4 sel) public {
+        require(y < -3);
+        require(sel == 0xa9059cbb);
+    }
+}
@@ -0,0 +1,42 @@
+contract KnownBits {
+    function packed(uint256 slot) public returns (uint256) {
+        uint256 a = (slot >> 160) & 0xffff;
+        return a;
+    }
+
+    function xored(uint256 x) public returns (uint256) {
+        require(x < 16);
+        uint256 a = x ^ 0xff;
+        return a;
+    }
+
+    function shifted(uint256 x) public returns (uint256) {
+        require(x < 256);
+        uint256 a = (x << 8) | 0xff;
+        return a;
+    }
+
+    function masked(uint256 x) public returns (uint256) {
+        require(x <= 8);
+        uint256 a = x & 0x7;
+        return a;
+    }
+
+    function ored(uint256 x) public returns (uint256) {
+        require(x > 100);
+        uint256 a = x | 1;
+        return a;
+    }
+
+    function anded(uint256 x) public returns (uint256) {
+        require(x < 100);
+        uint256 a = x & 0xff;
+        return a;
+    }
+
+    function odd(uint256 x) public returns (uint256) {
+        uint256 a = x & 0xf0;
+        require(a == 5);
+        return a;
+    }
+}
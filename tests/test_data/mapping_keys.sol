@@ -0,0 +1,37 @@
+contract MappingKeys {
+    mapping(address => uint256) balances;
+    mapping(uint256 => uint256) vals;
+
+    function read_after_write(uint256 x) public {
+        require(x < 100);
+        balances[msg.sender] = x;
+        uint256 y = balances[msg.sender];
+    }
+
+    function must_alias() public {
+        vals[1] = 5;
+        uint256 a = 1;
+        vals[a] = 10;
+    }
+
+    function may_alias(uint256 a, uint256 b) public {
+        vals[a] = 5;
+        vals[b] = 10;
+    }
+
+    function no_alias(uint256 a) public {
+        require(a > 10);
+        vals[a] = 5;
+        vals[1] = 10;
+    }
+
+    function reassigned_key() public {
+        uint256 a = 11;
+        uint256 b = 3;
+        vals[a] = 1;
+        vals[b] = 1;
+        a = 3;
+        b = 11;
+        vals[11] = 2;
+    }
+}
@@ -0,0 +1,23 @@
+contract NegativeFactor {
+    function div(int256 x) public returns (int256) {
+        require(x > -10);
+        require(x < 10);
+        int256 y = x / -2;
+        return y;
+    }
+
+    function mul(int256 x) public returns (int256) {
+        require(x > -10);
+        require(x < 10);
+        int256 y = x * -2;
+        return y;
+    }
+
+    function mulVar(int256 x, int256 c) public returns (int256) {
+        require(x > -10);
+        require(x < 10);
+        require(c == -2);
+        int256 y = x * c;
+        return y;
+    }
+}
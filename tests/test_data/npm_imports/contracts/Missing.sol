@@ -0,0 +1,11 @@
+pragma solidity ^0.8.0;
+
+import "@acme/contracts/Missing.sol";
+import * as Gone from "@acme/contracts/Gone.sol";
+
+contract UsesNothingImported {
+    function f(uint256 x) public pure returns (uint256) {
+        require(x < 10);
+        return x;
+    }
+}
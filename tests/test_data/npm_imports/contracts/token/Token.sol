@@ -0,0 +1,11 @@
+pragma solidity ^0.8.0;
+
+import "@acme/contracts/utils/Math.sol";
+import "shared/Cap.sol";
+
+contract Token {
+    function mint(uint256 amount) public pure returns (uint256) {
+        uint256 minted = Math.min(amount, 1000);
+        return minted;
+    }
+}
@@ -0,0 +1,7 @@
+pragma solidity ^0.8.0;
+
+contract Cap {
+    function cap() public pure returns (uint256) {
+        return 1000;
+    }
+}
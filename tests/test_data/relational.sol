@@ -0,0 +1,28 @@
+contract Relational {
+    function transitive(uint256 x, uint256 y) public {
+        require(y > x);
+        require(y < 10);
+    }
+
+    function copied(uint256 x) public {
+        uint256 y = x;
+        require(x != 0);
+        require(x < 100);
+    }
+
+    function offset(uint256 x) public {
+        uint256 y = x + 5;
+        require(x < 10);
+    }
+
+    function decided(uint256 x, uint256 y) public returns (bool) {
+        require(x <= y);
+        bool b = x > y;
+        return b;
+    }
+
+    function contradiction(uint256 x, uint256 y) public {
+        require(x > y);
+        require(y > x);
+    }
+}
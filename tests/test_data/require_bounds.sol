@@ -0,0 +1,25 @@
+contract RequireBounds {
+    function gt(uint256 x, uint256 y) public {
+        require(x > 10);
+        require(x < 100);
+        require(x > y);
+    }
+
+    function gte(uint256 x, uint256 y) public {
+        require(x >= 10);
+        require(x <= 100);
+        require(x >= y);
+    }
+
+    function lt(uint256 x, uint256 y) public {
+        require(x > 0);
+        require(x < 100);
+        require(y > 5);
+        require(x < y);
+    }
+
+    function lte(uint256 x, uint256 y) public {
+        require(x <= 100);
+        require(x <= y);
+    }
+}
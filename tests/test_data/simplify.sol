@@ -0,0 +1,31 @@
+contract Simplify {
+    function canceled(uint256 x) public returns (uint256) {
+        uint256 a = ((x + 1) - 1) * 1;
+        return a;
+    }
+
+    function gathered(uint256 x) public returns (uint256) {
+        uint256 a = (x + 2) + 3;
+        return a;
+    }
+
+    function offset(uint256 x) public returns (uint256) {
+        uint256 a = (x + 5) - 7;
+        return a;
+    }
+
+    function scaled(uint256 x) public returns (uint256) {
+        uint256 a = (x * 2) * 3;
+        return a;
+    }
+
+    function annihilated(uint256 x) public returns (uint256) {
+        uint256 a = x * 0;
+        return a;
+    }
+
+    function commuted(uint256 x) public returns (uint256) {
+        uint256 a = 3 + (4 + x);
+        return a;
+    }
+}
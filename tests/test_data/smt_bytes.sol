@@ -0,0 +1,8 @@
+contract SmtBytes {
+    function selector(bytes32 data) public {
+        bytes4 sel = bytes4(data);
+        require(sel == 0xa9059cbb);
+        bytes32 padded = bytes32(sel);
+        require(padded != data);
+    }
+}
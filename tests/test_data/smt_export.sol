@@ -0,0 +1,10 @@
+contract SmtExport {
+    function bounded(uint8 x, int16 y) public {
+        require(x > 5);
+        require(y < -3);
+        uint8 z = x + 1;
+        if (z == 10) {
+            z = 0;
+        }
+    }
+}
@@ -0,0 +1,11 @@
+contract SmtPrune {
+    function correlated(uint256 x, uint256 y) public {
+        require(x > y);
+        require(y > x);
+    }
+
+    function feasible(uint256 x, uint256 y) public {
+        require(x > y);
+        require(x < 100);
+    }
+}
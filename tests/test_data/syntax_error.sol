@@ -0,0 +1,5 @@
+contract SyntaxError {
+    function f(uint256 x) public returns (uint256) {
+        return x +;
+    }
+}
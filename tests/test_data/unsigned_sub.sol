@@ -0,0 +1,8 @@
+contract UnsignedSub {
+    function f(uint256 x, uint256 y) public returns (uint256) {
+        require(x < 10);
+        require(y < 10);
+        uint256 z = x - y;
+        return z;
+    }
+}
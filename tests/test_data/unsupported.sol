@@ -0,0 +1,65 @@
+contract Unsupported {
+    error Bad(uint256 code);
+
+    function bitNot(uint256 x) public returns (uint256) {
+        require(x < 10);
+        uint256 y = ~x;
+        return x;
+    }
+
+    function doWhile(uint256 x) public {
+        require(x < 10);
+        do {
+            x += 1;
+        } while (x < 5);
+        uint256 z = x;
+    }
+
+    function namedRevert(uint256 x) public {
+        if (x > 10) {
+            revert Bad({code: x});
+        }
+        uint256 y = x;
+    }
+
+    function slice(bytes calldata data) public returns (bytes calldata) {
+        return data[1:];
+    }
+
+    function hexes() public returns (bytes memory) {
+        bytes memory h = hex"01" hex"02";
+        return h;
+    }
+
+    uint256[] stack;
+
+    function popArgs() public {
+        stack.pop(1);
+    }
+
+    uint256 constant M = 2;
+    uint256[M] sized;
+
+    function sizedByConstant(uint256[M] memory xs, uint256[M + 1] memory ys) public {
+        uint256 n = xs.length + sized.length;
+    }
+
+    mapping(Missing => uint256) unknownKeys;
+
+    function unresolvedMapping() public {
+        uint256 v = unknownKeys[1];
+    }
+
+    function supported(uint256 x) public returns (uint256) {
+        require(x < 10);
+        return x + 1;
+    }
+    ;
+}
+
+event Moved(uint256 amount);
+
+using Missing for uint256;
+using Missing for *;
+using {missing} for uint256;
+;
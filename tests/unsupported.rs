@@ -0,0 +1,134 @@
+use ariadne::Span;
+use ethers_core::types::U256;
+use pyrometer::context::analyzers::{sarif::SarifDisplay, unsupported::UnsupportedAnalyzer};
+use pyrometer::Analyzer;
+use shared::analyzer::AnalyzerError;
+use shared::context::ContextNode;
+use shared::nodes::Concrete;
+use shared::range::{elem_ty::Elem, Range};
+use std::collections::BTreeMap;
+use std::env;
+mod helpers;
+use helpers::*;
+
+fn reasons(analyzer: &Analyzer, ctx: ContextNode) -> Vec<String> {
+    ctx.unsupported(analyzer)
+        .iter()
+        .map(|err| match err {
+            AnalyzerError::Unsupported(_, reason) => reason.clone(),
+            e => panic!("unexpected internal error: {e}"),
+        })
+        .collect()
+}
+
+#[test]
+fn test_unsupported() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/unsupported.sol");
+    let sol = include_str!("./test_data/unsupported.sol");
+    let mut analyzer = Analyzer::default();
+    let ctxs = body_ctxs(&mut analyzer, path_str, sol);
+
+    assert_eq!(reasons(&analyzer, ctxs["bitNot"]), vec!["bitwise not"]);
+    assert_eq!(reasons(&analyzer, ctxs["doWhile"]), vec!["do while loop"]);
+    assert_eq!(reasons(&analyzer, ctxs["slice"]), vec!["array slice"]);
+    assert_eq!(
+        reasons(&analyzer, ctxs["hexes"]),
+        vec!["concatenated hex literals"]
+    );
+    assert_eq!(
+        reasons(&analyzer, ctxs["popArgs"]),
+        vec!["pop with arguments"]
+    );
+    // array sizes given by a constant aren't resolved in declarations
+    assert_eq!(
+        reasons(&analyzer, ctxs["sizedByConstant"]),
+        vec![
+            "unresolved type of parameter `ys`",
+            "unresolved type of parameter `xs`",
+            "length of a non-variable",
+            "unresolved type `sized`"
+        ]
+    );
+    // the unresolved types are named by the source of their size
+    assert_eq!(
+        ctxs["sizedByConstant"].associated_fn_name(&analyzer),
+        "sizedByConstant(uint256[M], uint256[M + 1])"
+    );
+    // as are types whose parts can't be resolved
+    assert_eq!(
+        reasons(&analyzer, ctxs["unresolvedMapping"]),
+        vec!["unresolved type `unknownKeys`"]
+    );
+    assert!(reasons(&analyzer, ctxs["supported"]).is_empty());
+
+    // `x < 10` no longer holds once `~x` havocs the context
+    let x = ctxs["bitNot"]
+        .latest_var_by_name(&analyzer, "x")
+        .unwrap()
+        .range(&analyzer)
+        .unwrap();
+    assert_eq!(
+        x.evaled_range_max(&analyzer),
+        Elem::from(Concrete::from(U256::MAX))
+    );
+    // while functions without unsupported constructs are analyzed as usual
+    let ret = ctxs["supported"]
+        .latest_var_by_name(&analyzer, "x")
+        .unwrap()
+        .range(&analyzer)
+        .unwrap();
+    assert_eq!(
+        ret.evaled_range_max(&analyzer),
+        Elem::from(Concrete::from(U256::from(9)))
+    );
+}
+
+#[test]
+fn test_unsupported_summary() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/unsupported.sol");
+    let sol = include_str!("./test_data/unsupported.sol");
+    let mut analyzer = Analyzer::default();
+    let ctxs = body_ctxs(&mut analyzer, path_str.clone(), sol);
+    let file_mapping: BTreeMap<_, _> = vec![(0usize, path_str.clone())].into_iter().collect();
+    let sources: BTreeMap<_, _> = vec![(path_str, sol.to_string())].into_iter().collect();
+
+    let summary = analyzer.unsupported_constructs(&file_mapping, ctxs["doWhile"]);
+    assert_eq!(summary.constructs.len(), 1);
+    let (span, (reason, paths)) = summary.constructs.iter().next().unwrap();
+    assert_eq!(reason, "Unsupported: do while loop");
+    assert_eq!(paths, &vec!["doWhile(uint256)".to_string()]);
+    assert_eq!(span.start(), sol.find("do {").unwrap());
+
+    let results = summary.sarif_results(&analyzer, &sources);
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].rule_id, "unsupported");
+    assert_eq!(results[0].level, "warning");
+
+    // constructs on a forked path are found under the function's context
+    let summary = analyzer.unsupported_constructs(&file_mapping, ctxs["namedRevert"]);
+    let reasons = summary
+        .constructs
+        .values()
+        .map(|(reason, _paths)| reason.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(reasons, vec!["Unsupported: revert with named arguments"]);
+
+    // nothing is reported for a fully analyzed function
+    let summary = analyzer.unsupported_constructs(&file_mapping, ctxs["supported"]);
+    assert!(summary.constructs.is_empty());
+    assert!(summary.sarif_results(&analyzer, &sources).is_empty());
+}
+
+#[test]
+fn test_override_chain() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let path_str = format!("{manifest_dir}/tests/test_data/func_override.sol");
+    let sol = include_str!("./test_data/func_override.sol");
+    let mut analyzer = Analyzer::default();
+    let ctxs = body_ctxs(&mut analyzer, path_str, sol);
+    // three visible overrides of `a` resolve to the most derived one
+    assert!(reasons(&analyzer, ctxs["c"]).is_empty());
+    assert!(ctxs["c"].killed_loc(&analyzer).is_none());
+}